@@ -0,0 +1,37 @@
+//! # Build script for the MPS2 Peripheral Access Crate
+//!
+//! Puts the `device.x` linker script for the selected FPGA image in our output
+//! directory. The Rust source is generated ahead of time, by `regen.sh`.
+
+/// The generated PAC for each FPGA image, keyed by cargo feature
+///
+/// See `src/lib.rs` for why three of them share one.
+const PACS: [(&str, &str); 4] = [
+    ("an385", "src/an386"),
+    ("an386", "src/an386"),
+    ("an500", "src/an386"),
+    ("an505", "src/an505"),
+];
+
+fn main() {
+    let out = &std::path::PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+
+    // `src/lib.rs` reports a bad combination of features
+    let selected = PACS.iter().find(|(feature, _)| {
+        let var = format!("CARGO_FEATURE_{}", feature.to_uppercase());
+        std::env::var_os(var).is_some()
+    });
+
+    if let Some((_, dir)) = selected {
+        // Put `device.x` in our output directory and ensure it's on the linker
+        // search path. `cortex-m-rt`'s `link.x` includes it when the `device`
+        // feature is enabled.
+        std::fs::copy(format!("{dir}/device.x"), out.join("device.x")).unwrap();
+        println!("cargo:rustc-link-search={}", out.display());
+    }
+
+    for (_, dir) in PACS {
+        println!("cargo::rerun-if-changed={dir}/device.x");
+    }
+    println!("cargo::rerun-if-changed=build.rs");
+}
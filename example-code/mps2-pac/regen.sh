@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+#
+# Regenerates the PAC in `src/an386` and `src/an505` from the SVD files in
+# `./svd`. Run it after you edit an SVD file, and check in the result.
+#
+# Needs `svd2rust` 0.36 and `form` (`cargo install svd2rust form`), and
+# `rustfmt`.
+
+set -euo pipefail
+
+cd "$(dirname "$0")"
+
+for IMAGE in an386 an505; do
+    OUT="src/${IMAGE}"
+    TMP="$(mktemp -d)"
+    # `src/lib.rs` supplies the crate attributes, and pulls this in as a module
+    svd2rust --target cortex-m --skip-crate-attributes -i "svd/mps2-${IMAGE}.svd" -o "${TMP}"
+    rm -rf "${OUT}"
+    form -i "${TMP}/lib.rs" -o "${OUT}"
+    mv "${OUT}/lib.rs" "${OUT}/mod.rs"
+    cp "${TMP}/device.x" "${OUT}/device.x"
+    rm -rf "${TMP}"
+    find "${OUT}" -name '*.rs' -exec rustfmt --edition 2021 {} +
+    echo "Generated ${OUT}"
+done
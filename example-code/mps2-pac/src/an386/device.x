@@ -0,0 +1,33 @@
+PROVIDE(UART0_RX = DefaultHandler);
+PROVIDE(UART0_TX = DefaultHandler);
+PROVIDE(UART1_RX = DefaultHandler);
+PROVIDE(UART1_TX = DefaultHandler);
+PROVIDE(UART2_RX = DefaultHandler);
+PROVIDE(UART2_TX = DefaultHandler);
+PROVIDE(GPIO0_COMBINED = DefaultHandler);
+PROVIDE(GPIO1_COMBINED = DefaultHandler);
+PROVIDE(TIMER0 = DefaultHandler);
+PROVIDE(TIMER1 = DefaultHandler);
+PROVIDE(DUALTIMER = DefaultHandler);
+PROVIDE(SPI0_SPI1 = DefaultHandler);
+PROVIDE(UART_OVERFLOW = DefaultHandler);
+PROVIDE(ETHERNET = DefaultHandler);
+PROVIDE(AUDIO_I2S = DefaultHandler);
+PROVIDE(TOUCHSCREEN = DefaultHandler);
+PROVIDE(GPIO2_COMBINED = DefaultHandler);
+PROVIDE(GPIO3_COMBINED = DefaultHandler);
+PROVIDE(UART3_RX = DefaultHandler);
+PROVIDE(UART3_TX = DefaultHandler);
+PROVIDE(UART4_RX = DefaultHandler);
+PROVIDE(UART4_TX = DefaultHandler);
+PROVIDE(SPI2 = DefaultHandler);
+PROVIDE(SPI3_SPI4 = DefaultHandler);
+PROVIDE(GPIO0_0 = DefaultHandler);
+PROVIDE(GPIO0_1 = DefaultHandler);
+PROVIDE(GPIO0_2 = DefaultHandler);
+PROVIDE(GPIO0_3 = DefaultHandler);
+PROVIDE(GPIO0_4 = DefaultHandler);
+PROVIDE(GPIO0_5 = DefaultHandler);
+PROVIDE(GPIO0_6 = DefaultHandler);
+PROVIDE(GPIO0_7 = DefaultHandler);
+
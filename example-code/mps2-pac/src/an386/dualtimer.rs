@@ -0,0 +1,25 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    timer: (),
+}
+impl RegisterBlock {
+    #[doc = "0x00..0x38 - One of the two timers"]
+    #[inline(always)]
+    pub const fn timer(&self, n: usize) -> &Timer {
+        #[allow(clippy::no_effect)]
+        [(); 2][n];
+        unsafe { &*core::ptr::from_ref(self).cast::<u8>().add(32 * n).cast() }
+    }
+    #[doc = "Iterator for array of:"]
+    #[doc = "0x00..0x38 - One of the two timers"]
+    #[inline(always)]
+    pub fn timer_iter(&self) -> impl Iterator<Item = &Timer> {
+        (0..2).map(move |n| unsafe { &*core::ptr::from_ref(self).cast::<u8>().add(32 * n).cast() })
+    }
+}
+#[doc = "One of the two timers"]
+pub use self::timer::Timer;
+#[doc = r"Cluster"]
+#[doc = "One of the two timers"]
+pub mod timer;
@@ -0,0 +1,84 @@
+#[repr(C)]
+#[doc = "One of the two timers"]
+#[doc(alias = "TIMER")]
+pub struct Timer {
+    load: Load,
+    value: Value,
+    control: Control,
+    intclr: Intclr,
+    ris: Ris,
+    mis: Mis,
+    bgload: Bgload,
+}
+impl Timer {
+    #[doc = "0x00 - Load Register"]
+    #[inline(always)]
+    pub const fn load(&self) -> &Load {
+        &self.load
+    }
+    #[doc = "0x04 - Current Value Register"]
+    #[inline(always)]
+    pub const fn value(&self) -> &Value {
+        &self.value
+    }
+    #[doc = "0x08 - Control Register"]
+    #[inline(always)]
+    pub const fn control(&self) -> &Control {
+        &self.control
+    }
+    #[doc = "0x0c - Interrupt Clear Register"]
+    #[inline(always)]
+    pub const fn intclr(&self) -> &Intclr {
+        &self.intclr
+    }
+    #[doc = "0x10 - Raw Interrupt Status Register"]
+    #[inline(always)]
+    pub const fn ris(&self) -> &Ris {
+        &self.ris
+    }
+    #[doc = "0x14 - Masked Interrupt Status Register"]
+    #[inline(always)]
+    pub const fn mis(&self) -> &Mis {
+        &self.mis
+    }
+    #[doc = "0x18 - Background Load Register"]
+    #[inline(always)]
+    pub const fn bgload(&self) -> &Bgload {
+        &self.bgload
+    }
+}
+#[doc = "LOAD (rw) register accessor: Load Register\n\nYou can [`read`](crate::Reg::read) this register and get [`load::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`load::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@load`] module"]
+#[doc(alias = "LOAD")]
+pub type Load = crate::Reg<load::LoadSpec>;
+#[doc = "Load Register"]
+pub mod load;
+#[doc = "VALUE (r) register accessor: Current Value Register\n\nYou can [`read`](crate::Reg::read) this register and get [`value::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@value`] module"]
+#[doc(alias = "VALUE")]
+pub type Value = crate::Reg<value::ValueSpec>;
+#[doc = "Current Value Register"]
+pub mod value;
+#[doc = "CONTROL (rw) register accessor: Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@control`] module"]
+#[doc(alias = "CONTROL")]
+pub type Control = crate::Reg<control::ControlSpec>;
+#[doc = "Control Register"]
+pub mod control;
+#[doc = "INTCLR (w) register accessor: Interrupt Clear Register\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intclr::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intclr`] module"]
+#[doc(alias = "INTCLR")]
+pub type Intclr = crate::Reg<intclr::IntclrSpec>;
+#[doc = "Interrupt Clear Register"]
+pub mod intclr;
+#[doc = "RIS (r) register accessor: Raw Interrupt Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ris::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ris`] module"]
+#[doc(alias = "RIS")]
+pub type Ris = crate::Reg<ris::RisSpec>;
+#[doc = "Raw Interrupt Status Register"]
+pub mod ris;
+#[doc = "MIS (r) register accessor: Masked Interrupt Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`mis::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@mis`] module"]
+#[doc(alias = "MIS")]
+pub type Mis = crate::Reg<mis::MisSpec>;
+#[doc = "Masked Interrupt Status Register"]
+pub mod mis;
+#[doc = "BGLOAD (rw) register accessor: Background Load Register\n\nYou can [`read`](crate::Reg::read) this register and get [`bgload::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`bgload::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@bgload`] module"]
+#[doc(alias = "BGLOAD")]
+pub type Bgload = crate::Reg<bgload::BgloadSpec>;
+#[doc = "Background Load Register"]
+pub mod bgload;
@@ -0,0 +1,74 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    rx_data_port: RxDataPort,
+    _reserved1: [u8; 0x1c],
+    tx_data_port: TxDataPort,
+    _reserved2: [u8; 0x2c],
+    id_rev: IdRev,
+    irq_cfg: IrqCfg,
+    int_sts: IntSts,
+    int_en: IntEn,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - RX Data FIFO Port"]
+    #[inline(always)]
+    pub const fn rx_data_port(&self) -> &RxDataPort {
+        &self.rx_data_port
+    }
+    #[doc = "0x20 - TX Data FIFO Port"]
+    #[inline(always)]
+    pub const fn tx_data_port(&self) -> &TxDataPort {
+        &self.tx_data_port
+    }
+    #[doc = "0x50 - Chip ID and Revision"]
+    #[inline(always)]
+    pub const fn id_rev(&self) -> &IdRev {
+        &self.id_rev
+    }
+    #[doc = "0x54 - Main Interrupt Configuration"]
+    #[inline(always)]
+    pub const fn irq_cfg(&self) -> &IrqCfg {
+        &self.irq_cfg
+    }
+    #[doc = "0x58 - Interrupt Status"]
+    #[inline(always)]
+    pub const fn int_sts(&self) -> &IntSts {
+        &self.int_sts
+    }
+    #[doc = "0x5c - Interrupt Enable"]
+    #[inline(always)]
+    pub const fn int_en(&self) -> &IntEn {
+        &self.int_en
+    }
+}
+#[doc = "RX_DATA_PORT (r) register accessor: RX Data FIFO Port\n\nYou can [`read`](crate::Reg::read) this register and get [`rx_data_port::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@rx_data_port`] module"]
+#[doc(alias = "RX_DATA_PORT")]
+pub type RxDataPort = crate::Reg<rx_data_port::RxDataPortSpec>;
+#[doc = "RX Data FIFO Port"]
+pub mod rx_data_port;
+#[doc = "TX_DATA_PORT (w) register accessor: TX Data FIFO Port\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`tx_data_port::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@tx_data_port`] module"]
+#[doc(alias = "TX_DATA_PORT")]
+pub type TxDataPort = crate::Reg<tx_data_port::TxDataPortSpec>;
+#[doc = "TX Data FIFO Port"]
+pub mod tx_data_port;
+#[doc = "ID_REV (r) register accessor: Chip ID and Revision\n\nYou can [`read`](crate::Reg::read) this register and get [`id_rev::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@id_rev`] module"]
+#[doc(alias = "ID_REV")]
+pub type IdRev = crate::Reg<id_rev::IdRevSpec>;
+#[doc = "Chip ID and Revision"]
+pub mod id_rev;
+#[doc = "IRQ_CFG (rw) register accessor: Main Interrupt Configuration\n\nYou can [`read`](crate::Reg::read) this register and get [`irq_cfg::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`irq_cfg::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@irq_cfg`] module"]
+#[doc(alias = "IRQ_CFG")]
+pub type IrqCfg = crate::Reg<irq_cfg::IrqCfgSpec>;
+#[doc = "Main Interrupt Configuration"]
+pub mod irq_cfg;
+#[doc = "INT_STS (rw) register accessor: Interrupt Status\n\nYou can [`read`](crate::Reg::read) this register and get [`int_sts::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`int_sts::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@int_sts`] module"]
+#[doc(alias = "INT_STS")]
+pub type IntSts = crate::Reg<int_sts::IntStsSpec>;
+#[doc = "Interrupt Status"]
+pub mod int_sts;
+#[doc = "INT_EN (rw) register accessor: Interrupt Enable\n\nYou can [`read`](crate::Reg::read) this register and get [`int_en::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`int_en::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@int_en`] module"]
+#[doc(alias = "INT_EN")]
+pub type IntEn = crate::Reg<int_en::IntEnSpec>;
+#[doc = "Interrupt Enable"]
+pub mod int_en;
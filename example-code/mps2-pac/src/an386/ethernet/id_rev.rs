@@ -0,0 +1,18 @@
+#[doc = "Register `ID_REV` reader"]
+pub type R = crate::R<IdRevSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+#[doc = "Chip ID and Revision\n\nYou can [`read`](crate::Reg::read) this register and get [`id_rev::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IdRevSpec;
+impl crate::RegisterSpec for IdRevSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`id_rev::R`](R) reader structure"]
+impl crate::Readable for IdRevSpec {}
+#[doc = "`reset()` method sets ID_REV to value 0x0118_0001"]
+impl crate::Resettable for IdRevSpec {
+    const RESET_VALUE: u32 = 0x0118_0001;
+}
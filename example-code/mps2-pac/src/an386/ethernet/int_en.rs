@@ -0,0 +1,23 @@
+#[doc = "Register `INT_EN` reader"]
+pub type R = crate::R<IntEnSpec>;
+#[doc = "Register `INT_EN` writer"]
+pub type W = crate::W<IntEnSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Interrupt Enable\n\nYou can [`read`](crate::Reg::read) this register and get [`int_en::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`int_en::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IntEnSpec;
+impl crate::RegisterSpec for IntEnSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`int_en::R`](R) reader structure"]
+impl crate::Readable for IntEnSpec {}
+#[doc = "`write(|w| ..)` method takes [`int_en::W`](W) writer structure"]
+impl crate::Writable for IntEnSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets INT_EN to value 0"]
+impl crate::Resettable for IntEnSpec {}
@@ -0,0 +1,23 @@
+#[doc = "Register `INT_STS` reader"]
+pub type R = crate::R<IntStsSpec>;
+#[doc = "Register `INT_STS` writer"]
+pub type W = crate::W<IntStsSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Interrupt Status\n\nYou can [`read`](crate::Reg::read) this register and get [`int_sts::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`int_sts::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IntStsSpec;
+impl crate::RegisterSpec for IntStsSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`int_sts::R`](R) reader structure"]
+impl crate::Readable for IntStsSpec {}
+#[doc = "`write(|w| ..)` method takes [`int_sts::W`](W) writer structure"]
+impl crate::Writable for IntStsSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets INT_STS to value 0"]
+impl crate::Resettable for IntStsSpec {}
@@ -0,0 +1,23 @@
+#[doc = "Register `IRQ_CFG` reader"]
+pub type R = crate::R<IrqCfgSpec>;
+#[doc = "Register `IRQ_CFG` writer"]
+pub type W = crate::W<IrqCfgSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Main Interrupt Configuration\n\nYou can [`read`](crate::Reg::read) this register and get [`irq_cfg::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`irq_cfg::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IrqCfgSpec;
+impl crate::RegisterSpec for IrqCfgSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`irq_cfg::R`](R) reader structure"]
+impl crate::Readable for IrqCfgSpec {}
+#[doc = "`write(|w| ..)` method takes [`irq_cfg::W`](W) writer structure"]
+impl crate::Writable for IrqCfgSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets IRQ_CFG to value 0"]
+impl crate::Resettable for IrqCfgSpec {}
@@ -0,0 +1,16 @@
+#[doc = "Register `RX_DATA_PORT` reader"]
+pub type R = crate::R<RxDataPortSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+#[doc = "RX Data FIFO Port\n\nYou can [`read`](crate::Reg::read) this register and get [`rx_data_port::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct RxDataPortSpec;
+impl crate::RegisterSpec for RxDataPortSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`rx_data_port::R`](R) reader structure"]
+impl crate::Readable for RxDataPortSpec {}
+#[doc = "`reset()` method sets RX_DATA_PORT to value 0"]
+impl crate::Resettable for RxDataPortSpec {}
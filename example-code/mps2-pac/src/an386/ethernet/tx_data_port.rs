@@ -0,0 +1,19 @@
+#[doc = "Register `TX_DATA_PORT` writer"]
+pub type W = crate::W<TxDataPortSpec>;
+impl core::fmt::Debug for crate::generic::Reg<TxDataPortSpec> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "(not readable)")
+    }
+}
+impl W {}
+#[doc = "TX Data FIFO Port\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`tx_data_port::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct TxDataPortSpec;
+impl crate::RegisterSpec for TxDataPortSpec {
+    type Ux = u32;
+}
+#[doc = "`write(|w| ..)` method takes [`tx_data_port::W`](W) writer structure"]
+impl crate::Writable for TxDataPortSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets TX_DATA_PORT to value 0"]
+impl crate::Resettable for TxDataPortSpec {}
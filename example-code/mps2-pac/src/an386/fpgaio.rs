@@ -0,0 +1,97 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    led0: Led0,
+    _reserved1: [u8; 0x04],
+    button: Button,
+    _reserved2: [u8; 0x04],
+    clk1hz: Clk1hz,
+    clk100hz: Clk100hz,
+    counter: Counter,
+    prescale: Prescale,
+    pscntr: Pscntr,
+    _reserved7: [u8; 0x28],
+    misc: Misc,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - User LEDs"]
+    #[inline(always)]
+    pub const fn led0(&self) -> &Led0 {
+        &self.led0
+    }
+    #[doc = "0x08 - User push buttons"]
+    #[inline(always)]
+    pub const fn button(&self) -> &Button {
+        &self.button
+    }
+    #[doc = "0x10 - 1 Hz up counter"]
+    #[inline(always)]
+    pub const fn clk1hz(&self) -> &Clk1hz {
+        &self.clk1hz
+    }
+    #[doc = "0x14 - 100 Hz up counter"]
+    #[inline(always)]
+    pub const fn clk100hz(&self) -> &Clk100hz {
+        &self.clk100hz
+    }
+    #[doc = "0x18 - Cycle up counter, incremented when PSCNTR reaches zero"]
+    #[inline(always)]
+    pub const fn counter(&self) -> &Counter {
+        &self.counter
+    }
+    #[doc = "0x1c - Reload value for PSCNTR"]
+    #[inline(always)]
+    pub const fn prescale(&self) -> &Prescale {
+        &self.prescale
+    }
+    #[doc = "0x20 - Prescale down counter"]
+    #[inline(always)]
+    pub const fn pscntr(&self) -> &Pscntr {
+        &self.pscntr
+    }
+    #[doc = "0x4c - Miscellaneous control"]
+    #[inline(always)]
+    pub const fn misc(&self) -> &Misc {
+        &self.misc
+    }
+}
+#[doc = "LED0 (rw) register accessor: User LEDs\n\nYou can [`read`](crate::Reg::read) this register and get [`led0::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`led0::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@led0`] module"]
+#[doc(alias = "LED0")]
+pub type Led0 = crate::Reg<led0::Led0Spec>;
+#[doc = "User LEDs"]
+pub mod led0;
+#[doc = "BUTTON (r) register accessor: User push buttons\n\nYou can [`read`](crate::Reg::read) this register and get [`button::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@button`] module"]
+#[doc(alias = "BUTTON")]
+pub type Button = crate::Reg<button::ButtonSpec>;
+#[doc = "User push buttons"]
+pub mod button;
+#[doc = "CLK1HZ (rw) register accessor: 1 Hz up counter\n\nYou can [`read`](crate::Reg::read) this register and get [`clk1hz::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`clk1hz::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@clk1hz`] module"]
+#[doc(alias = "CLK1HZ")]
+pub type Clk1hz = crate::Reg<clk1hz::Clk1hzSpec>;
+#[doc = "1 Hz up counter"]
+pub mod clk1hz;
+#[doc = "CLK100HZ (rw) register accessor: 100 Hz up counter\n\nYou can [`read`](crate::Reg::read) this register and get [`clk100hz::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`clk100hz::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@clk100hz`] module"]
+#[doc(alias = "CLK100HZ")]
+pub type Clk100hz = crate::Reg<clk100hz::Clk100hzSpec>;
+#[doc = "100 Hz up counter"]
+pub mod clk100hz;
+#[doc = "COUNTER (rw) register accessor: Cycle up counter, incremented when PSCNTR reaches zero\n\nYou can [`read`](crate::Reg::read) this register and get [`counter::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`counter::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@counter`] module"]
+#[doc(alias = "COUNTER")]
+pub type Counter = crate::Reg<counter::CounterSpec>;
+#[doc = "Cycle up counter, incremented when PSCNTR reaches zero"]
+pub mod counter;
+#[doc = "PRESCALE (rw) register accessor: Reload value for PSCNTR\n\nYou can [`read`](crate::Reg::read) this register and get [`prescale::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`prescale::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@prescale`] module"]
+#[doc(alias = "PRESCALE")]
+pub type Prescale = crate::Reg<prescale::PrescaleSpec>;
+#[doc = "Reload value for PSCNTR"]
+pub mod prescale;
+#[doc = "PSCNTR (rw) register accessor: Prescale down counter\n\nYou can [`read`](crate::Reg::read) this register and get [`pscntr::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`pscntr::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@pscntr`] module"]
+#[doc(alias = "PSCNTR")]
+pub type Pscntr = crate::Reg<pscntr::PscntrSpec>;
+#[doc = "Prescale down counter"]
+pub mod pscntr;
+#[doc = "MISC (rw) register accessor: Miscellaneous control\n\nYou can [`read`](crate::Reg::read) this register and get [`misc::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`misc::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@misc`] module"]
+#[doc(alias = "MISC")]
+pub type Misc = crate::Reg<misc::MiscSpec>;
+#[doc = "Miscellaneous control"]
+pub mod misc;
@@ -0,0 +1,23 @@
+#[doc = "Register `CLK100HZ` reader"]
+pub type R = crate::R<Clk100hzSpec>;
+#[doc = "Register `CLK100HZ` writer"]
+pub type W = crate::W<Clk100hzSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "100 Hz up counter\n\nYou can [`read`](crate::Reg::read) this register and get [`clk100hz::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`clk100hz::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct Clk100hzSpec;
+impl crate::RegisterSpec for Clk100hzSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`clk100hz::R`](R) reader structure"]
+impl crate::Readable for Clk100hzSpec {}
+#[doc = "`write(|w| ..)` method takes [`clk100hz::W`](W) writer structure"]
+impl crate::Writable for Clk100hzSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CLK100HZ to value 0"]
+impl crate::Resettable for Clk100hzSpec {}
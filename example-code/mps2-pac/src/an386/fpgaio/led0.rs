@@ -0,0 +1,23 @@
+#[doc = "Register `LED0` reader"]
+pub type R = crate::R<Led0Spec>;
+#[doc = "Register `LED0` writer"]
+pub type W = crate::W<Led0Spec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "User LEDs\n\nYou can [`read`](crate::Reg::read) this register and get [`led0::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`led0::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct Led0Spec;
+impl crate::RegisterSpec for Led0Spec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`led0::R`](R) reader structure"]
+impl crate::Readable for Led0Spec {}
+#[doc = "`write(|w| ..)` method takes [`led0::W`](W) writer structure"]
+impl crate::Writable for Led0Spec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets LED0 to value 0"]
+impl crate::Resettable for Led0Spec {}
@@ -0,0 +1,23 @@
+#[doc = "Register `MISC` reader"]
+pub type R = crate::R<MiscSpec>;
+#[doc = "Register `MISC` writer"]
+pub type W = crate::W<MiscSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Miscellaneous control\n\nYou can [`read`](crate::Reg::read) this register and get [`misc::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`misc::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct MiscSpec;
+impl crate::RegisterSpec for MiscSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`misc::R`](R) reader structure"]
+impl crate::Readable for MiscSpec {}
+#[doc = "`write(|w| ..)` method takes [`misc::W`](W) writer structure"]
+impl crate::Writable for MiscSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets MISC to value 0"]
+impl crate::Resettable for MiscSpec {}
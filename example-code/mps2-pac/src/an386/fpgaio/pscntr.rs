@@ -0,0 +1,23 @@
+#[doc = "Register `PSCNTR` reader"]
+pub type R = crate::R<PscntrSpec>;
+#[doc = "Register `PSCNTR` writer"]
+pub type W = crate::W<PscntrSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Prescale down counter\n\nYou can [`read`](crate::Reg::read) this register and get [`pscntr::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`pscntr::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct PscntrSpec;
+impl crate::RegisterSpec for PscntrSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`pscntr::R`](R) reader structure"]
+impl crate::Readable for PscntrSpec {}
+#[doc = "`write(|w| ..)` method takes [`pscntr::W`](W) writer structure"]
+impl crate::Writable for PscntrSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets PSCNTR to value 0"]
+impl crate::Resettable for PscntrSpec {}
@@ -0,0 +1,129 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    data: Data,
+    dataout: Dataout,
+    _reserved2: [u8; 0x08],
+    outenset: Outenset,
+    outenclr: Outenclr,
+    _reserved4: [u8; 0x08],
+    intenset: Intenset,
+    intenclr: Intenclr,
+    inttypeset: Inttypeset,
+    inttypeclr: Inttypeclr,
+    intpolset: Intpolset,
+    intpolclr: Intpolclr,
+    intstatus: Intstatus,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Data Register (pin levels)"]
+    #[inline(always)]
+    pub const fn data(&self) -> &Data {
+        &self.data
+    }
+    #[doc = "0x04 - Data Output Latch Register"]
+    #[inline(always)]
+    pub const fn dataout(&self) -> &Dataout {
+        &self.dataout
+    }
+    #[doc = "0x10 - Output Enable Set Register"]
+    #[inline(always)]
+    pub const fn outenset(&self) -> &Outenset {
+        &self.outenset
+    }
+    #[doc = "0x14 - Output Enable Clear Register"]
+    #[inline(always)]
+    pub const fn outenclr(&self) -> &Outenclr {
+        &self.outenclr
+    }
+    #[doc = "0x20 - Interrupt Enable Set Register"]
+    #[inline(always)]
+    pub const fn intenset(&self) -> &Intenset {
+        &self.intenset
+    }
+    #[doc = "0x24 - Interrupt Enable Clear Register"]
+    #[inline(always)]
+    pub const fn intenclr(&self) -> &Intenclr {
+        &self.intenclr
+    }
+    #[doc = "0x28 - Interrupt Type Set Register (1 = edge)"]
+    #[inline(always)]
+    pub const fn inttypeset(&self) -> &Inttypeset {
+        &self.inttypeset
+    }
+    #[doc = "0x2c - Interrupt Type Clear Register"]
+    #[inline(always)]
+    pub const fn inttypeclr(&self) -> &Inttypeclr {
+        &self.inttypeclr
+    }
+    #[doc = "0x30 - Interrupt Polarity Set Register (1 = rising/high)"]
+    #[inline(always)]
+    pub const fn intpolset(&self) -> &Intpolset {
+        &self.intpolset
+    }
+    #[doc = "0x34 - Interrupt Polarity Clear Register"]
+    #[inline(always)]
+    pub const fn intpolclr(&self) -> &Intpolclr {
+        &self.intpolclr
+    }
+    #[doc = "0x38 - Interrupt Status / Clear Register (write 1 to clear)"]
+    #[inline(always)]
+    pub const fn intstatus(&self) -> &Intstatus {
+        &self.intstatus
+    }
+}
+#[doc = "DATA (rw) register accessor: Data Register (pin levels)\n\nYou can [`read`](crate::Reg::read) this register and get [`data::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`data::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@data`] module"]
+#[doc(alias = "DATA")]
+pub type Data = crate::Reg<data::DataSpec>;
+#[doc = "Data Register (pin levels)"]
+pub mod data;
+#[doc = "DATAOUT (rw) register accessor: Data Output Latch Register\n\nYou can [`read`](crate::Reg::read) this register and get [`dataout::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`dataout::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@dataout`] module"]
+#[doc(alias = "DATAOUT")]
+pub type Dataout = crate::Reg<dataout::DataoutSpec>;
+#[doc = "Data Output Latch Register"]
+pub mod dataout;
+#[doc = "OUTENSET (rw) register accessor: Output Enable Set Register\n\nYou can [`read`](crate::Reg::read) this register and get [`outenset::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`outenset::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@outenset`] module"]
+#[doc(alias = "OUTENSET")]
+pub type Outenset = crate::Reg<outenset::OutensetSpec>;
+#[doc = "Output Enable Set Register"]
+pub mod outenset;
+#[doc = "OUTENCLR (rw) register accessor: Output Enable Clear Register\n\nYou can [`read`](crate::Reg::read) this register and get [`outenclr::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`outenclr::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@outenclr`] module"]
+#[doc(alias = "OUTENCLR")]
+pub type Outenclr = crate::Reg<outenclr::OutenclrSpec>;
+#[doc = "Output Enable Clear Register"]
+pub mod outenclr;
+#[doc = "INTENSET (rw) register accessor: Interrupt Enable Set Register\n\nYou can [`read`](crate::Reg::read) this register and get [`intenset::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intenset::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intenset`] module"]
+#[doc(alias = "INTENSET")]
+pub type Intenset = crate::Reg<intenset::IntensetSpec>;
+#[doc = "Interrupt Enable Set Register"]
+pub mod intenset;
+#[doc = "INTENCLR (rw) register accessor: Interrupt Enable Clear Register\n\nYou can [`read`](crate::Reg::read) this register and get [`intenclr::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intenclr::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intenclr`] module"]
+#[doc(alias = "INTENCLR")]
+pub type Intenclr = crate::Reg<intenclr::IntenclrSpec>;
+#[doc = "Interrupt Enable Clear Register"]
+pub mod intenclr;
+#[doc = "INTTYPESET (rw) register accessor: Interrupt Type Set Register (1 = edge)\n\nYou can [`read`](crate::Reg::read) this register and get [`inttypeset::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`inttypeset::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@inttypeset`] module"]
+#[doc(alias = "INTTYPESET")]
+pub type Inttypeset = crate::Reg<inttypeset::InttypesetSpec>;
+#[doc = "Interrupt Type Set Register (1 = edge)"]
+pub mod inttypeset;
+#[doc = "INTTYPECLR (rw) register accessor: Interrupt Type Clear Register\n\nYou can [`read`](crate::Reg::read) this register and get [`inttypeclr::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`inttypeclr::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@inttypeclr`] module"]
+#[doc(alias = "INTTYPECLR")]
+pub type Inttypeclr = crate::Reg<inttypeclr::InttypeclrSpec>;
+#[doc = "Interrupt Type Clear Register"]
+pub mod inttypeclr;
+#[doc = "INTPOLSET (rw) register accessor: Interrupt Polarity Set Register (1 = rising/high)\n\nYou can [`read`](crate::Reg::read) this register and get [`intpolset::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intpolset::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intpolset`] module"]
+#[doc(alias = "INTPOLSET")]
+pub type Intpolset = crate::Reg<intpolset::IntpolsetSpec>;
+#[doc = "Interrupt Polarity Set Register (1 = rising/high)"]
+pub mod intpolset;
+#[doc = "INTPOLCLR (rw) register accessor: Interrupt Polarity Clear Register\n\nYou can [`read`](crate::Reg::read) this register and get [`intpolclr::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intpolclr::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intpolclr`] module"]
+#[doc(alias = "INTPOLCLR")]
+pub type Intpolclr = crate::Reg<intpolclr::IntpolclrSpec>;
+#[doc = "Interrupt Polarity Clear Register"]
+pub mod intpolclr;
+#[doc = "INTSTATUS (rw) register accessor: Interrupt Status / Clear Register (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`intstatus::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intstatus::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intstatus`] module"]
+#[doc(alias = "INTSTATUS")]
+pub type Intstatus = crate::Reg<intstatus::IntstatusSpec>;
+#[doc = "Interrupt Status / Clear Register (write 1 to clear)"]
+pub mod intstatus;
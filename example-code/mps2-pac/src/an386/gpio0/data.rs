@@ -0,0 +1,23 @@
+#[doc = "Register `DATA` reader"]
+pub type R = crate::R<DataSpec>;
+#[doc = "Register `DATA` writer"]
+pub type W = crate::W<DataSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Data Register (pin levels)\n\nYou can [`read`](crate::Reg::read) this register and get [`data::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`data::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct DataSpec;
+impl crate::RegisterSpec for DataSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`data::R`](R) reader structure"]
+impl crate::Readable for DataSpec {}
+#[doc = "`write(|w| ..)` method takes [`data::W`](W) writer structure"]
+impl crate::Writable for DataSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets DATA to value 0"]
+impl crate::Resettable for DataSpec {}
@@ -0,0 +1,23 @@
+#[doc = "Register `INTPOLSET` reader"]
+pub type R = crate::R<IntpolsetSpec>;
+#[doc = "Register `INTPOLSET` writer"]
+pub type W = crate::W<IntpolsetSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Interrupt Polarity Set Register (1 = rising/high)\n\nYou can [`read`](crate::Reg::read) this register and get [`intpolset::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intpolset::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IntpolsetSpec;
+impl crate::RegisterSpec for IntpolsetSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`intpolset::R`](R) reader structure"]
+impl crate::Readable for IntpolsetSpec {}
+#[doc = "`write(|w| ..)` method takes [`intpolset::W`](W) writer structure"]
+impl crate::Writable for IntpolsetSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets INTPOLSET to value 0"]
+impl crate::Resettable for IntpolsetSpec {}
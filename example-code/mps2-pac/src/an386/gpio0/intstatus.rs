@@ -0,0 +1,23 @@
+#[doc = "Register `INTSTATUS` reader"]
+pub type R = crate::R<IntstatusSpec>;
+#[doc = "Register `INTSTATUS` writer"]
+pub type W = crate::W<IntstatusSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Interrupt Status / Clear Register (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`intstatus::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intstatus::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IntstatusSpec;
+impl crate::RegisterSpec for IntstatusSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`intstatus::R`](R) reader structure"]
+impl crate::Readable for IntstatusSpec {}
+#[doc = "`write(|w| ..)` method takes [`intstatus::W`](W) writer structure"]
+impl crate::Writable for IntstatusSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets INTSTATUS to value 0"]
+impl crate::Resettable for IntstatusSpec {}
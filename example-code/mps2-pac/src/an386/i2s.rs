@@ -0,0 +1,72 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    control: Control,
+    status: Status,
+    error: Error,
+    divide: Divide,
+    txbuf: Txbuf,
+    rxbuf: Rxbuf,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Control Register"]
+    #[inline(always)]
+    pub const fn control(&self) -> &Control {
+        &self.control
+    }
+    #[doc = "0x04 - Status Register"]
+    #[inline(always)]
+    pub const fn status(&self) -> &Status {
+        &self.status
+    }
+    #[doc = "0x08 - Error Status Register"]
+    #[inline(always)]
+    pub const fn error(&self) -> &Error {
+        &self.error
+    }
+    #[doc = "0x0c - Clock Divide Ratio Register"]
+    #[inline(always)]
+    pub const fn divide(&self) -> &Divide {
+        &self.divide
+    }
+    #[doc = "0x10 - Transmit Buffer FIFO Data Register"]
+    #[inline(always)]
+    pub const fn txbuf(&self) -> &Txbuf {
+        &self.txbuf
+    }
+    #[doc = "0x14 - Receive Buffer FIFO Data Register"]
+    #[inline(always)]
+    pub const fn rxbuf(&self) -> &Rxbuf {
+        &self.rxbuf
+    }
+}
+#[doc = "CONTROL (rw) register accessor: Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@control`] module"]
+#[doc(alias = "CONTROL")]
+pub type Control = crate::Reg<control::ControlSpec>;
+#[doc = "Control Register"]
+pub mod control;
+#[doc = "STATUS (r) register accessor: Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`status::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@status`] module"]
+#[doc(alias = "STATUS")]
+pub type Status = crate::Reg<status::StatusSpec>;
+#[doc = "Status Register"]
+pub mod status;
+#[doc = "ERROR (rw) register accessor: Error Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`error::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`error::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@error`] module"]
+#[doc(alias = "ERROR")]
+pub type Error = crate::Reg<error::ErrorSpec>;
+#[doc = "Error Status Register"]
+pub mod error;
+#[doc = "DIVIDE (rw) register accessor: Clock Divide Ratio Register\n\nYou can [`read`](crate::Reg::read) this register and get [`divide::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`divide::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@divide`] module"]
+#[doc(alias = "DIVIDE")]
+pub type Divide = crate::Reg<divide::DivideSpec>;
+#[doc = "Clock Divide Ratio Register"]
+pub mod divide;
+#[doc = "TXBUF (w) register accessor: Transmit Buffer FIFO Data Register\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`txbuf::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@txbuf`] module"]
+#[doc(alias = "TXBUF")]
+pub type Txbuf = crate::Reg<txbuf::TxbufSpec>;
+#[doc = "Transmit Buffer FIFO Data Register"]
+pub mod txbuf;
+#[doc = "RXBUF (r) register accessor: Receive Buffer FIFO Data Register\n\nYou can [`read`](crate::Reg::read) this register and get [`rxbuf::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@rxbuf`] module"]
+#[doc(alias = "RXBUF")]
+pub type Rxbuf = crate::Reg<rxbuf::RxbufSpec>;
+#[doc = "Receive Buffer FIFO Data Register"]
+pub mod rxbuf;
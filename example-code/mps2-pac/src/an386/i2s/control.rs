@@ -0,0 +1,23 @@
+#[doc = "Register `CONTROL` reader"]
+pub type R = crate::R<ControlSpec>;
+#[doc = "Register `CONTROL` writer"]
+pub type W = crate::W<ControlSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ControlSpec;
+impl crate::RegisterSpec for ControlSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`control::R`](R) reader structure"]
+impl crate::Readable for ControlSpec {}
+#[doc = "`write(|w| ..)` method takes [`control::W`](W) writer structure"]
+impl crate::Writable for ControlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CONTROL to value 0"]
+impl crate::Resettable for ControlSpec {}
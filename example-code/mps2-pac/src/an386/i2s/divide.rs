@@ -0,0 +1,23 @@
+#[doc = "Register `DIVIDE` reader"]
+pub type R = crate::R<DivideSpec>;
+#[doc = "Register `DIVIDE` writer"]
+pub type W = crate::W<DivideSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Clock Divide Ratio Register\n\nYou can [`read`](crate::Reg::read) this register and get [`divide::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`divide::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct DivideSpec;
+impl crate::RegisterSpec for DivideSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`divide::R`](R) reader structure"]
+impl crate::Readable for DivideSpec {}
+#[doc = "`write(|w| ..)` method takes [`divide::W`](W) writer structure"]
+impl crate::Writable for DivideSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets DIVIDE to value 0"]
+impl crate::Resettable for DivideSpec {}
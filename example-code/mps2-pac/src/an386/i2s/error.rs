@@ -0,0 +1,23 @@
+#[doc = "Register `ERROR` reader"]
+pub type R = crate::R<ErrorSpec>;
+#[doc = "Register `ERROR` writer"]
+pub type W = crate::W<ErrorSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Error Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`error::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`error::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ErrorSpec;
+impl crate::RegisterSpec for ErrorSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`error::R`](R) reader structure"]
+impl crate::Readable for ErrorSpec {}
+#[doc = "`write(|w| ..)` method takes [`error::W`](W) writer structure"]
+impl crate::Writable for ErrorSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets ERROR to value 0"]
+impl crate::Resettable for ErrorSpec {}
@@ -0,0 +1,462 @@
+#[doc = r"Number available in the NVIC for configuring priority"]
+pub const NVIC_PRIO_BITS: u8 = 3;
+#[allow(unused_imports)]
+use generic::*;
+#[doc = r"Common register and bit access and modify traits"]
+pub mod generic;
+#[cfg(feature = "rt")]
+extern "C" {
+    fn UART0_RX();
+    fn UART0_TX();
+    fn UART1_RX();
+    fn UART1_TX();
+    fn UART2_RX();
+    fn UART2_TX();
+    fn GPIO0_COMBINED();
+    fn GPIO1_COMBINED();
+    fn TIMER0();
+    fn TIMER1();
+    fn DUALTIMER();
+    fn SPI0_SPI1();
+    fn UART_OVERFLOW();
+    fn ETHERNET();
+    fn AUDIO_I2S();
+    fn TOUCHSCREEN();
+    fn GPIO2_COMBINED();
+    fn GPIO3_COMBINED();
+    fn UART3_RX();
+    fn UART3_TX();
+    fn UART4_RX();
+    fn UART4_TX();
+    fn SPI2();
+    fn SPI3_SPI4();
+    fn GPIO0_0();
+    fn GPIO0_1();
+    fn GPIO0_2();
+    fn GPIO0_3();
+    fn GPIO0_4();
+    fn GPIO0_5();
+    fn GPIO0_6();
+    fn GPIO0_7();
+}
+#[doc(hidden)]
+#[repr(C)]
+pub union Vector {
+    _handler: unsafe extern "C" fn(),
+    _reserved: u32,
+}
+#[cfg(feature = "rt")]
+#[doc(hidden)]
+#[link_section = ".vector_table.interrupts"]
+#[no_mangle]
+pub static __INTERRUPTS: [Vector; 32] = [
+    Vector { _handler: UART0_RX },
+    Vector { _handler: UART0_TX },
+    Vector { _handler: UART1_RX },
+    Vector { _handler: UART1_TX },
+    Vector { _handler: UART2_RX },
+    Vector { _handler: UART2_TX },
+    Vector {
+        _handler: GPIO0_COMBINED,
+    },
+    Vector {
+        _handler: GPIO1_COMBINED,
+    },
+    Vector { _handler: TIMER0 },
+    Vector { _handler: TIMER1 },
+    Vector {
+        _handler: DUALTIMER,
+    },
+    Vector {
+        _handler: SPI0_SPI1,
+    },
+    Vector {
+        _handler: UART_OVERFLOW,
+    },
+    Vector { _handler: ETHERNET },
+    Vector {
+        _handler: AUDIO_I2S,
+    },
+    Vector {
+        _handler: TOUCHSCREEN,
+    },
+    Vector {
+        _handler: GPIO2_COMBINED,
+    },
+    Vector {
+        _handler: GPIO3_COMBINED,
+    },
+    Vector { _handler: UART3_RX },
+    Vector { _handler: UART3_TX },
+    Vector { _handler: UART4_RX },
+    Vector { _handler: UART4_TX },
+    Vector { _handler: SPI2 },
+    Vector {
+        _handler: SPI3_SPI4,
+    },
+    Vector { _handler: GPIO0_0 },
+    Vector { _handler: GPIO0_1 },
+    Vector { _handler: GPIO0_2 },
+    Vector { _handler: GPIO0_3 },
+    Vector { _handler: GPIO0_4 },
+    Vector { _handler: GPIO0_5 },
+    Vector { _handler: GPIO0_6 },
+    Vector { _handler: GPIO0_7 },
+];
+#[doc = r"Enumeration of all the interrupts."]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum Interrupt {
+    #[doc = "0 - UART 0 Receive"]
+    UART0_RX = 0,
+    #[doc = "1 - UART 0 Transmit"]
+    UART0_TX = 1,
+    #[doc = "2 - UART 1 Receive"]
+    UART1_RX = 2,
+    #[doc = "3 - UART 1 Transmit"]
+    UART1_TX = 3,
+    #[doc = "4 - UART 2 Receive"]
+    UART2_RX = 4,
+    #[doc = "5 - UART 2 Transmit"]
+    UART2_TX = 5,
+    #[doc = "6 - GPIO 0 Combined"]
+    GPIO0_COMBINED = 6,
+    #[doc = "7 - GPIO 1 Combined"]
+    GPIO1_COMBINED = 7,
+    #[doc = "8 - Timer 0"]
+    TIMER0 = 8,
+    #[doc = "9 - Timer 1"]
+    TIMER1 = 9,
+    #[doc = "10 - Dual Timer"]
+    DUALTIMER = 10,
+    #[doc = "11 - SPI0 and SPI1"]
+    SPI0_SPI1 = 11,
+    #[doc = "12 - UART0, UART1 and UART2 Overflow"]
+    UART_OVERFLOW = 12,
+    #[doc = "13 - Ethernet"]
+    ETHERNET = 13,
+    #[doc = "14 - Audio I²S"]
+    AUDIO_I2S = 14,
+    #[doc = "15 - Touch Screen"]
+    TOUCHSCREEN = 15,
+    #[doc = "16 - GPIO 2 Combined"]
+    GPIO2_COMBINED = 16,
+    #[doc = "17 - GPIO 3 Combined"]
+    GPIO3_COMBINED = 17,
+    #[doc = "18 - UART 3 Receive"]
+    UART3_RX = 18,
+    #[doc = "19 - UART 3 Transmit"]
+    UART3_TX = 19,
+    #[doc = "20 - UART 4 Receive"]
+    UART4_RX = 20,
+    #[doc = "21 - UART 4 Transmit"]
+    UART4_TX = 21,
+    #[doc = "22 - SPI2"]
+    SPI2 = 22,
+    #[doc = "23 - SPI3 and SPI4"]
+    SPI3_SPI4 = 23,
+    #[doc = "24 - GPIO 0, Pin 0"]
+    GPIO0_0 = 24,
+    #[doc = "25 - GPIO 0, Pin 1"]
+    GPIO0_1 = 25,
+    #[doc = "26 - GPIO 0, Pin 2"]
+    GPIO0_2 = 26,
+    #[doc = "27 - GPIO 0, Pin 3"]
+    GPIO0_3 = 27,
+    #[doc = "28 - GPIO 0, Pin 4"]
+    GPIO0_4 = 28,
+    #[doc = "29 - GPIO 0, Pin 5"]
+    GPIO0_5 = 29,
+    #[doc = "30 - GPIO 0, Pin 6"]
+    GPIO0_6 = 30,
+    #[doc = "31 - GPIO 0, Pin 7"]
+    GPIO0_7 = 31,
+}
+unsafe impl cortex_m::interrupt::InterruptNumber for Interrupt {
+    #[inline(always)]
+    fn number(self) -> u16 {
+        self as u16
+    }
+}
+#[doc = "CMSDK APB Timer"]
+pub type Timer0 = crate::Periph<timer0::RegisterBlock, 0x4000_0000>;
+impl core::fmt::Debug for Timer0 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Timer0").finish()
+    }
+}
+#[doc = "CMSDK APB Timer"]
+pub mod timer0;
+#[doc = "CMSDK APB Timer"]
+pub type Timer1 = crate::Periph<timer0::RegisterBlock, 0x4000_1000>;
+impl core::fmt::Debug for Timer1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Timer1").finish()
+    }
+}
+#[doc = "CMSDK APB Timer"]
+pub use self::timer0 as timer1;
+#[doc = "CMSDK APB Dual Timer"]
+pub type Dualtimer = crate::Periph<dualtimer::RegisterBlock, 0x4000_2000>;
+impl core::fmt::Debug for Dualtimer {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Dualtimer").finish()
+    }
+}
+#[doc = "CMSDK APB Dual Timer"]
+pub mod dualtimer;
+#[doc = "CMSDK APB UART"]
+pub type Uart0 = crate::Periph<uart0::RegisterBlock, 0x4000_4000>;
+impl core::fmt::Debug for Uart0 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart0").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub mod uart0;
+#[doc = "CMSDK APB UART"]
+pub type Uart1 = crate::Periph<uart0::RegisterBlock, 0x4000_5000>;
+impl core::fmt::Debug for Uart1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart1").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart1;
+#[doc = "CMSDK APB UART"]
+pub type Uart2 = crate::Periph<uart0::RegisterBlock, 0x4000_6000>;
+impl core::fmt::Debug for Uart2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart2").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart2;
+#[doc = "CMSDK APB UART"]
+pub type Uart3 = crate::Periph<uart0::RegisterBlock, 0x4000_7000>;
+impl core::fmt::Debug for Uart3 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart3").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart3;
+#[doc = "CMSDK APB UART"]
+pub type Uart4 = crate::Periph<uart0::RegisterBlock, 0x4000_9000>;
+impl core::fmt::Debug for Uart4 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart4").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart4;
+#[doc = "CMSDK AHB GPIO"]
+pub type Gpio0 = crate::Periph<gpio0::RegisterBlock, 0x4001_0000>;
+impl core::fmt::Debug for Gpio0 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Gpio0").finish()
+    }
+}
+#[doc = "CMSDK AHB GPIO"]
+pub mod gpio0;
+#[doc = "CMSDK AHB GPIO"]
+pub type Gpio1 = crate::Periph<gpio0::RegisterBlock, 0x4001_1000>;
+impl core::fmt::Debug for Gpio1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Gpio1").finish()
+    }
+}
+#[doc = "CMSDK AHB GPIO"]
+pub use self::gpio0 as gpio1;
+#[doc = "CMSDK AHB GPIO"]
+pub type Gpio2 = crate::Periph<gpio0::RegisterBlock, 0x4001_2000>;
+impl core::fmt::Debug for Gpio2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Gpio2").finish()
+    }
+}
+#[doc = "CMSDK AHB GPIO"]
+pub use self::gpio0 as gpio2;
+#[doc = "CMSDK AHB GPIO"]
+pub type Gpio3 = crate::Periph<gpio0::RegisterBlock, 0x4001_3000>;
+impl core::fmt::Debug for Gpio3 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Gpio3").finish()
+    }
+}
+#[doc = "CMSDK AHB GPIO"]
+pub use self::gpio0 as gpio3;
+#[doc = "PL022 SPI (user SPI)"]
+pub type Spi0 = crate::Periph<spi0::RegisterBlock, 0x4002_0000>;
+impl core::fmt::Debug for Spi0 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Spi0").finish()
+    }
+}
+#[doc = "PL022 SPI (user SPI)"]
+pub mod spi0;
+#[doc = "PL022 SPI (LCD SPI)"]
+pub type Spi1 = crate::Periph<spi0::RegisterBlock, 0x4002_1000>;
+impl core::fmt::Debug for Spi1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Spi1").finish()
+    }
+}
+#[doc = "PL022 SPI (LCD SPI)"]
+pub use self::spi0 as spi1;
+#[doc = "PL022 SPI (ADC SPI)"]
+pub type Spi2 = crate::Periph<spi0::RegisterBlock, 0x4002_5000>;
+impl core::fmt::Debug for Spi2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Spi2").finish()
+    }
+}
+#[doc = "PL022 SPI (ADC SPI)"]
+pub use self::spi0 as spi2;
+#[doc = "PL022 SPI (shield 0 SPI)"]
+pub type Spi3 = crate::Periph<spi0::RegisterBlock, 0x4002_6000>;
+impl core::fmt::Debug for Spi3 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Spi3").finish()
+    }
+}
+#[doc = "PL022 SPI (shield 0 SPI)"]
+pub use self::spi0 as spi3;
+#[doc = "PL022 SPI (shield 1 SPI)"]
+pub type Spi4 = crate::Periph<spi0::RegisterBlock, 0x4002_7000>;
+impl core::fmt::Debug for Spi4 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Spi4").finish()
+    }
+}
+#[doc = "PL022 SPI (shield 1 SPI)"]
+pub use self::spi0 as spi4;
+#[doc = "SBCon I2C interface to the touch screen controller"]
+pub type TscI2c = crate::Periph<tsc_i2c::RegisterBlock, 0x4002_2000>;
+impl core::fmt::Debug for TscI2c {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("TscI2c").finish()
+    }
+}
+#[doc = "SBCon I2C interface to the touch screen controller"]
+pub mod tsc_i2c;
+#[doc = "Audio I2S interface"]
+pub type I2s = crate::Periph<i2s::RegisterBlock, 0x4002_4000>;
+impl core::fmt::Debug for I2s {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("I2s").finish()
+    }
+}
+#[doc = "Audio I2S interface"]
+pub mod i2s;
+#[doc = "FPGA system control and I/O"]
+pub type Fpgaio = crate::Periph<fpgaio::RegisterBlock, 0x4002_8000>;
+impl core::fmt::Debug for Fpgaio {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Fpgaio").finish()
+    }
+}
+#[doc = "FPGA system control and I/O"]
+pub mod fpgaio;
+#[doc = "SMSC LAN9220 Ethernet controller"]
+pub type Ethernet = crate::Periph<ethernet::RegisterBlock, 0x4020_0000>;
+impl core::fmt::Debug for Ethernet {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Ethernet").finish()
+    }
+}
+#[doc = "SMSC LAN9220 Ethernet controller"]
+pub mod ethernet;
+#[no_mangle]
+static mut DEVICE_PERIPHERALS: bool = false;
+#[doc = r" All the peripherals."]
+#[allow(non_snake_case)]
+pub struct Peripherals {
+    #[doc = "TIMER0"]
+    pub timer0: Timer0,
+    #[doc = "TIMER1"]
+    pub timer1: Timer1,
+    #[doc = "DUALTIMER"]
+    pub dualtimer: Dualtimer,
+    #[doc = "UART0"]
+    pub uart0: Uart0,
+    #[doc = "UART1"]
+    pub uart1: Uart1,
+    #[doc = "UART2"]
+    pub uart2: Uart2,
+    #[doc = "UART3"]
+    pub uart3: Uart3,
+    #[doc = "UART4"]
+    pub uart4: Uart4,
+    #[doc = "GPIO0"]
+    pub gpio0: Gpio0,
+    #[doc = "GPIO1"]
+    pub gpio1: Gpio1,
+    #[doc = "GPIO2"]
+    pub gpio2: Gpio2,
+    #[doc = "GPIO3"]
+    pub gpio3: Gpio3,
+    #[doc = "SPI0"]
+    pub spi0: Spi0,
+    #[doc = "SPI1"]
+    pub spi1: Spi1,
+    #[doc = "SPI2"]
+    pub spi2: Spi2,
+    #[doc = "SPI3"]
+    pub spi3: Spi3,
+    #[doc = "SPI4"]
+    pub spi4: Spi4,
+    #[doc = "TSC_I2C"]
+    pub tsc_i2c: TscI2c,
+    #[doc = "I2S"]
+    pub i2s: I2s,
+    #[doc = "FPGAIO"]
+    pub fpgaio: Fpgaio,
+    #[doc = "ETHERNET"]
+    pub ethernet: Ethernet,
+}
+impl Peripherals {
+    #[doc = r" Returns all the peripherals *once*."]
+    #[cfg(feature = "critical-section")]
+    #[inline]
+    pub fn take() -> Option<Self> {
+        critical_section::with(|_| {
+            if unsafe { DEVICE_PERIPHERALS } {
+                return None;
+            }
+            Some(unsafe { Peripherals::steal() })
+        })
+    }
+    #[doc = r" Unchecked version of `Peripherals::take`."]
+    #[doc = r""]
+    #[doc = r" # Safety"]
+    #[doc = r""]
+    #[doc = r" Each of the returned peripherals must be used at most once."]
+    #[inline]
+    pub unsafe fn steal() -> Self {
+        DEVICE_PERIPHERALS = true;
+        Peripherals {
+            timer0: Timer0::steal(),
+            timer1: Timer1::steal(),
+            dualtimer: Dualtimer::steal(),
+            uart0: Uart0::steal(),
+            uart1: Uart1::steal(),
+            uart2: Uart2::steal(),
+            uart3: Uart3::steal(),
+            uart4: Uart4::steal(),
+            gpio0: Gpio0::steal(),
+            gpio1: Gpio1::steal(),
+            gpio2: Gpio2::steal(),
+            gpio3: Gpio3::steal(),
+            spi0: Spi0::steal(),
+            spi1: Spi1::steal(),
+            spi2: Spi2::steal(),
+            spi3: Spi3::steal(),
+            spi4: Spi4::steal(),
+            tsc_i2c: TscI2c::steal(),
+            i2s: I2s::steal(),
+            fpgaio: Fpgaio::steal(),
+            ethernet: Ethernet::steal(),
+        }
+    }
+}
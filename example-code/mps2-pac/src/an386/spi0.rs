@@ -0,0 +1,105 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    cr0: Cr0,
+    cr1: Cr1,
+    dr: Dr,
+    sr: Sr,
+    cpsr: Cpsr,
+    imsc: Imsc,
+    ris: Ris,
+    mis: Mis,
+    icr: Icr,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Control Register 0"]
+    #[inline(always)]
+    pub const fn cr0(&self) -> &Cr0 {
+        &self.cr0
+    }
+    #[doc = "0x04 - Control Register 1"]
+    #[inline(always)]
+    pub const fn cr1(&self) -> &Cr1 {
+        &self.cr1
+    }
+    #[doc = "0x08 - Data Register"]
+    #[inline(always)]
+    pub const fn dr(&self) -> &Dr {
+        &self.dr
+    }
+    #[doc = "0x0c - Status Register"]
+    #[inline(always)]
+    pub const fn sr(&self) -> &Sr {
+        &self.sr
+    }
+    #[doc = "0x10 - Clock Prescale Register"]
+    #[inline(always)]
+    pub const fn cpsr(&self) -> &Cpsr {
+        &self.cpsr
+    }
+    #[doc = "0x14 - Interrupt Mask Set/Clear Register"]
+    #[inline(always)]
+    pub const fn imsc(&self) -> &Imsc {
+        &self.imsc
+    }
+    #[doc = "0x18 - Raw Interrupt Status Register"]
+    #[inline(always)]
+    pub const fn ris(&self) -> &Ris {
+        &self.ris
+    }
+    #[doc = "0x1c - Masked Interrupt Status Register"]
+    #[inline(always)]
+    pub const fn mis(&self) -> &Mis {
+        &self.mis
+    }
+    #[doc = "0x20 - Interrupt Clear Register"]
+    #[inline(always)]
+    pub const fn icr(&self) -> &Icr {
+        &self.icr
+    }
+}
+#[doc = "CR0 (rw) register accessor: Control Register 0\n\nYou can [`read`](crate::Reg::read) this register and get [`cr0::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`cr0::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@cr0`] module"]
+#[doc(alias = "CR0")]
+pub type Cr0 = crate::Reg<cr0::Cr0Spec>;
+#[doc = "Control Register 0"]
+pub mod cr0;
+#[doc = "CR1 (rw) register accessor: Control Register 1\n\nYou can [`read`](crate::Reg::read) this register and get [`cr1::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`cr1::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@cr1`] module"]
+#[doc(alias = "CR1")]
+pub type Cr1 = crate::Reg<cr1::Cr1Spec>;
+#[doc = "Control Register 1"]
+pub mod cr1;
+#[doc = "DR (rw) register accessor: Data Register\n\nYou can [`read`](crate::Reg::read) this register and get [`dr::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`dr::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@dr`] module"]
+#[doc(alias = "DR")]
+pub type Dr = crate::Reg<dr::DrSpec>;
+#[doc = "Data Register"]
+pub mod dr;
+#[doc = "SR (r) register accessor: Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`sr::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@sr`] module"]
+#[doc(alias = "SR")]
+pub type Sr = crate::Reg<sr::SrSpec>;
+#[doc = "Status Register"]
+pub mod sr;
+#[doc = "CPSR (rw) register accessor: Clock Prescale Register\n\nYou can [`read`](crate::Reg::read) this register and get [`cpsr::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`cpsr::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@cpsr`] module"]
+#[doc(alias = "CPSR")]
+pub type Cpsr = crate::Reg<cpsr::CpsrSpec>;
+#[doc = "Clock Prescale Register"]
+pub mod cpsr;
+#[doc = "IMSC (rw) register accessor: Interrupt Mask Set/Clear Register\n\nYou can [`read`](crate::Reg::read) this register and get [`imsc::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`imsc::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@imsc`] module"]
+#[doc(alias = "IMSC")]
+pub type Imsc = crate::Reg<imsc::ImscSpec>;
+#[doc = "Interrupt Mask Set/Clear Register"]
+pub mod imsc;
+#[doc = "RIS (r) register accessor: Raw Interrupt Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ris::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ris`] module"]
+#[doc(alias = "RIS")]
+pub type Ris = crate::Reg<ris::RisSpec>;
+#[doc = "Raw Interrupt Status Register"]
+pub mod ris;
+#[doc = "MIS (r) register accessor: Masked Interrupt Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`mis::R`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@mis`] module"]
+#[doc(alias = "MIS")]
+pub type Mis = crate::Reg<mis::MisSpec>;
+#[doc = "Masked Interrupt Status Register"]
+pub mod mis;
+#[doc = "ICR (w) register accessor: Interrupt Clear Register\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`icr::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@icr`] module"]
+#[doc(alias = "ICR")]
+pub type Icr = crate::Reg<icr::IcrSpec>;
+#[doc = "Interrupt Clear Register"]
+pub mod icr;
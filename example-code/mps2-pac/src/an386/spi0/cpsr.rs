@@ -0,0 +1,23 @@
+#[doc = "Register `CPSR` reader"]
+pub type R = crate::R<CpsrSpec>;
+#[doc = "Register `CPSR` writer"]
+pub type W = crate::W<CpsrSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Clock Prescale Register\n\nYou can [`read`](crate::Reg::read) this register and get [`cpsr::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`cpsr::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct CpsrSpec;
+impl crate::RegisterSpec for CpsrSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`cpsr::R`](R) reader structure"]
+impl crate::Readable for CpsrSpec {}
+#[doc = "`write(|w| ..)` method takes [`cpsr::W`](W) writer structure"]
+impl crate::Writable for CpsrSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CPSR to value 0"]
+impl crate::Resettable for CpsrSpec {}
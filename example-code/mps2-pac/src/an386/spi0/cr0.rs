@@ -0,0 +1,23 @@
+#[doc = "Register `CR0` reader"]
+pub type R = crate::R<Cr0Spec>;
+#[doc = "Register `CR0` writer"]
+pub type W = crate::W<Cr0Spec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Control Register 0\n\nYou can [`read`](crate::Reg::read) this register and get [`cr0::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`cr0::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct Cr0Spec;
+impl crate::RegisterSpec for Cr0Spec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`cr0::R`](R) reader structure"]
+impl crate::Readable for Cr0Spec {}
+#[doc = "`write(|w| ..)` method takes [`cr0::W`](W) writer structure"]
+impl crate::Writable for Cr0Spec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CR0 to value 0"]
+impl crate::Resettable for Cr0Spec {}
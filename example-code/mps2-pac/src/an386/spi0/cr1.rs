@@ -0,0 +1,23 @@
+#[doc = "Register `CR1` reader"]
+pub type R = crate::R<Cr1Spec>;
+#[doc = "Register `CR1` writer"]
+pub type W = crate::W<Cr1Spec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Control Register 1\n\nYou can [`read`](crate::Reg::read) this register and get [`cr1::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`cr1::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct Cr1Spec;
+impl crate::RegisterSpec for Cr1Spec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`cr1::R`](R) reader structure"]
+impl crate::Readable for Cr1Spec {}
+#[doc = "`write(|w| ..)` method takes [`cr1::W`](W) writer structure"]
+impl crate::Writable for Cr1Spec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CR1 to value 0"]
+impl crate::Resettable for Cr1Spec {}
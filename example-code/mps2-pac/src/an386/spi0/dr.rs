@@ -0,0 +1,23 @@
+#[doc = "Register `DR` reader"]
+pub type R = crate::R<DrSpec>;
+#[doc = "Register `DR` writer"]
+pub type W = crate::W<DrSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Data Register\n\nYou can [`read`](crate::Reg::read) this register and get [`dr::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`dr::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct DrSpec;
+impl crate::RegisterSpec for DrSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`dr::R`](R) reader structure"]
+impl crate::Readable for DrSpec {}
+#[doc = "`write(|w| ..)` method takes [`dr::W`](W) writer structure"]
+impl crate::Writable for DrSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets DR to value 0"]
+impl crate::Resettable for DrSpec {}
@@ -0,0 +1,19 @@
+#[doc = "Register `ICR` writer"]
+pub type W = crate::W<IcrSpec>;
+impl core::fmt::Debug for crate::generic::Reg<IcrSpec> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "(not readable)")
+    }
+}
+impl W {}
+#[doc = "Interrupt Clear Register\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`icr::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IcrSpec;
+impl crate::RegisterSpec for IcrSpec {
+    type Ux = u32;
+}
+#[doc = "`write(|w| ..)` method takes [`icr::W`](W) writer structure"]
+impl crate::Writable for IcrSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets ICR to value 0"]
+impl crate::Resettable for IcrSpec {}
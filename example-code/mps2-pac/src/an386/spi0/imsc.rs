@@ -0,0 +1,23 @@
+#[doc = "Register `IMSC` reader"]
+pub type R = crate::R<ImscSpec>;
+#[doc = "Register `IMSC` writer"]
+pub type W = crate::W<ImscSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Interrupt Mask Set/Clear Register\n\nYou can [`read`](crate::Reg::read) this register and get [`imsc::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`imsc::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ImscSpec;
+impl crate::RegisterSpec for ImscSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`imsc::R`](R) reader structure"]
+impl crate::Readable for ImscSpec {}
+#[doc = "`write(|w| ..)` method takes [`imsc::W`](W) writer structure"]
+impl crate::Writable for ImscSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets IMSC to value 0"]
+impl crate::Resettable for ImscSpec {}
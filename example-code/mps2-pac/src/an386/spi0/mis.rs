@@ -0,0 +1,16 @@
+#[doc = "Register `MIS` reader"]
+pub type R = crate::R<MisSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+#[doc = "Masked Interrupt Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`mis::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct MisSpec;
+impl crate::RegisterSpec for MisSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`mis::R`](R) reader structure"]
+impl crate::Readable for MisSpec {}
+#[doc = "`reset()` method sets MIS to value 0"]
+impl crate::Resettable for MisSpec {}
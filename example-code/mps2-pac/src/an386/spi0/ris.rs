@@ -0,0 +1,16 @@
+#[doc = "Register `RIS` reader"]
+pub type R = crate::R<RisSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+#[doc = "Raw Interrupt Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ris::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct RisSpec;
+impl crate::RegisterSpec for RisSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`ris::R`](R) reader structure"]
+impl crate::Readable for RisSpec {}
+#[doc = "`reset()` method sets RIS to value 0"]
+impl crate::Resettable for RisSpec {}
@@ -0,0 +1,18 @@
+#[doc = "Register `SR` reader"]
+pub type R = crate::R<SrSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+#[doc = "Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`sr::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct SrSpec;
+impl crate::RegisterSpec for SrSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`sr::R`](R) reader structure"]
+impl crate::Readable for SrSpec {}
+#[doc = "`reset()` method sets SR to value 0x03"]
+impl crate::Resettable for SrSpec {
+    const RESET_VALUE: u32 = 0x03;
+}
@@ -0,0 +1,50 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    ctrl: Ctrl,
+    value: Value,
+    reload: Reload,
+    intstatus: Intstatus,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Control Register"]
+    #[inline(always)]
+    pub const fn ctrl(&self) -> &Ctrl {
+        &self.ctrl
+    }
+    #[doc = "0x04 - Current Value Register"]
+    #[inline(always)]
+    pub const fn value(&self) -> &Value {
+        &self.value
+    }
+    #[doc = "0x08 - Reload Value Register"]
+    #[inline(always)]
+    pub const fn reload(&self) -> &Reload {
+        &self.reload
+    }
+    #[doc = "0x0c - Interrupt Status Register (write 1 to clear)"]
+    #[inline(always)]
+    pub const fn intstatus(&self) -> &Intstatus {
+        &self.intstatus
+    }
+}
+#[doc = "CTRL (rw) register accessor: Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
+#[doc(alias = "CTRL")]
+pub type Ctrl = crate::Reg<ctrl::CtrlSpec>;
+#[doc = "Control Register"]
+pub mod ctrl;
+#[doc = "VALUE (rw) register accessor: Current Value Register\n\nYou can [`read`](crate::Reg::read) this register and get [`value::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`value::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@value`] module"]
+#[doc(alias = "VALUE")]
+pub type Value = crate::Reg<value::ValueSpec>;
+#[doc = "Current Value Register"]
+pub mod value;
+#[doc = "RELOAD (rw) register accessor: Reload Value Register\n\nYou can [`read`](crate::Reg::read) this register and get [`reload::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`reload::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@reload`] module"]
+#[doc(alias = "RELOAD")]
+pub type Reload = crate::Reg<reload::ReloadSpec>;
+#[doc = "Reload Value Register"]
+pub mod reload;
+#[doc = "INTSTATUS (rw) register accessor: Interrupt Status Register (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`intstatus::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intstatus::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intstatus`] module"]
+#[doc(alias = "INTSTATUS")]
+pub type Intstatus = crate::Reg<intstatus::IntstatusSpec>;
+#[doc = "Interrupt Status Register (write 1 to clear)"]
+pub mod intstatus;
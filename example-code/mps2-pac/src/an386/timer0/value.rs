@@ -0,0 +1,23 @@
+#[doc = "Register `VALUE` reader"]
+pub type R = crate::R<ValueSpec>;
+#[doc = "Register `VALUE` writer"]
+pub type W = crate::W<ValueSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Current Value Register\n\nYou can [`read`](crate::Reg::read) this register and get [`value::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`value::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ValueSpec;
+impl crate::RegisterSpec for ValueSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`value::R`](R) reader structure"]
+impl crate::Readable for ValueSpec {}
+#[doc = "`write(|w| ..)` method takes [`value::W`](W) writer structure"]
+impl crate::Writable for ValueSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets VALUE to value 0"]
+impl crate::Resettable for ValueSpec {}
@@ -0,0 +1,28 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    control: Control,
+    controlc: Controlc,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Read the SCL/SDA lines, or write 1s to set them"]
+    #[inline(always)]
+    pub const fn control(&self) -> &Control {
+        &self.control
+    }
+    #[doc = "0x04 - Write 1s to clear the SCL/SDA lines"]
+    #[inline(always)]
+    pub const fn controlc(&self) -> &Controlc {
+        &self.controlc
+    }
+}
+#[doc = "CONTROL (rw) register accessor: Read the SCL/SDA lines, or write 1s to set them\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@control`] module"]
+#[doc(alias = "CONTROL")]
+pub type Control = crate::Reg<control::ControlSpec>;
+#[doc = "Read the SCL/SDA lines, or write 1s to set them"]
+pub mod control;
+#[doc = "CONTROLC (w) register accessor: Write 1s to clear the SCL/SDA lines\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`controlc::W`]. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@controlc`] module"]
+#[doc(alias = "CONTROLC")]
+pub type Controlc = crate::Reg<controlc::ControlcSpec>;
+#[doc = "Write 1s to clear the SCL/SDA lines"]
+pub mod controlc;
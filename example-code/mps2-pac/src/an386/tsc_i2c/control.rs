@@ -0,0 +1,49 @@
+#[doc = "Register `CONTROL` reader"]
+pub type R = crate::R<ControlSpec>;
+#[doc = "Register `CONTROL` writer"]
+pub type W = crate::W<ControlSpec>;
+#[doc = "Field `SCL` reader - Serial clock line"]
+pub type SclR = crate::BitReader;
+#[doc = "Field `SCL` writer - Serial clock line"]
+pub type SclW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `SDA` reader - Serial data line"]
+pub type SdaR = crate::BitReader;
+#[doc = "Field `SDA` writer - Serial data line"]
+pub type SdaW<'a, REG> = crate::BitWriter<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - Serial clock line"]
+    #[inline(always)]
+    pub fn scl(&self) -> SclR {
+        SclR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Serial data line"]
+    #[inline(always)]
+    pub fn sda(&self) -> SdaR {
+        SdaR::new(((self.bits >> 1) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Serial clock line"]
+    #[inline(always)]
+    pub fn scl(&mut self) -> SclW<ControlSpec> {
+        SclW::new(self, 0)
+    }
+    #[doc = "Bit 1 - Serial data line"]
+    #[inline(always)]
+    pub fn sda(&mut self) -> SdaW<ControlSpec> {
+        SdaW::new(self, 1)
+    }
+}
+#[doc = "Read the SCL/SDA lines, or write 1s to set them\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ControlSpec;
+impl crate::RegisterSpec for ControlSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`control::R`](R) reader structure"]
+impl crate::Readable for ControlSpec {}
+#[doc = "`write(|w| ..)` method takes [`control::W`](W) writer structure"]
+impl crate::Writable for ControlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CONTROL to value 0"]
+impl crate::Resettable for ControlSpec {}
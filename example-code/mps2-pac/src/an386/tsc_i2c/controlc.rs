@@ -0,0 +1,19 @@
+#[doc = "Register `CONTROLC` writer"]
+pub type W = crate::W<ControlcSpec>;
+impl core::fmt::Debug for crate::generic::Reg<ControlcSpec> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "(not readable)")
+    }
+}
+impl W {}
+#[doc = "Write 1s to clear the SCL/SDA lines\n\nYou can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`controlc::W`](W). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ControlcSpec;
+impl crate::RegisterSpec for ControlcSpec {
+    type Ux = u32;
+}
+#[doc = "`write(|w| ..)` method takes [`controlc::W`](W) writer structure"]
+impl crate::Writable for ControlcSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CONTROLC to value 0"]
+impl crate::Resettable for ControlcSpec {}
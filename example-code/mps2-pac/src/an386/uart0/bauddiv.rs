@@ -0,0 +1,35 @@
+#[doc = "Register `BAUDDIV` reader"]
+pub type R = crate::R<BauddivSpec>;
+#[doc = "Register `BAUDDIV` writer"]
+pub type W = crate::W<BauddivSpec>;
+#[doc = "Field `BAUDDIV` reader - Divider (minimum value is 16)"]
+pub type BauddivR = crate::FieldReader<u32>;
+#[doc = "Field `BAUDDIV` writer - Divider (minimum value is 16)"]
+pub type BauddivW<'a, REG> = crate::FieldWriter<'a, REG, 20, u32>;
+impl R {
+    #[doc = "Bits 0:19 - Divider (minimum value is 16)"]
+    #[inline(always)]
+    pub fn bauddiv(&self) -> BauddivR {
+        BauddivR::new(self.bits & 0x000f_ffff)
+    }
+}
+impl W {
+    #[doc = "Bits 0:19 - Divider (minimum value is 16)"]
+    #[inline(always)]
+    pub fn bauddiv(&mut self) -> BauddivW<BauddivSpec> {
+        BauddivW::new(self, 0)
+    }
+}
+#[doc = "Baud Rate Divider Register\n\nYou can [`read`](crate::Reg::read) this register and get [`bauddiv::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`bauddiv::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct BauddivSpec;
+impl crate::RegisterSpec for BauddivSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`bauddiv::R`](R) reader structure"]
+impl crate::Readable for BauddivSpec {}
+#[doc = "`write(|w| ..)` method takes [`bauddiv::W`](W) writer structure"]
+impl crate::Writable for BauddivSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets BAUDDIV to value 0"]
+impl crate::Resettable for BauddivSpec {}
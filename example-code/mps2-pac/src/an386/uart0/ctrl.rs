@@ -0,0 +1,119 @@
+#[doc = "Register `CTRL` reader"]
+pub type R = crate::R<CtrlSpec>;
+#[doc = "Register `CTRL` writer"]
+pub type W = crate::W<CtrlSpec>;
+#[doc = "Field `TXE` reader - TX enable"]
+pub type TxeR = crate::BitReader;
+#[doc = "Field `TXE` writer - TX enable"]
+pub type TxeW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `RXE` reader - RX enable"]
+pub type RxeR = crate::BitReader;
+#[doc = "Field `RXE` writer - RX enable"]
+pub type RxeW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `TXIE` reader - TX interrupt enable"]
+pub type TxieR = crate::BitReader;
+#[doc = "Field `TXIE` writer - TX interrupt enable"]
+pub type TxieW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `RXIE` reader - RX interrupt enable"]
+pub type RxieR = crate::BitReader;
+#[doc = "Field `RXIE` writer - RX interrupt enable"]
+pub type RxieW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `TXOIE` reader - TX overrun interrupt enable"]
+pub type TxoieR = crate::BitReader;
+#[doc = "Field `TXOIE` writer - TX overrun interrupt enable"]
+pub type TxoieW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `RXOIE` reader - RX overrun interrupt enable"]
+pub type RxoieR = crate::BitReader;
+#[doc = "Field `RXOIE` writer - RX overrun interrupt enable"]
+pub type RxoieW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `HSTM` reader - High-speed test mode for TX only"]
+pub type HstmR = crate::BitReader;
+#[doc = "Field `HSTM` writer - High-speed test mode for TX only"]
+pub type HstmW<'a, REG> = crate::BitWriter<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - TX enable"]
+    #[inline(always)]
+    pub fn txe(&self) -> TxeR {
+        TxeR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - RX enable"]
+    #[inline(always)]
+    pub fn rxe(&self) -> RxeR {
+        RxeR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - TX interrupt enable"]
+    #[inline(always)]
+    pub fn txie(&self) -> TxieR {
+        TxieR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - RX interrupt enable"]
+    #[inline(always)]
+    pub fn rxie(&self) -> RxieR {
+        RxieR::new(((self.bits >> 3) & 1) != 0)
+    }
+    #[doc = "Bit 4 - TX overrun interrupt enable"]
+    #[inline(always)]
+    pub fn txoie(&self) -> TxoieR {
+        TxoieR::new(((self.bits >> 4) & 1) != 0)
+    }
+    #[doc = "Bit 5 - RX overrun interrupt enable"]
+    #[inline(always)]
+    pub fn rxoie(&self) -> RxoieR {
+        RxoieR::new(((self.bits >> 5) & 1) != 0)
+    }
+    #[doc = "Bit 6 - High-speed test mode for TX only"]
+    #[inline(always)]
+    pub fn hstm(&self) -> HstmR {
+        HstmR::new(((self.bits >> 6) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - TX enable"]
+    #[inline(always)]
+    pub fn txe(&mut self) -> TxeW<CtrlSpec> {
+        TxeW::new(self, 0)
+    }
+    #[doc = "Bit 1 - RX enable"]
+    #[inline(always)]
+    pub fn rxe(&mut self) -> RxeW<CtrlSpec> {
+        RxeW::new(self, 1)
+    }
+    #[doc = "Bit 2 - TX interrupt enable"]
+    #[inline(always)]
+    pub fn txie(&mut self) -> TxieW<CtrlSpec> {
+        TxieW::new(self, 2)
+    }
+    #[doc = "Bit 3 - RX interrupt enable"]
+    #[inline(always)]
+    pub fn rxie(&mut self) -> RxieW<CtrlSpec> {
+        RxieW::new(self, 3)
+    }
+    #[doc = "Bit 4 - TX overrun interrupt enable"]
+    #[inline(always)]
+    pub fn txoie(&mut self) -> TxoieW<CtrlSpec> {
+        TxoieW::new(self, 4)
+    }
+    #[doc = "Bit 5 - RX overrun interrupt enable"]
+    #[inline(always)]
+    pub fn rxoie(&mut self) -> RxoieW<CtrlSpec> {
+        RxoieW::new(self, 5)
+    }
+    #[doc = "Bit 6 - High-speed test mode for TX only"]
+    #[inline(always)]
+    pub fn hstm(&mut self) -> HstmW<CtrlSpec> {
+        HstmW::new(self, 6)
+    }
+}
+#[doc = "Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct CtrlSpec;
+impl crate::RegisterSpec for CtrlSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`ctrl::R`](R) reader structure"]
+impl crate::Readable for CtrlSpec {}
+#[doc = "`write(|w| ..)` method takes [`ctrl::W`](W) writer structure"]
+impl crate::Writable for CtrlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CTRL to value 0"]
+impl crate::Resettable for CtrlSpec {}
@@ -0,0 +1,64 @@
+#[doc = "Register `STATE` reader"]
+pub type R = crate::R<StateSpec>;
+#[doc = "Register `STATE` writer"]
+pub type W = crate::W<StateSpec>;
+#[doc = "Field `TXBF` reader - TX buffer full"]
+pub type TxbfR = crate::BitReader;
+#[doc = "Field `RXBF` reader - RX buffer full"]
+pub type RxbfR = crate::BitReader;
+#[doc = "Field `TXOV` reader - TX buffer overrun (write 1 to clear)"]
+pub type TxovR = crate::BitReader;
+#[doc = "Field `TXOV` writer - TX buffer overrun (write 1 to clear)"]
+pub type TxovW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `RXOV` reader - RX buffer overrun (write 1 to clear)"]
+pub type RxovR = crate::BitReader;
+#[doc = "Field `RXOV` writer - RX buffer overrun (write 1 to clear)"]
+pub type RxovW<'a, REG> = crate::BitWriter1C<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - TX buffer full"]
+    #[inline(always)]
+    pub fn txbf(&self) -> TxbfR {
+        TxbfR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - RX buffer full"]
+    #[inline(always)]
+    pub fn rxbf(&self) -> RxbfR {
+        RxbfR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - TX buffer overrun (write 1 to clear)"]
+    #[inline(always)]
+    pub fn txov(&self) -> TxovR {
+        TxovR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - RX buffer overrun (write 1 to clear)"]
+    #[inline(always)]
+    pub fn rxov(&self) -> RxovR {
+        RxovR::new(((self.bits >> 3) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 2 - TX buffer overrun (write 1 to clear)"]
+    #[inline(always)]
+    pub fn txov(&mut self) -> TxovW<StateSpec> {
+        TxovW::new(self, 2)
+    }
+    #[doc = "Bit 3 - RX buffer overrun (write 1 to clear)"]
+    #[inline(always)]
+    pub fn rxov(&mut self) -> RxovW<StateSpec> {
+        RxovW::new(self, 3)
+    }
+}
+#[doc = "Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`state::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`state::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct StateSpec;
+impl crate::RegisterSpec for StateSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`state::R`](R) reader structure"]
+impl crate::Readable for StateSpec {}
+#[doc = "`write(|w| ..)` method takes [`state::W`](W) writer structure"]
+impl crate::Writable for StateSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u32 = 0x0c;
+}
+#[doc = "`reset()` method sets STATE to value 0"]
+impl crate::Resettable for StateSpec {}
@@ -0,0 +1,20 @@
+PROVIDE(TIMER0 = DefaultHandler);
+PROVIDE(TIMER1 = DefaultHandler);
+PROVIDE(DUALTIMER = DefaultHandler);
+PROVIDE(UART0_RX = DefaultHandler);
+PROVIDE(UART0_TX = DefaultHandler);
+PROVIDE(UART1_RX = DefaultHandler);
+PROVIDE(UART1_TX = DefaultHandler);
+PROVIDE(UART2_RX = DefaultHandler);
+PROVIDE(UART2_TX = DefaultHandler);
+PROVIDE(UART3_RX = DefaultHandler);
+PROVIDE(UART3_TX = DefaultHandler);
+PROVIDE(UART4_RX = DefaultHandler);
+PROVIDE(UART4_TX = DefaultHandler);
+PROVIDE(UART0_COMBINED = DefaultHandler);
+PROVIDE(UART1_COMBINED = DefaultHandler);
+PROVIDE(UART2_COMBINED = DefaultHandler);
+PROVIDE(UART3_COMBINED = DefaultHandler);
+PROVIDE(UART4_COMBINED = DefaultHandler);
+PROVIDE(AUDIO_I2S = DefaultHandler);
+
@@ -0,0 +1,176 @@
+#[doc = "Register `CONTROL` reader"]
+pub type R = crate::R<ControlSpec>;
+#[doc = "Register `CONTROL` writer"]
+pub type W = crate::W<ControlSpec>;
+#[doc = "Field `ONE_SHOT` reader - Stop when the counter reaches zero"]
+pub type OneShotR = crate::BitReader;
+#[doc = "Field `ONE_SHOT` writer - Stop when the counter reaches zero"]
+pub type OneShotW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `SIZE_32` reader - Use a 32-bit counter (instead of 16-bit)"]
+pub type Size32R = crate::BitReader;
+#[doc = "Field `SIZE_32` writer - Use a 32-bit counter (instead of 16-bit)"]
+pub type Size32W<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Clock prescaler\n\nValue on reset: 0"]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Prescale {
+    #[doc = "0: Divide clock by 1"]
+    Div1 = 0,
+    #[doc = "1: Divide clock by 16"]
+    Div16 = 1,
+    #[doc = "2: Divide clock by 256"]
+    Div256 = 2,
+}
+impl From<Prescale> for u8 {
+    #[inline(always)]
+    fn from(variant: Prescale) -> Self {
+        variant as _
+    }
+}
+impl crate::FieldSpec for Prescale {
+    type Ux = u8;
+}
+impl crate::IsEnum for Prescale {}
+#[doc = "Field `PRESCALE` reader - Clock prescaler"]
+pub type PrescaleR = crate::FieldReader<Prescale>;
+impl PrescaleR {
+    #[doc = "Get enumerated values variant"]
+    #[inline(always)]
+    pub const fn variant(&self) -> Option<Prescale> {
+        match self.bits {
+            0 => Some(Prescale::Div1),
+            1 => Some(Prescale::Div16),
+            2 => Some(Prescale::Div256),
+            _ => None,
+        }
+    }
+    #[doc = "Divide clock by 1"]
+    #[inline(always)]
+    pub fn is_div1(&self) -> bool {
+        *self == Prescale::Div1
+    }
+    #[doc = "Divide clock by 16"]
+    #[inline(always)]
+    pub fn is_div16(&self) -> bool {
+        *self == Prescale::Div16
+    }
+    #[doc = "Divide clock by 256"]
+    #[inline(always)]
+    pub fn is_div256(&self) -> bool {
+        *self == Prescale::Div256
+    }
+}
+#[doc = "Field `PRESCALE` writer - Clock prescaler"]
+pub type PrescaleW<'a, REG> = crate::FieldWriter<'a, REG, 2, Prescale>;
+impl<'a, REG> PrescaleW<'a, REG>
+where
+    REG: crate::Writable + crate::RegisterSpec,
+    REG::Ux: From<u8>,
+{
+    #[doc = "Divide clock by 1"]
+    #[inline(always)]
+    pub fn div1(self) -> &'a mut crate::W<REG> {
+        self.variant(Prescale::Div1)
+    }
+    #[doc = "Divide clock by 16"]
+    #[inline(always)]
+    pub fn div16(self) -> &'a mut crate::W<REG> {
+        self.variant(Prescale::Div16)
+    }
+    #[doc = "Divide clock by 256"]
+    #[inline(always)]
+    pub fn div256(self) -> &'a mut crate::W<REG> {
+        self.variant(Prescale::Div256)
+    }
+}
+#[doc = "Field `INT_EN` reader - Interrupt enable"]
+pub type IntEnR = crate::BitReader;
+#[doc = "Field `INT_EN` writer - Interrupt enable"]
+pub type IntEnW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `PERIODIC` reader - Reload from LOAD (instead of free-running)"]
+pub type PeriodicR = crate::BitReader;
+#[doc = "Field `PERIODIC` writer - Reload from LOAD (instead of free-running)"]
+pub type PeriodicW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `EN` reader - Enable the timer"]
+pub type EnR = crate::BitReader;
+#[doc = "Field `EN` writer - Enable the timer"]
+pub type EnW<'a, REG> = crate::BitWriter<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - Stop when the counter reaches zero"]
+    #[inline(always)]
+    pub fn one_shot(&self) -> OneShotR {
+        OneShotR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Use a 32-bit counter (instead of 16-bit)"]
+    #[inline(always)]
+    pub fn size_32(&self) -> Size32R {
+        Size32R::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bits 2:3 - Clock prescaler"]
+    #[inline(always)]
+    pub fn prescale(&self) -> PrescaleR {
+        PrescaleR::new(((self.bits >> 2) & 3) as u8)
+    }
+    #[doc = "Bit 5 - Interrupt enable"]
+    #[inline(always)]
+    pub fn int_en(&self) -> IntEnR {
+        IntEnR::new(((self.bits >> 5) & 1) != 0)
+    }
+    #[doc = "Bit 6 - Reload from LOAD (instead of free-running)"]
+    #[inline(always)]
+    pub fn periodic(&self) -> PeriodicR {
+        PeriodicR::new(((self.bits >> 6) & 1) != 0)
+    }
+    #[doc = "Bit 7 - Enable the timer"]
+    #[inline(always)]
+    pub fn en(&self) -> EnR {
+        EnR::new(((self.bits >> 7) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Stop when the counter reaches zero"]
+    #[inline(always)]
+    pub fn one_shot(&mut self) -> OneShotW<ControlSpec> {
+        OneShotW::new(self, 0)
+    }
+    #[doc = "Bit 1 - Use a 32-bit counter (instead of 16-bit)"]
+    #[inline(always)]
+    pub fn size_32(&mut self) -> Size32W<ControlSpec> {
+        Size32W::new(self, 1)
+    }
+    #[doc = "Bits 2:3 - Clock prescaler"]
+    #[inline(always)]
+    pub fn prescale(&mut self) -> PrescaleW<ControlSpec> {
+        PrescaleW::new(self, 2)
+    }
+    #[doc = "Bit 5 - Interrupt enable"]
+    #[inline(always)]
+    pub fn int_en(&mut self) -> IntEnW<ControlSpec> {
+        IntEnW::new(self, 5)
+    }
+    #[doc = "Bit 6 - Reload from LOAD (instead of free-running)"]
+    #[inline(always)]
+    pub fn periodic(&mut self) -> PeriodicW<ControlSpec> {
+        PeriodicW::new(self, 6)
+    }
+    #[doc = "Bit 7 - Enable the timer"]
+    #[inline(always)]
+    pub fn en(&mut self) -> EnW<ControlSpec> {
+        EnW::new(self, 7)
+    }
+}
+#[doc = "Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`control::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`control::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ControlSpec;
+impl crate::RegisterSpec for ControlSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`control::R`](R) reader structure"]
+impl crate::Readable for ControlSpec {}
+#[doc = "`write(|w| ..)` method takes [`control::W`](W) writer structure"]
+impl crate::Writable for ControlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CONTROL to value 0x20"]
+impl crate::Resettable for ControlSpec {
+    const RESET_VALUE: u32 = 0x20;
+}
@@ -0,0 +1,16 @@
+#[doc = "Register `VALUE` reader"]
+pub type R = crate::R<ValueSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+#[doc = "Current Value Register\n\nYou can [`read`](crate::Reg::read) this register and get [`value::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ValueSpec;
+impl crate::RegisterSpec for ValueSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`value::R`](R) reader structure"]
+impl crate::Readable for ValueSpec {}
+#[doc = "`reset()` method sets VALUE to value 0"]
+impl crate::Resettable for ValueSpec {}
@@ -0,0 +1,16 @@
+#[doc = "Register `BUTTON` reader"]
+pub type R = crate::R<ButtonSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+#[doc = "User push buttons\n\nYou can [`read`](crate::Reg::read) this register and get [`button::R`](R). See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct ButtonSpec;
+impl crate::RegisterSpec for ButtonSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`button::R`](R) reader structure"]
+impl crate::Readable for ButtonSpec {}
+#[doc = "`reset()` method sets BUTTON to value 0"]
+impl crate::Resettable for ButtonSpec {}
@@ -0,0 +1,23 @@
+#[doc = "Register `CLK1HZ` reader"]
+pub type R = crate::R<Clk1hzSpec>;
+#[doc = "Register `CLK1HZ` writer"]
+pub type W = crate::W<Clk1hzSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "1 Hz up counter\n\nYou can [`read`](crate::Reg::read) this register and get [`clk1hz::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`clk1hz::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct Clk1hzSpec;
+impl crate::RegisterSpec for Clk1hzSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`clk1hz::R`](R) reader structure"]
+impl crate::Readable for Clk1hzSpec {}
+#[doc = "`write(|w| ..)` method takes [`clk1hz::W`](W) writer structure"]
+impl crate::Writable for Clk1hzSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CLK1HZ to value 0"]
+impl crate::Resettable for Clk1hzSpec {}
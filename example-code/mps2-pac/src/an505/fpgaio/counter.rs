@@ -0,0 +1,23 @@
+#[doc = "Register `COUNTER` reader"]
+pub type R = crate::R<CounterSpec>;
+#[doc = "Register `COUNTER` writer"]
+pub type W = crate::W<CounterSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Cycle up counter, incremented when PSCNTR reaches zero\n\nYou can [`read`](crate::Reg::read) this register and get [`counter::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`counter::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct CounterSpec;
+impl crate::RegisterSpec for CounterSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`counter::R`](R) reader structure"]
+impl crate::Readable for CounterSpec {}
+#[doc = "`write(|w| ..)` method takes [`counter::W`](W) writer structure"]
+impl crate::Writable for CounterSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets COUNTER to value 0"]
+impl crate::Resettable for CounterSpec {}
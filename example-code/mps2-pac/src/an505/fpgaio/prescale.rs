@@ -0,0 +1,23 @@
+#[doc = "Register `PRESCALE` reader"]
+pub type R = crate::R<PrescaleSpec>;
+#[doc = "Register `PRESCALE` writer"]
+pub type W = crate::W<PrescaleSpec>;
+impl core::fmt::Debug for R {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.bits())
+    }
+}
+impl W {}
+#[doc = "Reload value for PSCNTR\n\nYou can [`read`](crate::Reg::read) this register and get [`prescale::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`prescale::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct PrescaleSpec;
+impl crate::RegisterSpec for PrescaleSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`prescale::R`](R) reader structure"]
+impl crate::Readable for PrescaleSpec {}
+#[doc = "`write(|w| ..)` method takes [`prescale::W`](W) writer structure"]
+impl crate::Writable for PrescaleSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets PRESCALE to value 0"]
+impl crate::Resettable for PrescaleSpec {}
@@ -0,0 +1,303 @@
+#[doc = r"Number available in the NVIC for configuring priority"]
+pub const NVIC_PRIO_BITS: u8 = 3;
+#[allow(unused_imports)]
+use generic::*;
+#[doc = r"Common register and bit access and modify traits"]
+pub mod generic;
+#[cfg(feature = "rt")]
+extern "C" {
+    fn TIMER0();
+    fn TIMER1();
+    fn DUALTIMER();
+    fn UART0_RX();
+    fn UART0_TX();
+    fn UART1_RX();
+    fn UART1_TX();
+    fn UART2_RX();
+    fn UART2_TX();
+    fn UART3_RX();
+    fn UART3_TX();
+    fn UART4_RX();
+    fn UART4_TX();
+    fn UART0_COMBINED();
+    fn UART1_COMBINED();
+    fn UART2_COMBINED();
+    fn UART3_COMBINED();
+    fn UART4_COMBINED();
+    fn AUDIO_I2S();
+}
+#[doc(hidden)]
+#[repr(C)]
+pub union Vector {
+    _handler: unsafe extern "C" fn(),
+    _reserved: u32,
+}
+#[cfg(feature = "rt")]
+#[doc(hidden)]
+#[link_section = ".vector_table.interrupts"]
+#[no_mangle]
+pub static __INTERRUPTS: [Vector; 50] = [
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _handler: TIMER0 },
+    Vector { _handler: TIMER1 },
+    Vector {
+        _handler: DUALTIMER,
+    },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector { _handler: UART0_RX },
+    Vector { _handler: UART0_TX },
+    Vector { _handler: UART1_RX },
+    Vector { _handler: UART1_TX },
+    Vector { _handler: UART2_RX },
+    Vector { _handler: UART2_TX },
+    Vector { _handler: UART3_RX },
+    Vector { _handler: UART3_TX },
+    Vector { _handler: UART4_RX },
+    Vector { _handler: UART4_TX },
+    Vector {
+        _handler: UART0_COMBINED,
+    },
+    Vector {
+        _handler: UART1_COMBINED,
+    },
+    Vector {
+        _handler: UART2_COMBINED,
+    },
+    Vector {
+        _handler: UART3_COMBINED,
+    },
+    Vector {
+        _handler: UART4_COMBINED,
+    },
+    Vector { _reserved: 0 },
+    Vector { _reserved: 0 },
+    Vector {
+        _handler: AUDIO_I2S,
+    },
+];
+#[doc = r"Enumeration of all the interrupts."]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum Interrupt {
+    #[doc = "3 - Timer 0"]
+    TIMER0 = 3,
+    #[doc = "4 - Timer 1"]
+    TIMER1 = 4,
+    #[doc = "5 - Dual Timer"]
+    DUALTIMER = 5,
+    #[doc = "32 - UART 0 Receive"]
+    UART0_RX = 32,
+    #[doc = "33 - UART 0 Transmit"]
+    UART0_TX = 33,
+    #[doc = "34 - UART 1 Receive"]
+    UART1_RX = 34,
+    #[doc = "35 - UART 1 Transmit"]
+    UART1_TX = 35,
+    #[doc = "36 - UART 2 Receive"]
+    UART2_RX = 36,
+    #[doc = "37 - UART 2 Transmit"]
+    UART2_TX = 37,
+    #[doc = "38 - UART 3 Receive"]
+    UART3_RX = 38,
+    #[doc = "39 - UART 3 Transmit"]
+    UART3_TX = 39,
+    #[doc = "40 - UART 4 Receive"]
+    UART4_RX = 40,
+    #[doc = "41 - UART 4 Transmit"]
+    UART4_TX = 41,
+    #[doc = "42 - UART 0 combined interrupt"]
+    UART0_COMBINED = 42,
+    #[doc = "43 - UART 1 combined interrupt"]
+    UART1_COMBINED = 43,
+    #[doc = "44 - UART 2 combined interrupt"]
+    UART2_COMBINED = 44,
+    #[doc = "45 - UART 3 combined interrupt"]
+    UART3_COMBINED = 45,
+    #[doc = "46 - UART 4 combined interrupt"]
+    UART4_COMBINED = 46,
+    #[doc = "49 - Audio I²S"]
+    AUDIO_I2S = 49,
+}
+unsafe impl cortex_m::interrupt::InterruptNumber for Interrupt {
+    #[inline(always)]
+    fn number(self) -> u16 {
+        self as u16
+    }
+}
+#[doc = "CMSDK APB Timer"]
+pub type Timer0 = crate::Periph<timer0::RegisterBlock, 0x5000_0000>;
+impl core::fmt::Debug for Timer0 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Timer0").finish()
+    }
+}
+#[doc = "CMSDK APB Timer"]
+pub mod timer0;
+#[doc = "CMSDK APB Timer"]
+pub type Timer1 = crate::Periph<timer0::RegisterBlock, 0x5000_1000>;
+impl core::fmt::Debug for Timer1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Timer1").finish()
+    }
+}
+#[doc = "CMSDK APB Timer"]
+pub use self::timer0 as timer1;
+#[doc = "CMSDK APB Dual Timer"]
+pub type Dualtimer = crate::Periph<dualtimer::RegisterBlock, 0x5000_2000>;
+impl core::fmt::Debug for Dualtimer {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Dualtimer").finish()
+    }
+}
+#[doc = "CMSDK APB Dual Timer"]
+pub mod dualtimer;
+#[doc = "CMSDK APB UART"]
+pub type Uart0 = crate::Periph<uart0::RegisterBlock, 0x5020_0000>;
+impl core::fmt::Debug for Uart0 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart0").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub mod uart0;
+#[doc = "CMSDK APB UART"]
+pub type Uart1 = crate::Periph<uart0::RegisterBlock, 0x5020_1000>;
+impl core::fmt::Debug for Uart1 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart1").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart1;
+#[doc = "CMSDK APB UART"]
+pub type Uart2 = crate::Periph<uart0::RegisterBlock, 0x5020_2000>;
+impl core::fmt::Debug for Uart2 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart2").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart2;
+#[doc = "CMSDK APB UART"]
+pub type Uart3 = crate::Periph<uart0::RegisterBlock, 0x5020_3000>;
+impl core::fmt::Debug for Uart3 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart3").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart3;
+#[doc = "CMSDK APB UART"]
+pub type Uart4 = crate::Periph<uart0::RegisterBlock, 0x5020_4000>;
+impl core::fmt::Debug for Uart4 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Uart4").finish()
+    }
+}
+#[doc = "CMSDK APB UART"]
+pub use self::uart0 as uart4;
+#[doc = "Audio I2S interface"]
+pub type I2s = crate::Periph<i2s::RegisterBlock, 0x5030_1000>;
+impl core::fmt::Debug for I2s {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("I2s").finish()
+    }
+}
+#[doc = "Audio I2S interface"]
+pub mod i2s;
+#[doc = "FPGA system control and I/O"]
+pub type Fpgaio = crate::Periph<fpgaio::RegisterBlock, 0x5030_2000>;
+impl core::fmt::Debug for Fpgaio {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Fpgaio").finish()
+    }
+}
+#[doc = "FPGA system control and I/O"]
+pub mod fpgaio;
+#[no_mangle]
+static mut DEVICE_PERIPHERALS: bool = false;
+#[doc = r" All the peripherals."]
+#[allow(non_snake_case)]
+pub struct Peripherals {
+    #[doc = "TIMER0"]
+    pub timer0: Timer0,
+    #[doc = "TIMER1"]
+    pub timer1: Timer1,
+    #[doc = "DUALTIMER"]
+    pub dualtimer: Dualtimer,
+    #[doc = "UART0"]
+    pub uart0: Uart0,
+    #[doc = "UART1"]
+    pub uart1: Uart1,
+    #[doc = "UART2"]
+    pub uart2: Uart2,
+    #[doc = "UART3"]
+    pub uart3: Uart3,
+    #[doc = "UART4"]
+    pub uart4: Uart4,
+    #[doc = "I2S"]
+    pub i2s: I2s,
+    #[doc = "FPGAIO"]
+    pub fpgaio: Fpgaio,
+}
+impl Peripherals {
+    #[doc = r" Returns all the peripherals *once*."]
+    #[cfg(feature = "critical-section")]
+    #[inline]
+    pub fn take() -> Option<Self> {
+        critical_section::with(|_| {
+            if unsafe { DEVICE_PERIPHERALS } {
+                return None;
+            }
+            Some(unsafe { Peripherals::steal() })
+        })
+    }
+    #[doc = r" Unchecked version of `Peripherals::take`."]
+    #[doc = r""]
+    #[doc = r" # Safety"]
+    #[doc = r""]
+    #[doc = r" Each of the returned peripherals must be used at most once."]
+    #[inline]
+    pub unsafe fn steal() -> Self {
+        DEVICE_PERIPHERALS = true;
+        Peripherals {
+            timer0: Timer0::steal(),
+            timer1: Timer1::steal(),
+            dualtimer: Dualtimer::steal(),
+            uart0: Uart0::steal(),
+            uart1: Uart1::steal(),
+            uart2: Uart2::steal(),
+            uart3: Uart3::steal(),
+            uart4: Uart4::steal(),
+            i2s: I2s::steal(),
+            fpgaio: Fpgaio::steal(),
+        }
+    }
+}
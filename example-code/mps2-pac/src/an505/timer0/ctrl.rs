@@ -0,0 +1,77 @@
+#[doc = "Register `CTRL` reader"]
+pub type R = crate::R<CtrlSpec>;
+#[doc = "Register `CTRL` writer"]
+pub type W = crate::W<CtrlSpec>;
+#[doc = "Field `EN` reader - Enable the timer"]
+pub type EnR = crate::BitReader;
+#[doc = "Field `EN` writer - Enable the timer"]
+pub type EnW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `EXT_EN` reader - Use external input as enable"]
+pub type ExtEnR = crate::BitReader;
+#[doc = "Field `EXT_EN` writer - Use external input as enable"]
+pub type ExtEnW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `EXT_CLK` reader - Use external input as clock"]
+pub type ExtClkR = crate::BitReader;
+#[doc = "Field `EXT_CLK` writer - Use external input as clock"]
+pub type ExtClkW<'a, REG> = crate::BitWriter<'a, REG>;
+#[doc = "Field `IRQ_EN` reader - Interrupt enable"]
+pub type IrqEnR = crate::BitReader;
+#[doc = "Field `IRQ_EN` writer - Interrupt enable"]
+pub type IrqEnW<'a, REG> = crate::BitWriter<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - Enable the timer"]
+    #[inline(always)]
+    pub fn en(&self) -> EnR {
+        EnR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - Use external input as enable"]
+    #[inline(always)]
+    pub fn ext_en(&self) -> ExtEnR {
+        ExtEnR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - Use external input as clock"]
+    #[inline(always)]
+    pub fn ext_clk(&self) -> ExtClkR {
+        ExtClkR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - Interrupt enable"]
+    #[inline(always)]
+    pub fn irq_en(&self) -> IrqEnR {
+        IrqEnR::new(((self.bits >> 3) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Enable the timer"]
+    #[inline(always)]
+    pub fn en(&mut self) -> EnW<CtrlSpec> {
+        EnW::new(self, 0)
+    }
+    #[doc = "Bit 1 - Use external input as enable"]
+    #[inline(always)]
+    pub fn ext_en(&mut self) -> ExtEnW<CtrlSpec> {
+        ExtEnW::new(self, 1)
+    }
+    #[doc = "Bit 2 - Use external input as clock"]
+    #[inline(always)]
+    pub fn ext_clk(&mut self) -> ExtClkW<CtrlSpec> {
+        ExtClkW::new(self, 2)
+    }
+    #[doc = "Bit 3 - Interrupt enable"]
+    #[inline(always)]
+    pub fn irq_en(&mut self) -> IrqEnW<CtrlSpec> {
+        IrqEnW::new(self, 3)
+    }
+}
+#[doc = "Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct CtrlSpec;
+impl crate::RegisterSpec for CtrlSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`ctrl::R`](R) reader structure"]
+impl crate::Readable for CtrlSpec {}
+#[doc = "`write(|w| ..)` method takes [`ctrl::W`](W) writer structure"]
+impl crate::Writable for CtrlSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets CTRL to value 0"]
+impl crate::Resettable for CtrlSpec {}
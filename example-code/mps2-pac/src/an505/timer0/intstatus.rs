@@ -0,0 +1,36 @@
+#[doc = "Register `INTSTATUS` reader"]
+pub type R = crate::R<IntstatusSpec>;
+#[doc = "Register `INTSTATUS` writer"]
+pub type W = crate::W<IntstatusSpec>;
+#[doc = "Field `IRQ` reader - Interrupt pending"]
+pub type IrqR = crate::BitReader;
+#[doc = "Field `IRQ` writer - Interrupt pending"]
+pub type IrqW<'a, REG> = crate::BitWriter1C<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - Interrupt pending"]
+    #[inline(always)]
+    pub fn irq(&self) -> IrqR {
+        IrqR::new((self.bits & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - Interrupt pending"]
+    #[inline(always)]
+    pub fn irq(&mut self) -> IrqW<IntstatusSpec> {
+        IrqW::new(self, 0)
+    }
+}
+#[doc = "Interrupt Status Register (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`intstatus::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intstatus::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IntstatusSpec;
+impl crate::RegisterSpec for IntstatusSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`intstatus::R`](R) reader structure"]
+impl crate::Readable for IntstatusSpec {}
+#[doc = "`write(|w| ..)` method takes [`intstatus::W`](W) writer structure"]
+impl crate::Writable for IntstatusSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u32 = 0x01;
+}
+#[doc = "`reset()` method sets INTSTATUS to value 0"]
+impl crate::Resettable for IntstatusSpec {}
@@ -0,0 +1,61 @@
+#[repr(C)]
+#[doc = "Register block"]
+pub struct RegisterBlock {
+    data: Data,
+    state: State,
+    ctrl: Ctrl,
+    intstatus: Intstatus,
+    bauddiv: Bauddiv,
+}
+impl RegisterBlock {
+    #[doc = "0x00 - Data Register"]
+    #[inline(always)]
+    pub const fn data(&self) -> &Data {
+        &self.data
+    }
+    #[doc = "0x04 - Status Register"]
+    #[inline(always)]
+    pub const fn state(&self) -> &State {
+        &self.state
+    }
+    #[doc = "0x08 - Control Register"]
+    #[inline(always)]
+    pub const fn ctrl(&self) -> &Ctrl {
+        &self.ctrl
+    }
+    #[doc = "0x0c - Interrupt Status / Clear Register (write 1 to clear)"]
+    #[inline(always)]
+    pub const fn intstatus(&self) -> &Intstatus {
+        &self.intstatus
+    }
+    #[doc = "0x10 - Baud Rate Divider Register"]
+    #[inline(always)]
+    pub const fn bauddiv(&self) -> &Bauddiv {
+        &self.bauddiv
+    }
+}
+#[doc = "DATA (rw) register accessor: Data Register\n\nYou can [`read`](crate::Reg::read) this register and get [`data::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`data::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@data`] module"]
+#[doc(alias = "DATA")]
+pub type Data = crate::Reg<data::DataSpec>;
+#[doc = "Data Register"]
+pub mod data;
+#[doc = "STATE (rw) register accessor: Status Register\n\nYou can [`read`](crate::Reg::read) this register and get [`state::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`state::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@state`] module"]
+#[doc(alias = "STATE")]
+pub type State = crate::Reg<state::StateSpec>;
+#[doc = "Status Register"]
+pub mod state;
+#[doc = "CTRL (rw) register accessor: Control Register\n\nYou can [`read`](crate::Reg::read) this register and get [`ctrl::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`ctrl::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@ctrl`] module"]
+#[doc(alias = "CTRL")]
+pub type Ctrl = crate::Reg<ctrl::CtrlSpec>;
+#[doc = "Control Register"]
+pub mod ctrl;
+#[doc = "INTSTATUS (rw) register accessor: Interrupt Status / Clear Register (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`intstatus::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intstatus::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@intstatus`] module"]
+#[doc(alias = "INTSTATUS")]
+pub type Intstatus = crate::Reg<intstatus::IntstatusSpec>;
+#[doc = "Interrupt Status / Clear Register (write 1 to clear)"]
+pub mod intstatus;
+#[doc = "BAUDDIV (rw) register accessor: Baud Rate Divider Register\n\nYou can [`read`](crate::Reg::read) this register and get [`bauddiv::R`]. You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`bauddiv::W`]. You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api).\n\nFor information about available fields see [`mod@bauddiv`] module"]
+#[doc(alias = "BAUDDIV")]
+pub type Bauddiv = crate::Reg<bauddiv::BauddivSpec>;
+#[doc = "Baud Rate Divider Register"]
+pub mod bauddiv;
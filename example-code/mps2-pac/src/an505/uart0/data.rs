@@ -0,0 +1,35 @@
+#[doc = "Register `DATA` reader"]
+pub type R = crate::R<DataSpec>;
+#[doc = "Register `DATA` writer"]
+pub type W = crate::W<DataSpec>;
+#[doc = "Field `DATA` reader - Data value"]
+pub type DataR = crate::FieldReader;
+#[doc = "Field `DATA` writer - Data value"]
+pub type DataW<'a, REG> = crate::FieldWriter<'a, REG, 8>;
+impl R {
+    #[doc = "Bits 0:7 - Data value"]
+    #[inline(always)]
+    pub fn data(&self) -> DataR {
+        DataR::new((self.bits & 0xff) as u8)
+    }
+}
+impl W {
+    #[doc = "Bits 0:7 - Data value"]
+    #[inline(always)]
+    pub fn data(&mut self) -> DataW<DataSpec> {
+        DataW::new(self, 0)
+    }
+}
+#[doc = "Data Register\n\nYou can [`read`](crate::Reg::read) this register and get [`data::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`data::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct DataSpec;
+impl crate::RegisterSpec for DataSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`data::R`](R) reader structure"]
+impl crate::Readable for DataSpec {}
+#[doc = "`write(|w| ..)` method takes [`data::W`](W) writer structure"]
+impl crate::Writable for DataSpec {
+    type Safety = crate::Unsafe;
+}
+#[doc = "`reset()` method sets DATA to value 0"]
+impl crate::Resettable for DataSpec {}
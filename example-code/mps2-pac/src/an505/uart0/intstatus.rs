@@ -0,0 +1,78 @@
+#[doc = "Register `INTSTATUS` reader"]
+pub type R = crate::R<IntstatusSpec>;
+#[doc = "Register `INTSTATUS` writer"]
+pub type W = crate::W<IntstatusSpec>;
+#[doc = "Field `TXI` reader - TX interrupt"]
+pub type TxiR = crate::BitReader;
+#[doc = "Field `TXI` writer - TX interrupt"]
+pub type TxiW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `RXI` reader - RX interrupt"]
+pub type RxiR = crate::BitReader;
+#[doc = "Field `RXI` writer - RX interrupt"]
+pub type RxiW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `TXOI` reader - TX overrun interrupt"]
+pub type TxoiR = crate::BitReader;
+#[doc = "Field `TXOI` writer - TX overrun interrupt"]
+pub type TxoiW<'a, REG> = crate::BitWriter1C<'a, REG>;
+#[doc = "Field `RXOI` reader - RX overrun interrupt"]
+pub type RxoiR = crate::BitReader;
+#[doc = "Field `RXOI` writer - RX overrun interrupt"]
+pub type RxoiW<'a, REG> = crate::BitWriter1C<'a, REG>;
+impl R {
+    #[doc = "Bit 0 - TX interrupt"]
+    #[inline(always)]
+    pub fn txi(&self) -> TxiR {
+        TxiR::new((self.bits & 1) != 0)
+    }
+    #[doc = "Bit 1 - RX interrupt"]
+    #[inline(always)]
+    pub fn rxi(&self) -> RxiR {
+        RxiR::new(((self.bits >> 1) & 1) != 0)
+    }
+    #[doc = "Bit 2 - TX overrun interrupt"]
+    #[inline(always)]
+    pub fn txoi(&self) -> TxoiR {
+        TxoiR::new(((self.bits >> 2) & 1) != 0)
+    }
+    #[doc = "Bit 3 - RX overrun interrupt"]
+    #[inline(always)]
+    pub fn rxoi(&self) -> RxoiR {
+        RxoiR::new(((self.bits >> 3) & 1) != 0)
+    }
+}
+impl W {
+    #[doc = "Bit 0 - TX interrupt"]
+    #[inline(always)]
+    pub fn txi(&mut self) -> TxiW<IntstatusSpec> {
+        TxiW::new(self, 0)
+    }
+    #[doc = "Bit 1 - RX interrupt"]
+    #[inline(always)]
+    pub fn rxi(&mut self) -> RxiW<IntstatusSpec> {
+        RxiW::new(self, 1)
+    }
+    #[doc = "Bit 2 - TX overrun interrupt"]
+    #[inline(always)]
+    pub fn txoi(&mut self) -> TxoiW<IntstatusSpec> {
+        TxoiW::new(self, 2)
+    }
+    #[doc = "Bit 3 - RX overrun interrupt"]
+    #[inline(always)]
+    pub fn rxoi(&mut self) -> RxoiW<IntstatusSpec> {
+        RxoiW::new(self, 3)
+    }
+}
+#[doc = "Interrupt Status / Clear Register (write 1 to clear)\n\nYou can [`read`](crate::Reg::read) this register and get [`intstatus::R`](R). You can [`reset`](crate::Reg::reset), [`write`](crate::Reg::write), [`write_with_zero`](crate::Reg::write_with_zero) this register using [`intstatus::W`](W). You can also [`modify`](crate::Reg::modify) this register. See [API](https://docs.rs/svd2rust/#read--modify--write-api)."]
+pub struct IntstatusSpec;
+impl crate::RegisterSpec for IntstatusSpec {
+    type Ux = u32;
+}
+#[doc = "`read()` method returns [`intstatus::R`](R) reader structure"]
+impl crate::Readable for IntstatusSpec {}
+#[doc = "`write(|w| ..)` method takes [`intstatus::W`](W) writer structure"]
+impl crate::Writable for IntstatusSpec {
+    type Safety = crate::Unsafe;
+    const ONE_TO_MODIFY_FIELDS_BITMAP: u32 = 0x0f;
+}
+#[doc = "`reset()` method sets INTSTATUS to value 0"]
+impl crate::Resettable for IntstatusSpec {}
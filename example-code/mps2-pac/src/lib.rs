@@ -0,0 +1,66 @@
+//! Peripheral Access Crate for the Arm MPS2 boards emulated by QEMU
+//!
+//! Everything in `./an386` and `./an505` is generated by `svd2rust`, from the
+//! SVD files in `../svd`. To add a peripheral or an interrupt, edit the SVD
+//! file and run `./regen.sh`. The generated code is checked in, so you can
+//! read it there.
+
+#![no_std]
+
+#[cfg(not(any(
+    feature = "an385",
+    feature = "an386",
+    feature = "an500",
+    feature = "an505"
+)))]
+compile_error!("Enable one of the an385, an386, an500 or an505 features");
+
+#[cfg(all(
+    feature = "an505",
+    any(feature = "an385", feature = "an386", feature = "an500")
+))]
+compile_error!("The an385, an386, an500 and an505 features are mutually exclusive");
+
+// QEMU gives the AN385, AN386 and AN500 the same peripherals at the same
+// addresses, with the same interrupts (see `hw/arm/mps2.c`) - only the CPU
+// differs, and that doesn't change the generated code. So they share a PAC.
+// The AN505 is a completely different design.
+#[cfg(any(feature = "an385", feature = "an386", feature = "an500"))]
+#[allow(
+    non_camel_case_types,
+    non_snake_case,
+    mismatched_lifetime_syntaxes,
+    clippy::all
+)]
+mod an386;
+#[cfg(any(feature = "an385", feature = "an386", feature = "an500"))]
+pub use an386::*;
+
+#[cfg(all(
+    feature = "an505",
+    not(any(feature = "an385", feature = "an386", feature = "an500"))
+))]
+#[allow(
+    non_camel_case_types,
+    non_snake_case,
+    mismatched_lifetime_syntaxes,
+    clippy::all
+)]
+mod an505;
+#[cfg(all(
+    feature = "an505",
+    not(any(feature = "an385", feature = "an386", feature = "an500"))
+))]
+pub use an505::*;
+
+// The generated code expects the register access traits at the crate root
+#[cfg(any(
+    feature = "an385",
+    feature = "an386",
+    feature = "an500",
+    feature = "an505"
+))]
+#[allow(unused_imports)]
+use generic::*;
+
+// End of file
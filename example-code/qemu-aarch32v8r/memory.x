@@ -6,10 +6,17 @@ See https://github.com/qemu/qemu/blob/master/hw/arm/mps3r.c
 
 MEMORY {
     QSPI : ORIGIN = 0x08000000, LENGTH = 8M
-    DDR  : ORIGIN = 0x20000000, LENGTH = 128M
+    DDR  : ORIGIN = 0x20000000, LENGTH = 128M - 1K
 }
 
 REGION_ALIAS("VECTORS", QSPI);
 REGION_ALIAS("CODE", QSPI);
 REGION_ALIAS("DATA", DDR);
 REGION_ALIAS("STACKS", DDR);
+
+/*
+The last 1 KiB of our DDR holds the crash log (see `src/crash.rs`). It is
+outside every section, so neither the startup code nor QEMU's ELF loader will
+clear it when the machine resets.
+*/
+_crash_log = ORIGIN(DDR) + LENGTH(DDR);
@@ -6,6 +6,7 @@ use qemu_aarch32v8r as _;
 /// The entry-point to the Rust application.
 #[aarch32_rt::entry]
 fn main() -> ! {
+    qemu_aarch32v8r::stack::paint();
     defmt::println!("Hello, world!");
     defmt::error!("This is an error log");
     defmt::warn!("This is a warn log");
@@ -19,6 +19,7 @@ static UART: MutexUart = MutexUart::empty();
 /// The entry-point to the Rust application.
 #[aarch32_rt::entry]
 fn main() -> ! {
+    qemu_aarch32v8r::stack::paint();
     defmt::println!("This is the global_uart example.");
     UART.init(
         unsafe { CmsdkUart::new_with_raw_addr(UART0_ADDR) },
@@ -27,7 +28,7 @@ fn main() -> ! {
     )
     .unwrap();
     print_stuff().unwrap();
-    semihosting::process::exit(0);
+    qemu_aarch32v8r::exit(0);
 }
 
 /// Prints some text to the global UART
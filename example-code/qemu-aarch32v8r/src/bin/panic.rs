@@ -12,6 +12,7 @@ use qemu_aarch32v8r as _;
 /// The entry-point to the Rust application.
 #[aarch32_rt::entry]
 fn main() -> ! {
+    qemu_aarch32v8r::stack::paint();
     defmt::println!("This is the panic example.");
     panic!("Oh no");
 }
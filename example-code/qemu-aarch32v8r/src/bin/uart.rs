@@ -13,12 +13,13 @@ use qemu_aarch32v8r::{uart, PERIPHERAL_CLOCK};
 /// The entry-point to the Rust application.
 #[aarch32_rt::entry]
 fn main() -> ! {
+    qemu_aarch32v8r::stack::paint();
     defmt::println!("This is the no_heap example.");
     let mut uart0 = unsafe { uart::CmsdkUart::new_with_raw_addr(uart::UART0_ADDR) };
     uart0.init(115200, PERIPHERAL_CLOCK).unwrap();
     uart0.check().expect("Your UART is missing");
     let _ = writeln!(uart0, "This text appears on the UART");
-    semihosting::process::exit(0);
+    qemu_aarch32v8r::exit(0);
 }
 
 // End of file
@@ -1,5 +1,7 @@
 //! An example program for QEMU's Armv8-R Virtual Machine
 //!
+//! Sets up a heap that keeps statistics, uses it, and reports on it.
+//!
 //! Written by Jonathan Pallant at Ferrous Systems
 //!
 //! Copyright (c) Ferrous Systems, 2025
@@ -9,19 +11,25 @@
 
 extern crate alloc;
 
-use embedded_alloc::LlffHeap as Heap;
+use alloc::vec::Vec;
+use qemu_common::heap::Heap;
+
 use qemu_aarch32v8r as _;
 
 #[global_allocator]
 static HEAP: Heap = Heap::empty();
 
+/// How big our heap is, in bytes
+const HEAP_SIZE: usize = 4096;
+
 /// The entry-point to the Rust application.
 #[aarch32_rt::entry]
 fn main() -> ! {
+    qemu_aarch32v8r::stack::paint();
     // Initialize the allocator BEFORE you use it
     {
-        const HEAP_SIZE: usize = 1024;
         static mut HEAP_MEM: [u8; HEAP_SIZE] = [0u8; HEAP_SIZE];
+        // Safety: we only do this once, and nothing else uses HEAP_MEM
         unsafe {
             let heap_start = core::ptr::addr_of_mut!(HEAP_MEM);
             HEAP.init(heap_start as usize, HEAP_SIZE);
@@ -31,7 +39,32 @@ fn main() -> ! {
     let pi: f64 = core::f64::consts::PI;
     let s = alloc::format!("This is a heap allocated string, π = {:0.6}", pi);
     defmt::println!("s = {:?}", s.as_str());
-    semihosting::process::exit(0);
+    HEAP.report();
+
+    // Grow a vector, which reallocates a few times as it goes
+    let mut squares: Vec<u32> = Vec::new();
+    for x in 0..200 {
+        squares.push(x * x);
+    }
+    defmt::println!(
+        "The sum of the first 200 squares is {=u32}",
+        squares.iter().sum::<u32>()
+    );
+    drop(s);
+    HEAP.report();
+
+    // Ask for more than we have. `try_reserve` gives us an error rather than
+    // panicking, but the failure hook still logs it.
+    let mut too_big: Vec<u8> = Vec::new();
+    if too_big.try_reserve(HEAP_SIZE * 2).is_err() {
+        defmt::println!(
+            "We couldn't reserve {=usize} bytes, as expected",
+            HEAP_SIZE * 2
+        );
+    }
+    drop(squares);
+    HEAP.report();
+    qemu_aarch32v8r::exit(0);
 }
 
 // End of file
@@ -0,0 +1,52 @@
+//! Crash records which survive a reset
+//!
+//! Call [`set_reset_on_crash(true)`](set_reset_on_crash) and the panic handler
+//! will store what went wrong in a [`CrashRecord`] and call [`reset`]. Call
+//! [`take_previous`] when you boot to see if the last run crashed.
+//!
+//! The Cortex-R52 can only request a reset from EL2, and QEMU's MPS3-AN536
+//! has no reset controller, so [`reset`] has to exit QEMU instead. On real
+//! hardware you would reset with a watchdog, and the record would survive.
+//!
+//! The record lives in the top 1 KiB of DDR - see `memory.x`.
+
+pub use qemu_common::crash::{reset_on_crash, set_reset_on_crash, CrashKind, CrashRecord};
+
+use qemu_common::crash::CrashLog;
+
+extern "C" {
+    /// The start of the memory reserved for the crash log, from `memory.x`
+    static mut _crash_log: u8;
+}
+
+/// Get a handle to our crash log
+fn crash_log() -> CrashLog {
+    // Safety: `memory.x` reserves this memory for us
+    unsafe { CrashLog::new(&raw mut _crash_log) }
+}
+
+/// Get the crash record from before the last reset, if there is one
+///
+/// This also clears it, so you only see each crash once.
+pub fn take_previous() -> Option<CrashRecord> {
+    crash_log().take()
+}
+
+/// How many times we have reset because of a crash since QEMU started
+pub fn reset_count() -> u32 {
+    crash_log().reset_count()
+}
+
+/// Store a crash record for a panic, and reset
+pub(crate) fn panic_reset(info: &core::panic::PanicInfo) -> ! {
+    crash_log().record_panic(info);
+    reset()
+}
+
+/// Reset the machine - or rather, exit QEMU, as we can't reset it
+pub fn reset() -> ! {
+    defmt::error!("Crash record stored, but we can't reset. Exiting.");
+    semihosting::process::exit(1);
+}
+
+// End of file
@@ -5,6 +5,9 @@ use defmt_semihosting as _;
 // pull in critical-section
 use aarch32_cpu as _;
 
+pub mod crash;
+pub mod stack;
+pub mod testing;
 pub mod uart;
 
 /// The clock speed of the peripheral subsystem on an SSE-300 SoC an on MPS3 board.
@@ -14,10 +17,14 @@ pub const PERIPHERAL_CLOCK: u32 = 25_000_000;
 
 /// Called when the application raises an unrecoverable `panic!`.
 ///
-/// Prints the panic to the console and then exits QEMU using a semihosting
-/// breakpoint.
+/// Prints the panic, and how much stack we used, to the console and then exits
+/// QEMU using a semihosting breakpoint. If [`crash::set_reset_on_crash`] has
+/// been turned on, it stores a crash record first.
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
+    if crash::reset_on_crash() {
+        crash::panic_reset(info);
+    }
     match (info.message().as_str(), info.location()) {
         (Some(m), Some(l)) => {
             defmt::error!("PANIC at {=str}:{=u32}: {}", l.file(), l.line(), m);
@@ -32,7 +39,15 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
             defmt::error!("PANIC!");
         }
     }
-    semihosting::process::exit(1);
+    // If we're running tests, this might not return
+    qemu_common::testing::on_panic();
+    exit(1);
+}
+
+/// Print how much stack we used, and then exit QEMU
+pub fn exit(code: i32) -> ! {
+    stack::report();
+    semihosting::process::exit(code);
 }
 
 // End of file
@@ -0,0 +1,74 @@
+//! Measuring how much stack we use
+//!
+//! Our code runs in System mode. `aarch32-rt` puts the stacks for the other
+//! modes at `_stack_top`, one below the other, and the System mode stack
+//! (which is `_sys_stack_size` bytes) below those. It doesn't give us a way to
+//! run code before `main`, so call [`paint`] at the top of `main` to fill the
+//! unused part of the System mode stack with
+//! [`PAINT`](qemu_common::stack::PAINT). Call [`usage`] at any time to see how
+//! much of it has been used since. The panic handler and [`crate::exit`] both
+//! print it for you.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use qemu_common::stack::{Stack, Usage};
+
+// The sizes are linker symbols whose *address* is the size
+extern "C" {
+    static _stack_top: u8;
+    static _hyp_stack_size: u8;
+    static _und_stack_size: u8;
+    static _svc_stack_size: u8;
+    static _abt_stack_size: u8;
+    static _irq_stack_size: u8;
+    static _fiq_stack_size: u8;
+    static _sys_stack_size: u8;
+}
+
+/// Have we painted the stack yet?
+static PAINTED: AtomicBool = AtomicBool::new(false);
+
+/// Get our (System mode) stack
+pub fn stack() -> Stack {
+    let other_stacks = [
+        &raw const _hyp_stack_size,
+        &raw const _und_stack_size,
+        &raw const _svc_stack_size,
+        &raw const _abt_stack_size,
+        &raw const _irq_stack_size,
+        &raw const _fiq_stack_size,
+    ]
+    .iter()
+    .map(|size| *size as usize)
+    .sum::<usize>();
+    let top = (&raw const _stack_top) as usize - other_stacks;
+    let bottom = top - (&raw const _sys_stack_size) as usize;
+    // Safety: the linker gives this memory to the System mode stack
+    unsafe { Stack::new(bottom, top) }
+}
+
+/// Fill the unused part of the stack with paint
+///
+/// Only the first call does anything, so that later calls don't wipe out the
+/// high-water mark.
+pub fn paint() {
+    critical_section::with(|_| {
+        if !PAINTED.swap(true, Ordering::Relaxed) {
+            // Safety: this is the stack we're running on, and with interrupts
+            // off nothing else is using it
+            unsafe { stack().paint() };
+        }
+    });
+}
+
+/// Work out how much of the stack has been used
+pub fn usage() -> Usage {
+    stack().usage()
+}
+
+/// Print the stack usage with defmt
+pub fn report() {
+    stack().report();
+}
+
+// End of file
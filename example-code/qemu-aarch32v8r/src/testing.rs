@@ -0,0 +1,111 @@
+//! Support for running the on-target tests in `./tests`
+//!
+//! The harness itself lives in `qemu_common::testing`. This module provides
+//! the clock it needs, and a [`test_suite!`](crate::test_suite) macro which
+//! adds an entry point.
+//!
+//! `#[timeout(ms)]` is only checked once a test returns, so a test which hangs
+//! will hang the run. Catching it sooner needs a timer interrupt, and this
+//! crate has no driver for the MPS3-AN536's GIC and no IRQ handler to take
+//! one with - so a timeout here only tells you a test was too slow, not that
+//! it never finished.
+
+pub use qemu_common::testing::*;
+
+#[doc(hidden)]
+pub use qemu_common::test_suite as __test_suite;
+
+/// Our implementation of the harness's [`Platform`] trait
+pub struct Qemu;
+
+impl Platform for Qemu {
+    fn now_us() -> u64 {
+        let (low, high, freq): (u32, u32, u32);
+        // Safety: Reading the virtual count (CNTVCT) and its frequency
+        // (CNTFRQ) is always allowed at EL1
+        unsafe {
+            core::arch::asm!(
+                "isb",
+                "mrrc p15, 1, {low}, {high}, c14",
+                "mrc p15, 0, {freq}, c14, c0, 0",
+                low = out(reg) low,
+                high = out(reg) high,
+                freq = out(reg) freq,
+                options(nomem, nostack, preserves_flags)
+            );
+        }
+        let ticks = (u64::from(high) << 32) | u64::from(low);
+        ticks * 1_000_000 / u64::from(freq)
+    }
+
+    fn exit(code: i32) -> ! {
+        crate::exit(code)
+    }
+
+    fn checkpoint() -> Checkpoint {
+        let (stack_pointer, cpsr): (usize, u32);
+        // Safety: reading the stack pointer and the CPSR has no side-effects
+        unsafe {
+            core::arch::asm!(
+                "mov {stack_pointer}, sp",
+                "mrs {cpsr}, cpsr",
+                stack_pointer = out(reg) stack_pointer,
+                cpsr = out(reg) cpsr,
+                options(nomem, nostack, preserves_flags)
+            );
+        }
+        const CPSR_I_BIT: u32 = 1 << 7;
+        Checkpoint {
+            stack_pointer,
+            interrupts_enabled: cpsr & CPSR_I_BIT == 0,
+        }
+    }
+
+    /// Go back to the checkpoint
+    ///
+    /// This assumes the test panicked in the mode the tests run in. A panic
+    /// in an exception handler carries on in that exception's mode.
+    unsafe fn restart(checkpoint: Checkpoint, resume: Resume, next: usize) -> ! {
+        // Safety: this only changes the IRQ mask, to how it was when the
+        // tests started
+        unsafe {
+            if checkpoint.interrupts_enabled {
+                core::arch::asm!("cpsie i", options(nomem, nostack, preserves_flags));
+            } else {
+                core::arch::asm!("cpsid i", options(nomem, nostack, preserves_flags));
+            }
+        }
+        // Safety: our caller says nothing below the checkpoint is in use
+        unsafe {
+            core::arch::asm!(
+                "mov sp, {stack_pointer}",
+                "bx {resume}",
+                stack_pointer = in(reg) checkpoint.stack_pointer,
+                resume = in(reg) resume,
+                in("r0") next,
+                options(noreturn)
+            )
+        }
+    }
+}
+
+/// Declare some tests, and an entry point which runs them
+///
+/// See `qemu_common::test_suite!` for the syntax.
+#[macro_export]
+macro_rules! test_suite {
+    ($($tests:tt)*) => {
+        $crate::testing::__test_suite! {
+            platform: $crate::testing::Qemu;
+            $($tests)*
+        }
+
+        #[aarch32_rt::entry]
+        fn main() -> ! {
+            $crate::stack::paint();
+            __run_tests(0)
+        }
+    };
+}
+
+// End of file
@@ -0,0 +1,51 @@
+//! On-target tests for the CMSDK UART driver
+//!
+//! Run with `cargo test --test cmsdk_uart`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch32v8r::{
+    uart::{self, CmsdkUart, UART0_ADDR},
+    PERIPHERAL_CLOCK,
+};
+
+qemu_aarch32v8r::test_suite! {
+    setup: fn setup() -> CmsdkUart {
+        // Safety: `UART0_ADDR` is the first CMSDK UART on the MPS3-AN536, and
+        // the harness has finished with the last test's driver before it calls
+        // `setup` again
+        unsafe { CmsdkUart::new_with_raw_addr(UART0_ADDR) }
+    }
+
+    fn passes_id_check(uart: &mut CmsdkUart) {
+        uart.check().unwrap();
+    }
+
+    fn accepts_115200_baud(uart: &mut CmsdkUart) {
+        assert!(uart.init(115200, PERIPHERAL_CLOCK).is_ok());
+    }
+
+    fn rejects_baud_rate_above_clock_over_16(uart: &mut CmsdkUart) {
+        let result = uart.init(PERIPHERAL_CLOCK / 8, PERIPHERAL_CLOCK);
+        assert!(matches!(result, Err(uart::Error::InvalidBaudRate)));
+    }
+
+    #[timeout(100)]
+    fn writes_without_blocking_forever(uart: &mut CmsdkUart) {
+        uart.init(115200, PERIPHERAL_CLOCK).unwrap();
+        for byte in b"Hello from the UART test\n" {
+            uart.tx().write_blocking(*byte);
+        }
+    }
+
+    #[should_panic]
+    fn rejects_zero_baud_rate(uart: &mut CmsdkUart) {
+        // dividing by zero panics
+        let _ = uart.init(core::hint::black_box(0), PERIPHERAL_CLOCK);
+    }
+}
+
+// End of file
@@ -0,0 +1,37 @@
+//! An example program for QEMU's Aarch64 Virtual Machine
+//!
+//! Panics twice, resetting the machine each time, and picks up the crash
+//! record from the previous run when it boots again.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use qemu_aarch64v8a::crash;
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the crash_reset example.");
+    match crash::take_previous() {
+        Some(record) => defmt::println!("Last run crashed: {}", record),
+        None => defmt::println!("No crash record - this must be a cold boot"),
+    }
+
+    let resets = crash::reset_count();
+    if resets < 2 {
+        crash::set_reset_on_crash(true);
+        panic!("Deliberate panic number {}", resets + 1);
+    }
+
+    defmt::println!("Survived {=u32} resets - that's enough", resets);
+    qemu_aarch64v8a::exit(0);
+}
+
+// End of file
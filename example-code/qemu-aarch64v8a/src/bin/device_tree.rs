@@ -0,0 +1,69 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Reads the device tree QEMU gives us, and prints what it found. If there
+//! isn't one, it prints the defaults for the `virt` machine instead. Try it
+//! with more cores, or a GICv3, to see the difference:
+//!
+//! ```console
+//! $ cargo run --bin device_tree -- --smp 2
+//! ```
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use qemu_aarch64v8a::{devicetree::Platform, psci};
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the device_tree example.");
+    defmt::println!("x0 was {=u64:#x}", arg0);
+
+    let platform = Platform::discover(arg0);
+    defmt::println!("Platform from {}:", platform.source);
+    defmt::println!(
+        "  UART:  PL011 at {=usize:#010x}, INTID {=u32}",
+        platform.uart,
+        platform.uart_intid
+    );
+    defmt::println!(
+        "  GIC:   {} with Distributor at {=usize:#010x}, CPU registers at {=usize:#010x}",
+        platform.gic.version,
+        platform.gic.distributor,
+        platform.gic.cpu
+    );
+    defmt::println!(
+        "  Timer: physical INTID {=u32}, virtual INTID {=u32}",
+        platform.physical_timer_intid,
+        platform.virtual_timer_intid
+    );
+    let (major, minor) = psci::version();
+    defmt::println!(
+        "  PSCI:  version {=u16}.{=u16} over {}",
+        major,
+        minor,
+        platform.psci
+    );
+    defmt::println!(
+        "  RAM:   {=usize} MiB at {=usize:#010x}",
+        platform.ram_size / (1024 * 1024),
+        platform.ram_start
+    );
+    defmt::println!("  CPUs:  {=usize}", platform.cpus);
+
+    // Check the GIC is where it says
+    // Safety: this is the only GIC driver
+    let gic = unsafe { platform.gic() }.unwrap();
+    defmt::println!("The GIC supports {=u32} INTIDs", gic.num_intids());
+
+    qemu_aarch64v8a::exit(0);
+}
+
+// End of file
@@ -0,0 +1,85 @@
+//! An embassy example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Runs two tasks which tick at different rates, using `embassy-time` on top
+//! of the virtual generic timer, and a third which exits after a few seconds.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use embassy_executor::raw::Executor;
+use embassy_time::{Instant, Timer};
+use static_cell::StaticCell;
+
+use qemu_aarch64v8a::{devicetree::Platform, time_driver};
+
+/// The executor, which has to live forever
+static EXECUTOR: StaticCell<Executor> = StaticCell::new();
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("Embassy example application");
+    // This finds which interrupt the virtual timer raises
+    let platform = Platform::discover(arg0);
+
+    // Safety: this is the only GIC driver
+    let mut gic = unsafe { platform.gic() }.unwrap();
+    gic.init();
+    time_driver::init(&mut gic).unwrap();
+    // Safety: the time driver is ready for its interrupt
+    unsafe { qemu_aarch64v8a::enable_interrupts() };
+
+    let executor = EXECUTOR.init(Executor::new(core::ptr::null_mut()));
+    let spawner = executor.spawner();
+    spawner.spawn(ticker("fast", 500).unwrap());
+    spawner.spawn(ticker("slow", 1200).unwrap());
+    spawner.spawn(finish(5).unwrap());
+
+    loop {
+        // Safety: we only poll the executor from here
+        unsafe { executor.poll() };
+        // Sleep until `__pender` says a task is ready. If it already did,
+        // while we were polling, this returns straight away.
+        // Safety: waiting for an event has no side-effects
+        unsafe { core::arch::asm!("wfe", options(nomem, nostack, preserves_flags)) };
+    }
+}
+
+/// Print a message every `period_ms` milliseconds
+#[embassy_executor::task(pool_size = 2)]
+async fn ticker(name: &'static str, period_ms: u64) {
+    loop {
+        defmt::info!(
+            "Hello from the {=str} task at {=u64} ms",
+            name,
+            Instant::now().as_millis()
+        );
+        Timer::after_millis(period_ms).await;
+    }
+}
+
+/// Exit QEMU after `secs` seconds
+#[embassy_executor::task]
+async fn finish(secs: u64) {
+    Timer::after_secs(secs).await;
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Called by embassy when a task is woken
+///
+/// This can happen in an interrupt handler, so we send an event to wake the
+/// main loop from its `wfe`.
+#[unsafe(export_name = "__pender")]
+fn pender(_context: *mut ()) {
+    // Safety: sending an event has no side-effects
+    unsafe { core::arch::asm!("sev", options(nomem, nostack, preserves_flags)) };
+}
+
+// End of file
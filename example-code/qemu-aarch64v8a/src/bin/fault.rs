@@ -0,0 +1,91 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Deliberately causes a synchronous exception, a few function calls deep, to
+//! show off the decoding in the exception handler.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+
+use qemu_aarch64v8a as _;
+
+entry!(main);
+
+/// The different exceptions this example can cause
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, defmt::Format)]
+enum Fault {
+    /// A read from an address with nothing mapped there (translation fault)
+    BadRead,
+    /// An exclusive load from a misaligned address (alignment fault)
+    Misaligned,
+    /// An undefined instruction
+    Undefined,
+    /// A `brk` instruction
+    Breakpoint,
+}
+
+/// Change this to try out a different exception
+const FAULT: Fault = Fault::BadRead;
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the fault example. Causing {}", FAULT);
+    let result = outer(FAULT);
+    defmt::println!("We didn't fault? Got {}", result);
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Some stack frames, so the fault isn't in `main`
+#[inline(never)]
+fn outer(fault: Fault) -> u64 {
+    middle(fault) + 1
+}
+
+/// Some more stack frames, so the fault isn't in `main`
+#[inline(never)]
+fn middle(fault: Fault) -> u64 {
+    inner(fault) + 1
+}
+
+/// Actually does the bad thing
+#[inline(never)]
+fn inner(fault: Fault) -> u64 {
+    match fault {
+        Fault::BadRead => {
+            // Our initial page table only maps the first 2 GiB
+            let ptr = 0x1_0000_0000 as *const u64;
+            unsafe { ptr.read_volatile() }
+        }
+        Fault::Misaligned => {
+            // Ordinary loads can be misaligned, but exclusive loads can't
+            let buffer = [0u64; 2];
+            let ptr = buffer.as_ptr() as usize + 1;
+            let result: u64;
+            unsafe {
+                core::arch::asm!("ldxr {0}, [{1}]", out(reg) result, in(reg) ptr);
+            }
+            result
+        }
+        Fault::Undefined => {
+            unsafe {
+                core::arch::asm!("udf #0");
+            }
+            0
+        }
+        Fault::Breakpoint => {
+            unsafe {
+                core::arch::asm!("brk #0x1234");
+            }
+            0
+        }
+    }
+}
+
+// End of file
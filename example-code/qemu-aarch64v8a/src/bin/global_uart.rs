@@ -7,49 +7,12 @@
 #![no_std]
 #![no_main]
 
-use core::{cell::RefCell, fmt::Write, ptr::NonNull};
+use core::fmt::Write;
 
 use aarch64_rt::entry;
-use arm_pl011_uart::{
-    DataBits, LineConfig, PL011Registers, Parity, StopBits, Uart, UniqueMmioPointer,
-};
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits};
 
-use qemu_aarch64v8a as _;
-
-const UART_ADDRESS: NonNull<PL011Registers> =
-    NonNull::new(0x0900_0000 as *mut PL011Registers).unwrap();
-
-struct GlobalUart {
-    inner: critical_section::Mutex<RefCell<Option<Uart<'static>>>>,
-}
-
-impl GlobalUart {
-    pub const fn new() -> GlobalUart {
-        GlobalUart {
-            inner: critical_section::Mutex::new(RefCell::new(None)),
-        }
-    }
-
-    pub fn init(&self, uart: Uart<'static>) {
-        critical_section::with(|cs| {
-            let mut lock = self.inner.borrow_ref_mut(cs);
-            lock.replace(uart);
-        });
-    }
-}
-
-impl core::fmt::Write for &GlobalUart {
-    fn write_str(&mut self, s: &str) -> core::fmt::Result {
-        critical_section::with(|cs| {
-            let mut lock = self.inner.borrow_ref_mut(cs);
-            if let Some(uart) = lock.as_mut() {
-                uart.write_str(s)
-            } else {
-                Ok(())
-            }
-        })
-    }
-}
+use qemu_aarch64v8a::{devicetree::Platform, global_uart::GlobalUart};
 
 static UART: GlobalUart = GlobalUart::new();
 
@@ -58,15 +21,13 @@ entry!(main);
 /// The entry-point to the Rust application.
 ///
 /// It is called by the start-up code in `aarch64-rt`
-fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
     defmt::println!("This is the global_uart example.");
+    let platform = Platform::discover(arg0);
 
-    // SAFETY: `UART_ADDRESS` is the base address of a PL011 UART register block. It remains valid for
-    // the lifetime of the application and nothing else references this address range.
-    let uart_pointer = unsafe { UniqueMmioPointer::new(UART_ADDRESS) };
-
-    // Create driver instance
-    let mut uart0 = Uart::new(uart_pointer);
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
 
     // Configure and enable UART
     let line_config = LineConfig {
@@ -77,7 +38,7 @@ fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
     uart0.enable(line_config, 115_200, 16_000_000).unwrap();
     UART.init(uart0);
     print_stuff().unwrap();
-    semihosting::process::exit(0);
+    qemu_aarch64v8a::exit(0);
 }
 
 /// Prints some text to the global UART
@@ -0,0 +1,85 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Builds page tables which map each part of our image with only the
+//! permissions it needs, and switches to them. Pass `--write-rodata` or
+//! `--overflow-stack` to see what happens when the program breaks the rules.
+//!
+//! ```console
+//! $ cargo run --bin page_tables -- --write-rodata
+//! $ cargo run --bin page_tables -- --overflow-stack
+//! ```
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use qemu_aarch64v8a::mmu::{self, AddressSpace};
+use qemu_common::host_env::CommandLine;
+
+/// Something which lives in `.rodata`
+static GREETING: [u8; 5] = *b"Hello";
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the page_tables example.");
+    let command_line = CommandLine::fetch().ok();
+    let has_flag = |flag| command_line.as_ref().is_some_and(|c| c.has_flag(flag));
+    let write_rodata = has_flag("--write-rodata");
+    let overflow_stack = has_flag("--overflow-stack");
+
+    let space = AddressSpace::for_image().unwrap();
+    let stack = qemu_aarch64v8a::stack::stack();
+    defmt::info!(
+        "Stack 0x{=usize:08x}..0x{=usize:08x}, guard page at 0x{=usize:08x}",
+        stack.bottom(),
+        stack.top(),
+        stack.bottom() - mmu::STACK_GUARD_SIZE
+    );
+    defmt::info!(
+        "Guard page above the stack at 0x{=usize:08x}",
+        qemu_aarch64v8a::stack::guard_pages()[1].start
+    );
+    defmt::info!("{} page tables left in the pool", mmu::tables_free());
+    // Safety: `for_image` maps everything this program uses
+    unsafe { space.activate() };
+    defmt::info!("Switched to the new page tables");
+    defmt::info!("GREETING is {=[u8]:a}", GREETING);
+
+    if write_rodata {
+        defmt::info!("Writing to .rodata - this should cause a permission fault");
+        let ptr = GREETING.as_ptr().cast_mut();
+        // Safety: this breaks Rust's rules on purpose - `GREETING` is
+        // immutable. The page tables map `.rodata` read-only, so the write
+        // faults before anything changes, and the fault handler exits rather
+        // than coming back here.
+        unsafe { ptr.write_volatile(b'J') };
+    }
+
+    if overflow_stack {
+        defmt::info!("Recursing forever - this should hit the stack guard page");
+        let depth = recurse(0);
+        defmt::error!("Got to a depth of {=u32} without faulting!", depth);
+    }
+
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Use another 256 bytes of stack each time we go around, until we run out
+#[inline(never)]
+fn recurse(depth: u32) -> u32 {
+    let buffer = core::hint::black_box([depth; 64]);
+    if depth == u32::MAX {
+        return 0;
+    }
+    // Adding afterwards means this can't become a loop
+    recurse(depth + 1) + buffer[0]
+}
+
+// End of file
@@ -17,6 +17,7 @@ entry!(main);
 ///
 /// It is called by the start-up code in `aarch64-rt`
 fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
     defmt::println!("This is the panic example.");
     panic!("Oh no");
 }
@@ -0,0 +1,60 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Reads the PL031 Real Time Clock, moves it, and waits for an alarm.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+
+use qemu_aarch64v8a::rtc::{self, DateTime, Rtc};
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the rtc example.");
+
+    // SAFETY: `RTC_ADDRESS` is the base address of a PL031 register block. It remains valid for
+    // the lifetime of the application and nothing else writes to this address range.
+    let mut rtc = unsafe { Rtc::new_with_raw_addr(rtc::RTC_ADDRESS) };
+    rtc.check().expect("Your RTC is missing");
+    rtc.enable();
+
+    let now = rtc.now();
+    defmt::info!(
+        "Host time is {} ({}), timestamp {=u32}",
+        now,
+        now.weekday(),
+        rtc.timestamp()
+    );
+
+    // Move the clock to a known time
+    let new_time = DateTime::new(2000, 2, 29, 23, 59, 58).unwrap();
+    rtc.set_time(&new_time).unwrap();
+    defmt::info!("Clock set to {}", rtc.now());
+
+    // Match interrupts stay masked (we have no GIC driver) but the raw status
+    // still tells us when the alarm goes off.
+    rtc.enable_interrupt(false);
+    rtc.clear_interrupt();
+    rtc.set_alarm_in(3);
+    defmt::info!(
+        "Alarm set for {}",
+        DateTime::from_timestamp(rtc.alarm_timestamp())
+    );
+    while !rtc.alarm_fired() {
+        core::hint::spin_loop();
+    }
+    rtc.clear_interrupt();
+    defmt::info!("Alarm went off at {}", rtc.now());
+
+    qemu_aarch64v8a::exit(0);
+}
+
+// End of file
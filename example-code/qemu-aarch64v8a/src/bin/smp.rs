@@ -0,0 +1,125 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Starts every other core with PSCI. The cores take turns to print to a
+//! shared UART, then all add to one counter at the same time, and core 0
+//! checks the total. Needs the `multicore` feature, and QEMU started with
+//! more than one core:
+//!
+//! ```console
+//! $ cargo run --features multicore --bin smp -- --smp 4 --uart-telnet
+//! ```
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::{
+    fmt::Write,
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+};
+
+use aarch64_rt::entry;
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits};
+use qemu_aarch64v8a::{devicetree::Platform, global_uart::GlobalUart, psci, smp};
+
+/// How many times each core adds one to [`COUNTER`]
+const INCREMENTS: u32 = 100_000;
+
+/// The UART every core prints to
+static UART: GlobalUart = GlobalUart::new();
+
+/// Which core may print its greeting next
+static TURN: AtomicUsize = AtomicUsize::new(0);
+
+/// Every core adds to this at the same time
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// How many cores have finished counting
+static FINISHED: AtomicUsize = AtomicUsize::new(0);
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the smp example.");
+    // This picks the PSCI conduit we start the other cores with
+    let platform = Platform::discover(arg0);
+    let (major, minor) = psci::version();
+    defmt::println!("PSCI version {=u16}.{=u16}", major, minor);
+
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
+    let line_config = LineConfig {
+        data_bits: DataBits::Bits8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+    uart0.enable(line_config, 115_200, 16_000_000).unwrap();
+    UART.init(uart0);
+
+    // Start cores until QEMU runs out of them
+    let mut cores = 1;
+    for core in 1..smp::MAX_CORES {
+        match smp::start_core(core, secondary) {
+            Ok(()) => {
+                defmt::info!("Started core {=usize}", core);
+                cores += 1;
+            }
+            Err(e) => {
+                defmt::info!("Couldn't start core {=usize}: {}", core, e);
+                break;
+            }
+        }
+    }
+
+    work(0);
+
+    while FINISHED.load(Ordering::Acquire) < cores {
+        core::hint::spin_loop();
+    }
+    let total = COUNTER.load(Ordering::Relaxed);
+    writeln!(&UART, "{} cores counted to {}", cores, total).unwrap();
+    defmt::println!("{=usize} cores counted to {=u32}", cores, total);
+    if total != INCREMENTS * cores as u32 {
+        defmt::error!("Expected {=u32}!", INCREMENTS * cores as u32);
+        qemu_aarch64v8a::exit(1);
+    }
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Where the secondary cores start
+fn secondary(core: usize) -> ! {
+    work(core);
+    loop {
+        // Safety: waiting for an event has no side-effects
+        unsafe { core::arch::asm!("wfe", options(nomem, nostack, preserves_flags)) };
+    }
+}
+
+/// The work every core does, including core 0
+fn work(core: usize) {
+    // Wait our turn, so the greetings come out in order
+    while TURN.load(Ordering::Acquire) != core {
+        core::hint::spin_loop();
+    }
+    writeln!(
+        &UART,
+        "Hello from core {} (MPIDR says {})",
+        core,
+        smp::core_id()
+    )
+    .unwrap();
+    TURN.store(core + 1, Ordering::Release);
+
+    // Now everyone at once
+    for _ in 0..INCREMENTS {
+        COUNTER.fetch_add(1, Ordering::Relaxed);
+    }
+    FINISHED.fetch_add(1, Ordering::Release);
+}
+
+// End of file
@@ -0,0 +1,155 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Runs some threads with the preemptive scheduler. They share the first
+//! UART through a mutex, so their lines don't get mixed up:
+//!
+//! * `tick` and `tock` print a line and sleep, at different rates
+//! * `busy` does a long calculation without ever yielding - the timer
+//!   interrupt makes sure everyone else still gets a turn
+//! * `producer` hands numbers to `consumer` through a semaphore
+//!
+//! ```console
+//! $ cargo run --bin threads -- --uart-telnet
+//! ```
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::{
+    fmt::Write as _,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use aarch64_rt::entry;
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits, Uart};
+use qemu_aarch64v8a::{
+    devicetree::Platform,
+    thread::{
+        self,
+        sync::{Mutex, Semaphore},
+        ThreadStack,
+    },
+    timer::{self, Counter},
+};
+use static_cell::ConstStaticCell;
+
+/// The UART every thread prints to
+static UART: Mutex<Option<Uart<'static>>> = Mutex::new(None);
+
+/// How many numbers `producer` has made, and `consumer` can take
+static ITEMS: Semaphore = Semaphore::new(0);
+
+/// The last number `producer` made
+static LATEST: AtomicU32 = AtomicU32::new(0);
+
+static TICK_STACK: ConstStaticCell<ThreadStack> = ConstStaticCell::new(ThreadStack::new());
+static TOCK_STACK: ConstStaticCell<ThreadStack> = ConstStaticCell::new(ThreadStack::new());
+static BUSY_STACK: ConstStaticCell<ThreadStack> = ConstStaticCell::new(ThreadStack::new());
+static PRODUCER_STACK: ConstStaticCell<ThreadStack> = ConstStaticCell::new(ThreadStack::new());
+static CONSUMER_STACK: ConstStaticCell<ThreadStack> = ConstStaticCell::new(ThreadStack::new());
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the threads example.");
+    let platform = Platform::discover(arg0);
+
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
+    let line_config = LineConfig {
+        data_bits: DataBits::Bits8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+    uart0.enable(line_config, 115_200, 16_000_000).unwrap();
+    *UART.lock() = Some(uart0);
+
+    // Safety: this is the only GIC driver
+    let mut gic = unsafe { platform.gic() }.unwrap();
+    gic.init();
+
+    thread::spawn("tick", tick, TICK_STACK.take()).unwrap();
+    thread::spawn("tock", tock, TOCK_STACK.take()).unwrap();
+    thread::spawn("busy", busy, BUSY_STACK.take()).unwrap();
+    thread::spawn("producer", producer, PRODUCER_STACK.take()).unwrap();
+    thread::spawn("consumer", consumer, CONSUMER_STACK.take()).unwrap();
+    thread::run(&mut gic).unwrap();
+
+    say(format_args!("All the threads have finished"));
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Print a line on the UART, with the time and the name of the thread
+fn say(args: core::fmt::Arguments) {
+    let ms = timer::ticks_to_hz(timer::count(Counter::Physical), 1_000);
+    let name = thread::name().unwrap_or("main");
+    let mut uart = UART.lock();
+    if let Some(uart) = uart.as_mut() {
+        let _ = writeln!(uart, "[{:>6} ms] {:>8}: {}", ms, name, args);
+    }
+}
+
+/// Prints every 100 ms
+fn tick() {
+    for n in 1..=10 {
+        say(format_args!("tick {}", n));
+        thread::sleep_ms(100);
+    }
+}
+
+/// Prints every 250 ms
+fn tock() {
+    for n in 1..=4 {
+        say(format_args!("tock {}", n));
+        thread::sleep_ms(250);
+    }
+}
+
+/// Works out the longest Collatz sequence it can find, without yielding
+fn busy() {
+    let mut longest = (0, 0);
+    for start in 1..200_000u64 {
+        let mut n = start;
+        let mut steps = 0;
+        while n != 1 {
+            n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+            steps += 1;
+        }
+        if steps > longest.1 {
+            longest = (start, steps);
+        }
+        if start % 50_000 == 0 {
+            say(format_args!("checked up to {}", start));
+        }
+    }
+    say(format_args!(
+        "the longest sequence starts at {}, with {} steps",
+        longest.0, longest.1
+    ));
+}
+
+/// Makes a number every 200 ms
+fn producer() {
+    for n in 1..=5 {
+        thread::sleep_ms(200);
+        LATEST.store(n * n, Ordering::Relaxed);
+        say(format_args!("made {}", n * n));
+        ITEMS.release();
+    }
+}
+
+/// Waits for each number `producer` makes
+fn consumer() {
+    for _ in 0..5 {
+        ITEMS.acquire();
+        say(format_args!("got {}", LATEST.load(Ordering::Relaxed)));
+    }
+}
+
+// End of file
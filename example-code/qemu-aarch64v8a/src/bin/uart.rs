@@ -1,5 +1,8 @@
 //! An example program for QEMU's Armv8-R Virtual Machine
 //!
+//! Takes its baud rate from the command line, e.g.
+//! `cargo run --bin uart -- --baud 9600`.
+//!
 //! Written by Jonathan Pallant at Ferrous Systems
 //!
 //! Copyright (c) Ferrous Systems, 2025
@@ -7,32 +10,36 @@
 #![no_std]
 #![no_main]
 
-use core::{fmt::Write, ptr::NonNull};
+use core::fmt::Write;
 
 use aarch64_rt::entry;
-use arm_pl011_uart::{
-    DataBits, LineConfig, PL011Registers, Parity, StopBits, Uart, UniqueMmioPointer,
-};
-
-use qemu_aarch64v8a as _;
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits};
 
-const UART_ADDRESS: NonNull<PL011Registers> =
-    NonNull::new(0x0900_0000 as *mut PL011Registers).unwrap();
+use qemu_aarch64v8a::devicetree::Platform;
+use qemu_common::host_env::{self, CommandLine};
 
 entry!(main);
 
 /// The entry-point to the Rust application.
 ///
 /// It is called by the start-up code in `aarch64-rt`
-fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
     defmt::println!("This is the no_heap example.");
+    let platform = Platform::discover(arg0);
 
-    // SAFETY: `UART_ADDRESS` is the base address of a PL011 UART register block. It remains valid for
-    // the lifetime of the application and nothing else references this address range.
-    let uart_pointer = unsafe { UniqueMmioPointer::new(UART_ADDRESS) };
+    let cmdline = CommandLine::fetch().expect("fetching command line");
+    defmt::println!("Command line: {}", cmdline);
+    let baud_rate = match cmdline.value_of("--baud") {
+        Some(value) => value.parse().expect("--baud needs a number"),
+        None => 115_200,
+    };
+    if let Ok(now) = host_env::unix_time() {
+        defmt::println!("Host time is {=u32} seconds since 1970", now);
+    }
 
-    // Create driver instance
-    let mut uart0 = Uart::new(uart_pointer);
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
 
     // Configure and enable UART
     let line_config = LineConfig {
@@ -40,9 +47,9 @@ fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
         parity: Parity::None,
         stop_bits: StopBits::One,
     };
-    uart0.enable(line_config, 115_200, 16_000_000).unwrap();
-    let _ = writeln!(uart0, "This text appears on the UART");
-    semihosting::process::exit(0);
+    uart0.enable(line_config, baud_rate, 16_000_000).unwrap();
+    let _ = writeln!(uart0, "This text appears on the UART at {} baud", baud_rate);
+    qemu_aarch64v8a::exit(0);
 }
 
 // End of file
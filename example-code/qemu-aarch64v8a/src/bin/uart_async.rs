@@ -0,0 +1,130 @@
+//! An async UART example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Echoes anything received on the first UART from an embassy task, using
+//! the async PL011 driver. Another task logs a heartbeat while the first is
+//! waiting for input. Type `q` to quit.
+//!
+//! Run as `cargo run --bin uart_async -- --uart-telnet` to get a telnet server
+//! you can interface with.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits};
+use embassy_executor::raw::Executor;
+use embassy_time::{Instant, Timer};
+use static_cell::StaticCell;
+
+use qemu_aarch64v8a::{
+    devicetree::Platform,
+    gic,
+    pl011::asynch::{AsyncRx, AsyncTx, State},
+    time_driver,
+};
+
+/// What the UART halves share with the interrupt handler
+static UART0_STATE: State = State::new();
+
+/// The executor, which has to live forever
+static EXECUTOR: StaticCell<Executor> = StaticCell::new();
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the uart_async example.");
+    let platform = Platform::discover(arg0);
+
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
+    let line_config = LineConfig {
+        data_bits: DataBits::Bits8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+    uart0.enable(line_config, 115_200, 16_000_000).unwrap();
+    let (tx, rx) = UART0_STATE.init(uart0);
+
+    // Safety: this is the only GIC driver
+    let mut gic = unsafe { platform.gic() }.unwrap();
+    gic.init();
+    time_driver::init(&mut gic).unwrap();
+    gic.register(platform.uart_intid, gic::DEFAULT_PRIORITY, uart0_irq)
+        .unwrap();
+    // Safety: the time driver and the UART are ready for their interrupts
+    unsafe { qemu_aarch64v8a::enable_interrupts() };
+
+    let executor = EXECUTOR.init(Executor::new(core::ptr::null_mut()));
+    let spawner = executor.spawner();
+    spawner.spawn(echo(tx, rx).unwrap());
+    spawner.spawn(heartbeat().unwrap());
+
+    loop {
+        // Safety: we only poll the executor from here
+        unsafe { executor.poll() };
+        // Sleep until `__pender` says a task is ready. If it already did,
+        // while we were polling, this returns straight away.
+        // Safety: waiting for an event has no side-effects
+        unsafe { core::arch::asm!("wfe", options(nomem, nostack, preserves_flags)) };
+    }
+}
+
+/// Echo everything we receive, until we see a `q`
+#[embassy_executor::task]
+async fn echo(mut tx: AsyncTx, mut rx: AsyncRx) {
+    tx.write_all(b"Type something (or 'q' to quit)\r\n").await;
+    let mut buffer = [0u8; 16];
+    loop {
+        // This is a non-blocking read! While we wait, the executor runs the
+        // other task, and the UART interrupt wakes us when data arrives.
+        let read_bytes = rx.read(&mut buffer).await;
+        let valid_data = &buffer[0..read_bytes];
+        defmt::info!(
+            "Application read {} bytes ({=[u8]:02x}). Echoing back.",
+            read_bytes,
+            valid_data
+        );
+        tx.write_all(valid_data).await;
+        if valid_data.contains(&b'q') {
+            break;
+        }
+    }
+    tx.write_all(b"\r\nBye!\r\n").await;
+    tx.flush().await;
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Log something every couple of seconds, to show we aren't stuck
+#[embassy_executor::task]
+async fn heartbeat() {
+    loop {
+        Timer::after_secs(2).await;
+        defmt::info!(
+            "Still waiting for input at {=u64} ms",
+            Instant::now().as_millis()
+        );
+    }
+}
+
+/// Called by the GIC driver when the UART interrupts
+fn uart0_irq() {
+    UART0_STATE.on_interrupt();
+}
+
+/// Called by embassy when a task is woken
+///
+/// This can happen in an interrupt handler, so we send an event to wake the
+/// main loop from its `wfe`.
+#[unsafe(export_name = "__pender")]
+fn pender(_context: *mut ()) {
+    // Safety: sending an event has no side-effects
+    unsafe { core::arch::asm!("sev", options(nomem, nostack, preserves_flags)) };
+}
+
+// End of file
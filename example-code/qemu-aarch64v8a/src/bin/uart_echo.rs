@@ -0,0 +1,88 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Echoes anything received on the first UART, using the interrupt-driven
+//! [`BufferedUart`]. Type `q` to quit.
+//!
+//! Run as `cargo run --bin uart_echo -- --uart-telnet` to get a telnet server
+//! you can interface with.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits};
+use embedded_io::Write as _;
+use qemu_aarch64v8a::{devicetree::Platform, gic, pl011::BufferedUart};
+
+/// Our UART buffer size
+const QLEN: usize = 256;
+
+/// How much we process every go around the loop
+const MAX_READ_LEN: usize = 16;
+
+/// A global UART we can write to
+static UART0: BufferedUart<QLEN> = BufferedUart::empty();
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the uart_echo example.");
+    let platform = Platform::discover(arg0);
+
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
+    let line_config = LineConfig {
+        data_bits: DataBits::Bits8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+    uart0.enable(line_config, 115_200, 16_000_000).unwrap();
+    UART0.init(uart0);
+
+    // Safety: this is the only GIC driver
+    let mut gic = unsafe { platform.gic() }.unwrap();
+    gic.init();
+    gic.register(platform.uart_intid, gic::DEFAULT_PRIORITY, uart0_irq)
+        .unwrap();
+    // Safety: the UART is ready for the interrupt handler
+    unsafe { qemu_aarch64v8a::enable_interrupts() };
+
+    (&UART0)
+        .write_all(b"Type something (or 'q' to quit)\r\n")
+        .unwrap();
+
+    let mut rx_buffer = [0u8; MAX_READ_LEN];
+    loop {
+        // Waits, with `wfi`, until the interrupt handler has received
+        // something
+        let read_bytes = embedded_io::Read::read(&mut &UART0, &mut rx_buffer).unwrap();
+        let valid_data = &rx_buffer[0..read_bytes];
+        defmt::info!(
+            "Application read {} bytes ({=[u8]:02x}). Echoing back.",
+            read_bytes,
+            valid_data
+        );
+        (&UART0).write_all(valid_data).unwrap();
+        if valid_data.contains(&b'q') {
+            break;
+        }
+    }
+
+    (&UART0).write_all(b"\r\nBye!\r\n").unwrap();
+    UART0.flush();
+    gic.unregister(platform.uart_intid).unwrap();
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Called by the GIC driver when the UART interrupts
+fn uart0_irq() {
+    UART0.on_interrupt();
+}
+
+// End of file
@@ -0,0 +1,109 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Receives bytes on the first UART using its RX interrupt, and echoes them
+//! back from the main loop. Type `q` to quit.
+//!
+//! Run as `cargo run --bin uart_irq -- --uart-telnet` to get a telnet server
+//! you can interface with. Add `,gic-version=3` to the `--machine` option in
+//! `.cargo/config.toml` to try it with a GICv3.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::{cell::RefCell, fmt::Write as _};
+
+use aarch64_rt::entry;
+use arm_pl011_uart::{DataBits, Interrupts, LineConfig, Parity, StopBits, Uart};
+use critical_section::Mutex;
+use qemu_aarch64v8a::{devicetree::Platform, gic};
+
+/// How many received bytes we can hold before the main loop reads them
+const QLEN: usize = 64;
+
+/// The UART, shared between the main loop and the interrupt handler
+static UART: Mutex<RefCell<Option<Uart<'static>>>> = Mutex::new(RefCell::new(None));
+
+/// Bytes the interrupt handler has received
+static RX_QUEUE: Mutex<RefCell<heapless::Deque<u8, QLEN>>> =
+    Mutex::new(RefCell::new(heapless::Deque::new()));
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the uart_irq example.");
+    let platform = Platform::discover(arg0);
+
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
+    let line_config = LineConfig {
+        data_bits: DataBits::Bits8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+    uart0.enable(line_config, 115_200, 16_000_000).unwrap();
+    let _ = writeln!(uart0, "Type something (or 'q' to quit)");
+    // Interrupt when the RX FIFO fills up, or when it has something in it
+    // and nothing more has arrived for a while
+    uart0.set_interrupt_masks(Interrupts::RXI | Interrupts::RTI);
+    critical_section::with(|cs| UART.borrow(cs).replace(Some(uart0)));
+
+    // Safety: this is the only GIC driver
+    let mut gic = unsafe { platform.gic() }.unwrap();
+    gic.init();
+    defmt::info!("Found a {}", gic.version());
+    gic.register(platform.uart_intid, gic::DEFAULT_PRIORITY, uart0_irq)
+        .unwrap();
+    // Safety: the UART and the queue are ready for the interrupt handler
+    unsafe { qemu_aarch64v8a::enable_interrupts() };
+
+    loop {
+        let byte = critical_section::with(|cs| RX_QUEUE.borrow_ref_mut(cs).pop_front());
+        let Some(byte) = byte else {
+            wait_for_interrupt();
+            continue;
+        };
+        defmt::info!("Got {=u8:#04x}", byte);
+        if byte == b'q' {
+            break;
+        }
+        critical_section::with(|cs| {
+            if let Some(uart) = UART.borrow_ref_mut(cs).as_mut() {
+                uart.write_word(byte);
+            }
+        });
+    }
+
+    gic.unregister(platform.uart_intid).unwrap();
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Called by the GIC driver when the UART interrupts
+fn uart0_irq() {
+    critical_section::with(|cs| {
+        let mut uart = UART.borrow_ref_mut(cs);
+        let Some(uart) = uart.as_mut() else {
+            return;
+        };
+        let mut queue = RX_QUEUE.borrow_ref_mut(cs);
+        while let Ok(Some(byte)) = uart.read_word() {
+            if queue.push_back(byte).is_err() {
+                defmt::warn!("RX queue full - dropped {=u8:#04x}", byte);
+            }
+        }
+        uart.clear_interrupts(Interrupts::RXI | Interrupts::RTI);
+    });
+}
+
+/// Wait for an interrupt
+fn wait_for_interrupt() {
+    // Safety: waiting for an interrupt has no side-effects
+    unsafe { core::arch::asm!("wfi", options(nomem, nostack, preserves_flags)) };
+}
+
+// End of file
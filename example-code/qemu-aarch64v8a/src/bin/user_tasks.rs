@@ -0,0 +1,104 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Runs two tasks at EL0, which print to the UART using system calls and take
+//! turns with `SYS_YIELD`. Pass `--touch-uart` to have a third task try to
+//! use the UART directly, which faults.
+//!
+//! ```console
+//! $ cargo run --bin user_tasks -- --touch-uart
+//! ```
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits};
+use qemu_aarch64v8a::{
+    devicetree::{self, Platform},
+    user::{self, Mailbox, Task, UserMemory},
+};
+use qemu_common::host_env::CommandLine;
+use static_cell::ConstStaticCell;
+
+static HELLO_MEMORY: ConstStaticCell<UserMemory> = ConstStaticCell::new(UserMemory::new());
+static COUNTER_MEMORY: ConstStaticCell<UserMemory> = ConstStaticCell::new(UserMemory::new());
+static ROGUE_MEMORY: ConstStaticCell<UserMemory> = ConstStaticCell::new(UserMemory::new());
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the user_tasks example.");
+    let platform = Platform::discover(arg0);
+    let touch_uart = CommandLine::fetch().is_ok_and(|c| c.has_flag("--touch-uart"));
+
+    // Safety: this is the only driver for the UART
+    let mut uart0 = unsafe { platform.uart() };
+    let line_config = LineConfig {
+        data_bits: DataBits::Bits8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+    uart0.enable(line_config, 115_200, 16_000_000).unwrap();
+    user::set_console(uart0);
+
+    let mut tasks = [
+        Task::new("hello", hello, HELLO_MEMORY.take()),
+        Task::new("counter", counter, COUNTER_MEMORY.take()),
+        Task::new("rogue", rogue, ROGUE_MEMORY.take()),
+    ];
+    let tasks = if touch_uart {
+        &mut tasks[..]
+    } else {
+        &mut tasks[..2]
+    };
+    let space = user::address_space(tasks).unwrap();
+    // Safety: the address space maps everything the kernel uses
+    unsafe { space.activate() };
+    user::run(tasks);
+
+    for task in tasks.iter() {
+        defmt::info!("{=str}: {}", task.name(), task.status());
+    }
+    qemu_aarch64v8a::exit(0);
+}
+
+/// Says hello a few times
+extern "C" fn hello(sys: &'static mut Mailbox) -> ! {
+    for _ in 0..3 {
+        sys.write("Hello from EL0!\n");
+        sys.yield_now();
+    }
+    sys.exit(0);
+}
+
+/// Counts, and says how long it took
+extern "C" fn counter(sys: &'static mut Mailbox) -> ! {
+    let start = sys.time_us();
+    for digit in b"12345" {
+        let line = [b'C', b'o', b'u', b'n', b't', b':', b' ', *digit, b'\n'];
+        // Safety: it's all ASCII
+        sys.write(unsafe { core::str::from_utf8_unchecked(&line) });
+        sys.yield_now();
+    }
+    let elapsed = sys.time_us() - start;
+    sys.exit(if elapsed > 0 { 5 } else { 1 });
+}
+
+/// Tries to go around the kernel, and write to the UART itself
+extern "C" fn rogue(sys: &'static mut Mailbox) -> ! {
+    sys.write("Writing to the UART directly...\n");
+    let data_register = devicetree::UART_ADDRESS as *mut u32;
+    // Safety: the kernel's `Uart` owns this register, so this write would
+    // alias it. But the UART isn't mapped for EL0, so the write faults before
+    // it happens, and `user::run` stops this task instead of resuming it.
+    unsafe { data_register.write_volatile(u32::from(b'!')) };
+    sys.exit(0);
+}
+
+// End of file
@@ -0,0 +1,68 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Finds a virtio console and says hello on it. The console driver keeps its
+//! buffers on the heap, so this sets up a heap first.
+//!
+//! ```console
+//! $ cargo run --features virtio-console --bin virtio_console -- --virtio-console
+//! ```
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use aarch64_rt::entry;
+use qemu_aarch64v8a::{
+    devicetree::Platform,
+    frames,
+    heap::HEAP,
+    mmu::AddressSpace,
+    virtio::{console::VirtioConsole, hal::VirtioHal},
+};
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the virtio_console example.");
+
+    let platform = Platform::discover(arg0);
+    frames::init(&platform);
+    let space = AddressSpace::for_image().expect("building page tables");
+    // Safety: `for_image` maps everything this program uses, including the
+    // virtio-mmio transports, and nothing else maps anything where the heap
+    // goes
+    unsafe {
+        space.activate();
+        HEAP.init(space).expect("setting up the heap");
+    }
+
+    // Safety: this is the only driver for the console
+    let mut console = match unsafe { VirtioConsole::find() } {
+        Ok(console) => console,
+        Err(e) => {
+            defmt::error!(
+                "No virtio console: {}. Did you run with `-- --virtio-console`?",
+                e
+            );
+            qemu_aarch64v8a::exit(1);
+        }
+    };
+    writeln!(console, "Hello from virtio!").expect("writing to the console");
+    defmt::println!("Said hello on the virtio console");
+
+    defmt::println!(
+        "{=usize} pages of DMA memory in use",
+        VirtioHal::pages_in_use()
+    );
+    HEAP.report();
+    qemu_aarch64v8a::exit(0);
+}
+
+// End of file
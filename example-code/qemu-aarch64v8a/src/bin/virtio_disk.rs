@@ -0,0 +1,94 @@
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Lists the virtio devices, then reads the first sector of a virtio-blk
+//! disk and keeps a boot counter in the second sector.
+//!
+//! ```console
+//! $ truncate -s 1M virtio-disk.img
+//! $ cargo run --bin virtio_disk -- --drive virtio-disk.img
+//! ```
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use aarch64_rt::entry;
+use qemu_aarch64v8a::virtio::{
+    self,
+    blk::{VirtioBlk, SECTOR_SIZE},
+    hal::VirtioHal,
+};
+
+/// Marks the sector holding our boot counter
+const MAGIC: &[u8; 8] = b"FERROUS!";
+
+entry!(main);
+
+/// The entry-point to the Rust application.
+///
+/// It is called by the start-up code in `aarch64-rt`
+fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
+    defmt::println!("This is the virtio_disk example.");
+
+    for device in virtio::devices() {
+        defmt::println!("Found {}", device);
+    }
+
+    // Safety: this is the only driver for the disk
+    let mut disk = match unsafe { VirtioBlk::find() } {
+        Ok(disk) => disk,
+        Err(e) => {
+            defmt::error!(
+                "No virtio disk: {}. Did you run with `-- --drive virtio-disk.img`?",
+                e
+            );
+            qemu_aarch64v8a::exit(1);
+        }
+    };
+    defmt::println!(
+        "Disk has {=u32} sectors of {=usize} bytes{}",
+        disk.sector_count(),
+        SECTOR_SIZE,
+        if disk.is_read_only() {
+            " (read-only)"
+        } else {
+            ""
+        }
+    );
+
+    let mut sector = [0u8; SECTOR_SIZE];
+    disk.read_sectors(0, &mut sector).expect("reading sector 0");
+    defmt::println!("Sector 0 starts {=[u8]:02x}", &sector[..32]);
+
+    // Sector 1 holds our magic number and a count of how many times we've run
+    disk.read_sectors(1, &mut sector).expect("reading sector 1");
+    let boots = if &sector[0..8] == MAGIC {
+        u32::from_le_bytes(sector[8..12].try_into().unwrap())
+    } else {
+        defmt::println!("No boot counter yet");
+        0
+    } + 1;
+    sector[0..8].copy_from_slice(MAGIC);
+    sector[8..12].copy_from_slice(&boots.to_le_bytes());
+    disk.write_sectors(1, &sector).expect("writing sector 1");
+
+    // Read it back, to check it went to the disk
+    let mut check = [0u8; SECTOR_SIZE];
+    disk.read_sectors(1, &mut check)
+        .expect("reading sector 1 again");
+    if check != sector {
+        defmt::error!("Sector 1 didn't read back the same!");
+        qemu_aarch64v8a::exit(1);
+    }
+    defmt::println!("This is boot number {=u32} for this disk", boots);
+
+    defmt::println!(
+        "{=usize} pages of DMA memory in use",
+        VirtioHal::pages_in_use()
+    );
+    qemu_aarch64v8a::exit(0);
+}
+
+// End of file
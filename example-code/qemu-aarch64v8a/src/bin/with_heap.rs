@@ -1,4 +1,8 @@
-//! An example program for QEMU's Armv8-R Virtual Machine
+//! An example program for QEMU's Armv8-A Virtual Machine
+//!
+//! Sets up a heap which grows by mapping frames of RAM into the page tables,
+//! gives it some real work to do, and reports on the heap and the frame
+//! allocator as it goes.
 //!
 //! Written by Jonathan Pallant at Ferrous Systems
 //!
@@ -10,33 +14,102 @@
 extern crate alloc;
 
 use aarch64_rt::entry;
-use embedded_alloc::LlffHeap as Heap;
-
-use qemu_aarch64v8a as _;
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use qemu_aarch64v8a::{devicetree::Platform, frames, heap, mmu::AddressSpace};
 
+#[cfg(not(feature = "virtio-console"))]
 #[global_allocator]
-static HEAP: Heap = Heap::empty();
+static HEAP: heap::GrowableHeap = heap::GrowableHeap::empty();
+
+// The library brings its own global allocator with this feature on
+#[cfg(feature = "virtio-console")]
+use heap::HEAP;
+
+/// How many numbers we sieve for primes
+const SIEVE_SIZE: usize = 4 * 1024 * 1024;
 
 entry!(main);
 
 /// The entry-point to the Rust application.
 ///
 /// It is called by the start-up code in `aarch64-rt`
-fn main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
-    // Initialize the allocator BEFORE you use it
-    {
-        const HEAP_SIZE: usize = 1024;
-        static mut HEAP_MEM: [u8; HEAP_SIZE] = [0u8; HEAP_SIZE];
-        unsafe {
-            let heap_start = core::ptr::addr_of_mut!(HEAP_MEM);
-            HEAP.init(heap_start as usize, HEAP_SIZE);
-        }
-    }
+fn main(arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+    qemu_aarch64v8a::stack::paint();
     defmt::println!("This is the with_heap example.");
+
+    // Initialize the allocator BEFORE you use it. It needs to know where RAM
+    // is, and page tables it can add to.
+    let platform = Platform::discover(arg0);
+    frames::init(&platform);
+    let space = AddressSpace::for_image().expect("building page tables");
+    // Safety: `for_image` maps everything this program uses, and nothing
+    // else maps anything where the heap goes
+    unsafe {
+        space.activate();
+        HEAP.init(space).expect("setting up the heap");
+    }
+
     let pi: f64 = core::f64::consts::PI;
     let s = alloc::format!("This is a heap allocated string, π = {:0.6}", pi);
     defmt::println!("s = {:?}", s.as_str());
-    semihosting::process::exit(0);
+    HEAP.report();
+
+    // A sieve of Eratosthenes, with a byte per number - much more than the
+    // heap starts out with
+    let mut is_prime = alloc::vec![true; SIEVE_SIZE];
+    is_prime[0] = false;
+    is_prime[1] = false;
+    let mut n = 2;
+    while n * n < SIEVE_SIZE {
+        if is_prime[n] {
+            for multiple in (n * n..SIEVE_SIZE).step_by(n) {
+                is_prime[multiple] = false;
+            }
+        }
+        n += 1;
+    }
+    let primes: Vec<u32> = (0..SIEVE_SIZE)
+        .filter(|&n| is_prime[n])
+        .map(|n| n as u32)
+        .collect();
+    defmt::println!(
+        "There are {=usize} primes below {=usize}, the biggest is {=u32}",
+        primes.len(),
+        SIEVE_SIZE,
+        primes.last().copied().unwrap_or(0)
+    );
+    drop(is_prime);
+    HEAP.report();
+
+    // Count the gaps between the primes, and name the most common ones
+    let mut gaps: BTreeMap<u32, usize> = BTreeMap::new();
+    for pair in primes.windows(2) {
+        *gaps.entry(pair[1] - pair[0]).or_default() += 1;
+    }
+    let mut by_count: Vec<(u32, usize)> = gaps.into_iter().collect();
+    by_count.sort_by_key(|&(_, count)| core::cmp::Reverse(count));
+    let mut summary = String::new();
+    for (gap, count) in by_count.iter().take(5) {
+        summary += &alloc::format!("{} ({} times), ", gap, count);
+    }
+    defmt::println!(
+        "The most common gaps are {=str}",
+        summary.trim_end_matches(", ")
+    );
+    drop(primes);
+    drop(by_count);
+    HEAP.report();
+
+    // Ask for more than the heap can ever have. `try_reserve` gives us an
+    // error rather than panicking, but the failure hook still logs it.
+    let mut too_big: Vec<u8> = Vec::new();
+    let size = qemu_aarch64v8a::heap::MAX_HEAP_SIZE + 1;
+    if too_big.try_reserve(size).is_err() {
+        defmt::println!("We couldn't reserve {=usize} bytes, as expected", size);
+    }
+    drop(s);
+    HEAP.report();
+    qemu_aarch64v8a::exit(0);
 }
 
 // End of file
@@ -0,0 +1,58 @@
+//! Crash records which survive a reset
+//!
+//! Call [`set_reset_on_crash(true)`](set_reset_on_crash) and the panic handler
+//! and the synchronous exception handler will store what went wrong in a
+//! [`CrashRecord`] and reset the machine with a PSCI `SYSTEM_RESET` call,
+//! instead of exiting QEMU. Call [`take_previous`] when you boot to see if the
+//! last run crashed.
+//!
+//! The record lives in the 1 KiB of RAM after our image - see `memory.ld`.
+
+pub use qemu_common::crash::{reset_on_crash, set_reset_on_crash, CrashKind, CrashRecord};
+
+use qemu_common::crash::CrashLog;
+
+extern "C" {
+    /// The start of the memory reserved for the crash log, from `memory.ld`
+    static mut _crash_log: u8;
+}
+
+/// Get a handle to our crash log
+fn crash_log() -> CrashLog {
+    // Safety: `memory.ld` reserves this memory for us
+    unsafe { CrashLog::new(&raw mut _crash_log) }
+}
+
+/// Get the crash record from before the last reset, if there is one
+///
+/// This also clears it, so you only see each crash once.
+pub fn take_previous() -> Option<CrashRecord> {
+    crash_log().take()
+}
+
+/// How many times we have reset because of a crash since QEMU started
+pub fn reset_count() -> u32 {
+    crash_log().reset_count()
+}
+
+/// Store a crash record for a panic, and reset
+pub(crate) fn panic_reset(info: &core::panic::PanicInfo) -> ! {
+    crash_log().record_panic(info);
+    reset()
+}
+
+/// Store a crash record for a synchronous exception, and reset
+///
+/// The registers are ELR_EL1, SPSR_EL1, ESR_EL1 and FAR_EL1.
+pub(crate) fn fault_reset(syndrome: &crate::exception::Syndrome) -> ! {
+    let registers = [syndrome.elr, syndrome.spsr, syndrome.esr, syndrome.far];
+    crash_log().record_fault("SyncException", &registers);
+    reset()
+}
+
+/// Reset the machine, with a PSCI `SYSTEM_RESET` call
+pub fn reset() -> ! {
+    crate::psci::system_reset()
+}
+
+// End of file
@@ -1,8 +1,20 @@
 //! Code that implements the `critical-section` traits on 64-bit Aarch64.
+//!
+//! By default we only mask interrupts, which is enough when only one core is
+//! running. Build with the `multicore` feature to also take a spinlock, so
+//! that a critical section on one core keeps out every other core too (see
+//! [`crate::smp`]).
 
+#[cfg(not(feature = "multicore"))]
 struct SingleCoreCriticalSection;
+#[cfg(not(feature = "multicore"))]
 critical_section::set_impl!(SingleCoreCriticalSection);
 
+#[cfg(feature = "multicore")]
+struct MultiCoreCriticalSection;
+#[cfg(feature = "multicore")]
+critical_section::set_impl!(MultiCoreCriticalSection);
+
 /// Reads the CPU interrupt status bit from DAIF
 ///
 /// Returns true if interrupts enabled.
@@ -13,24 +25,127 @@ pub fn interrupts_enabled() -> bool {
     unsafe {
         core::arch::asm!("mrs {0:x}, DAIF", out(reg) r, options(nomem, nostack, preserves_flags))
     };
-    r & DAIF_I_BIT != 0
+    r & DAIF_I_BIT == 0
+}
+
+/// Mask interrupts, and say whether they were enabled before
+#[inline]
+unsafe fn mask_interrupts() -> bool {
+    let was_active = interrupts_enabled();
+    // Disable interrupts by masking IRQs (leave FIQ enabled)
+    core::arch::asm!("msr DAIFset, #7", options(nomem, nostack, preserves_flags));
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    was_active
+}
+
+/// Unmask interrupts, if they were enabled before [`mask_interrupts`]
+#[inline]
+unsafe fn restore_interrupts(was_active: bool) {
+    // Only re-enable interrupts if they were enabled before the critical section.
+    if was_active {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+        // Enable interrupts by unmasking IRQs
+        core::arch::asm!("msr DAIFclr, #7", options(nomem, nostack, preserves_flags));
+    }
+}
+
+/// Run `f` with interrupts masked on this core, without taking the lock
+///
+/// This is for when we only need to keep our own interrupt handlers out -
+/// say, to check a queue and then `wfi` until an interrupt fills it - and
+/// don't want to hold up the other cores while we sleep. A `wfi` with
+/// interrupts masked still wakes up when one is pending. `f` can still take a
+/// critical section, for as long as it needs the data.
+pub fn with_interrupts_masked<T>(f: impl FnOnce() -> T) -> T {
+    // Safety: masking interrupts can't break anything
+    let was_active = unsafe { mask_interrupts() };
+    let result = f();
+    // Safety: we put the mask back how we found it
+    unsafe { restore_interrupts(was_active) };
+    result
+}
+
+/// Forget about any critical section this core is in
+///
+/// The test harness calls this when it abandons a test which panicked, so
+/// that a lock the test never released doesn't shut out the rest of the
+/// tests (or the other cores). It's up to the caller to set the interrupt
+/// mask as it should be.
+///
+/// # Safety
+///
+/// Nothing may still be relying on the critical section, because the code
+/// which was in it is never going to run again.
+pub(crate) unsafe fn abandon() {
+    #[cfg(feature = "multicore")]
+    multicore::abandon();
 }
 
+#[cfg(not(feature = "multicore"))]
 unsafe impl critical_section::Impl for SingleCoreCriticalSection {
     unsafe fn acquire() -> critical_section::RawRestoreState {
-        let was_active = interrupts_enabled();
-        // Disable interrupts by masking IRQs (leave FIQ enabled)
-        core::arch::asm!("msr DAIFset, #7", options(nomem, nostack, preserves_flags));
-        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
-        was_active
+        mask_interrupts()
     }
 
     unsafe fn release(was_active: critical_section::RawRestoreState) {
-        // Only re-enable interrupts if they were enabled before the critical section.
-        if was_active {
-            core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
-            // Enable interrupts by unmasking IRQs
-            core::arch::asm!("msr DAIFclr, #7", options(nomem, nostack, preserves_flags));
+        restore_interrupts(was_active)
+    }
+}
+
+#[cfg(feature = "multicore")]
+mod multicore {
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// The value of [`OWNER`] when no core holds the lock
+    const NO_OWNER: usize = usize::MAX;
+
+    /// Which core holds the lock
+    static OWNER: AtomicUsize = AtomicUsize::new(NO_OWNER);
+
+    /// Whether the core which holds the lock had interrupts enabled when it
+    /// took it
+    ///
+    /// Only the core which holds the lock touches this.
+    static WAS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+    /// Drop the lock, if this core holds it
+    pub(super) fn abandon() {
+        let core = crate::smp::core_id();
+        let _ = OWNER.compare_exchange(core, NO_OWNER, Ordering::Release, Ordering::Relaxed);
+    }
+
+    unsafe impl critical_section::Impl for super::MultiCoreCriticalSection {
+        /// Mask interrupts and take the lock
+        ///
+        /// Returns `true` if we took the lock, or `false` if this core
+        /// already had it - critical sections can nest.
+        unsafe fn acquire() -> critical_section::RawRestoreState {
+            let was_active = super::mask_interrupts();
+            let core = crate::smp::core_id();
+            if OWNER.load(Ordering::Relaxed) == core {
+                // Only we can have set it to our ID, and with interrupts
+                // masked we can't be in the middle of taking it
+                return false;
+            }
+            while OWNER
+                .compare_exchange_weak(NO_OWNER, core, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                core::hint::spin_loop();
+            }
+            WAS_ACTIVE.store(was_active, Ordering::Relaxed);
+            true
+        }
+
+        unsafe fn release(took_lock: critical_section::RawRestoreState) {
+            // An inner critical section leaves the lock, and interrupts, alone
+            if took_lock {
+                let was_active = WAS_ACTIVE.load(Ordering::Relaxed);
+                OWNER.store(NO_OWNER, Ordering::Release);
+                super::restore_interrupts(was_active);
+            }
         }
     }
 }
+
+// End of file
@@ -0,0 +1,331 @@
+//! Finding our hardware from the device tree
+//!
+//! QEMU describes the `virt` machine in a Flattened Device Tree (FDT) blob,
+//! and passes its address to the kernel in `x0`, which `aarch64-rt` gives to
+//! `main` as its first argument. [`Platform::discover`] reads the blob, with
+//! the `fdt` crate, and finds:
+//!
+//! * the first PL011 UART, and its interrupt
+//! * the GIC's version and addresses
+//! * the generic timer's interrupts
+//! * whether PSCI calls use `hvc` or `smc`
+//! * how much RAM there is, and how many CPUs
+//!
+//! If there's no blob (`x0` is zero, or doesn't point at a valid FDT in RAM),
+//! or it leaves something out, we use the fixed addresses of the `virt`
+//! machine instead, from [`Platform::qemu_virt`].
+//!
+//! [`Platform::discover`] also tells [`crate::psci`] which conduit to use and
+//! [`crate::timer`] which interrupts the timers raise, so everything which
+//! makes PSCI calls or takes timer interrupts uses what it found.
+//!
+//! Call it before switching to the page tables from [`crate::mmu`], which
+//! don't map the blob.
+
+use core::ptr::NonNull;
+
+use arm_pl011_uart::{PL011Registers, Uart, UniqueMmioPointer};
+use fdt::{node::FdtNode, Fdt};
+
+use crate::{gic, psci, timer};
+
+/// Where RAM starts on the QEMU `virt` machine
+pub const RAM_ADDRESS: usize = 0x4000_0000;
+
+/// How much RAM the QEMU `virt` machine has, unless you ask for more
+pub const DEFAULT_RAM_SIZE: usize = 128 * 1024 * 1024;
+
+/// How much RAM our initial identity map covers, which the blob must be in
+const MAPPED_RAM_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Base address of the first PL011 UART on the QEMU `virt` machine
+pub const UART_ADDRESS: usize = 0x0900_0000;
+
+/// The GIC Interrupt ID of the first PL011 on the QEMU `virt` machine (SPI 1)
+pub const UART_INTID: u32 = 32 + 1;
+
+/// The value of the `interrupts` type cell for a Shared Peripheral Interrupt
+const GIC_SPI: u32 = 0;
+
+/// The value of the `interrupts` type cell for a Private Peripheral Interrupt
+const GIC_PPI: u32 = 1;
+
+/// Where we found the platform description
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Source {
+    /// From a device tree blob
+    DeviceTree,
+    /// There was no device tree, so these are the defaults for `virt`
+    Defaults,
+}
+
+/// Where the GIC is
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct GicInfo {
+    /// Which version it is
+    pub version: gic::Version,
+    /// The address of the Distributor
+    pub distributor: usize,
+    /// The address of the CPU Interface (GICv2) or first Redistributor
+    /// (GICv3)
+    pub cpu: usize,
+}
+
+/// The hardware we found
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct Platform {
+    /// Where this came from
+    pub source: Source,
+    /// The address of the first PL011 UART
+    pub uart: usize,
+    /// The GIC Interrupt ID of the first PL011 UART
+    pub uart_intid: u32,
+    /// Where the GIC is
+    pub gic: GicInfo,
+    /// The GIC Interrupt ID of the EL1 physical timer
+    pub physical_timer_intid: u32,
+    /// The GIC Interrupt ID of the virtual timer
+    pub virtual_timer_intid: u32,
+    /// How to make PSCI calls
+    pub psci: psci::Conduit,
+    /// Where RAM starts
+    pub ram_start: usize,
+    /// How much RAM there is, in bytes
+    pub ram_size: usize,
+    /// How many CPUs there are
+    pub cpus: usize,
+    /// Where the device tree blob is, or zero if we didn't use one
+    pub dtb: usize,
+    /// How big the device tree blob is, in bytes
+    pub dtb_size: usize,
+}
+
+/// Why we couldn't use a device tree blob
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// We weren't given an address
+    NoPointer,
+    /// The address isn't in the RAM we have mapped
+    NotInRam,
+    /// The blob is broken, or isn't an FDT at all
+    Invalid,
+}
+
+impl Platform {
+    /// The fixed hardware of the QEMU `virt` machine
+    ///
+    /// We can't tell how much RAM or how many CPUs there are, so we assume
+    /// QEMU's defaults. We can tell which GIC there is, from the CPU.
+    pub fn qemu_virt() -> Platform {
+        let version = gic::detect_version();
+        Platform {
+            source: Source::Defaults,
+            uart: UART_ADDRESS,
+            uart_intid: UART_INTID,
+            gic: GicInfo {
+                version,
+                distributor: gic::GICD_ADDRESS,
+                cpu: match version {
+                    gic::Version::V2 => gic::GICC_ADDRESS,
+                    gic::Version::V3 => gic::GICR_ADDRESS,
+                },
+            },
+            physical_timer_intid: timer::PHYSICAL_TIMER_INTID,
+            virtual_timer_intid: timer::VIRTUAL_TIMER_INTID,
+            psci: psci::Conduit::Hvc,
+            ram_start: RAM_ADDRESS,
+            ram_size: DEFAULT_RAM_SIZE,
+            cpus: 1,
+            dtb: 0,
+            dtb_size: 0,
+        }
+    }
+
+    /// Describe the platform, from the device tree blob at `dtb` if there is
+    /// one
+    ///
+    /// Pass `main`'s first argument. If we can't use the blob, we log why and
+    /// fall back to [`Platform::qemu_virt`]. Either way, we set the PSCI
+    /// conduit and the timer interrupts to match.
+    pub fn discover(dtb: u64) -> Platform {
+        // Safety: `from_dtb` checks the blob is in RAM before reading it, and
+        // `x0` either points at a blob or at nothing in particular
+        let platform = match unsafe { Platform::from_dtb(dtb as usize) } {
+            Ok(platform) => platform,
+            Err(e) => {
+                defmt::warn!("No device tree ({}), using the defaults", e);
+                Platform::qemu_virt()
+            }
+        };
+        psci::set_conduit(platform.psci);
+        timer::set_intids(platform.physical_timer_intid, platform.virtual_timer_intid);
+        platform
+    }
+
+    /// Describe the platform, from the device tree blob at `dtb`
+    ///
+    /// Anything the blob doesn't mention comes from
+    /// [`Platform::qemu_virt`].
+    ///
+    /// # Safety
+    ///
+    /// If `dtb` is in RAM, it must be safe to read the blob's header there,
+    /// and all of the blob if the header is valid.
+    pub unsafe fn from_dtb(dtb: usize) -> Result<Platform, Error> {
+        if dtb == 0 {
+            return Err(Error::NoPointer);
+        }
+        if !(RAM_ADDRESS..RAM_ADDRESS + MAPPED_RAM_SIZE).contains(&dtb) || !dtb.is_multiple_of(8) {
+            return Err(Error::NotInRam);
+        }
+        // Safety: it's in mapped RAM, and our caller says we can read it
+        let fdt = unsafe { Fdt::from_ptr(dtb as *const u8) }.map_err(|e| {
+            defmt::debug!("FDT error: {}", defmt::Debug2Format(&e));
+            Error::Invalid
+        })?;
+        defmt::debug!(
+            "Device tree at {=usize:#x}, {=usize} bytes",
+            dtb,
+            fdt.total_size()
+        );
+        Ok(Platform {
+            dtb,
+            dtb_size: fdt.total_size(),
+            ..Platform::from_fdt(&fdt)
+        })
+    }
+
+    /// Describe the platform, from a parsed device tree
+    pub fn from_fdt(fdt: &Fdt) -> Platform {
+        let mut platform = Platform::qemu_virt();
+        platform.source = Source::DeviceTree;
+
+        if let Some(node) = fdt.find_compatible(&["arm,pl011"]) {
+            if let [Some(address)] = regs(node) {
+                platform.uart = address;
+            }
+            if let [Some(intid)] = interrupts(node) {
+                platform.uart_intid = intid;
+            }
+        }
+
+        if let Some(node) = fdt.find_compatible(&["arm,gic-v3"]) {
+            if let [Some(distributor), Some(redistributor)] = regs(node) {
+                platform.gic = GicInfo {
+                    version: gic::Version::V3,
+                    distributor,
+                    cpu: redistributor,
+                };
+            }
+        } else if let Some(node) = fdt.find_compatible(&["arm,cortex-a15-gic", "arm,gic-400"]) {
+            if let [Some(distributor), Some(cpu_interface)] = regs(node) {
+                platform.gic = GicInfo {
+                    version: gic::Version::V2,
+                    distributor,
+                    cpu: cpu_interface,
+                };
+            }
+        }
+
+        // The interrupts are the secure physical, non-secure physical,
+        // virtual and hypervisor timers, in that order
+        if let Some(node) = fdt.find_compatible(&["arm,armv8-timer", "arm,armv7-timer"]) {
+            if let [_, Some(physical), Some(virtual_)] = interrupts(node) {
+                platform.physical_timer_intid = physical;
+                platform.virtual_timer_intid = virtual_;
+            }
+        }
+
+        if let Some(node) = fdt.find_compatible(&["arm,psci-1.0", "arm,psci-0.2", "arm,psci"]) {
+            match node.property("method").and_then(|p| p.as_str()) {
+                Some("smc") => platform.psci = psci::Conduit::Smc,
+                Some("hvc") => platform.psci = psci::Conduit::Hvc,
+                other => defmt::warn!("Unknown PSCI method {}", other),
+            }
+        }
+
+        if let Some(ram) = fdt.memory().regions().next() {
+            platform.ram_start = ram.starting_address as usize;
+            platform.ram_size = ram.size.unwrap_or(platform.ram_size);
+        }
+
+        platform.cpus = fdt.cpus().count().max(1);
+        platform
+    }
+
+    /// The address of the first PL011 UART, ready for `UniqueMmioPointer`
+    pub fn uart_pointer(&self) -> NonNull<PL011Registers> {
+        NonNull::new(self.uart as *mut PL011Registers).expect("UART at address zero")
+    }
+
+    /// Create a driver for the first PL011 UART
+    ///
+    /// The UART isn't enabled yet - call `Uart::enable` to pick the line
+    /// settings and baud rate.
+    ///
+    /// # Safety
+    ///
+    /// Only create one driver at a time.
+    pub unsafe fn uart(&self) -> Uart<'static> {
+        // Safety: the device tree (or the `virt` machine, if there wasn't one)
+        // says there's a PL011 here. Our page tables map it as device memory
+        // for as long as the program runs, and our caller says there's no
+        // other driver.
+        let registers = unsafe { UniqueMmioPointer::new(self.uart_pointer()) };
+        Uart::new(registers)
+    }
+
+    /// Create a driver for the GIC
+    ///
+    /// # Safety
+    ///
+    /// Only create one driver at a time.
+    pub unsafe fn gic(&self) -> Result<gic::Gic, gic::Error> {
+        // Safety: the device tree says the GIC is here, and our caller says
+        // there's no other driver
+        unsafe {
+            gic::Gic::new_with_raw_addrs(
+                self.gic.version,
+                self.gic.distributor,
+                self.gic.cpu,
+                self.gic.cpu,
+            )
+        }
+    }
+}
+
+/// The first `N` addresses in a node's `reg` property
+fn regs<const N: usize>(node: FdtNode) -> [Option<usize>; N] {
+    let mut addresses = [None; N];
+    for (address, region) in addresses.iter_mut().zip(node.reg().into_iter().flatten()) {
+        *address = Some(region.starting_address as usize);
+    }
+    addresses
+}
+
+/// The first `N` GIC Interrupt IDs in a node's `interrupts` property
+///
+/// The GIC's bindings use three cells for each interrupt: the type, the
+/// number and the flags.
+fn interrupts<const N: usize>(node: FdtNode) -> [Option<u32>; N] {
+    let mut intids = [None; N];
+    let cells = node.property("interrupts").map(|p| p.value).unwrap_or(&[]);
+    for (intid, specifier) in intids.iter_mut().zip(cells.chunks_exact(12)) {
+        let cell = |n: usize| u32::from_be_bytes(specifier[n * 4..n * 4 + 4].try_into().unwrap());
+        *intid = gic_intid(cell(0), cell(1));
+    }
+    intids
+}
+
+/// Turn the type and number cells of a GIC interrupt specifier into an INTID
+///
+/// SPI *n* is INTID 32 + *n*, and PPI *n* is INTID 16 + *n*.
+pub fn gic_intid(kind: u32, number: u32) -> Option<u32> {
+    match kind {
+        GIC_SPI => Some(32 + number),
+        GIC_PPI => Some(16 + number),
+        _ => None,
+    }
+}
+
+// End of file
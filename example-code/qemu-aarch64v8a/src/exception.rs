@@ -0,0 +1,518 @@
+//! Decoding and reporting of synchronous exceptions
+//!
+//! When an instruction can't complete - it touched memory that isn't mapped,
+//! it isn't a valid instruction, it was a `brk` - an Armv8-A processor takes a
+//! synchronous exception. It records why in the Exception Syndrome Register
+//! (`ESR_EL1`), and for memory aborts it records the address it was trying to
+//! access in the Fault Address Register (`FAR_EL1`). The handler is given the
+//! address of the instruction (`ELR_EL1`) and the saved processor state
+//! (`SPSR_EL1`).
+//!
+//! This module reads those registers, turns them into something a human can
+//! read, and exits QEMU. Returning from the handler would just run the same
+//! instruction again, forever. Exceptions from user tasks at EL0 go to
+//! [`crate::user`] first, which stops the task instead.
+//!
+//! `aarch64-rt`'s handlers save registers on whatever stack we were using.
+//! If that stack has just overflowed into a guard page, saving them faults
+//! too, over and over. [`use_exception_stacks`] switches to our own vector
+//! table, which sends everything to `aarch64-rt`'s handlers apart from
+//! synchronous exceptions at EL1. Those never return, so we move to a stack
+//! of their own first, and say so if the fault was in a stack guard page.
+
+use crate::smp::MAX_CORES;
+
+/// How much stack each core's synchronous exception handler gets
+const EXCEPTION_STACK_SIZE: usize = 8 * 1024;
+
+/// The stacks for synchronous exceptions at EL1, one for each core
+#[repr(C, align(16))]
+struct ExceptionStacks([[u8; EXCEPTION_STACK_SIZE]; MAX_CORES]);
+
+/// Only our vector table uses these, and only one core uses each stack
+static mut EXCEPTION_STACKS: ExceptionStacks =
+    ExceptionStacks([[0; EXCEPTION_STACK_SIZE]; MAX_CORES]);
+
+extern "C" {
+    /// Our vector table, from the `global_asm!` below
+    static __exception_vectors: u8;
+}
+
+/// Where the exception was taken from
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Origin {
+    /// The Exception Level the handler runs at (EL1)
+    CurrentEl,
+    /// A lower Exception Level (EL0)
+    LowerEl,
+}
+
+/// The Exception Class, from `ESR_EL1.EC`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExceptionClass {
+    /// An instruction that isn't allocated, or isn't allowed at this level
+    Unknown,
+    /// A trapped `wfi` or `wfe`
+    WfiWfe,
+    /// A trapped access to the FPU or SIMD registers
+    FpAccess,
+    /// An exception return to an illegal state
+    IllegalState,
+    /// An `svc` instruction
+    Svc,
+    /// An `hvc` instruction
+    Hvc,
+    /// An `smc` instruction
+    Smc,
+    /// A trapped `msr`, `mrs` or system instruction
+    SystemRegister,
+    /// An Instruction Abort from a lower Exception Level
+    InstructionAbortLower,
+    /// An Instruction Abort from the current Exception Level
+    InstructionAbort,
+    /// A branch to a misaligned address
+    PcAlignment,
+    /// A Data Abort from a lower Exception Level
+    DataAbortLower,
+    /// A Data Abort from the current Exception Level
+    DataAbort,
+    /// A memory access through a misaligned stack pointer
+    SpAlignment,
+    /// A trapped floating-point exception
+    FpException,
+    /// A hardware breakpoint, single step or watchpoint
+    Debug,
+    /// A `brk` instruction
+    Brk,
+    /// Anything else
+    Other(u8),
+}
+
+impl ExceptionClass {
+    /// Decode the 6-bit `EC` field
+    pub const fn from_raw(ec: u8) -> ExceptionClass {
+        match ec {
+            0x00 => ExceptionClass::Unknown,
+            0x01 => ExceptionClass::WfiWfe,
+            0x07 => ExceptionClass::FpAccess,
+            0x0E => ExceptionClass::IllegalState,
+            0x15 => ExceptionClass::Svc,
+            0x16 => ExceptionClass::Hvc,
+            0x17 => ExceptionClass::Smc,
+            0x18 => ExceptionClass::SystemRegister,
+            0x20 => ExceptionClass::InstructionAbortLower,
+            0x21 => ExceptionClass::InstructionAbort,
+            0x22 => ExceptionClass::PcAlignment,
+            0x24 => ExceptionClass::DataAbortLower,
+            0x25 => ExceptionClass::DataAbort,
+            0x26 => ExceptionClass::SpAlignment,
+            0x2C => ExceptionClass::FpException,
+            0x30..=0x35 => ExceptionClass::Debug,
+            0x3C => ExceptionClass::Brk,
+            other => ExceptionClass::Other(other),
+        }
+    }
+
+    /// A human-readable description of this class
+    pub const fn description(self) -> &'static str {
+        match self {
+            ExceptionClass::Unknown => "undefined instruction",
+            ExceptionClass::WfiWfe => "trapped WFI or WFE",
+            ExceptionClass::FpAccess => "FP/SIMD access with the FPU disabled",
+            ExceptionClass::IllegalState => "illegal execution state",
+            ExceptionClass::Svc => "supervisor call (SVC)",
+            ExceptionClass::Hvc => "hypervisor call (HVC)",
+            ExceptionClass::Smc => "secure monitor call (SMC)",
+            ExceptionClass::SystemRegister => "trapped system register access",
+            ExceptionClass::InstructionAbortLower => "instruction abort from a lower EL",
+            ExceptionClass::InstructionAbort => "instruction abort",
+            ExceptionClass::PcAlignment => "misaligned PC",
+            ExceptionClass::DataAbortLower => "data abort from a lower EL",
+            ExceptionClass::DataAbort => "data abort",
+            ExceptionClass::SpAlignment => "misaligned SP",
+            ExceptionClass::FpException => "floating-point exception",
+            ExceptionClass::Debug => "breakpoint, step or watchpoint",
+            ExceptionClass::Brk => "breakpoint instruction (BRK)",
+            ExceptionClass::Other(_) => "unexpected exception class",
+        }
+    }
+
+    /// Is this an Instruction Abort or a Data Abort?
+    pub const fn is_abort(self) -> bool {
+        matches!(
+            self,
+            ExceptionClass::InstructionAbortLower
+                | ExceptionClass::InstructionAbort
+                | ExceptionClass::DataAbortLower
+                | ExceptionClass::DataAbort
+        )
+    }
+
+    /// Is this a Data Abort?
+    pub const fn is_data_abort(self) -> bool {
+        matches!(
+            self,
+            ExceptionClass::DataAbortLower | ExceptionClass::DataAbort
+        )
+    }
+}
+
+impl defmt::Format for ExceptionClass {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=str}", self.description());
+    }
+}
+
+/// The Fault Status Code of an abort, from `ISS.DFSC` or `ISS.IFSC`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FaultStatus {
+    /// The address was too big for the translation tables, at some level
+    AddressSize(u8),
+    /// There was no valid translation table entry, at some level
+    Translation(u8),
+    /// The translation table entry had its Access Flag clear, at some level
+    AccessFlag(u8),
+    /// The translation table entry didn't allow this access, at some level
+    Permission(u8),
+    /// The memory system returned an error
+    SynchronousExternal,
+    /// The memory system returned an error during a translation table walk,
+    /// at some level
+    SynchronousExternalOnWalk(u8),
+    /// A misaligned access
+    Alignment,
+    /// More than one translation table entry matched
+    TlbConflict,
+    /// Anything else
+    Other(u8),
+}
+
+impl FaultStatus {
+    /// Decode the 6-bit `DFSC` or `IFSC` field
+    pub const fn from_raw(fsc: u8) -> FaultStatus {
+        let level = fsc & 0b11;
+        match fsc & 0x3F {
+            0x00..=0x03 => FaultStatus::AddressSize(level),
+            0x04..=0x07 => FaultStatus::Translation(level),
+            0x09..=0x0B => FaultStatus::AccessFlag(level),
+            0x0D..=0x0F => FaultStatus::Permission(level),
+            0x10 => FaultStatus::SynchronousExternal,
+            0x14..=0x17 => FaultStatus::SynchronousExternalOnWalk(level),
+            0x21 => FaultStatus::Alignment,
+            0x30 => FaultStatus::TlbConflict,
+            other => FaultStatus::Other(other),
+        }
+    }
+
+    /// A human-readable description of this fault status
+    pub const fn description(self) -> &'static str {
+        match self {
+            FaultStatus::AddressSize(_) => "address size fault",
+            FaultStatus::Translation(_) => "translation fault (nothing mapped there)",
+            FaultStatus::AccessFlag(_) => "access flag fault",
+            FaultStatus::Permission(_) => "permission fault",
+            FaultStatus::SynchronousExternal => "synchronous external abort",
+            FaultStatus::SynchronousExternalOnWalk(_) => {
+                "synchronous external abort on a translation table walk"
+            }
+            FaultStatus::Alignment => "alignment fault",
+            FaultStatus::TlbConflict => "TLB conflict abort",
+            FaultStatus::Other(_) => "unexpected fault status",
+        }
+    }
+
+    /// Which level of translation table the fault happened at, if any
+    pub const fn level(self) -> Option<u8> {
+        match self {
+            FaultStatus::AddressSize(level)
+            | FaultStatus::Translation(level)
+            | FaultStatus::AccessFlag(level)
+            | FaultStatus::Permission(level)
+            | FaultStatus::SynchronousExternalOnWalk(level) => Some(level),
+            _ => None,
+        }
+    }
+}
+
+impl defmt::Format for FaultStatus {
+    fn format(&self, f: defmt::Formatter) {
+        match self.level() {
+            Some(level) => defmt::write!(f, "{=str}, level {=u8}", self.description(), level),
+            None => defmt::write!(f, "{=str}", self.description()),
+        }
+    }
+}
+
+/// A snapshot of the registers describing a synchronous exception
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Syndrome {
+    /// Exception Syndrome Register
+    pub esr: u64,
+    /// Fault Address Register
+    pub far: u64,
+    /// Exception Link Register - the address of the instruction
+    pub elr: u64,
+    /// Saved Program Status Register - the state we were in
+    pub spsr: u64,
+}
+
+impl Syndrome {
+    /// `ESR_EL1` bit which says the instruction was 32 bits long
+    const ESR_IL: u64 = 1 << 25;
+
+    /// `ISS` bit which says a Data Abort was caused by a write
+    const ISS_WNR: u32 = 1 << 6;
+
+    /// `ISS` bit which says `FAR_EL1` is not valid, for an abort
+    const ISS_FNV: u32 = 1 << 10;
+
+    /// Read `ESR_EL1` and `FAR_EL1`, and combine them with the ELR and SPSR
+    /// values the exception handler was given
+    pub fn read(elr: u64, spsr: u64) -> Syndrome {
+        let esr: u64;
+        let far: u64;
+        // Safety: We only read registers here, which has no side-effects
+        unsafe {
+            core::arch::asm!(
+                "mrs {esr}, ESR_EL1",
+                "mrs {far}, FAR_EL1",
+                esr = out(reg) esr,
+                far = out(reg) far,
+                options(nomem, nostack, preserves_flags)
+            );
+        }
+        Syndrome {
+            esr,
+            far,
+            elr,
+            spsr,
+        }
+    }
+
+    /// The Exception Class
+    pub fn class(&self) -> ExceptionClass {
+        ExceptionClass::from_raw(((self.esr >> 26) & 0x3F) as u8)
+    }
+
+    /// The Instruction Specific Syndrome
+    pub fn iss(&self) -> u32 {
+        (self.esr & 0x01FF_FFFF) as u32
+    }
+
+    /// Was the instruction 32 bits long (rather than a 16-bit T32 one)?
+    pub fn is_32_bit_instruction(&self) -> bool {
+        self.esr & Self::ESR_IL != 0
+    }
+
+    /// The fault status, if this was an abort
+    pub fn fault_status(&self) -> Option<FaultStatus> {
+        self.class()
+            .is_abort()
+            .then(|| FaultStatus::from_raw((self.iss() & 0x3F) as u8))
+    }
+
+    /// The address that was being accessed, if this was an abort and the
+    /// processor recorded one
+    pub fn fault_address(&self) -> Option<u64> {
+        let class = self.class();
+        let valid = match class {
+            _ if class.is_abort() => self.iss() & Self::ISS_FNV == 0,
+            ExceptionClass::PcAlignment | ExceptionClass::Debug => true,
+            _ => false,
+        };
+        valid.then_some(self.far)
+    }
+
+    /// Was this a Data Abort caused by a write (rather than a read)?
+    pub fn is_write(&self) -> bool {
+        self.class().is_data_abort() && self.iss() & Self::ISS_WNR != 0
+    }
+
+    /// The 16-bit immediate from an `svc`, `hvc`, `smc` or `brk` instruction
+    pub fn immediate(&self) -> Option<u16> {
+        match self.class() {
+            ExceptionClass::Svc
+            | ExceptionClass::Hvc
+            | ExceptionClass::Smc
+            | ExceptionClass::Brk => Some(self.iss() as u16),
+            _ => None,
+        }
+    }
+
+    /// The Exception Level and stack pointer we were using, from `SPSR.M`
+    pub fn mode(&self) -> &'static str {
+        match self.spsr & 0b1111 {
+            0b0000 => "EL0t",
+            0b0100 => "EL1t",
+            0b0101 => "EL1h",
+            0b1000 => "EL2t",
+            0b1001 => "EL2h",
+            0b1100 => "EL3t",
+            0b1101 => "EL3h",
+            _ => "unknown",
+        }
+    }
+}
+
+/// Print everything we know about a synchronous exception, and exit QEMU.
+///
+/// If [`crate::crash::set_reset_on_crash`] has been turned on, store a crash
+/// record and reset instead.
+pub fn report(origin: Origin, elr: u64, spsr: u64) -> ! {
+    let syndrome = Syndrome::read(elr, spsr);
+    if crate::crash::reset_on_crash() {
+        crate::crash::fault_reset(&syndrome);
+    }
+    log(origin, &syndrome);
+    crate::exit(1);
+}
+
+/// Print everything we know about a synchronous exception
+pub fn log(origin: Origin, syndrome: &Syndrome) {
+    let class = syndrome.class();
+    defmt::error!(
+        "Synchronous exception ({}) at pc=0x{=u64:016x}: {}",
+        origin,
+        syndrome.elr,
+        class
+    );
+    defmt::error!(
+        "ESR_EL1=0x{=u64:08x} (EC=0x{=u8:02x}, IL={=bool}, ISS=0x{=u32:07x})",
+        syndrome.esr,
+        ((syndrome.esr >> 26) & 0x3F) as u8,
+        syndrome.is_32_bit_instruction(),
+        syndrome.iss()
+    );
+    defmt::error!(
+        "ELR_EL1=0x{=u64:016x}, SPSR_EL1=0x{=u64:08x} ({=str}, DAIF=0b{=u64:04b}), FAR_EL1=0x{=u64:016x}",
+        syndrome.elr,
+        syndrome.spsr,
+        syndrome.mode(),
+        (syndrome.spsr >> 6) & 0b1111,
+        syndrome.far
+    );
+    if let Some(status) = syndrome.fault_status() {
+        let access = if !class.is_data_abort() {
+            "instruction fetch"
+        } else if syndrome.is_write() {
+            "write"
+        } else {
+            "read"
+        };
+        defmt::error!("- {} on {=str}", status, access);
+    }
+    if let Some(addr) = syndrome.fault_address() {
+        defmt::error!("- Fault address (FAR_EL1) = 0x{=u64:016x}", addr);
+    }
+    if let Some(imm) = syndrome.immediate() {
+        defmt::error!("- Immediate = 0x{=u16:04x}", imm);
+    }
+}
+
+/// Point `VBAR_EL1` at our vector table, so synchronous exceptions at EL1 are
+/// handled on a stack of their own
+///
+/// Secondary cores take their vectors from core 0 when [`crate::smp`] starts
+/// them. [`crate::mmu::AddressSpace::activate`] calls this for you.
+pub fn use_exception_stacks() {
+    // Safety: our vector table handles everything `aarch64-rt`'s does
+    unsafe {
+        core::arch::asm!(
+            "msr VBAR_EL1, {0}",
+            "isb",
+            in(reg) &raw const __exception_vectors,
+            options(nostack, preserves_flags)
+        );
+    }
+}
+
+/// Called by our vector table for a synchronous exception at EL1, on this
+/// core's exception stack
+///
+/// `sp` is where the stack pointer was when the exception was taken.
+extern "C" fn sync_exception_on_own_stack(elr: u64, spsr: u64, sp: u64) -> ! {
+    let syndrome = Syndrome::read(elr, spsr);
+    if let Some(addr) = syndrome.fault_address() {
+        if crate::stack::is_guard_page(addr as usize) {
+            defmt::error!(
+                "Stack overflow! Hit the guard page at 0x{=u64:016x} with sp=0x{=u64:016x}",
+                addr,
+                sp
+            );
+        }
+    }
+    report(Origin::CurrentEl, elr, spsr);
+}
+
+// Our vector table. Each entry is 0x80 bytes, and all but one of them go
+// straight to the same entry in `aarch64-rt`'s table.
+//
+// For a synchronous exception at EL1 using SP_EL1, we throw away the stack
+// we were on (which might be what overflowed) and the registers, and go to
+// the top of this core's exception stack.
+core::arch::global_asm!(
+    r#"
+    .section .text.__exception_vectors, "ax"
+    .global __exception_vectors
+    .balign 0x800
+__exception_vectors:
+    // Current EL with SP_EL0
+    b       vector_table_el1 + 0x000
+    .balign 0x80
+    b       vector_table_el1 + 0x080
+    .balign 0x80
+    b       vector_table_el1 + 0x100
+    .balign 0x80
+    b       vector_table_el1 + 0x180
+    .balign 0x80
+    // Current EL with SP_EL1 - synchronous
+    mov     x2, sp
+    mrs     x9, MPIDR_EL1
+    and     x9, x9, #0xff
+    mov     x10, #{last_core}
+    cmp     x9, x10
+    csel    x9, x9, x10, lo
+    add     x9, x9, #1
+    mov     x10, #{stack_size}
+    adrp    x11, {stacks}
+    add     x11, x11, :lo12:{stacks}
+    madd    x11, x9, x10, x11
+    mov     sp, x11
+    mov     x29, xzr
+    mov     x30, xzr
+    mrs     x0, ELR_EL1
+    mrs     x1, SPSR_EL1
+    b       {handler}
+    .balign 0x80
+    // Current EL with SP_EL1 - IRQ, FIQ and SError
+    b       vector_table_el1 + 0x280
+    .balign 0x80
+    b       vector_table_el1 + 0x300
+    .balign 0x80
+    b       vector_table_el1 + 0x380
+    .balign 0x80
+    // Lower EL, AArch64
+    b       vector_table_el1 + 0x400
+    .balign 0x80
+    b       vector_table_el1 + 0x480
+    .balign 0x80
+    b       vector_table_el1 + 0x500
+    .balign 0x80
+    b       vector_table_el1 + 0x580
+    .balign 0x80
+    // Lower EL, AArch32
+    b       vector_table_el1 + 0x600
+    .balign 0x80
+    b       vector_table_el1 + 0x680
+    .balign 0x80
+    b       vector_table_el1 + 0x700
+    .balign 0x80
+    b       vector_table_el1 + 0x780
+    "#,
+    last_core = const MAX_CORES - 1,
+    stack_size = const EXCEPTION_STACK_SIZE,
+    stacks = sym EXCEPTION_STACKS,
+    handler = sym sync_exception_on_own_stack,
+);
+
+// End of file
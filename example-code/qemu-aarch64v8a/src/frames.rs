@@ -0,0 +1,286 @@
+//! A physical frame allocator
+//!
+//! A frame is a page-sized, page-aligned piece of RAM. [`FrameAllocator`]
+//! keeps one bit per frame, set when the frame is free, and hands out runs
+//! of contiguous frames first-fit, starting its search where the last one
+//! finished. It never reads or writes the frames themselves.
+//!
+//! There's one global allocator, which manages all of RAM. Call [`init`] once
+//! you know how much RAM there is (see [`crate::devicetree`]), and it marks
+//! our image, the crash log and the device tree blob as reserved. Then use
+//! [`allocate`] and [`free`], and [`report`] to see how it's going.
+//!
+//! The global bitmap covers up to [`MAX_RAM`] - the most our page tables
+//! identity map. Any more RAM is ignored.
+
+use core::{cell::RefCell, ops::Range};
+
+use critical_section::Mutex;
+
+use crate::{devicetree::Platform, mmu};
+
+/// The size of a frame
+pub const FRAME_SIZE: usize = mmu::PAGE_SIZE;
+
+/// The most RAM the global allocator manages
+pub const MAX_RAM: usize = 1024 * 1024 * 1024;
+
+/// How many words the global allocator's bitmap needs
+const GLOBAL_WORDS: usize = MAX_RAM / FRAME_SIZE / 64;
+
+extern "C" {
+    /// The start of the crash log, just after our image, from `memory.ld`
+    static _crash_log: u8;
+}
+
+/// A snapshot of the frame allocator's statistics
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct FrameStats {
+    /// How many frames we manage
+    pub total: usize,
+    /// How many frames were reserved, and can't be allocated
+    pub reserved: usize,
+    /// How many frames are allocated right now
+    pub used: usize,
+    /// The most frames that have been allocated at once
+    pub peak: usize,
+    /// How many frames are free
+    pub free: usize,
+    /// How many allocations have succeeded
+    pub allocations: u32,
+    /// How many allocations have been freed
+    pub frees: u32,
+    /// How many allocations have failed
+    pub failures: u32,
+}
+
+/// A bitmap frame allocator for up to `WORDS * 64` frames
+pub struct FrameAllocator<const WORDS: usize> {
+    /// The address of the first frame
+    base: usize,
+    /// How many frames we manage
+    frames: usize,
+    /// One bit per frame, set if the frame is free
+    ///
+    /// An all-zero bitmap means we start out in `.bss`, rather than taking
+    /// up space in the image.
+    bitmap: [u64; WORDS],
+    /// Where to start looking next time
+    next: usize,
+    reserved: usize,
+    used: usize,
+    peak: usize,
+    allocations: u32,
+    frees: u32,
+    failures: u32,
+}
+
+impl<const WORDS: usize> FrameAllocator<WORDS> {
+    /// Make an allocator with no frames
+    pub const fn new() -> Self {
+        FrameAllocator {
+            base: 0,
+            frames: 0,
+            bitmap: [0; WORDS],
+            next: 0,
+            reserved: 0,
+            used: 0,
+            peak: 0,
+            allocations: 0,
+            frees: 0,
+            failures: 0,
+        }
+    }
+
+    /// Manage the frames in `ram`, which all start out free
+    ///
+    /// Any partial frames at either end are left out, and so is anything
+    /// past `WORDS * 64` frames. This forgets about any earlier allocations.
+    pub fn init(&mut self, ram: Range<usize>) {
+        let start = mmu::page_align_up(ram.start);
+        let end = mmu::page_align_down(ram.end).max(start);
+        self.base = start;
+        self.frames = ((end - start) / FRAME_SIZE).min(WORDS * 64);
+        self.bitmap.fill(0);
+        for frame in 0..self.frames {
+            self.set_free(frame, true);
+        }
+        self.next = 0;
+        self.reserved = 0;
+        self.used = 0;
+        self.peak = 0;
+        self.allocations = 0;
+        self.frees = 0;
+        self.failures = 0;
+    }
+
+    /// Mark the frames overlapping `range` as in use, so they are never
+    /// allocated
+    pub fn reserve(&mut self, range: Range<usize>) {
+        let end = self.base + self.frames * FRAME_SIZE;
+        let start = range.start.clamp(self.base, end);
+        let end = range.end.clamp(start, end);
+        let first = (start - self.base) / FRAME_SIZE;
+        let last = (end - self.base).div_ceil(FRAME_SIZE);
+        for frame in first..last {
+            if self.is_free(frame) {
+                self.set_free(frame, false);
+                self.reserved += 1;
+            }
+        }
+    }
+
+    /// Allocate `count` contiguous frames, and return the address of the
+    /// first
+    pub fn allocate(&mut self, count: usize) -> Option<usize> {
+        let Some(first) = self.find(count) else {
+            self.failures += 1;
+            return None;
+        };
+        for frame in first..first + count {
+            self.set_free(frame, false);
+        }
+        self.next = (first + count) % self.frames.max(1);
+        self.used += count;
+        self.peak = self.peak.max(self.used);
+        self.allocations += 1;
+        Some(self.base + first * FRAME_SIZE)
+    }
+
+    /// Free `count` frames starting at `addr`, which [`allocate`] gave us
+    ///
+    /// [`allocate`]: FrameAllocator::allocate
+    pub fn free(&mut self, addr: usize, count: usize) {
+        let frames = self
+            .frames_in(addr..addr + count * FRAME_SIZE)
+            .expect("freeing frames we don't manage");
+        for frame in frames {
+            assert!(!self.is_free(frame), "double free of frame {:#x}", addr);
+            self.set_free(frame, true);
+        }
+        self.used -= count;
+        self.frees += 1;
+    }
+
+    /// Get the current statistics
+    pub fn stats(&self) -> FrameStats {
+        FrameStats {
+            total: self.frames,
+            reserved: self.reserved,
+            used: self.used,
+            peak: self.peak,
+            free: self.frames - self.reserved - self.used,
+            allocations: self.allocations,
+            frees: self.frees,
+            failures: self.failures,
+        }
+    }
+
+    /// The frame numbers covering `range`, if they are all ours
+    fn frames_in(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let first = range.start.checked_sub(self.base)? / FRAME_SIZE;
+        let last = (range.end.checked_sub(self.base)?).div_ceil(FRAME_SIZE);
+        (last <= self.frames).then_some(first..last)
+    }
+
+    /// Find `count` free frames in a row
+    fn find(&self, count: usize) -> Option<usize> {
+        if count == 0 || count > self.frames {
+            return None;
+        }
+        // Look from `next` to the end, and then from the start
+        let mut first = self.next;
+        let mut wrapped = false;
+        loop {
+            if first + count > self.frames {
+                if wrapped {
+                    return None;
+                }
+                wrapped = true;
+                first = 0;
+                continue;
+            }
+            if wrapped && first >= self.next {
+                return None;
+            }
+            // Skip whole words at a time when they're full
+            if first.is_multiple_of(64) && self.bitmap[first / 64] == 0 {
+                first += 64;
+                continue;
+            }
+            match (first..first + count)
+                .rev()
+                .find(|&frame| !self.is_free(frame))
+            {
+                // The run is free
+                None => return Some(first),
+                // Start again after the frame in use
+                Some(used) => first = used + 1,
+            }
+        }
+    }
+
+    /// Is this frame free?
+    fn is_free(&self, frame: usize) -> bool {
+        self.bitmap[frame / 64] & (1 << (frame % 64)) != 0
+    }
+
+    /// Mark this frame as free, or not
+    fn set_free(&mut self, frame: usize, free: bool) {
+        if free {
+            self.bitmap[frame / 64] |= 1 << (frame % 64);
+        } else {
+            self.bitmap[frame / 64] &= !(1 << (frame % 64));
+        }
+    }
+}
+
+impl<const WORDS: usize> Default for FrameAllocator<WORDS> {
+    fn default() -> Self {
+        FrameAllocator::new()
+    }
+}
+
+/// The global frame allocator
+static FRAMES: Mutex<RefCell<FrameAllocator<GLOBAL_WORDS>>> =
+    Mutex::new(RefCell::new(FrameAllocator::new()));
+
+/// Manage the RAM on this platform with the global frame allocator
+///
+/// Our image and the crash log after it are reserved, and so is the device
+/// tree blob, if there is one.
+pub fn init(platform: &Platform) {
+    let ram = platform.ram_start..platform.ram_start + platform.ram_size;
+    let image_end = (&raw const _crash_log) as usize + qemu_common::crash::CRASH_LOG_SIZE;
+    critical_section::with(|cs| {
+        let mut frames = FRAMES.borrow_ref_mut(cs);
+        frames.init(ram.clone());
+        frames.reserve(ram.start..image_end);
+        frames.reserve(platform.dtb..platform.dtb + platform.dtb_size);
+    });
+    defmt::debug!("Frame allocator: {}", stats());
+}
+
+/// Allocate `count` contiguous frames from the global allocator
+///
+/// Returns the address of the first. The frames may hold anything.
+pub fn allocate(count: usize) -> Option<usize> {
+    critical_section::with(|cs| FRAMES.borrow_ref_mut(cs).allocate(count))
+}
+
+/// Give `count` frames starting at `addr` back to the global allocator
+pub fn free(addr: usize, count: usize) {
+    critical_section::with(|cs| FRAMES.borrow_ref_mut(cs).free(addr, count))
+}
+
+/// Get the global allocator's statistics
+pub fn stats() -> FrameStats {
+    critical_section::with(|cs| FRAMES.borrow_ref(cs).stats())
+}
+
+/// Print the global allocator's statistics with defmt
+pub fn report() {
+    defmt::info!("Frames: {}", stats());
+}
+
+// End of file
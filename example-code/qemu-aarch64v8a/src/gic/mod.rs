@@ -0,0 +1,667 @@
+//! A driver for the Arm Generic Interrupt Controller (GICv2 and GICv3)
+//!
+//! The QEMU `virt` machine has a GICv2 unless you ask for a GICv3 with
+//! `--machine virt,gic-version=3`. We work out which one we have from
+//! `ID_AA64PFR0_EL1`, which says whether the CPU has the GICv3 system register
+//! interface.
+//!
+//! Every GIC has a *Distributor*, which decides which interrupts are enabled
+//! and which CPU they go to. Each CPU then has:
+//!
+//! * on a GICv2, a memory-mapped *CPU Interface*, which we read to acknowledge
+//!   an interrupt and write to finish with it
+//! * on a GICv3, a *Redistributor*, which handles that CPU's SGIs and PPIs,
+//!   plus a CPU Interface made of system registers
+//!
+//! Interrupt IDs (INTIDs) 0 to 15 are Software Generated Interrupts (SGIs),
+//! 16 to 31 are Private Peripheral Interrupts (PPIs) and 32 upwards are Shared
+//! Peripheral Interrupts (SPIs). On QEMU `virt`, device interrupt *n* is SPI
+//! *n*, which is INTID 32 + *n*.
+//!
+//! Call [`Gic::register`] with a handler and a priority, and the handler is
+//! called from our IRQ exception handler, via [`handle_irq`]. You still have
+//! to unmask IRQs on the CPU, with [`crate::enable_interrupts`].
+//!
+//! We put every interrupt in Group 1 on a GICv3 (as Group 0 interrupts are
+//! signalled as FIQs), and leave them in Group 0 on a GICv2 (where, with
+//! `FIQEn` clear, they are signalled as IRQs).
+
+use core::{
+    cell::Cell,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+
+use critical_section::Mutex;
+
+pub mod registers;
+
+/// Base address of the Distributor on the QEMU `virt` machine
+pub const GICD_ADDRESS: usize = 0x0800_0000;
+
+/// Base address of the GICv2 CPU Interface on the QEMU `virt` machine
+pub const GICC_ADDRESS: usize = 0x0801_0000;
+
+/// Base address of the first GICv3 Redistributor on the QEMU `virt` machine
+pub const GICR_ADDRESS: usize = 0x080A_0000;
+
+/// The gap between one GICv3 Redistributor and the next
+const GICR_STRIDE: usize = 0x2_0000;
+
+/// How many interrupts we keep handlers for
+///
+/// That's the 16 SGIs, 16 PPIs and the 256 SPIs on the QEMU `virt` machine.
+pub const NUM_INTIDS: usize = 32 + 256;
+
+/// The priority given to every interrupt by [`Gic::init`]
+///
+/// Lower numbers are more urgent. The GIC might only implement the top few
+/// bits of each priority.
+pub const DEFAULT_PRIORITY: u8 = 0xA0;
+
+/// INTIDs of this value and above mean "no interrupt"
+const SPECIAL_INTIDS: u32 = 1020;
+
+/// A function which handles an interrupt
+pub type Handler = fn();
+
+/// Which version of the GIC we have
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Version {
+    /// A GICv2, with a memory-mapped CPU Interface
+    V2,
+    /// A GICv3, with Redistributors and a system register CPU Interface
+    V3,
+}
+
+/// How an interrupt is signalled
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Trigger {
+    /// The interrupt is pending as long as the signal is high
+    Level,
+    /// The interrupt becomes pending on a rising edge
+    Edge,
+}
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// The INTID is out of range
+    InvalidIntId,
+    /// SGIs are always edge-triggered
+    CannotConfigure,
+    /// There's no Redistributor for this CPU
+    NoRedistributor,
+}
+
+/// An interrupt we have acknowledged, and must finish with
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct Acknowledged {
+    /// The interrupt ID
+    pub intid: u32,
+    /// What we read from the acknowledge register, which we must write back
+    /// unchanged
+    raw: u32,
+}
+
+/// The version of the GIC we initialised, or 0 if we haven't
+static ACTIVE_VERSION: AtomicU8 = AtomicU8::new(0);
+
+/// The address of the Distributor we initialised
+static ACTIVE_GICD: AtomicUsize = AtomicUsize::new(0);
+
+/// The address of the GICv2 CPU Interface or GICv3 Redistributor we
+/// initialised
+static ACTIVE_CPU: AtomicUsize = AtomicUsize::new(0);
+
+/// The registered interrupt handlers, indexed by INTID
+static HANDLERS: Mutex<[Cell<Option<Handler>>; NUM_INTIDS]> =
+    Mutex::new([const { Cell::new(None) }; NUM_INTIDS]);
+
+/// The per-CPU part of the GIC
+enum Cpu {
+    V2(registers::MmioCpuInterface<'static>),
+    V3(registers::MmioRedistributor<'static>),
+}
+
+/// A GICv2 or GICv3 driver
+pub struct Gic {
+    distributor: registers::MmioDistributor<'static>,
+    cpu: Cpu,
+    /// Where the Distributor is
+    gicd_addr: usize,
+    /// Where the CPU Interface or Redistributor is
+    cpu_addr: usize,
+    /// The number of INTIDs the Distributor supports
+    num_intids: u32,
+}
+
+impl Gic {
+    /// Create a driver for the GIC on the QEMU `virt` machine
+    ///
+    /// # Safety
+    ///
+    /// Only create one driver at a time.
+    pub unsafe fn new_virt() -> Result<Gic, Error> {
+        // Safety: these are the addresses of the GIC on QEMU `virt`
+        unsafe {
+            Self::new_with_raw_addrs(detect_version(), GICD_ADDRESS, GICC_ADDRESS, GICR_ADDRESS)
+        }
+    }
+
+    /// Create a driver for a GIC at the given addresses
+    ///
+    /// We use `gicc_addr` for a GICv2, and search the Redistributors starting
+    /// at `gicr_addr` for this CPU's one on a GICv3.
+    ///
+    /// # Safety
+    ///
+    /// * Only create one driver at a time.
+    /// * The addresses must point at the relevant GIC registers.
+    pub unsafe fn new_with_raw_addrs(
+        version: Version,
+        gicd_addr: usize,
+        gicc_addr: usize,
+        gicr_addr: usize,
+    ) -> Result<Gic, Error> {
+        // Safety: the caller says this is a Distributor
+        let distributor = unsafe { registers::Distributor::new_mmio_at(gicd_addr) };
+        let (cpu, cpu_addr) = match version {
+            // Safety: the caller says this is a CPU Interface
+            Version::V2 => (
+                Cpu::V2(unsafe { registers::CpuInterface::new_mmio_at(gicc_addr) }),
+                gicc_addr,
+            ),
+            Version::V3 => {
+                // Safety: the caller says these are Redistributors
+                let addr = unsafe { find_redistributor(gicr_addr)? };
+                // Safety: we just found it
+                let redistributor = unsafe { registers::Redistributor::new_mmio_at(addr) };
+                (Cpu::V3(redistributor), addr)
+            }
+        };
+        let lines = distributor.read_controller_type() & 0x1F;
+        let num_intids = (32 * (lines + 1)).min(SPECIAL_INTIDS);
+        Ok(Gic {
+            distributor,
+            cpu,
+            gicd_addr,
+            cpu_addr,
+            num_intids,
+        })
+    }
+
+    /// Which version of GIC is this?
+    pub fn version(&self) -> Version {
+        match self.cpu {
+            Cpu::V2(_) => Version::V2,
+            Cpu::V3(_) => Version::V3,
+        }
+    }
+
+    /// The number of INTIDs the Distributor supports
+    pub fn num_intids(&self) -> u32 {
+        self.num_intids
+    }
+
+    /// Set up the GIC, so that interrupts can be delivered to this CPU
+    ///
+    /// Every interrupt starts off disabled, level-triggered and with
+    /// [`DEFAULT_PRIORITY`]. The priority mask is opened up fully.
+    pub fn init(&mut self) {
+        self.distributor
+            .write_control(registers::DistributorControl::DEFAULT);
+        self.wait_for_distributor();
+
+        let version = self.version();
+        for intid in 32..self.num_intids {
+            let (word, bit) = bit_index(intid);
+            if bit == 0 {
+                self.distributor.write_clear_enable(word, u32::MAX).unwrap();
+                self.distributor
+                    .write_clear_pending(word, u32::MAX)
+                    .unwrap();
+                // Group 1 on a GICv3, Group 0 on a GICv2
+                let group = if version == Version::V3 { u32::MAX } else { 0 };
+                self.distributor.write_group(word, group).unwrap();
+            }
+            self.distributor
+                .write_priority(intid as usize, DEFAULT_PRIORITY)
+                .unwrap();
+            let _ = self.set_trigger(intid, Trigger::Level);
+            match version {
+                // Send every SPI to CPU 0
+                Version::V2 => self
+                    .distributor
+                    .write_targets(intid as usize, 0x01)
+                    .unwrap(),
+                Version::V3 => self
+                    .distributor
+                    .write_router(intid as usize, current_affinity_routing())
+                    .unwrap(),
+            }
+        }
+
+        match &mut self.cpu {
+            Cpu::V2(cpu) => {
+                // On a GICv2, SGIs and PPIs are banked in the Distributor
+                self.distributor.write_clear_enable(0, u32::MAX).unwrap();
+                self.distributor.write_clear_pending(0, u32::MAX).unwrap();
+                for intid in 0..32 {
+                    self.distributor
+                        .write_priority(intid, DEFAULT_PRIORITY)
+                        .unwrap();
+                }
+                self.distributor.write_control(
+                    registers::DistributorControl::DEFAULT
+                        .with_enable_group1(true)
+                        .with_enable_group0(true),
+                );
+                cpu.write_priority_mask(0xFF);
+                cpu.write_binary_point(0);
+                cpu.write_control(
+                    registers::CpuInterfaceControl::DEFAULT
+                        .with_enable_group1(true)
+                        .with_enable_group0(true),
+                );
+            }
+            Cpu::V3(redistributor) => {
+                self.distributor.write_control(
+                    registers::DistributorControl::DEFAULT
+                        .with_affinity_routing(true)
+                        .with_enable_group1(true)
+                        .with_enable_group0(true),
+                );
+                // Wake our Redistributor up
+                redistributor.modify_waker(|w| w.with_processor_sleep(false));
+                while redistributor.read_waker().children_asleep() {
+                    core::hint::spin_loop();
+                }
+                redistributor.write_group(u32::MAX);
+                redistributor.write_clear_enable(u32::MAX);
+                redistributor.write_clear_pending(u32::MAX);
+                for intid in 0..32 {
+                    redistributor
+                        .write_priority(intid, DEFAULT_PRIORITY)
+                        .unwrap();
+                }
+                wait_for_redistributor(redistributor);
+                // Safety: turning on the system register interface, opening
+                // the priority mask and enabling Group 1 is how we start the
+                // GICv3 CPU Interface
+                unsafe {
+                    core::arch::asm!(
+                        "mrs {tmp}, S3_0_C12_C12_5",
+                        "orr {tmp}, {tmp}, #1",
+                        "msr S3_0_C12_C12_5, {tmp}",
+                        "isb",
+                        "msr S3_0_C4_C6_0, {mask}",
+                        "msr S3_0_C12_C12_3, xzr",
+                        "msr S3_0_C12_C12_7, {enable}",
+                        "isb",
+                        tmp = out(reg) _,
+                        mask = in(reg) 0xFFu64,
+                        enable = in(reg) 1u64,
+                        options(nostack, preserves_flags)
+                    );
+                }
+            }
+        }
+        ACTIVE_GICD.store(self.gicd_addr, Ordering::Relaxed);
+        ACTIVE_CPU.store(self.cpu_addr, Ordering::Relaxed);
+        let version = match version {
+            Version::V2 => 2,
+            Version::V3 => 3,
+        };
+        ACTIVE_VERSION.store(version, Ordering::Release);
+    }
+
+    /// Set the handler and priority for an interrupt, and enable it
+    pub fn register(&mut self, intid: u32, priority: u8, handler: Handler) -> Result<(), Error> {
+        self.check(intid)?;
+        critical_section::with(|cs| HANDLERS.borrow(cs)[intid as usize].set(Some(handler)));
+        self.set_priority(intid, priority)?;
+        self.enable_interrupt(intid, true)
+    }
+
+    /// Disable an interrupt, and remove its handler
+    pub fn unregister(&mut self, intid: u32) -> Result<(), Error> {
+        self.enable_interrupt(intid, false)?;
+        critical_section::with(|cs| HANDLERS.borrow(cs)[intid as usize].set(None));
+        Ok(())
+    }
+
+    /// Enable or disable an interrupt
+    ///
+    /// If you enable an interrupt with no handler, [`handle_irq`] disables it
+    /// again when it fires.
+    pub fn enable_interrupt(&mut self, intid: u32, enabled: bool) -> Result<(), Error> {
+        self.check(intid)?;
+        let (word, bit) = bit_index(intid);
+        match (&mut self.cpu, intid < 32) {
+            (Cpu::V3(redistributor), true) => {
+                if enabled {
+                    redistributor.write_set_enable(1 << bit);
+                } else {
+                    redistributor.write_clear_enable(1 << bit);
+                    wait_for_redistributor(redistributor);
+                }
+            }
+            _ => {
+                if enabled {
+                    self.distributor.write_set_enable(word, 1 << bit).unwrap();
+                } else {
+                    self.distributor.write_clear_enable(word, 1 << bit).unwrap();
+                    self.wait_for_distributor();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Is an interrupt enabled?
+    pub fn is_enabled(&self, intid: u32) -> Result<bool, Error> {
+        self.check(intid)?;
+        let (word, bit) = bit_index(intid);
+        let bits = match (&self.cpu, intid < 32) {
+            (Cpu::V3(redistributor), true) => redistributor.read_set_enable(),
+            _ => self.distributor.read_set_enable(word).unwrap(),
+        };
+        Ok(bits & (1 << bit) != 0)
+    }
+
+    /// Set the priority of an interrupt
+    ///
+    /// Lower numbers are more urgent.
+    pub fn set_priority(&mut self, intid: u32, priority: u8) -> Result<(), Error> {
+        self.check(intid)?;
+        match (&mut self.cpu, intid < 32) {
+            (Cpu::V3(redistributor), true) => redistributor
+                .write_priority(intid as usize, priority)
+                .unwrap(),
+            _ => self
+                .distributor
+                .write_priority(intid as usize, priority)
+                .unwrap(),
+        }
+        Ok(())
+    }
+
+    /// Set whether an interrupt is level or edge triggered
+    ///
+    /// SGIs are always edge-triggered, and whether you can change a PPI is up
+    /// to the implementation.
+    pub fn set_trigger(&mut self, intid: u32, trigger: Trigger) -> Result<(), Error> {
+        self.check(intid)?;
+        if intid < 16 {
+            return Err(Error::CannotConfigure);
+        }
+        let index = intid as usize / 16;
+        let shift = (intid % 16) * 2 + 1;
+        let update = |config: u32| match trigger {
+            Trigger::Level => config & !(1 << shift),
+            Trigger::Edge => config | (1 << shift),
+        };
+        match (&mut self.cpu, intid < 32) {
+            (Cpu::V3(redistributor), true) => {
+                let config = redistributor.read_config(index).unwrap();
+                redistributor.write_config(index, update(config)).unwrap();
+            }
+            _ => {
+                let config = self.distributor.read_config(index).unwrap();
+                self.distributor
+                    .write_config(index, update(config))
+                    .unwrap();
+            }
+        }
+        Ok(())
+    }
+
+    /// Make an interrupt pending (or not), as if the device had raised it
+    pub fn set_pending(&mut self, intid: u32, pending: bool) -> Result<(), Error> {
+        self.check(intid)?;
+        let (word, bit) = bit_index(intid);
+        match (&mut self.cpu, intid < 32, pending) {
+            (Cpu::V3(redistributor), true, true) => redistributor.write_set_pending(1 << bit),
+            (Cpu::V3(redistributor), true, false) => redistributor.write_clear_pending(1 << bit),
+            (_, _, true) => self.distributor.write_set_pending(word, 1 << bit).unwrap(),
+            (_, _, false) => self
+                .distributor
+                .write_clear_pending(word, 1 << bit)
+                .unwrap(),
+        }
+        Ok(())
+    }
+
+    /// Is an interrupt pending?
+    pub fn is_pending(&self, intid: u32) -> Result<bool, Error> {
+        self.check(intid)?;
+        let (word, bit) = bit_index(intid);
+        let bits = match (&self.cpu, intid < 32) {
+            (Cpu::V3(redistributor), true) => redistributor.read_set_pending(),
+            _ => self.distributor.read_set_pending(word).unwrap(),
+        };
+        Ok(bits & (1 << bit) != 0)
+    }
+
+    /// Only deliver interrupts which are more urgent than `mask`
+    ///
+    /// A mask of `0xFF` lets everything through.
+    pub fn set_priority_mask(&mut self, mask: u8) {
+        match &mut self.cpu {
+            Cpu::V2(cpu) => cpu.write_priority_mask(u32::from(mask)),
+            // Safety: writing ICC_PMR_EL1 only changes which interrupts we get
+            Cpu::V3(_) => unsafe {
+                core::arch::asm!(
+                    "msr S3_0_C4_C6_0, {mask}",
+                    mask = in(reg) u64::from(mask),
+                    options(nomem, nostack, preserves_flags)
+                );
+            },
+        }
+    }
+
+    /// Check an INTID is one we can handle
+    fn check(&self, intid: u32) -> Result<(), Error> {
+        if intid < self.num_intids && (intid as usize) < NUM_INTIDS {
+            Ok(())
+        } else {
+            Err(Error::InvalidIntId)
+        }
+    }
+
+    /// Wait for a write to the Distributor Control Register, or to disable an
+    /// interrupt, to take effect
+    ///
+    /// Only a GICv3 tells us. On a GICv2 this bit reads as zero.
+    fn wait_for_distributor(&self) {
+        while self.distributor.read_control().write_pending() {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Acknowledge the most urgent pending interrupt, if there is one
+///
+/// You must pass what you get back to [`end_of_interrupt`] when you're done.
+/// Does nothing if [`Gic::init`] hasn't been called.
+pub fn acknowledge() -> Option<Acknowledged> {
+    let (raw, intid) = match active_version()? {
+        Version::V2 => {
+            // Safety: `Gic::init` stored the address of the CPU Interface, and
+            // the acknowledge register is ours to read in an IRQ handler
+            let mut cpu =
+                unsafe { registers::CpuInterface::new_mmio_at(ACTIVE_CPU.load(Ordering::Relaxed)) };
+            let raw = cpu.read_acknowledge();
+            // The CPU ID of an SGI's sender is in bits 12:10
+            (raw, raw & 0x3FF)
+        }
+        Version::V3 => {
+            let iar: u64;
+            // Safety: reading ICC_IAR1_EL1 acknowledges an interrupt, which is
+            // what we want
+            unsafe {
+                core::arch::asm!(
+                    "mrs {iar}, S3_0_C12_C12_0",
+                    iar = out(reg) iar,
+                    options(nomem, nostack, preserves_flags)
+                );
+            }
+            (iar as u32, iar as u32 & 0x00FF_FFFF)
+        }
+    };
+    (intid < SPECIAL_INTIDS).then_some(Acknowledged { intid, raw })
+}
+
+/// Tell the GIC we have finished handling an interrupt
+pub fn end_of_interrupt(interrupt: Acknowledged) {
+    match active_version() {
+        Some(Version::V2) => {
+            // Safety: `Gic::init` stored the address of the CPU Interface, and
+            // we are finishing an interrupt we acknowledged
+            let mut cpu =
+                unsafe { registers::CpuInterface::new_mmio_at(ACTIVE_CPU.load(Ordering::Relaxed)) };
+            cpu.write_end_of_interrupt(interrupt.raw);
+        }
+        Some(Version::V3) => {
+            // Safety: we are finishing an interrupt we acknowledged
+            unsafe {
+                core::arch::asm!(
+                    "msr S3_0_C12_C12_1, {eoi}",
+                    "isb",
+                    eoi = in(reg) u64::from(interrupt.raw),
+                    options(nomem, nostack, preserves_flags)
+                );
+            }
+        }
+        None => {}
+    }
+}
+
+/// Handle every pending interrupt, by calling its registered handler
+///
+/// This is called from our IRQ exception handler. An interrupt with no
+/// handler is reported and disabled, so it doesn't fire forever.
+pub fn handle_irq() {
+    while let Some(interrupt) = acknowledge() {
+        let handler = critical_section::with(|cs| {
+            HANDLERS
+                .borrow(cs)
+                .get(interrupt.intid as usize)
+                .and_then(Cell::get)
+        });
+        match handler {
+            Some(handler) => handler(),
+            None => {
+                defmt::warn!("Disabling unhandled interrupt {=u32}", interrupt.intid);
+                disable_unhandled(interrupt.intid);
+            }
+        }
+        end_of_interrupt(interrupt);
+    }
+}
+
+/// Disable an interrupt which has no handler
+fn disable_unhandled(intid: u32) {
+    let (word, bit) = bit_index(intid);
+    if active_version() == Some(Version::V3) && intid < 32 {
+        // Safety: `Gic::init` stored the address of our Redistributor, and we
+        // only touch the enable bit of an interrupt nobody is handling
+        let mut redistributor =
+            unsafe { registers::Redistributor::new_mmio_at(ACTIVE_CPU.load(Ordering::Relaxed)) };
+        redistributor.write_clear_enable(1 << bit);
+    } else {
+        // Safety: `Gic::init` stored the address of the Distributor, and we
+        // only touch the enable bit of an interrupt nobody is handling
+        let mut distributor =
+            unsafe { registers::Distributor::new_mmio_at(ACTIVE_GICD.load(Ordering::Relaxed)) };
+        let _ = distributor.write_clear_enable(word, 1 << bit);
+    }
+}
+
+/// Which version of the GIC does this CPU have?
+pub fn detect_version() -> Version {
+    let pfr0: u64;
+    // Safety: reading an ID register has no side-effects
+    unsafe {
+        core::arch::asm!("mrs {pfr0}, ID_AA64PFR0_EL1", pfr0 = out(reg) pfr0, options(nomem, nostack, preserves_flags));
+    }
+    // ID_AA64PFR0_EL1.GIC, bits 27:24, is non-zero if the GICv3 system
+    // registers are there
+    if (pfr0 >> 24) & 0xF != 0 {
+        Version::V3
+    } else {
+        Version::V2
+    }
+}
+
+/// The version of the GIC that [`Gic::init`] set up, if any
+fn active_version() -> Option<Version> {
+    match ACTIVE_VERSION.load(Ordering::Acquire) {
+        2 => Some(Version::V2),
+        3 => Some(Version::V3),
+        _ => None,
+    }
+}
+
+/// Which register, and which bit within it, holds the one-bit setting for an
+/// INTID
+fn bit_index(intid: u32) -> (usize, u32) {
+    (intid as usize / 32, intid % 32)
+}
+
+/// This CPU's affinity, in the format used by GICR_TYPER
+fn current_affinity() -> u32 {
+    let mpidr = read_mpidr();
+    let aff0_2 = (mpidr & 0xFF_FFFF) as u32;
+    let aff3 = ((mpidr >> 32) & 0xFF) as u32;
+    (aff3 << 24) | aff0_2
+}
+
+/// This CPU's affinity, in the format used by GICD_IROUTER
+fn current_affinity_routing() -> u64 {
+    let mpidr = read_mpidr();
+    mpidr & 0xFF_00FF_FFFF
+}
+
+/// Read the Multiprocessor Affinity Register
+fn read_mpidr() -> u64 {
+    let mpidr: u64;
+    // Safety: reading an ID register has no side-effects
+    unsafe {
+        core::arch::asm!("mrs {mpidr}, MPIDR_EL1", mpidr = out(reg) mpidr, options(nomem, nostack, preserves_flags));
+    }
+    mpidr
+}
+
+/// Find the Redistributor for this CPU
+///
+/// # Safety
+///
+/// `gicr_addr` must point at the first of a block of GICv3 Redistributors.
+unsafe fn find_redistributor(gicr_addr: usize) -> Result<usize, Error> {
+    let affinity = current_affinity();
+    let mut addr = gicr_addr;
+    loop {
+        // Safety: the caller says this is a Redistributor, and we stop at the
+        // last one
+        let redistributor = unsafe { registers::Redistributor::new_mmio_at(addr) };
+        let typer = redistributor.read_redistributor_type();
+        if typer.affinity() == affinity {
+            return Ok(addr);
+        }
+        if typer.last() {
+            return Err(Error::NoRedistributor);
+        }
+        addr += GICR_STRIDE;
+    }
+}
+
+/// Wait for a write to the Redistributor to take effect
+fn wait_for_redistributor(redistributor: &registers::MmioRedistributor<'static>) {
+    // GICR_CTLR.RWP
+    while redistributor.read_control() & (1 << 3) != 0 {
+        core::hint::spin_loop();
+    }
+}
+
+// End of file
@@ -0,0 +1,206 @@
+//! Register definitions for the Arm Generic Interrupt Controller
+
+/// Represents the MMIO registers for a GICv2 or GICv3 Distributor
+///
+/// The `router` registers only exist on a GICv3, and only when affinity
+/// routing is enabled. On a GICv2 the whole Distributor is only 4 KiB, so
+/// don't touch them.
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+pub struct Distributor {
+    /// Distributor Control Register (GICD_CTLR)
+    #[mmio(PureRead, Write, Modify)]
+    control: DistributorControl,
+    /// Interrupt Controller Type Register (GICD_TYPER)
+    #[mmio(PureRead)]
+    controller_type: u32,
+    /// Distributor Implementer Identification Register (GICD_IIDR)
+    #[mmio(PureRead)]
+    implementer: u32,
+    _reserved0: [u32; 29],
+    /// Interrupt Group Registers (GICD_IGROUPRn) - one bit per interrupt
+    #[mmio(PureRead, Write)]
+    group: [u32; 32],
+    /// Interrupt Set-Enable Registers (GICD_ISENABLERn)
+    #[mmio(PureRead, Write)]
+    set_enable: [u32; 32],
+    /// Interrupt Clear-Enable Registers (GICD_ICENABLERn)
+    #[mmio(PureRead, Write)]
+    clear_enable: [u32; 32],
+    /// Interrupt Set-Pending Registers (GICD_ISPENDRn)
+    #[mmio(PureRead, Write)]
+    set_pending: [u32; 32],
+    /// Interrupt Clear-Pending Registers (GICD_ICPENDRn)
+    #[mmio(PureRead, Write)]
+    clear_pending: [u32; 32],
+    /// Interrupt Set-Active Registers (GICD_ISACTIVERn)
+    #[mmio(PureRead, Write)]
+    set_active: [u32; 32],
+    /// Interrupt Clear-Active Registers (GICD_ICACTIVERn)
+    #[mmio(PureRead, Write)]
+    clear_active: [u32; 32],
+    /// Interrupt Priority Registers (GICD_IPRIORITYRn) - one byte per
+    /// interrupt
+    #[mmio(PureRead, Write)]
+    priority: [u8; 1020],
+    _reserved1: u32,
+    /// Interrupt Processor Targets Registers (GICD_ITARGETSRn) - one byte per
+    /// interrupt, GICv2 only
+    #[mmio(PureRead, Write)]
+    targets: [u8; 1020],
+    _reserved2: u32,
+    /// Interrupt Configuration Registers (GICD_ICFGRn) - two bits per
+    /// interrupt
+    #[mmio(PureRead, Write)]
+    config: [u32; 64],
+    _reserved3: [u32; 128],
+    /// Software Generated Interrupt Register (GICD_SGIR), GICv2 only
+    #[mmio(Write)]
+    software_interrupt: u32,
+    _reserved4: [u32; 5183],
+    /// Interrupt Routing Registers (GICD_IROUTERn), GICv3 only
+    #[mmio(PureRead, Write)]
+    router: [u64; 1020],
+}
+
+/// Distributor Control
+///
+/// Bits 0 and 1 enable the two interrupt groups. Their exact meaning depends
+/// on the GIC version and which security state you are in, but setting both
+/// enables everything we use.
+#[bitbybit::bitfield(u32, default = 0, defmt_bitfields)]
+pub struct DistributorControl {
+    /// Register Write Pending (GICv3 only)
+    #[bit(31, r)]
+    write_pending: bool,
+    /// Affinity Routing Enable (GICv3 only)
+    #[bit(4, rw)]
+    affinity_routing: bool,
+    /// Enable interrupt group 1
+    #[bit(1, rw)]
+    enable_group1: bool,
+    /// Enable interrupt group 0
+    #[bit(0, rw)]
+    enable_group0: bool,
+}
+
+/// Represents the MMIO registers for a GICv2 CPU Interface
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+pub struct CpuInterface {
+    /// CPU Interface Control Register (GICC_CTLR)
+    #[mmio(PureRead, Write, Modify)]
+    control: CpuInterfaceControl,
+    /// Interrupt Priority Mask Register (GICC_PMR)
+    #[mmio(PureRead, Write)]
+    priority_mask: u32,
+    /// Binary Point Register (GICC_BPR)
+    #[mmio(PureRead, Write)]
+    binary_point: u32,
+    /// Interrupt Acknowledge Register (GICC_IAR)
+    ///
+    /// Reading this acknowledges the interrupt.
+    #[mmio(Read)]
+    acknowledge: u32,
+    /// End of Interrupt Register (GICC_EOIR)
+    #[mmio(Write)]
+    end_of_interrupt: u32,
+    /// Running Priority Register (GICC_RPR)
+    #[mmio(PureRead)]
+    running_priority: u32,
+    /// Highest Priority Pending Interrupt Register (GICC_HPPIR)
+    #[mmio(PureRead)]
+    highest_pending: u32,
+}
+
+/// CPU Interface Control
+#[bitbybit::bitfield(u32, default = 0, defmt_bitfields)]
+pub struct CpuInterfaceControl {
+    /// Signal group 0 interrupts as FIQ instead of IRQ
+    #[bit(3, rw)]
+    fiq_enable: bool,
+    /// Enable interrupt group 1
+    #[bit(1, rw)]
+    enable_group1: bool,
+    /// Enable interrupt group 0
+    #[bit(0, rw)]
+    enable_group0: bool,
+}
+
+/// Represents the MMIO registers for a GICv3 Redistributor
+///
+/// Each CPU has its own Redistributor, made of two 64 KiB frames: one for
+/// control (`RD_base`) and one for its SGIs and PPIs (`SGI_base`).
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+pub struct Redistributor {
+    /// Redistributor Control Register (GICR_CTLR)
+    #[mmio(PureRead, Write)]
+    control: u32,
+    /// Implementer Identification Register (GICR_IIDR)
+    #[mmio(PureRead)]
+    implementer: u32,
+    /// Redistributor Type Register (GICR_TYPER)
+    #[mmio(PureRead)]
+    redistributor_type: RedistributorType,
+    _reserved0: u32,
+    /// Redistributor Wake Register (GICR_WAKER)
+    #[mmio(PureRead, Write, Modify)]
+    waker: Waker,
+    _reserved1: [u32; 16378],
+    // This is the start of the SGI_base frame
+    _reserved2: [u32; 32],
+    /// Interrupt Group Register 0 (GICR_IGROUPR0)
+    #[mmio(PureRead, Write)]
+    group: u32,
+    _reserved3: [u32; 31],
+    /// Interrupt Set-Enable Register 0 (GICR_ISENABLER0)
+    #[mmio(PureRead, Write)]
+    set_enable: u32,
+    _reserved4: [u32; 31],
+    /// Interrupt Clear-Enable Register 0 (GICR_ICENABLER0)
+    #[mmio(PureRead, Write)]
+    clear_enable: u32,
+    _reserved5: [u32; 31],
+    /// Interrupt Set-Pending Register 0 (GICR_ISPENDR0)
+    #[mmio(PureRead, Write)]
+    set_pending: u32,
+    _reserved6: [u32; 31],
+    /// Interrupt Clear-Pending Register 0 (GICR_ICPENDR0)
+    #[mmio(PureRead, Write)]
+    clear_pending: u32,
+    _reserved7: [u32; 95],
+    /// Interrupt Priority Registers (GICR_IPRIORITYRn) - one byte per
+    /// interrupt
+    #[mmio(PureRead, Write)]
+    priority: [u8; 32],
+    _reserved8: [u32; 504],
+    /// Interrupt Configuration Registers (GICR_ICFGR0 and GICR_ICFGR1)
+    #[mmio(PureRead, Write)]
+    config: [u32; 2],
+}
+
+/// Redistributor Type
+#[bitbybit::bitfield(u64, default = 0, defmt_bitfields)]
+pub struct RedistributorType {
+    /// The affinity of the CPU this Redistributor belongs to, as
+    /// `Aff3.Aff2.Aff1.Aff0`
+    #[bits(32..=63, r)]
+    affinity: u32,
+    /// This is the last Redistributor in the block
+    #[bit(4, r)]
+    last: bool,
+}
+
+/// Redistributor Wake
+#[bitbybit::bitfield(u32, default = 0, defmt_bitfields)]
+pub struct Waker {
+    /// The interface to the CPU is asleep
+    #[bit(2, r)]
+    children_asleep: bool,
+    /// Tell the Redistributor the CPU is asleep
+    #[bit(1, rw)]
+    processor_sleep: bool,
+}
+
+// End of file
@@ -0,0 +1,56 @@
+//! A PL011 UART which can be shared as a global variable
+//!
+//! [`GlobalUart`] wraps the blocking driver from `arm-pl011-uart` in a
+//! `critical_section::Mutex`, so anything - an interrupt handler, or another
+//! core when built with the `multicore` feature - can print to it with
+//! `write!`. Each `write_str` call happens in one critical section, so text
+//! from different writers is only interleaved between calls.
+
+use core::cell::RefCell;
+
+use arm_pl011_uart::Uart;
+
+/// A UART that can be shared as a global variable
+pub struct GlobalUart {
+    inner: critical_section::Mutex<RefCell<Option<Uart<'static>>>>,
+}
+
+impl GlobalUart {
+    /// Create an empty global UART
+    ///
+    /// Anything written before [`GlobalUart::init`] is thrown away.
+    pub const fn new() -> GlobalUart {
+        GlobalUart {
+            inner: critical_section::Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Give the global UART a driver to write to
+    pub fn init(&self, uart: Uart<'static>) {
+        critical_section::with(|cs| {
+            let mut lock = self.inner.borrow_ref_mut(cs);
+            lock.replace(uart);
+        });
+    }
+}
+
+impl Default for GlobalUart {
+    fn default() -> GlobalUart {
+        GlobalUart::new()
+    }
+}
+
+impl core::fmt::Write for &GlobalUart {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        critical_section::with(|cs| {
+            let mut lock = self.inner.borrow_ref_mut(cs);
+            if let Some(uart) = lock.as_mut() {
+                uart.write_str(s)
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
+// End of file
@@ -0,0 +1,268 @@
+//! A heap which grows by mapping in more RAM
+//!
+//! [`GrowableHeap`] is a global allocator which lives at [`HEAP_START`], a
+//! virtual address range nothing else uses. It starts out empty. When an
+//! allocation doesn't fit, it takes frames from [`crate::frames`], wherever
+//! they happen to be in RAM, maps them onto the top of the heap with
+//! [`AddressSpace::map_at`], and tells the linked-list allocator it has more
+//! memory. The heap looks contiguous, even though the RAM behind it isn't.
+//!
+//! The heap needs page tables it can change, so give [`GrowableHeap::init`]
+//! an active [`AddressSpace`] - the one from [`AddressSpace::for_image`] is a
+//! good start. Call [`crate::frames::init`] first.
+//!
+//! It keeps the same statistics as `qemu_common::heap::Heap`, where `size` is
+//! how much memory has been mapped so far, and calls the same sort of failure
+//! hook when it can't grow any more. The mappings come out of the page table
+//! pool in [`crate::mmu`] (one table per 2 MiB of heap), so that can run out
+//! before RAM does.
+//!
+//! ```rust ignore
+//! #[global_allocator]
+//! static HEAP: GrowableHeap = GrowableHeap::empty();
+//! ```
+//!
+//! With the `virtio-console` feature on, the library has to give every program
+//! a global allocator, so it declares [`HEAP`] itself and you use that one.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::{Cell, RefCell},
+    ops::Range,
+    ptr::NonNull,
+};
+
+use critical_section::Mutex;
+use qemu_common::heap::{largest_free, log_failure, FailureHook, Stats};
+
+use crate::{
+    frames,
+    mmu::{self, AddressSpace},
+};
+
+/// Where the heap starts - above the 1 GiB of RAM our page tables can
+/// identity map
+pub const HEAP_START: usize = 0x1_0000_0000;
+
+/// The most the heap can grow to
+pub const MAX_HEAP_SIZE: usize = 512 * 1024 * 1024;
+
+/// The least we grow the heap by at once
+pub const GROW_SIZE: usize = 64 * 1024;
+
+/// The global allocator, when the `virtio-console` feature is on
+///
+/// The console driver in `virtio-drivers` needs `alloc`, and a program which
+/// links against `alloc` doesn't link without a global allocator - even if it
+/// never allocates. It stays empty until you call [`GrowableHeap::init`].
+#[cfg(feature = "virtio-console")]
+#[global_allocator]
+pub static HEAP: GrowableHeap = GrowableHeap::empty();
+
+/// Why the heap couldn't grow
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// [`GrowableHeap::init`] hasn't been called
+    NotReady,
+    /// We'd go past [`MAX_HEAP_SIZE`]
+    TooBig,
+    /// We're out of frames
+    OutOfFrames,
+    /// We couldn't map the frames
+    Mmu(mmu::Error),
+}
+
+/// The linked-list heap from `linked_list_allocator`
+struct Inner(linked_list_allocator::Heap);
+
+// Safety: the heap's pointers are into memory only the heap uses, so it can
+// be used from any core
+unsafe impl Send for Inner {}
+
+/// Everything that changes as the heap is used
+struct State {
+    heap: Inner,
+    space: Option<AddressSpace>,
+    used: usize,
+    peak: usize,
+    allocations: u32,
+    frees: u32,
+    failures: u32,
+}
+
+/// A heap which keeps statistics, and grows by mapping frames
+pub struct GrowableHeap {
+    state: Mutex<RefCell<State>>,
+    on_failure: Mutex<Cell<FailureHook>>,
+}
+
+impl GrowableHeap {
+    /// Make an empty heap
+    ///
+    /// Call [`GrowableHeap::init`] before you allocate anything.
+    pub const fn empty() -> GrowableHeap {
+        GrowableHeap {
+            state: Mutex::new(RefCell::new(State {
+                heap: Inner(linked_list_allocator::Heap::empty()),
+                space: None,
+                used: 0,
+                peak: 0,
+                allocations: 0,
+                frees: 0,
+                failures: 0,
+            })),
+            on_failure: Mutex::new(Cell::new(log_failure)),
+        }
+    }
+
+    /// Give the heap the page tables to grow in
+    ///
+    /// The heap maps its first [`GROW_SIZE`] bytes straight away.
+    ///
+    /// # Safety
+    ///
+    /// `space` must be the active address space, and stay that way. Nothing
+    /// else may map anything at [`HEAP_START`].
+    pub unsafe fn init(&self, mut space: AddressSpace) -> Result<(), Error> {
+        assert!(space.is_active(), "the heap's address space must be active");
+        map_frames(&mut space, HEAP_START, GROW_SIZE)?;
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            // Safety: we just mapped this memory, for the heap's use only
+            unsafe { state.heap.0.init(HEAP_START as *mut u8, GROW_SIZE) };
+            state.space = Some(space);
+        });
+        Ok(())
+    }
+
+    /// Change what happens when an allocation fails
+    pub fn set_failure_hook(&self, hook: FailureHook) {
+        critical_section::with(|cs| self.on_failure.borrow(cs).set(hook));
+    }
+
+    /// Get the current statistics
+    pub fn stats(&self) -> Stats {
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            Stats {
+                size: state.heap.0.size(),
+                used: state.used,
+                peak: state.peak,
+                allocations: state.allocations,
+                frees: state.frees,
+                failures: state.failures,
+                largest_free: largest_free(&mut state.heap.0),
+            }
+        })
+    }
+
+    /// Print the current heap and frame statistics with defmt
+    pub fn report(&self) {
+        defmt::info!("Heap: {}", self.stats());
+        frames::report();
+    }
+}
+
+// Safety: `linked_list_allocator` is a sound allocator, and we only give it
+// memory which is mapped and which nothing else uses
+unsafe impl GlobalAlloc for GrowableHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            let ptr = match state.heap.0.allocate_first_fit(layout) {
+                Ok(ptr) => Some(ptr),
+                Err(()) => grow(&mut state, layout)
+                    .ok()
+                    .and_then(|()| state.heap.0.allocate_first_fit(layout).ok()),
+            };
+            match ptr {
+                Some(_) => {
+                    state.allocations += 1;
+                    state.used += layout.size();
+                    state.peak = state.peak.max(state.used);
+                }
+                None => state.failures += 1,
+            }
+            ptr
+        });
+        match ptr {
+            Some(ptr) => ptr.as_ptr(),
+            None => {
+                let hook = critical_section::with(|cs| self.on_failure.borrow(cs).get());
+                hook(layout, &self.stats());
+                core::ptr::null_mut()
+            }
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            // Safety: our caller says we allocated this, with this layout
+            unsafe { state.heap.0.deallocate(NonNull::new_unchecked(ptr), layout) };
+            state.frees += 1;
+            state.used -= layout.size();
+        });
+    }
+}
+
+/// Map enough memory onto the top of the heap for `layout` to fit
+fn grow(state: &mut State, layout: Layout) -> Result<(), Error> {
+    let space = state.space.as_mut().ok_or(Error::NotReady)?;
+    // The free space at the top might not be usable, so assume we need room
+    // for the whole thing, plus alignment
+    let wanted = layout.size() + layout.align();
+    let size = mmu::page_align_up(wanted.max(GROW_SIZE));
+    let top = state.heap.0.top() as usize;
+    if top + size > HEAP_START + MAX_HEAP_SIZE {
+        return Err(Error::TooBig);
+    }
+    map_frames(space, top, size).inspect_err(|e| defmt::warn!("Couldn't grow the heap: {}", e))?;
+    // Safety: we just mapped this memory, right on top of the heap
+    unsafe { state.heap.0.extend(size) };
+    defmt::debug!(
+        "Heap grew by {=usize} bytes to {=usize}",
+        size,
+        state.heap.0.size()
+    );
+    Ok(())
+}
+
+/// Map `size` bytes of fresh frames at `addr`, one frame at a time
+///
+/// If we run out part way, we unmap the frames we already mapped and give
+/// them back, so the heap is left as it was.
+fn map_frames(space: &mut AddressSpace, addr: usize, size: usize) -> Result<(), Error> {
+    for page in (addr..addr + size).step_by(mmu::PAGE_SIZE) {
+        if let Err(e) = map_frame(space, page) {
+            unmap_frames(space, addr..page);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Map one fresh frame at `page`
+fn map_frame(space: &mut AddressSpace, page: usize) -> Result<(), Error> {
+    let frame = frames::allocate(1).ok_or(Error::OutOfFrames)?;
+    space
+        .map_at(page..page + mmu::PAGE_SIZE, frame, mmu::READ_WRITE)
+        .map_err(|e| {
+            frames::free(frame, 1);
+            Error::Mmu(e)
+        })
+}
+
+/// Unmap the frames [`map_frames`] mapped in `range`, and free them
+fn unmap_frames(space: &mut AddressSpace, range: Range<usize>) {
+    for page in range.step_by(mmu::PAGE_SIZE) {
+        let frame = space.translate(page).expect("heap page wasn't mapped");
+        // Each page has its own descriptor, so this doesn't need a new table
+        space
+            .unmap(page..page + mmu::PAGE_SIZE)
+            .expect("unmapping a heap page");
+        frames::free(frame, 1);
+    }
+}
+
+// End of file
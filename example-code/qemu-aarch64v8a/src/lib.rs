@@ -8,7 +8,29 @@ use aarch64_rt::initial_pagetable;
 use aarch64_rt::InitialPagetable;
 use defmt_semihosting as _;
 
+pub mod crash;
 pub mod critical_section;
+pub mod devicetree;
+pub mod exception;
+pub mod frames;
+pub mod gic;
+pub mod global_uart;
+pub mod heap;
+pub mod mmu;
+pub mod pl011;
+pub mod psci;
+pub mod rtc;
+pub mod smp;
+pub mod stack;
+pub mod testing;
+pub mod thread;
+pub mod time_driver;
+pub mod timer;
+pub mod user;
+pub mod virtio;
+
+// Stamp every defmt log with the wall-clock time from the PL031
+defmt::timestamp!("{=u32:iso8601s}", rtc::timestamp());
 
 /// An Aarch64 Exception Level
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -38,6 +60,25 @@ pub fn exception_level() -> ExceptionLevel {
     }
 }
 
+/// Unmask IRQs on this CPU
+///
+/// # Safety
+///
+/// Interrupt handlers will start running, so anything they share with the
+/// rest of the program must be ready for them.
+#[inline]
+pub unsafe fn enable_interrupts() {
+    // Safety: our caller says the handlers are ready
+    unsafe { core::arch::asm!("msr DAIFClr, #2", options(nomem, nostack, preserves_flags)) };
+}
+
+/// Mask IRQs on this CPU
+#[inline]
+pub fn disable_interrupts() {
+    // Safety: masking interrupts can't break anything
+    unsafe { core::arch::asm!("msr DAIFSet, #2", options(nomem, nostack, preserves_flags)) };
+}
+
 /// Attributes to use for device memory in the initial identity map.
 const DEVICE_ATTRIBUTES: Attributes = Attributes::VALID
     .union(Attributes::ATTRIBUTE_INDEX_0)
@@ -79,10 +120,16 @@ initial_pagetable!(
 
 /// Called when the application raises an unrecoverable `panic!`.
 ///
-/// Prints the panic to the console and then exits QEMU using a semihosting
-/// breakpoint.
+/// Prints the panic, the time it happened and how much stack we used, to the
+/// console and then exits QEMU using a semihosting breakpoint. If
+/// [`crash::set_reset_on_crash`] has been turned on, it stores a crash record
+/// and resets instead.
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
+    if crash::reset_on_crash() {
+        crash::panic_reset(info);
+    }
+    defmt::error!("PANIC occurred at {}", rtc::now());
     match (info.message().as_str(), info.location()) {
         (Some(m), Some(l)) => {
             defmt::error!("PANIC at {=str}:{=u32}: {}", l.file(), l.line(), m);
@@ -97,14 +144,31 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
             defmt::error!("PANIC!");
         }
     }
-    semihosting::process::exit(1);
+    // If we're running tests, this might not return
+    qemu_common::testing::on_panic();
+    exit(1);
+}
+
+/// Print how much stack we used, and then exit QEMU
+pub fn exit(code: i32) -> ! {
+    stack::report();
+    semihosting::process::exit(code);
 }
 
+/// Called when an instruction at EL1 faults, which we report before exiting
 #[unsafe(no_mangle)]
-extern "C" fn sync_exception_current(_elr: u64, _spsr: u64) {}
+extern "C" fn sync_exception_current(elr: u64, spsr: u64) {
+    exception::report(exception::Origin::CurrentEl, elr, spsr);
+}
 
+/// Called when an IRQ arrives, which we pass on to the GIC driver
 #[unsafe(no_mangle)]
-extern "C" fn irq_current(_elr: u64, _spsr: u64) {}
+extern "C" fn irq_current(_elr: u64, _spsr: u64) {
+    gic::handle_irq();
+    // The GIC is finished with the interrupt, so if the scheduler's timer
+    // went off, we can switch threads now
+    thread::preempt();
+}
 
 #[unsafe(no_mangle)]
 extern "C" fn fiq_current(_elr: u64, _spsr: u64) {}
@@ -112,11 +176,17 @@ extern "C" fn fiq_current(_elr: u64, _spsr: u64) {}
 #[unsafe(no_mangle)]
 extern "C" fn serr_current(_elr: u64, _spsr: u64) {}
 
+/// Called when a user task makes a system call or faults
 #[unsafe(no_mangle)]
-extern "C" fn sync_lower(_elr: u64, _spsr: u64) {}
+extern "C" fn sync_lower(elr: u64, spsr: u64) {
+    user::handle_exception(elr, spsr);
+}
 
+/// Called when an IRQ arrives while a user task is running
 #[unsafe(no_mangle)]
-extern "C" fn irq_lower(_elr: u64, _spsr: u64) {}
+extern "C" fn irq_lower(_elr: u64, _spsr: u64) {
+    gic::handle_irq();
+}
 
 #[unsafe(no_mangle)]
 extern "C" fn fiq_lower(_elr: u64, _spsr: u64) {}
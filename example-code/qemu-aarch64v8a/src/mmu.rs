@@ -0,0 +1,498 @@
+//! Runtime page tables
+//!
+//! `aarch64-rt` boots us with the `initial_pagetable!` from `lib.rs`, which
+//! maps 1 GiB of device memory and 1 GiB of normal memory with two block
+//! entries. That's enough to get going, but everything - including our code -
+//! is writable and executable, and running off the end of the stack just
+//! scribbles over whatever is below it.
+//!
+//! [`AddressSpace`] builds identity-mapped page tables at runtime, using 4 KiB
+//! pages where it has to and 2 MiB or 1 GiB blocks where it can.
+//! [`AddressSpace::for_image`] maps each part of our image with only the
+//! permissions it needs:
+//!
+//! * `.text` is read-only and executable
+//! * `.rodata` is read-only and never executable
+//! * `.data`, `.bss` and the stack are read-write and never executable
+//!
+//! The lowest page of the stack and the page above it are left unmapped, as
+//! guard pages (see [`crate::stack`]), and so is everything between and
+//! around the sections. A stack overflow which hits a guard page faults
+//! straight away, rather than quietly corrupting `.bss`. Call
+//! [`AddressSpace::activate`] to switch `TTBR0_EL1` over to the new tables.
+//! That also switches to the exception vectors from [`crate::exception`],
+//! which handle the fault on a stack of their own, so it gets reported.
+//!
+//! [`AddressSpace::map_at`] maps a range somewhere else instead, which is how
+//! [`crate::heap`] makes frames from all over RAM look like one contiguous
+//! heap.
+//!
+//! The tables come from a fixed pool in `.bss`. We use the same translation
+//! setup (`TCR_EL1`) as `aarch64-rt`, which starts the walk at level 1, so
+//! each table entry at the top level covers 1 GiB.
+
+use core::{
+    ops::Range,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use aarch64_paging::paging::Attributes;
+
+/// The size of a page, and of a page table
+pub const PAGE_SIZE: usize = 4096;
+
+/// The size of the unmapped guard pages below and above the stack
+pub const STACK_GUARD_SIZE: usize = PAGE_SIZE;
+
+/// The devices on QEMU's `virt` machine that we use - the GIC, the UARTs, the
+/// RTC and the virtio-mmio transports
+pub const VIRT_DEVICES: Range<usize> = 0x0800_0000..0x0A00_4000;
+
+/// How many entries there are in each table
+const ENTRIES: usize = PAGE_SIZE / 8;
+
+/// The level the translation table walk starts at
+const ROOT_LEVEL: usize = 1;
+
+/// How many tables there are in the pool
+const NUM_TABLES: usize = 32;
+
+/// The bits of a descriptor which hold the output address
+const ADDRESS_MASK: u64 = 0x0000_FFFF_FFFF_F000;
+
+/// Attributes for device memory
+///
+/// `ATTRIBUTE_INDEX_0` is Device-nGnRE in the MAIR `aarch64-rt` sets up for
+/// us.
+pub const DEVICE: Attributes = Attributes::ATTRIBUTE_INDEX_0
+    .union(Attributes::ACCESSED)
+    .union(Attributes::UXN)
+    .union(Attributes::PXN);
+
+/// Attributes for normal memory which we only read
+///
+/// `ATTRIBUTE_INDEX_1` is Normal, Write-Back in the MAIR `aarch64-rt` sets up
+/// for us.
+pub const READ_ONLY: Attributes = Attributes::ATTRIBUTE_INDEX_1
+    .union(Attributes::INNER_SHAREABLE)
+    .union(Attributes::ACCESSED)
+    .union(Attributes::NON_GLOBAL)
+    .union(Attributes::READ_ONLY)
+    .union(Attributes::UXN)
+    .union(Attributes::PXN);
+
+/// Attributes for normal memory which we read and write
+pub const READ_WRITE: Attributes = READ_ONLY.difference(Attributes::READ_ONLY);
+
+/// Attributes for our code, which EL1 can execute
+pub const EXECUTABLE: Attributes = READ_ONLY.difference(Attributes::PXN);
+
+/// Attributes for code which EL0 and EL1 can both execute
+///
+/// EL1 can only execute memory which EL0 can't write to, so this must stay
+/// read-only.
+pub const USER_EXECUTABLE: Attributes = EXECUTABLE
+    .union(Attributes::USER)
+    .difference(Attributes::UXN);
+
+/// Attributes for normal memory which EL0 and EL1 can both read
+pub const USER_READ_ONLY: Attributes = READ_ONLY.union(Attributes::USER);
+
+/// Attributes for normal memory which EL0 and EL1 can both read and write
+pub const USER_READ_WRITE: Attributes = READ_WRITE.union(Attributes::USER);
+
+/// Something went wrong while changing the page tables
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// The range doesn't start and end on a page boundary
+    Misaligned,
+    /// The range is outside the 512 GiB the tables can map
+    OutOfRange,
+    /// There are no more tables in the pool
+    OutOfTables,
+}
+
+/// One page table
+#[repr(C, align(4096))]
+struct Table([u64; ENTRIES]);
+
+/// The pool we take tables from
+static mut TABLES: [Table; NUM_TABLES] = [const { Table([0; ENTRIES]) }; NUM_TABLES];
+
+/// How many tables have been taken from the pool
+static TABLES_USED: AtomicUsize = AtomicUsize::new(0);
+
+/// Take an empty table from the pool
+///
+/// Tables are never given back.
+fn allocate_table() -> Result<*mut Table, Error> {
+    let index = TABLES_USED
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+            (used < NUM_TABLES).then_some(used + 1)
+        })
+        .map_err(|_| Error::OutOfTables)?;
+    // Safety: each index is handed out once, so nothing else has this table.
+    // It's in `.bss`, so it starts out as all invalid entries.
+    Ok(unsafe { &raw mut TABLES[index] })
+}
+
+/// How many tables are left in the pool
+pub fn tables_free() -> usize {
+    NUM_TABLES - TABLES_USED.load(Ordering::Relaxed)
+}
+
+/// How many bytes each entry in a table at `level` covers
+const fn entry_size(level: usize) -> usize {
+    PAGE_SIZE << (9 * (3 - level))
+}
+
+/// Round `addr` down to a page boundary
+pub const fn page_align_down(addr: usize) -> usize {
+    addr & !(PAGE_SIZE - 1)
+}
+
+/// Round `addr` up to a page boundary
+pub const fn page_align_up(addr: usize) -> usize {
+    page_align_down(addr + PAGE_SIZE - 1)
+}
+
+extern "C" {
+    /// The start of `.text`, from `aarch64-rt`
+    static text_begin: u8;
+    /// The end of `.text`, from `aarch64-rt`
+    static text_end: u8;
+    /// The start of `.rodata`, from `aarch64-rt`
+    static rodata_begin: u8;
+    /// The end of `.rodata`, from `aarch64-rt`
+    static rodata_end: u8;
+    /// The start of `.data`, from `aarch64-rt`
+    static data_begin: u8;
+    /// The end of `.data`, from `aarch64-rt`
+    static data_end: u8;
+    /// The start of `.bss`, from `aarch64-rt`
+    static bss_begin: u8;
+    /// The end of `.bss`, from `aarch64-rt`
+    static bss_end: u8;
+    /// The start of the crash log, from `memory.ld`
+    static _crash_log: u8;
+}
+
+/// The pages a section occupies
+fn section(begin: *const u8, end: *const u8) -> Range<usize> {
+    page_align_down(begin as usize)..page_align_up(end as usize)
+}
+
+/// The pages holding our code
+pub fn text_section() -> Range<usize> {
+    section(&raw const text_begin, &raw const text_end)
+}
+
+/// The pages holding our read-only data
+pub fn rodata_section() -> Range<usize> {
+    section(&raw const rodata_begin, &raw const rodata_end)
+}
+
+/// Make a block (levels 1 and 2) or page (level 3) descriptor
+fn leaf_descriptor(level: usize, addr: usize, attributes: Attributes) -> u64 {
+    let mut descriptor = attributes.union(Attributes::VALID);
+    if level == 3 {
+        descriptor |= Attributes::TABLE_OR_PAGE;
+    } else {
+        descriptor -= Attributes::TABLE_OR_PAGE;
+    }
+    (addr as u64 & ADDRESS_MASK) | descriptor.bits() as u64
+}
+
+/// Is this descriptor a pointer to another table?
+fn is_table(level: usize, descriptor: u64) -> bool {
+    level < 3 && descriptor & 0b11 == 0b11
+}
+
+/// The attributes of a block or page descriptor
+fn leaf_attributes(descriptor: u64) -> Attributes {
+    Attributes::from_bits_retain((descriptor & !ADDRESS_MASK) as usize)
+        .difference(Attributes::VALID | Attributes::TABLE_OR_PAGE)
+}
+
+/// Make sure our page table writes are visible to the table walker, and
+/// throw away any stale translations
+///
+/// The table walks are cacheable (`aarch64-rt` sets that up in `TCR_EL1`), so
+/// a barrier is enough - we don't need to clean the data cache.
+fn flush_tlb() {
+    // Safety: these are barriers and TLB maintenance, which have no effect
+    // on memory
+    unsafe {
+        core::arch::asm!(
+            "dsb ishst",
+            "tlbi vmalle1is",
+            "dsb ish",
+            "isb",
+            options(nostack, preserves_flags)
+        );
+    }
+}
+
+/// An identity-mapped address space
+#[derive(Debug)]
+pub struct AddressSpace {
+    root: *mut Table,
+    asid: u8,
+}
+
+// Safety: we only touch the tables through `&mut self`
+unsafe impl Send for AddressSpace {}
+
+impl AddressSpace {
+    /// Make an empty address space, with nothing mapped
+    ///
+    /// The Address Space ID tags this address space's entries in the TLB.
+    /// The initial page table uses ASID 0, so use something else.
+    pub fn new(asid: u8) -> Result<AddressSpace, Error> {
+        Ok(AddressSpace {
+            root: allocate_table()?,
+            asid,
+        })
+    }
+
+    /// Make an address space which maps our image, the crash log and the
+    /// `virt` devices, each with the permissions it needs
+    ///
+    /// `aarch64-rt` starts each section on a page boundary, so we can round
+    /// each one out to whole pages.
+    pub fn for_image() -> Result<AddressSpace, Error> {
+        let mut space = AddressSpace::new(1)?;
+        space.map(VIRT_DEVICES, DEVICE)?;
+        space.map(text_section(), EXECUTABLE)?;
+        space.map(rodata_section(), READ_ONLY)?;
+        space.map(
+            section(&raw const data_begin, &raw const data_end),
+            READ_WRITE,
+        )?;
+        space.map(
+            section(&raw const bss_begin, &raw const bss_end),
+            READ_WRITE,
+        )?;
+        let stack = crate::stack::stack();
+        space.map(
+            page_align_down(stack.bottom())..page_align_up(stack.top()),
+            READ_WRITE,
+        )?;
+        let crash_log = &raw const _crash_log;
+        space.map(section(crash_log, crash_log.wrapping_add(1)), READ_WRITE)?;
+        Ok(space)
+    }
+
+    /// The value to put in `TTBR0_EL1` for this address space
+    fn ttbr(&self) -> u64 {
+        (self.root as u64) | (u64::from(self.asid) << 48)
+    }
+
+    /// Is this the address space the CPU is using right now?
+    pub fn is_active(&self) -> bool {
+        let ttbr: u64;
+        // Safety: We only read a register here, which has no side-effects
+        unsafe {
+            core::arch::asm!("mrs {0}, TTBR0_EL1", out(reg) ttbr, options(nomem, nostack, preserves_flags))
+        };
+        ttbr == self.ttbr()
+    }
+
+    /// Map `range` to the same physical addresses, with these attributes
+    ///
+    /// If anything in `range` is already mapped, it is replaced. That's fine
+    /// in an active address space, as long as you aren't using the memory
+    /// you are remapping at the time - in particular, don't remap the code or
+    /// stack you are running on.
+    pub fn map(&mut self, range: Range<usize>, attributes: Attributes) -> Result<(), Error> {
+        let phys = range.start;
+        self.update(range, Some((phys, attributes)))
+    }
+
+    /// Map `range` to the physical addresses starting at `phys`, with these
+    /// attributes
+    ///
+    /// The same rules apply as for [`AddressSpace::map`].
+    pub fn map_at(
+        &mut self,
+        range: Range<usize>,
+        phys: usize,
+        attributes: Attributes,
+    ) -> Result<(), Error> {
+        if !phys.is_multiple_of(PAGE_SIZE) {
+            return Err(Error::Misaligned);
+        }
+        self.update(range, Some((phys, attributes)))
+    }
+
+    /// Unmap `range`, so that any access to it faults
+    pub fn unmap(&mut self, range: Range<usize>) -> Result<(), Error> {
+        self.update(range, None)
+    }
+
+    /// Look up the physical address `addr` is mapped to, if it is mapped
+    pub fn translate(&self, addr: usize) -> Option<usize> {
+        let (level, descriptor) = self.leaf(addr)?;
+        let size = entry_size(level);
+        Some((descriptor & ADDRESS_MASK) as usize + addr % size)
+    }
+
+    /// Look up the attributes `addr` is mapped with, if it is mapped
+    pub fn attributes(&self, addr: usize) -> Option<Attributes> {
+        self.leaf(addr)
+            .map(|(_, descriptor)| leaf_attributes(descriptor))
+    }
+
+    /// Find the level and block or page descriptor which maps `addr`
+    fn leaf(&self, addr: usize) -> Option<(usize, u64)> {
+        if addr >= entry_size(ROOT_LEVEL - 1) {
+            return None;
+        }
+        let mut table = self.root;
+        let mut level = ROOT_LEVEL;
+        loop {
+            let index = (addr / entry_size(level)) % ENTRIES;
+            // Safety: `table` is one of our tables
+            let descriptor = unsafe { (*table).0[index] };
+            if descriptor & 0b1 == 0 {
+                return None;
+            }
+            if !is_table(level, descriptor) {
+                return Some((level, descriptor));
+            }
+            table = (descriptor & ADDRESS_MASK) as *mut Table;
+            level += 1;
+        }
+    }
+
+    /// Switch `TTBR0_EL1` to this address space
+    ///
+    /// We also switch to the exception vectors from
+    /// [`crate::exception::use_exception_stacks`], so running into a stack
+    /// guard page gets reported. Do this before starting any other cores, as
+    /// they take their vectors from core 0.
+    ///
+    /// # Safety
+    ///
+    /// Everything the program uses from now on must be mapped - the code and
+    /// stack we're running on, any statics, and any devices.
+    pub unsafe fn activate(&self) {
+        crate::exception::use_exception_stacks();
+        // Safety: our caller says everything we need is mapped
+        unsafe {
+            core::arch::asm!(
+                "dsb ishst",
+                "msr TTBR0_EL1, {0}",
+                "isb",
+                "tlbi vmalle1is",
+                "dsb ish",
+                "isb",
+                in(reg) self.ttbr(),
+                options(nostack, preserves_flags)
+            );
+        }
+    }
+
+    /// Map `range` to the physical address and with the attributes in
+    /// `mapping`, or unmap it
+    fn update(
+        &mut self,
+        range: Range<usize>,
+        mapping: Option<(usize, Attributes)>,
+    ) -> Result<(), Error> {
+        if !range.start.is_multiple_of(PAGE_SIZE) || !range.end.is_multiple_of(PAGE_SIZE) {
+            return Err(Error::Misaligned);
+        }
+        if range.end > entry_size(ROOT_LEVEL - 1) {
+            return Err(Error::OutOfRange);
+        }
+        if range.is_empty() {
+            return Ok(());
+        }
+        let active = self.is_active();
+        let offset = mapping.map_or(0, |(phys, _)| phys.wrapping_sub(range.start));
+        let attributes = mapping.map(|(_, attributes)| attributes);
+        let result = update_table(self.root, ROOT_LEVEL, 0, &range, offset, attributes, active);
+        flush_tlb();
+        result
+    }
+}
+
+/// Map or unmap the part of `range` covered by `table`, which is at `level`
+/// and starts at `base`
+///
+/// Each address is mapped to itself plus `offset` (wrapping).
+fn update_table(
+    table: *mut Table,
+    level: usize,
+    base: usize,
+    range: &Range<usize>,
+    offset: usize,
+    attributes: Option<Attributes>,
+    active: bool,
+) -> Result<(), Error> {
+    let size = entry_size(level);
+    let first = (range.start.max(base) - base) / size;
+    let last = (range.end.min(base + size * ENTRIES) - base).div_ceil(size);
+    for index in first..last {
+        let start = base + index * size;
+        let end = start + size;
+        // Safety: `table` is one of our tables, and we have `&mut` access to
+        // the address space it belongs to
+        let entry = unsafe { &mut (*table).0[index] };
+        let old = *entry;
+        let output = start.wrapping_add(offset);
+        let covered = range.start <= start && end <= range.end;
+        let aligned = attributes.is_none() || output.is_multiple_of(size);
+        if covered && aligned {
+            // The whole entry is in the range, and where it maps to starts on
+            // a block boundary, so make it a block or page (or nothing). Any
+            // table it pointed to is leaked.
+            let new = attributes.map_or(0, |attributes| leaf_descriptor(level, output, attributes));
+            replace(entry, new, active);
+            continue;
+        }
+        // Only part of the entry is in the range, or it maps somewhere that
+        // isn't aligned to a block, so we need a table
+        let next = if is_table(level, old) {
+            (old & ADDRESS_MASK) as *mut Table
+        } else {
+            let next = allocate_table()?;
+            if old & 0b1 != 0 {
+                // Split the block into smaller ones with the same attributes
+                let old_attributes = leaf_attributes(old);
+                let old_output = (old & ADDRESS_MASK) as usize;
+                let child_size = entry_size(level + 1);
+                // Safety: we just took this table from the pool, so nothing
+                // else is using it
+                for (i, child) in unsafe { (*next).0.iter_mut() }.enumerate() {
+                    *child =
+                        leaf_descriptor(level + 1, old_output + i * child_size, old_attributes);
+                }
+            }
+            let descriptor = (next as u64 & ADDRESS_MASK) | 0b11;
+            replace(entry, descriptor, active);
+            next
+        };
+        update_table(next, level + 1, start, range, offset, attributes, active)?;
+    }
+    Ok(())
+}
+
+/// Write a new descriptor over an old one
+///
+/// If the tables are in use and both are valid, we have to go through an
+/// invalid entry and flush the TLB in between ("break-before-make"), or the
+/// CPU could see both at once.
+fn replace(entry: &mut u64, new: u64, active: bool) {
+    let old = *entry;
+    if active && old & 0b1 != 0 && new & 0b1 != 0 {
+        // Safety: `entry` is a valid reference
+        unsafe { core::ptr::write_volatile(entry, 0) };
+        flush_tlb();
+    }
+    // Safety: `entry` is a valid reference
+    unsafe { core::ptr::write_volatile(entry, new) };
+}
+
+// End of file
@@ -0,0 +1,238 @@
+//! An async PL011 UART driver
+//!
+//! [`State::init`] takes an enabled `Uart` and splits it into an
+//! [`AsyncTx`] and an [`AsyncRx`], which implement `embedded_io_async`'s
+//! `Write` and `Read`. Both halves keep the UART in the [`State`], so the
+//! interrupt handler can get at it too.
+//!
+//! The hardware FIFOs are our only buffers. A write puts as many bytes into
+//! the TX FIFO as will fit, and only waits if it is full - then it unmasks
+//! the TX interrupt, which fires as the FIFO drains. A read takes whatever is
+//! in the RX FIFO, and only waits if it is empty - then it unmasks the RX and
+//! receive timeout interrupts. [`State::on_interrupt`] masks whichever
+//! interrupts fired, and wakes the task that was waiting for them.
+//!
+//! Nothing is shared with the interrupt handler apart from the UART itself,
+//! so dropping a future part-way through is fine. At worst an interrupt
+//! wakes a task that has stopped waiting.
+
+use core::{cell::RefCell, convert::Infallible, future::poll_fn, task::Poll};
+
+use arm_pl011_uart::{Interrupts, Uart};
+use atomic_waker::AtomicWaker;
+
+/// The interrupts which say we have received something
+const RX_INTERRUPTS: Interrupts = Interrupts::RXI.union(Interrupts::RTI);
+
+/// Everything the two halves and the interrupt handler share
+///
+/// Make one of these a `static`, so it lives as long as the halves do.
+pub struct State {
+    uart: critical_section::Mutex<RefCell<Option<Uart<'static>>>>,
+    tx_waker: AtomicWaker,
+    rx_waker: AtomicWaker,
+}
+
+impl State {
+    /// Make a new, empty, state
+    pub const fn new() -> State {
+        State {
+            uart: critical_section::Mutex::new(RefCell::new(None)),
+            tx_waker: AtomicWaker::new(),
+            rx_waker: AtomicWaker::new(),
+        }
+    }
+
+    /// Take an enabled `Uart`, and split it into async halves
+    ///
+    /// Call [`State::on_interrupt`] from the UART's interrupt handler. If
+    /// this state already had a UART, it is dropped.
+    pub fn init(&'static self, mut uart: Uart<'static>) -> (AsyncTx, AsyncRx) {
+        uart.set_interrupt_masks(Interrupts::empty());
+        uart.clear_interrupts(Interrupts::all());
+        critical_section::with(|cs| {
+            self.uart.borrow_ref_mut(cs).replace(uart);
+        });
+        (AsyncTx { state: self }, AsyncRx { state: self })
+    }
+
+    /// UART IRQ handler
+    ///
+    /// Register a function which calls this with the GIC, for the UART's
+    /// interrupt. It masks the interrupts which fired, and wakes the halves
+    /// waiting for them.
+    pub fn on_interrupt(&self) {
+        self.with(|uart| {
+            let status = uart.masked_interrupt_status();
+            if status.intersects(RX_INTERRUPTS) {
+                // Leave the data in the FIFO, for the reader to collect
+                set_masks(uart, RX_INTERRUPTS, false);
+                self.rx_waker.wake();
+            }
+            if status.contains(Interrupts::TXI) {
+                set_masks(uart, Interrupts::TXI, false);
+                self.tx_waker.wake();
+            }
+            uart.clear_interrupts(status);
+        });
+    }
+
+    fn with<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut Uart<'static>) -> T,
+    {
+        critical_section::with(|cs| {
+            let mut guard = self.uart.borrow_ref_mut(cs);
+            let Some(uart) = guard.as_mut() else {
+                panic!("UART not initialised!");
+            };
+            f(uart)
+        })
+    }
+}
+
+impl Default for State {
+    fn default() -> State {
+        State::new()
+    }
+}
+
+/// Turn some of the UART's interrupts on or off, leaving the others alone
+fn set_masks(uart: &mut Uart<'static>, interrupts: Interrupts, enabled: bool) {
+    let masks = uart.interrupt_masks();
+    let masks = if enabled {
+        masks.union(interrupts)
+    } else {
+        masks.difference(interrupts)
+    };
+    uart.set_interrupt_masks(masks);
+}
+
+/// The transmit half of an async UART
+pub struct AsyncTx {
+    state: &'static State,
+}
+
+impl AsyncTx {
+    /// Write some of `buf`, waiting until there's room for at least one byte
+    ///
+    /// Returns how many bytes were written.
+    pub async fn write(&mut self, buf: &[u8]) -> usize {
+        if buf.is_empty() {
+            return 0;
+        }
+        poll_fn(|cx| {
+            // Register first, so an interrupt after our check still wakes us
+            self.state.tx_waker.register(cx.waker());
+            self.state.with(|uart| {
+                let mut written = 0;
+                for byte in buf {
+                    if uart.is_tx_fifo_full() {
+                        break;
+                    }
+                    uart.write_word(*byte);
+                    written += 1;
+                }
+                if written > 0 {
+                    Poll::Ready(written)
+                } else {
+                    set_masks(uart, Interrupts::TXI, true);
+                    Poll::Pending
+                }
+            })
+        })
+        .await
+    }
+
+    /// Write all of `buf`, waiting for room as we go
+    pub async fn write_all(&mut self, mut buf: &[u8]) {
+        while !buf.is_empty() {
+            let written = self.write(buf).await;
+            buf = &buf[written..];
+        }
+    }
+
+    /// Wait until the UART has finished sending everything
+    ///
+    /// The PL011 can't interrupt when it has finished, so this checks once
+    /// each time the executor polls it, letting other tasks run in between.
+    pub async fn flush(&mut self) {
+        poll_fn(|cx| {
+            if self.state.with(|uart| uart.is_busy()) {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        })
+        .await
+    }
+}
+
+impl embedded_io::ErrorType for AsyncTx {
+    type Error = Infallible;
+}
+
+impl embedded_io_async::Write for AsyncTx {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(AsyncTx::write(self, buf).await)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        AsyncTx::flush(self).await;
+        Ok(())
+    }
+}
+
+/// The receive half of an async UART
+pub struct AsyncRx {
+    state: &'static State,
+}
+
+impl AsyncRx {
+    /// Read into `buf`, waiting until at least one byte has been received
+    ///
+    /// Returns how many bytes were read. Bytes with a framing, parity or
+    /// overrun error are logged and dropped.
+    pub async fn read(&mut self, buf: &mut [u8]) -> usize {
+        if buf.is_empty() {
+            return 0;
+        }
+        poll_fn(|cx| {
+            // Register first, so an interrupt after our check still wakes us
+            self.state.rx_waker.register(cx.waker());
+            self.state.with(|uart| {
+                let mut count = 0;
+                while count < buf.len() {
+                    match uart.read_word() {
+                        Ok(Some(byte)) => {
+                            buf[count] = byte;
+                            count += 1;
+                        }
+                        Ok(None) => break,
+                        Err(_) => defmt::warn!("UART receive error - byte dropped"),
+                    }
+                }
+                if count > 0 {
+                    Poll::Ready(count)
+                } else {
+                    set_masks(uart, RX_INTERRUPTS, true);
+                    Poll::Pending
+                }
+            })
+        })
+        .await
+    }
+}
+
+impl embedded_io::ErrorType for AsyncRx {
+    type Error = Infallible;
+}
+
+impl embedded_io_async::Read for AsyncRx {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        Ok(AsyncRx::read(self, buf).await)
+    }
+}
+
+// End of file
@@ -0,0 +1,316 @@
+//! An interrupt-driven buffered PL011 UART driver
+//!
+//! Bytes to send go straight into the UART's TX FIFO while there's room.
+//! When it fills up, they wait in our own queue and we unmask the TX
+//! interrupt, which fires as the FIFO drains past its trigger level. The
+//! handler tops the FIFO up from the queue, and masks the TX interrupt again
+//! once the queue is empty. QEMU sends each byte as soon as it is written, so
+//! there the queue is rarely used.
+//!
+//! The RX and receive timeout interrupts are always unmasked. The handler
+//! moves everything in the RX FIFO into our receive queue, dropping the
+//! oldest bytes if nobody has read them in time.
+
+use core::{cell::RefCell, convert::Infallible};
+
+use arm_pl011_uart::{Interrupts, Uart};
+
+/// The interrupts which say we have received something
+const RX_INTERRUPTS: Interrupts = Interrupts::RXI.union(Interrupts::RTI);
+
+/// Our context, stored inside a lock
+struct Inner<const QLEN: usize> {
+    /// Our UART
+    uart: Uart<'static>,
+    /// Bytes waiting for space in the TX FIFO
+    tx_buffer: heapless::Deque<u8, QLEN>,
+    /// Bytes received, but not yet read
+    rx_buffer: heapless::Deque<u8, QLEN>,
+}
+
+impl<const QLEN: usize> Inner<QLEN> {
+    /// Move queued bytes into the TX FIFO until one of them runs out
+    fn fill_tx_fifo(&mut self) {
+        while !self.uart.is_tx_fifo_full() {
+            let Some(byte) = self.tx_buffer.pop_front() else {
+                break;
+            };
+            defmt::debug!("> TX 0x{=u8:02x}", byte);
+            self.uart.write_word(byte);
+        }
+    }
+
+    /// Turn the TX interrupt on or off, leaving the others alone
+    fn enable_tx_interrupt(&mut self, enabled: bool) {
+        let masks = self.uart.interrupt_masks();
+        let masks = if enabled {
+            masks.union(Interrupts::TXI)
+        } else {
+            masks.difference(Interrupts::TXI)
+        };
+        self.uart.set_interrupt_masks(masks);
+    }
+}
+
+/// A PL011 UART with a buffer in each direction
+pub struct BufferedUart<const QLEN: usize> {
+    inner: critical_section::Mutex<RefCell<Option<Inner<QLEN>>>>,
+}
+
+impl<const QLEN: usize> BufferedUart<QLEN> {
+    /// Make a new, empty, driver
+    pub const fn empty() -> Self {
+        Self {
+            inner: critical_section::Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Initialise this global UART.
+    ///
+    /// Pass in a `Uart` which has already been enabled, and it will be stored
+    /// within and available at a later time. This unmasks the UART's RX
+    /// interrupts, so call [`BufferedUart::on_interrupt`] from its handler.
+    pub fn init(&self, mut uart: Uart<'static>) {
+        uart.set_interrupt_masks(RX_INTERRUPTS);
+        uart.clear_interrupts(Interrupts::all());
+        critical_section::with(|cs| {
+            let mut guard = self.inner.borrow_ref_mut(cs);
+            guard.replace(Inner {
+                uart,
+                tx_buffer: heapless::Deque::new(),
+                rx_buffer: heapless::Deque::new(),
+            });
+        });
+    }
+
+    /// Read the available buffered bytes into the provided buffer.
+    ///
+    /// Returns the number of read bytes, which is zero if nothing has been
+    /// received.
+    pub fn read(&self, buf: &mut [u8]) -> usize {
+        self.with(|inner| {
+            let mut count = 0;
+            for b in buf.iter_mut() {
+                let Some(byte) = inner.rx_buffer.pop_front() else {
+                    break;
+                };
+                defmt::debug!("< RXQ 0x{=u8:02x}", byte);
+                *b = byte;
+                count += 1;
+            }
+            count
+        })
+    }
+
+    /// Transmit a byte slice, blocking until done
+    ///
+    /// This might leave bytes in the buffer that haven't yet been sent.
+    pub fn tx_blocking(&self, bytes: &[u8]) {
+        for b in bytes {
+            self.tx_byte_blocking(*b);
+        }
+    }
+
+    /// Transmit a byte, blocking until queued
+    ///
+    /// If the queue is full, we move what we can into the TX FIFO ourselves
+    /// each time around, so this still finishes when called with interrupts
+    /// masked.
+    pub fn tx_byte_blocking(&self, byte: u8) {
+        loop {
+            let finished = self.with(|inner| {
+                // Anything already queued has to go first
+                inner.fill_tx_fifo();
+                if inner.tx_buffer.is_empty() && !inner.uart.is_tx_fifo_full() {
+                    defmt::debug!("> TX 0x{=u8:02x}", byte);
+                    inner.uart.write_word(byte);
+                    true
+                } else if inner.tx_buffer.push_back(byte).is_ok() {
+                    // The FIFO is full, so the TX interrupt will fire as it
+                    // drains. Because we're in a CS, it can't fire between
+                    // the enqueue and turning it on.
+                    defmt::debug!("> TXQ 0x{=u8:02x}, TXI on", byte);
+                    inner.enable_tx_interrupt(true);
+                    true
+                } else {
+                    // buffer is full ... we need to try again
+                    false
+                }
+            });
+            if finished {
+                break;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Block until all bytes are gone
+    pub fn flush(&self) {
+        loop {
+            let done = self.with(|inner| {
+                inner.fill_tx_fifo();
+                inner.tx_buffer.is_empty() && !inner.uart.is_busy()
+            });
+            if done {
+                break;
+            }
+            core::hint::spin_loop();
+        }
+    }
+
+    /// UART IRQ handler
+    ///
+    /// Register a function which calls this with the GIC, for the UART's
+    /// interrupt. It refills the TX FIFO from our queue, turning the TX
+    /// interrupt off when the queue runs out, and empties the RX FIFO into
+    /// our receive queue.
+    pub fn on_interrupt(&self) {
+        defmt::debug!("- UART ISR");
+        self.with(|inner| {
+            let status = inner.uart.masked_interrupt_status();
+            if status.intersects(RX_INTERRUPTS) {
+                loop {
+                    match inner.uart.read_word() {
+                        Ok(Some(byte)) => {
+                            defmt::debug!("< RX 0x{=u8:02x}", byte);
+                            if let Err(byte) = inner.rx_buffer.push_back(byte) {
+                                // Drop old data if buffer full
+                                let _ = inner.rx_buffer.pop_front();
+                                let _ = inner.rx_buffer.push_back(byte);
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(_) => defmt::warn!("UART receive error - byte dropped"),
+                    }
+                }
+                inner.uart.clear_interrupts(RX_INTERRUPTS);
+            }
+            if status.contains(Interrupts::TXI) {
+                inner.uart.clear_interrupts(Interrupts::TXI);
+                inner.fill_tx_fifo();
+                if inner.tx_buffer.is_empty() {
+                    defmt::debug!("- TX buffer empty ... turning TXI off");
+                    inner.enable_tx_interrupt(false);
+                }
+            }
+        });
+    }
+
+    fn with<T, F>(&self, f: F) -> T
+    where
+        F: FnOnce(&mut Inner<QLEN>) -> T,
+    {
+        critical_section::with(|cs| {
+            let mut guard = self.inner.borrow_ref_mut(cs);
+            let Some(inner) = guard.as_mut() else {
+                panic!("UART not initialised!");
+            };
+            f(inner)
+        })
+    }
+}
+
+impl<const QLEN: usize> core::fmt::Write for BufferedUart<QLEN> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        // convert from &mut BufferedUart to &BufferedUart
+        let mut uart = &*self;
+        // call the impl on &BufferedUart
+        <&BufferedUart<QLEN> as core::fmt::Write>::write_str(&mut uart, s)
+    }
+}
+
+impl<const QLEN: usize> core::fmt::Write for &BufferedUart<QLEN> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.tx_blocking(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<const QLEN: usize> embedded_io::ErrorType for BufferedUart<QLEN> {
+    type Error = Infallible;
+}
+
+impl<const QLEN: usize> embedded_io::Write for BufferedUart<QLEN> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        // convert from &mut BufferedUart to &BufferedUart
+        let mut uart = &*self;
+        // call the impl on &BufferedUart
+        <&BufferedUart<QLEN> as embedded_io::Write>::write(&mut uart, buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        BufferedUart::flush(self);
+        Ok(())
+    }
+}
+
+impl<const QLEN: usize> embedded_io::ErrorType for &BufferedUart<QLEN> {
+    type Error = Infallible;
+}
+
+impl<const QLEN: usize> embedded_io::Write for &BufferedUart<QLEN> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.tx_blocking(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.tx_blocking(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        BufferedUart::flush(self);
+        Ok(())
+    }
+}
+
+impl<const QLEN: usize> embedded_io::Read for BufferedUart<QLEN> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        // convert from &mut BufferedUart to &BufferedUart
+        let mut uart = &*self;
+        // call the impl on &BufferedUart
+        <&BufferedUart<QLEN> as embedded_io::Read>::read(&mut uart, buf)
+    }
+}
+
+impl<const QLEN: usize> embedded_io::Read for &BufferedUart<QLEN> {
+    /// Wait until something has been received, then read it
+    ///
+    /// The UART interrupt has to be unmasked, or this waits forever.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            // Check and sleep with interrupts masked, so the interrupt can't
+            // sneak in between them. It still wakes us up. We don't sleep in
+            // a critical section, which would keep the other cores out too.
+            let count = crate::critical_section::with_interrupts_masked(|| {
+                let count = BufferedUart::read(self, buf);
+                if count == 0 {
+                    // Safety: waiting for an interrupt has no side-effects
+                    unsafe { core::arch::asm!("wfi", options(nomem, nostack, preserves_flags)) };
+                }
+                count
+            });
+            if count > 0 {
+                return Ok(count);
+            }
+        }
+    }
+}
+
+impl<const QLEN: usize> embedded_io::ReadReady for BufferedUart<QLEN> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.with(|inner| !inner.rx_buffer.is_empty()))
+    }
+}
+
+impl<const QLEN: usize> embedded_io::ReadReady for &BufferedUart<QLEN> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.with(|inner| !inner.rx_buffer.is_empty()))
+    }
+}
+
+// End of file
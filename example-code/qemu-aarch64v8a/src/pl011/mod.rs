@@ -0,0 +1,22 @@
+//! Interrupt-driven drivers for the PL011 UART
+//!
+//! [`GlobalUart`](crate::global_uart::GlobalUart) waits for the UART with
+//! interrupts masked. These drivers use the UART's interrupt instead, and
+//! can receive as well as send:
+//!
+//! * [`buffered`] has [`BufferedUart`], which keeps a queue in each
+//!   direction and blocks when it has to
+//! * [`asynch`] has an [`AsyncTx`](asynch::AsyncTx) and
+//!   [`AsyncRx`](asynch::AsyncRx), which implement `embedded_io_async`
+//!
+//! Both take a `Uart` from `arm-pl011-uart` which has already been enabled.
+//! The PL011 has one interrupt for everything - `Platform::uart_intid` from
+//! the device tree - so register a handler for it with the GIC which calls
+//! the driver's `on_interrupt` method.
+
+pub mod asynch;
+pub mod buffered;
+
+pub use buffered::BufferedUart;
+
+// End of file
@@ -0,0 +1,159 @@
+//! Calls to the Power State Coordination Interface
+//!
+//! PSCI is how an operating system asks the firmware to turn cores on and off,
+//! or to reset or power off the whole machine. We boot in EL1 with no
+//! hypervisor, so QEMU handles PSCI calls made with `hvc` itself. If the
+//! device tree says to use `smc` instead (see [`crate::devicetree`]), call
+//! [`set_conduit`].
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// The function ID for `PSCI_VERSION`
+const PSCI_VERSION: u32 = 0x8400_0000;
+
+/// The function ID for `CPU_ON` (64-bit calling convention)
+const PSCI_CPU_ON: u32 = 0xC400_0003;
+
+/// The function ID for `SYSTEM_OFF`
+const PSCI_SYSTEM_OFF: u32 = 0x8400_0008;
+
+/// The function ID for `SYSTEM_RESET`
+const PSCI_SYSTEM_RESET: u32 = 0x8400_0009;
+
+/// Which instruction we make PSCI calls with
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Conduit {
+    /// `hvc`, which goes to EL2 - or to QEMU, if there's nothing at EL2
+    Hvc,
+    /// `smc`, which goes to the secure firmware at EL3
+    Smc,
+}
+
+/// Do we make calls with `smc` instead of `hvc`?
+static USE_SMC: AtomicBool = AtomicBool::new(false);
+
+/// Choose which instruction to make PSCI calls with
+///
+/// The default is [`Conduit::Hvc`].
+pub fn set_conduit(conduit: Conduit) {
+    USE_SMC.store(conduit == Conduit::Smc, Ordering::Relaxed);
+}
+
+/// Which instruction do we make PSCI calls with?
+pub fn conduit() -> Conduit {
+    if USE_SMC.load(Ordering::Relaxed) {
+        Conduit::Smc
+    } else {
+        Conduit::Hvc
+    }
+}
+
+/// An error returned by a PSCI call
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// The function isn't implemented
+    NotSupported,
+    /// One of the arguments was wrong - for `CPU_ON`, there's no such core
+    InvalidParameters,
+    /// The firmware won't do that
+    Denied,
+    /// The core is already on
+    AlreadyOn,
+    /// The core is already being turned on
+    OnPending,
+    /// Something went wrong in the firmware
+    InternalFailure,
+    /// The entry point isn't a valid address
+    InvalidAddress,
+    /// Some other error code
+    Other(i32),
+}
+
+impl Error {
+    /// Turn a PSCI return code into a result
+    fn check(code: i32) -> Result<(), Error> {
+        match code {
+            0 => Ok(()),
+            -1 => Err(Error::NotSupported),
+            -2 => Err(Error::InvalidParameters),
+            -3 => Err(Error::Denied),
+            -4 => Err(Error::AlreadyOn),
+            -5 => Err(Error::OnPending),
+            -6 => Err(Error::InternalFailure),
+            -9 => Err(Error::InvalidAddress),
+            other => Err(Error::Other(other)),
+        }
+    }
+}
+
+/// Generates a function which makes a PSCI call with one instruction
+macro_rules! psci_call {
+    ($name:ident, $instruction:literal) => {
+        #[doc = concat!("Make a PSCI call with `", $instruction, "`")]
+        fn $name(function: u32, arg0: u64, arg1: u64, arg2: u64) -> i32 {
+            let result: u64;
+            // Safety: PSCI calls only touch the registers the SMC Calling
+            // Convention says they do
+            unsafe {
+                core::arch::asm!(
+                    $instruction,
+                    inout("x0") u64::from(function) => result,
+                    inout("x1") arg0 => _,
+                    inout("x2") arg1 => _,
+                    inout("x3") arg2 => _,
+                    out("x4") _, out("x5") _, out("x6") _, out("x7") _,
+                    out("x8") _, out("x9") _, out("x10") _, out("x11") _,
+                    out("x12") _, out("x13") _, out("x14") _, out("x15") _,
+                    out("x16") _, out("x17") _,
+                    options(nostack)
+                );
+            }
+            result as i32
+        }
+    };
+}
+
+psci_call!(call_hvc, "hvc #0");
+psci_call!(call_smc, "smc #0");
+
+/// Make a PSCI call, with up to three arguments
+fn call(function: u32, arg0: u64, arg1: u64, arg2: u64) -> i32 {
+    match conduit() {
+        Conduit::Hvc => call_hvc(function, arg0, arg1, arg2),
+        Conduit::Smc => call_smc(function, arg0, arg1, arg2),
+    }
+}
+
+/// Get the PSCI version, as `(major, minor)`
+pub fn version() -> (u16, u16) {
+    let version = call(PSCI_VERSION, 0, 0, 0) as u32;
+    ((version >> 16) as u16, version as u16)
+}
+
+/// Turn on the core with this `MPIDR` affinity value
+///
+/// It starts at EL1 at `entry`, with the MMU and caches off, and `context` in
+/// `x0`.
+///
+/// # Safety
+///
+/// `entry` must be code which can run like that.
+pub unsafe fn cpu_on(target: u64, entry: u64, context: u64) -> Result<(), Error> {
+    Error::check(call(PSCI_CPU_ON, target, entry, context))
+}
+
+/// Turn the machine off
+///
+/// QEMU exits, with a success code.
+pub fn system_off() -> ! {
+    call(PSCI_SYSTEM_OFF, 0, 0, 0);
+    unreachable!("SYSTEM_OFF returned");
+}
+
+/// Reset the machine
+pub fn system_reset() -> ! {
+    call(PSCI_SYSTEM_RESET, 0, 0, 0);
+    unreachable!("SYSTEM_RESET returned");
+}
+
+// End of file
@@ -0,0 +1,224 @@
+//! Conversion between Unix timestamps and calendar date/time
+//!
+//! The PL031 just counts seconds. By convention (and QEMU follows it) that
+//! count is the number of seconds since 1970-01-01 00:00:00 UTC, ignoring
+//! leap seconds. The conversion uses the days-from-civil algorithm described
+//! at <https://howardhinnant.github.io/date_algorithms.html>.
+
+use super::Error;
+
+/// Seconds in one day
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// A calendar date and time, in UTC
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+/// A day of the week
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl DateTime {
+    /// The earliest time a 32-bit RTC can represent
+    pub const EPOCH: DateTime = DateTime {
+        year: 1970,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+
+    /// Create a new date/time, checking that all the fields are in range.
+    ///
+    /// The year must be between 1970 and 2106, and the whole value must fit
+    /// in a 32-bit Unix timestamp.
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<DateTime, Error> {
+        if !(1970..=2106).contains(&year)
+            || !(1..=12).contains(&month)
+            || day == 0
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return Err(Error::InvalidDateTime);
+        }
+        let dt = DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        };
+        // reject anything the RTC cannot hold
+        dt.to_timestamp()?;
+        Ok(dt)
+    }
+
+    /// Convert a Unix timestamp into a calendar date/time
+    pub fn from_timestamp(timestamp: u32) -> DateTime {
+        let days = timestamp / SECONDS_PER_DAY;
+        let seconds_of_day = timestamp % SECONDS_PER_DAY;
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (seconds_of_day / 3600) as u8,
+            minute: ((seconds_of_day / 60) % 60) as u8,
+            second: (seconds_of_day % 60) as u8,
+        }
+    }
+
+    /// Convert this calendar date/time into a Unix timestamp
+    pub fn to_timestamp(&self) -> Result<u32, Error> {
+        let days = days_from_civil(self.year, self.month, self.day).ok_or(Error::OutOfRange)?;
+        let seconds_of_day =
+            u64::from(self.hour) * 3600 + u64::from(self.minute) * 60 + u64::from(self.second);
+        let timestamp = u64::from(days) * u64::from(SECONDS_PER_DAY) + seconds_of_day;
+        u32::try_from(timestamp).map_err(|_| Error::OutOfRange)
+    }
+
+    /// Get the year (e.g. 2025)
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Get the month (1 to 12)
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Get the day of the month (1 to 31)
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Get the hour (0 to 23)
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute (0 to 59)
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Get the second (0 to 59)
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Work out which day of the week this is
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        const WEEKDAYS: [Weekday; 7] = [
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+        ];
+        let days = days_from_civil(self.year, self.month, self.day).unwrap_or(0);
+        WEEKDAYS[(days % 7) as usize]
+    }
+}
+
+impl defmt::Format for DateTime {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "{=u16:04}-{=u8:02}-{=u8:02} {=u8:02}:{=u8:02}:{=u8:02}",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second
+        );
+    }
+}
+
+impl core::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// Is this a leap year in the Gregorian calendar?
+pub const fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// How many days are in the given month (1 to 12) of the given year?
+pub const fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Convert days since 1970-01-01 into (year, month, day)
+fn civil_from_days(days: u32) -> (u16, u8, u8) {
+    // shift the epoch to 0000-03-01, so leap days come at the end of a year
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // months are counted from March
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u32::from(month <= 2);
+    (year as u16, month as u8, day as u8)
+}
+
+/// Convert (year, month, day) into days since 1970-01-01
+///
+/// Returns `None` for dates before 1970.
+fn days_from_civil(year: u16, month: u8, day: u8) -> Option<u32> {
+    let year = u32::from(year) - u32::from(month <= 2);
+    let month = u32::from(month);
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * mp + 2) / 5 + u32::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    (era * 146_097 + day_of_era).checked_sub(719_468)
+}
+
+// End of file
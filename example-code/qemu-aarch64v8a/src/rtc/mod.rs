@@ -0,0 +1,192 @@
+//! A driver for the Arm PL031 Real Time Clock
+//!
+//! The QEMU `virt` machine has one PL031 at `0x0901_0000`, wired to SPI 2 on
+//! the GIC. QEMU starts the counter running at the host's wall-clock time, as
+//! seconds since the Unix epoch.
+//!
+//! Registers:
+//!
+//! * DR (Data)
+//!   * 31-0: Current counter value
+//! * MR (Match)
+//!   * 31-0: Interrupt fires when DR equals this value
+//! * LR (Load)
+//!   * 31-0: Writing here sets the counter
+//! * CR (Control)
+//!   * 0: Start
+//! * IMSC, RIS, MIS, ICR
+//!   * 0: RTC (match) interrupt
+
+mod datetime;
+pub use datetime::*;
+
+pub mod registers;
+
+/// Base address of the PL031 on the QEMU `virt` machine
+pub const RTC_ADDRESS: usize = 0x0901_0000;
+
+/// The GIC Interrupt ID of the PL031 on the QEMU `virt` machine (SPI 2)
+pub const RTC_INTID: u32 = 32 + 2;
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// Invalid instance.
+    InvalidInstance,
+    /// A calendar field was out of range.
+    InvalidDateTime,
+    /// The date/time cannot be represented by a 32-bit seconds counter.
+    OutOfRange,
+}
+
+/// A PL031 RTC driver
+pub struct Rtc {
+    regs: registers::MmioRegisters<'static>,
+}
+
+impl Rtc {
+    /// What we expect in the CID registers
+    const VALID_CID: [u32; 4] = [0x0D, 0xF0, 0x05, 0xB1];
+
+    /// What we expect in PID0 and the bottom half of PID1
+    const VALID_PID: u16 = 0x031;
+
+    /// Create a new PL031 driver from a register block.
+    pub const fn new(regs: registers::MmioRegisters<'static>) -> Self {
+        Self { regs }
+    }
+
+    /// Create a new PL031 driver.
+    ///
+    /// # Safety
+    ///
+    /// * Ensure only one driver exists for any RTC at a time, or that you
+    ///   never race on register accesses if multiple drivers exist.
+    /// * Ensure the base address points to a valid PL031 MMIO instance, with
+    ///   at least 32-bit alignment.
+    pub const unsafe fn new_with_raw_addr(base_addr: usize) -> Self {
+        Self::new(unsafe { registers::Registers::new_mmio_at(base_addr) })
+    }
+
+    /// Check that this is a valid PL031 instance
+    pub fn check(&mut self) -> Result<(), Error> {
+        let cid_read = [
+            self.regs.read_cid(0).unwrap(),
+            self.regs.read_cid(1).unwrap(),
+            self.regs.read_cid(2).unwrap(),
+            self.regs.read_cid(3).unwrap(),
+        ];
+        defmt::debug!("CIDS: {:?} vs {:?}", cid_read, Self::VALID_CID);
+        if cid_read != Self::VALID_CID {
+            return Err(Error::InvalidInstance);
+        }
+        let pid0 = self.regs.read_pid(0).unwrap() as u8;
+        let pid1 = self.regs.read_pid(1).unwrap() as u8 & 0x0F;
+        let pid = u16::from_be_bytes([pid1, pid0]);
+        if pid != Self::VALID_PID {
+            return Err(Error::InvalidInstance);
+        }
+        Ok(())
+    }
+
+    /// Start the counter, if it isn't already running.
+    ///
+    /// QEMU starts the counter for you, but real hardware might not.
+    pub fn enable(&mut self) {
+        self.regs.modify_control(|c| c.with_start(true));
+    }
+
+    /// Is the counter running?
+    pub fn is_enabled(&self) -> bool {
+        self.regs.read_control().start()
+    }
+
+    /// Read the current time, as seconds since the Unix epoch.
+    pub fn timestamp(&self) -> u32 {
+        self.regs.read_data()
+    }
+
+    /// Read the current time, as a calendar date/time.
+    pub fn now(&self) -> DateTime {
+        DateTime::from_timestamp(self.timestamp())
+    }
+
+    /// Set the current time, as seconds since the Unix epoch.
+    pub fn set_timestamp(&mut self, timestamp: u32) {
+        self.regs.write_load(timestamp);
+    }
+
+    /// Set the current time, as a calendar date/time.
+    pub fn set_time(&mut self, datetime: &DateTime) -> Result<(), Error> {
+        self.set_timestamp(datetime.to_timestamp()?);
+        Ok(())
+    }
+
+    /// Set the alarm (match register), as seconds since the Unix epoch.
+    ///
+    /// The interrupt status bit will be set when the counter reaches this
+    /// value. The interrupt is only sent to the GIC if you also call
+    /// [`Rtc::enable_interrupt`].
+    pub fn set_alarm_timestamp(&mut self, timestamp: u32) {
+        self.regs.write_match_value(timestamp);
+    }
+
+    /// Set the alarm (match register), as a calendar date/time.
+    pub fn set_alarm(&mut self, datetime: &DateTime) -> Result<(), Error> {
+        self.set_alarm_timestamp(datetime.to_timestamp()?);
+        Ok(())
+    }
+
+    /// Set the alarm to go off the given number of seconds from now.
+    pub fn set_alarm_in(&mut self, seconds: u32) {
+        self.set_alarm_timestamp(self.timestamp().wrapping_add(seconds));
+    }
+
+    /// Read back the alarm time, as seconds since the Unix epoch.
+    pub fn alarm_timestamp(&self) -> u32 {
+        self.regs.read_match_value()
+    }
+
+    /// Control whether the match interrupt is passed to the GIC
+    ///
+    /// NOTE: You might also need to enable [`RTC_INTID`] in the GIC
+    pub fn enable_interrupt(&mut self, enabled: bool) {
+        self.regs.modify_interrupt_mask(|m| m.with_rtc(enabled));
+    }
+
+    /// Has the alarm gone off?
+    ///
+    /// This checks the raw status, so works even if the interrupt is masked.
+    pub fn alarm_fired(&self) -> bool {
+        self.regs.read_raw_interrupt_status().rtc()
+    }
+
+    /// Is the match interrupt pending (i.e. fired and not masked)?
+    pub fn interrupt_pending(&self) -> bool {
+        self.regs.read_masked_interrupt_status().rtc()
+    }
+
+    /// Clear the match interrupt
+    pub fn clear_interrupt(&mut self) {
+        self.regs
+            .write_interrupt_clear(registers::Interrupt::builder().with_rtc(true).build());
+    }
+}
+
+/// Read the current RTC value, as seconds since the Unix epoch.
+///
+/// This does not need an [`Rtc`] object, so it can be used from the defmt
+/// timestamp function and the panic handler.
+pub fn timestamp() -> u32 {
+    // Safety: The data register is read-only and reading it has no
+    // side-effects, so it doesn't matter if someone else holds an `Rtc`
+    let regs = unsafe { registers::Registers::new_mmio_at(RTC_ADDRESS) };
+    regs.read_data()
+}
+
+/// Read the current RTC value, as a calendar date/time.
+pub fn now() -> DateTime {
+    DateTime::from_timestamp(timestamp())
+}
+
+// End of file
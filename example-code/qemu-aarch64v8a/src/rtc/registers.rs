@@ -0,0 +1,56 @@
+//! Register definitions for the Arm PL031 Real Time Clock
+
+/// Represents the MMIO registers for a PL031 Peripheral
+#[derive(derive_mmio::Mmio)]
+#[repr(C)]
+pub struct Registers {
+    /// Data Register - the current time, in seconds
+    #[mmio(PureRead)]
+    data: u32,
+    /// Match Register - raises an interrupt when `data` reaches this value
+    #[mmio(PureRead, Write)]
+    match_value: u32,
+    /// Load Register - writing here sets the current time
+    #[mmio(PureRead, Write)]
+    load: u32,
+    /// Control Register
+    #[mmio(PureRead, Write, Modify)]
+    control: Control,
+    /// Interrupt Mask Set/Clear Register
+    #[mmio(PureRead, Write, Modify)]
+    interrupt_mask: Interrupt,
+    /// Raw Interrupt Status Register
+    #[mmio(PureRead)]
+    raw_interrupt_status: Interrupt,
+    /// Masked Interrupt Status Register
+    #[mmio(PureRead)]
+    masked_interrupt_status: Interrupt,
+    /// Interrupt Clear Register
+    #[mmio(Write)]
+    interrupt_clear: Interrupt,
+    _reserved: [u32; 1008],
+    #[mmio(PureRead)]
+    pid: [u32; 4],
+    #[mmio(PureRead)]
+    cid: [u32; 4],
+}
+
+/// RTC Control
+#[bitbybit::bitfield(u32, default = 0, defmt_bitfields)]
+pub struct Control {
+    /// Start the counter
+    ///
+    /// Once set, this bit can only be cleared by a reset.
+    #[bit(0, rw)]
+    start: bool,
+}
+
+/// RTC Interrupt bits (used for mask, status and clear registers)
+#[bitbybit::bitfield(u32, default = 0, defmt_bitfields)]
+pub struct Interrupt {
+    /// The match interrupt
+    #[bit(0, rw)]
+    rtc: bool,
+}
+
+// End of file
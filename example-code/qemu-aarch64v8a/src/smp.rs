@@ -0,0 +1,256 @@
+//! Starting the secondary cores
+//!
+//! QEMU's `virt` machine holds every core except core 0 in reset until
+//! someone asks for it with a PSCI `CPU_ON` call (see [`crate::psci`]). Run
+//! QEMU with more than one core (`cargo run -- --smp 4`), then call
+//! [`start_core`] on core 0 to start core 1, 2 and so on.
+//!
+//! A new core comes up at EL1 with its MMU and caches off. The start-up code
+//! in `aarch64-rt` only runs on core 0, so we have our own: it copies core 0's
+//! memory system and exception settings onto the new core, switches to the
+//! stack for that core, and calls the function you gave [`start_core`].
+//!
+//! Build with the `multicore` feature if more than one core uses
+//! `critical-section`, or anything built on it, like `defmt` - see
+//! [`crate::critical_section`].
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::psci;
+
+/// The most cores we can start, including core 0
+pub const MAX_CORES: usize = 4;
+
+/// How much stack each secondary core gets
+pub const STACK_SIZE: usize = 16 * 1024;
+
+/// The size of a data cache line, which we clean [`StartInfo`] in
+const CACHE_LINE_SIZE: usize = 64;
+
+/// A stack for a secondary core
+#[repr(C, align(16))]
+struct CoreStack(UnsafeCell<[u8; STACK_SIZE]>);
+
+// Safety: Each stack is only used by the core it belongs to
+unsafe impl Sync for CoreStack {}
+
+/// The stacks for cores 1 to `MAX_CORES - 1`
+static STACKS: [CoreStack; MAX_CORES - 1] =
+    [const { CoreStack(UnsafeCell::new([0; STACK_SIZE])) }; MAX_CORES - 1];
+
+/// Everything a new core needs to get going
+///
+/// `__secondary_entry` reads the first seven fields with the MMU off, so
+/// don't move them.
+#[repr(C, align(64))]
+struct StartInfo {
+    mair: u64,
+    tcr: u64,
+    ttbr0: u64,
+    sctlr: u64,
+    vbar: u64,
+    cpacr: u64,
+    stack_top: u64,
+    core: usize,
+    entry: Option<fn(usize) -> !>,
+}
+
+/// A [`StartInfo`] for each secondary core
+struct StartInfoSlot(UnsafeCell<StartInfo>);
+
+// Safety: Only core 0 writes to a slot, before it starts the core that reads
+// it, and each slot is only written once
+unsafe impl Sync for StartInfoSlot {}
+
+/// The start-up information for cores 1 to `MAX_CORES - 1`
+static START_INFO: [StartInfoSlot; MAX_CORES - 1] = [const {
+    StartInfoSlot(UnsafeCell::new(StartInfo {
+        mair: 0,
+        tcr: 0,
+        ttbr0: 0,
+        sctlr: 0,
+        vbar: 0,
+        cpacr: 0,
+        stack_top: 0,
+        core: 0,
+        entry: None,
+    }))
+}; MAX_CORES - 1];
+
+/// Which secondary cores we have asked for already
+static STARTED: [AtomicBool; MAX_CORES - 1] = [const { AtomicBool::new(false) }; MAX_CORES - 1];
+
+/// Something went wrong starting a core
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// Core 0 is already running, and we only have stacks for `MAX_CORES`
+    InvalidCore,
+    /// We already started that core
+    AlreadyStarted,
+    /// The firmware wouldn't start it - probably QEMU doesn't have that many
+    /// cores
+    Psci(psci::Error),
+}
+
+impl From<psci::Error> for Error {
+    fn from(error: psci::Error) -> Error {
+        Error::Psci(error)
+    }
+}
+
+/// Which core are we running on?
+///
+/// This is affinity level 0 from `MPIDR_EL1`, which counts up from 0 on
+/// QEMU's `virt` machine.
+#[inline]
+pub fn core_id() -> usize {
+    let r: u64;
+    // Safety: We only read a register here, which has no side-effects
+    unsafe {
+        core::arch::asm!("mrs {0}, MPIDR_EL1", out(reg) r, options(nomem, nostack, preserves_flags))
+    };
+    (r & 0xFF) as usize
+}
+
+/// Start a secondary core, which calls `entry` with its core number
+///
+/// Core 0 has to call this. The new core shares core 0's page tables and
+/// exception vectors, but IRQs are masked and its GIC CPU interface is not
+/// set up.
+pub fn start_core(core: usize, entry: fn(usize) -> !) -> Result<(), Error> {
+    if core == 0 || core >= MAX_CORES {
+        return Err(Error::InvalidCore);
+    }
+    if STARTED[core - 1].swap(true, Ordering::Relaxed) {
+        return Err(Error::AlreadyStarted);
+    }
+
+    let info = START_INFO[core - 1].0.get();
+    let stack_top = STACKS[core - 1].0.get() as usize + STACK_SIZE;
+    // Safety: Nothing else is using this slot - its core isn't running yet,
+    // and `STARTED` stops us filling it in twice
+    unsafe {
+        info.write(StartInfo {
+            mair: read_mair(),
+            tcr: read_tcr(),
+            ttbr0: read_ttbr0(),
+            sctlr: read_sctlr(),
+            vbar: read_vbar(),
+            cpacr: read_cpacr(),
+            stack_top: stack_top as u64,
+            core,
+            entry: Some(entry),
+        });
+        // The new core reads this with its caches off, so it has to be in RAM
+        clean_to_poc(info as usize, core::mem::size_of::<StartInfo>());
+    }
+
+    extern "C" {
+        /// The start-up code for secondary cores, from the `global_asm!` below
+        fn __secondary_entry();
+    }
+    let result = unsafe {
+        // Safety: `__secondary_entry` is written to run with the MMU off
+        psci::cpu_on(
+            core as u64,
+            __secondary_entry as *const () as usize as u64,
+            info as usize as u64,
+        )
+    };
+    if let Err(e) = result {
+        STARTED[core - 1].store(false, Ordering::Relaxed);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Where a secondary core goes once it has its MMU and stack
+extern "C" fn secondary_main(info: &'static StartInfo) -> ! {
+    let entry = info.entry.expect("secondary core started without an entry");
+    entry(info.core)
+}
+
+/// Clean a range of memory from the data cache to the Point of Coherency
+///
+/// # Safety
+///
+/// The range must be mapped.
+unsafe fn clean_to_poc(start: usize, len: usize) {
+    let mut line = start & !(CACHE_LINE_SIZE - 1);
+    while line < start + len {
+        // Safety: Our caller says the memory is mapped
+        unsafe {
+            core::arch::asm!("dc cvac, {0}", in(reg) line, options(nostack, preserves_flags))
+        };
+        line += CACHE_LINE_SIZE;
+    }
+    // Safety: A barrier has no side-effects
+    unsafe { core::arch::asm!("dsb sy", options(nostack, preserves_flags)) };
+}
+
+/// Generates a function which reads one system register
+macro_rules! read_register {
+    ($name:ident, $register:literal) => {
+        #[doc = concat!("Read `", $register, "`")]
+        fn $name() -> u64 {
+            let r: u64;
+            // Safety: We only read a register here, which has no side-effects
+            unsafe {
+                core::arch::asm!(
+                    concat!("mrs {0}, ", $register),
+                    out(reg) r,
+                    options(nomem, nostack, preserves_flags)
+                )
+            };
+            r
+        }
+    };
+}
+
+read_register!(read_mair, "MAIR_EL1");
+read_register!(read_tcr, "TCR_EL1");
+read_register!(read_ttbr0, "TTBR0_EL1");
+read_register!(read_sctlr, "SCTLR_EL1");
+read_register!(read_vbar, "VBAR_EL1");
+read_register!(read_cpacr, "CPACR_EL1");
+
+// The start-up code for secondary cores
+//
+// PSCI gives us the address of our `StartInfo` in x0. The MMU is off, but
+// core 0 uses an identity map, so the same address works once it is on.
+core::arch::global_asm!(
+    r#"
+    .section .text.__secondary_entry, "ax"
+    .global __secondary_entry
+    .type __secondary_entry, %function
+__secondary_entry:
+    // Same memory system as core 0
+    ldp     x1, x2, [x0, #0]
+    msr     MAIR_EL1, x1
+    msr     TCR_EL1, x2
+    ldp     x1, x2, [x0, #16]
+    msr     TTBR0_EL1, x1
+    // Same exception vectors, and let us use the FPU
+    ldp     x3, x4, [x0, #32]
+    msr     VBAR_EL1, x3
+    msr     CPACR_EL1, x4
+    // Throw away anything left in the TLB from before the reset
+    tlbi    vmalle1
+    dsb     nsh
+    isb
+    // Turn on the MMU and caches
+    msr     SCTLR_EL1, x2
+    isb
+    // Switch to our own stack, and go to Rust
+    ldr     x1, [x0, #48]
+    mov     sp, x1
+    mov     x29, xzr
+    mov     x30, xzr
+    b       {secondary_main}
+    .size __secondary_entry, . - __secondary_entry
+    "#,
+    secondary_main = sym secondary_main,
+);
+
+// End of file
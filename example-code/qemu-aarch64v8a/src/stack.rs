@@ -0,0 +1,89 @@
+//! Measuring how much stack we use
+//!
+//! `aarch64-rt` puts our boot stack between the `boot_stack_begin` and
+//! `boot_stack_end` linker symbols. It doesn't give us a way to run code
+//! before `main`, so call [`paint`] at the top of `main` to fill the unused
+//! part of it with [`PAINT`](qemu_common::stack::PAINT). Call [`usage`] at any
+//! time to see how much of it has been used since. The panic handler and
+//! [`crate::exit`] both print it for you.
+//!
+//! When we use the page tables from [`crate::mmu`], the lowest page of the
+//! stack is an unmapped guard page. It can never be used, so we don't count
+//! it. The page above the stack is a guard page too - we reserve it in the
+//! `.stack.guard` section, which `aarch64-rt` links straight after the boot
+//! stack, so nothing else can end up there.
+
+use core::{
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use qemu_common::stack::{Stack, Usage};
+
+extern "C" {
+    /// The bottom of the boot stack, from `aarch64-rt`
+    static boot_stack_begin: u8;
+    /// The top of the boot stack, from `aarch64-rt`
+    static boot_stack_end: u8;
+}
+
+/// A page which is never mapped
+#[repr(C, align(4096))]
+struct GuardPage([u8; crate::mmu::STACK_GUARD_SIZE]);
+
+/// The guard page above the boot stack
+#[unsafe(link_section = ".stack.guard")]
+#[used]
+static TOP_GUARD: GuardPage = GuardPage([0; crate::mmu::STACK_GUARD_SIZE]);
+
+/// Have we painted the stack yet?
+static PAINTED: AtomicBool = AtomicBool::new(false);
+
+/// Get our stack
+pub fn stack() -> Stack {
+    let bottom = (&raw const boot_stack_begin) as usize + crate::mmu::STACK_GUARD_SIZE;
+    let top = (&raw const boot_stack_end) as usize;
+    // Safety: the linker gives this memory to the stack
+    unsafe { Stack::new(bottom, top) }
+}
+
+/// The guard pages below and above our stack
+pub fn guard_pages() -> [Range<usize>; 2] {
+    let below = (&raw const boot_stack_begin) as usize;
+    let above = (&raw const TOP_GUARD) as usize;
+    [
+        below..below + crate::mmu::STACK_GUARD_SIZE,
+        above..above + crate::mmu::STACK_GUARD_SIZE,
+    ]
+}
+
+/// Is `addr` in one of the guard pages around our stack?
+pub fn is_guard_page(addr: usize) -> bool {
+    guard_pages().iter().any(|page| page.contains(&addr))
+}
+
+/// Fill the unused part of the stack with paint
+///
+/// Only the first call does anything, so that later calls don't wipe out the
+/// high-water mark.
+pub fn paint() {
+    critical_section::with(|_| {
+        if !PAINTED.swap(true, Ordering::Relaxed) {
+            // Safety: this is the stack we're running on, and with interrupts
+            // off nothing else is using it
+            unsafe { stack().paint() };
+        }
+    });
+}
+
+/// Work out how much of the stack has been used
+pub fn usage() -> Usage {
+    stack().usage()
+}
+
+/// Print the stack usage with defmt
+pub fn report() {
+    stack().report();
+}
+
+// End of file
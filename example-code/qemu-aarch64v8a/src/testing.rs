@@ -0,0 +1,154 @@
+//! Support for running the on-target tests in `./tests`
+//!
+//! The harness itself lives in `qemu_common::testing`. This module provides
+//! the clock it needs, and a [`test_suite!`](crate::test_suite) macro which
+//! adds an entry point.
+//!
+//! The harness owns the virtual timer, which interrupts every
+//! [`TICK_MS`] milliseconds to check whether a test has run past its
+//! `#[timeout(ms)]`, so tests cannot use the virtual timer themselves (and
+//! that includes the Embassy time driver). Each test starts with IRQs unmasked
+//! and the timer's interrupt enabled at the GIC, even if `setup` initialised
+//! the GIC again. A test which masks IRQs and then hangs is only caught if it
+//! returns.
+
+use crate::{
+    gic::Gic,
+    timer::{self, Counter, GenericTimer},
+};
+
+pub use qemu_common::testing::*;
+
+#[doc(hidden)]
+pub use qemu_common::test_suite as __test_suite;
+
+/// How often we check whether a test has run out of time
+pub const TICK_MS: u64 = 10;
+
+/// The priority of our timer interrupt
+///
+/// This is the most urgent priority, so a test which raises the GIC's priority
+/// mask doesn't hold it back.
+const TICK_PRIORITY: u8 = 0x00;
+
+/// Called by the GIC driver when the virtual timer fires
+fn on_tick() {
+    // Safety: the harness owns the virtual timer
+    let mut timer = unsafe { GenericTimer::new(Counter::Virtual) };
+    timer.set_countdown(timer::hz_to_ticks(TICK_MS, 1_000));
+    check_timeout::<Qemu>();
+}
+
+/// Start the virtual timer, and have the GIC pass its interrupt on to us
+fn start_ticking() {
+    // Safety: a test might be holding a GIC driver too, but we only touch the
+    // virtual timer's handler, priority and enable bit, which no test uses
+    let mut gic = unsafe { Gic::new_virt() }.expect("finding the GIC");
+    gic.register(timer::VIRTUAL_TIMER_INTID, TICK_PRIORITY, on_tick)
+        .expect("registering the timer interrupt");
+    // Safety: the harness owns the virtual timer
+    let mut timer = unsafe { GenericTimer::new(Counter::Virtual) };
+    timer.set_countdown(timer::hz_to_ticks(TICK_MS, 1_000));
+    timer.enable_interrupt(true);
+    timer.enable(true);
+    // Safety: `on_tick` only touches the virtual timer and the harness state,
+    // which is behind a critical section
+    unsafe { crate::enable_interrupts() };
+}
+
+/// Our implementation of the harness's [`Platform`] trait
+pub struct Qemu;
+
+impl Platform for Qemu {
+    fn init() {
+        // Safety: no test has started yet, so nothing else has a GIC driver
+        let mut gic = unsafe { Gic::new_virt() }.expect("finding the GIC");
+        gic.init();
+        start_ticking();
+    }
+
+    fn before_test() {
+        // `setup` may have initialised the GIC again, which disables our
+        // interrupt, or masked IRQs
+        start_ticking();
+    }
+
+    fn now_us() -> u64 {
+        let (ticks, freq): (u64, u64);
+        // Safety: Reading the virtual count and its frequency is always
+        // allowed at EL1
+        unsafe {
+            core::arch::asm!(
+                "isb",
+                "mrs {ticks}, cntvct_el0",
+                "mrs {freq}, cntfrq_el0",
+                ticks = out(reg) ticks,
+                freq = out(reg) freq,
+                options(nomem, nostack, preserves_flags)
+            );
+        }
+        ticks * 1_000_000 / freq
+    }
+
+    fn exit(code: i32) -> ! {
+        crate::exit(code)
+    }
+
+    fn checkpoint() -> Checkpoint {
+        let stack_pointer: usize;
+        // Safety: reading the stack pointer has no side-effects
+        unsafe {
+            core::arch::asm!("mov {}, sp", out(reg) stack_pointer, options(nomem, nostack, preserves_flags))
+        };
+        Checkpoint {
+            stack_pointer,
+            interrupts_enabled: crate::critical_section::interrupts_enabled(),
+        }
+    }
+
+    unsafe fn restart(checkpoint: Checkpoint, resume: Resume, next: usize) -> ! {
+        // Safety: the test which held it is never coming back
+        unsafe { crate::critical_section::abandon() };
+        if checkpoint.interrupts_enabled {
+            // Safety: they were enabled when the tests started
+            unsafe { crate::enable_interrupts() };
+        } else {
+            crate::disable_interrupts();
+        }
+        // Safety: our caller says nothing below the checkpoint is in use
+        unsafe {
+            core::arch::asm!(
+                "mov sp, {stack_pointer}",
+                "br {resume}",
+                stack_pointer = in(reg) checkpoint.stack_pointer,
+                resume = in(reg) resume,
+                in("x0") next,
+                options(noreturn)
+            )
+        }
+    }
+}
+
+/// Declare some tests, and an entry point which runs them
+///
+/// See `qemu_common::test_suite!` for the syntax.
+#[macro_export]
+macro_rules! test_suite {
+    ($($tests:tt)*) => {
+        $crate::testing::__test_suite! {
+            platform: $crate::testing::Qemu;
+            $($tests)*
+        }
+
+        // The one-argument form expands to an unqualified `entry!`, which
+        // doesn't resolve in the test crate, so give the stack size here.
+        aarch64_rt::entry!(__test_main, 40);
+
+        fn __test_main(_arg0: u64, _arg1: u64, _arg2: u64, _arg3: u64) -> ! {
+            $crate::stack::paint();
+            __run_tests(0)
+        }
+    };
+}
+
+// End of file
@@ -0,0 +1,594 @@
+//! Preemptive round-robin threads
+//!
+//! A thread is a function which runs at EL1 on its own [`ThreadStack`].
+//! [`spawn`] some, then call [`run`], which turns the caller into the *idle
+//! thread* and returns once every thread has finished. Threads take turns in
+//! the order they were spawned, and each gets up to [`TIME_SLICE_MS`] before
+//! the EL1 physical timer interrupts it and the next one runs. A thread can
+//! give up the rest of its turn with [`yield_now`], or stop for a while with
+//! [`sleep_ms`]. When no thread is ready, the idle thread waits for an
+//! interrupt.
+//!
+//! [`sync`] has a [`sync::Mutex`] and a [`sync::Semaphore`], which block the
+//! thread that is waiting, so that the others can run.
+//!
+//! # Switching threads
+//!
+//! A switch saves the callee-saved registers, the stack pointer and all of
+//! the FP/SIMD registers into the old thread's `Context`, and loads the new
+//! thread's. The caller of the switch has saved everything else. When the
+//! timer interrupts a thread, that's the exception entry code in
+//! `aarch64-rt`, which leaves the registers (along with `ELR_EL1` and
+//! `SPSR_EL1`) on the interrupted thread's stack. Our IRQ handler calls
+//! `preempt` after the GIC driver has finished with the interrupt, so the
+//! switch happens with the GIC ready for the next one.
+//!
+//! Switches only happen with IRQs masked, and the thread we switch to puts
+//! them back the way it had them. We don't hold a critical section across
+//! a switch, so this also works with the `multicore` feature - but every
+//! thread runs on the core which called [`run`].
+//!
+//! The scheduler uses the EL1 physical timer, so it can run alongside the
+//! [`crate::time_driver`], which uses the virtual one.
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use critical_section::{CriticalSection, Mutex};
+
+use crate::{
+    gic::{self, Gic},
+    timer::{self, Counter, GenericTimer},
+};
+
+pub mod sync;
+
+/// The most threads there can be, including the idle thread
+pub const MAX_THREADS: usize = 8;
+
+/// How much stack each thread gets
+pub const STACK_SIZE: usize = 16 * 1024;
+
+/// The longest a thread runs before the next one gets a turn
+pub const TIME_SLICE_MS: u64 = 10;
+
+/// The slot the idle thread uses
+const IDLE: usize = 0;
+
+/// A stack for a thread
+///
+/// Put it in a static, such as a `static_cell::ConstStaticCell`.
+#[repr(C, align(16))]
+pub struct ThreadStack([u8; STACK_SIZE]);
+
+impl ThreadStack {
+    /// Make an empty stack
+    pub const fn new() -> ThreadStack {
+        ThreadStack([0; STACK_SIZE])
+    }
+}
+
+impl Default for ThreadStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The function a thread runs
+///
+/// The thread finishes when it returns.
+pub type Entry = fn();
+
+/// Identifies a thread
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct ThreadId(usize);
+
+impl ThreadId {
+    /// The idle thread, which called [`run`]
+    pub const IDLE: ThreadId = ThreadId(IDLE);
+}
+
+/// What a thread is doing
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum State {
+    /// It is running, or waiting for its turn
+    Ready,
+    /// It is sleeping until the physical count reaches this value
+    Sleeping(u64),
+    /// It is waiting for a [`sync`] object, identified by its address
+    Blocked(usize),
+    /// Its entry function returned
+    Finished,
+}
+
+/// Something went wrong with a thread
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// All [`MAX_THREADS`] slots are in use
+    TooManyThreads,
+}
+
+/// A thread's registers, saved while it isn't running
+///
+/// `__switch_thread` relies on this layout.
+#[repr(C, align(16))]
+struct Context {
+    /// x19 to x30
+    x: [u64; 12],
+    sp: u64,
+    fpcr: u64,
+    fpsr: u64,
+    _padding: u64,
+    /// q0 to q31
+    q: [u128; 32],
+}
+
+impl Context {
+    /// A context with every register zero
+    const fn empty() -> Context {
+        Context {
+            x: [0; 12],
+            sp: 0,
+            fpcr: 0,
+            fpsr: 0,
+            _padding: 0,
+            q: [0; 32],
+        }
+    }
+}
+
+/// The saved registers of each thread
+///
+/// These live outside [`SCHEDULER`] because `__switch_thread` writes to them
+/// after we have let go of it.
+static mut CONTEXTS: [Context; MAX_THREADS] = [const { Context::empty() }; MAX_THREADS];
+
+/// Everything we know about one thread
+struct Thread {
+    name: &'static str,
+    entry: Entry,
+    state: State,
+    /// How many times it has been switched to
+    switches: u32,
+}
+
+/// The threads, and which one is running
+struct Scheduler {
+    threads: [Option<Thread>; MAX_THREADS],
+    current: usize,
+    running: bool,
+}
+
+impl Scheduler {
+    /// Pick the next thread to run, and make it current
+    ///
+    /// Returns the slots to switch from and to, or `None` if the current
+    /// thread should carry on.
+    fn pick_next(&mut self) -> Option<(usize, usize)> {
+        if !self.running {
+            return None;
+        }
+        let from = self.current;
+        // Look at every other thread in turn, and then this one
+        let to = (1..=MAX_THREADS)
+            .map(|offset| (from + offset) % MAX_THREADS)
+            .filter(|&slot| slot != IDLE)
+            .find(|&slot| matches!(&self.threads[slot], Some(t) if t.state == State::Ready))
+            .unwrap_or(IDLE);
+        if to == from {
+            return None;
+        }
+        self.current = to;
+        if let Some(thread) = self.threads[to].as_mut() {
+            thread.switches += 1;
+        }
+        Some((from, to))
+    }
+
+    /// Make any sleeping threads whose time is up ready, and return when the
+    /// next one wakes, if any are still asleep
+    fn wake_sleepers(&mut self, now: u64) -> Option<u64> {
+        let mut next = None;
+        for thread in self.threads.iter_mut().flatten() {
+            if let State::Sleeping(deadline) = thread.state {
+                if deadline <= now {
+                    thread.state = State::Ready;
+                } else {
+                    next = Some(next.map_or(deadline, |n: u64| n.min(deadline)));
+                }
+            }
+        }
+        next
+    }
+
+    /// The thread which is running
+    fn current_mut(&mut self) -> &mut Thread {
+        self.threads[self.current]
+            .as_mut()
+            .expect("the current thread has no slot")
+    }
+}
+
+/// The scheduler's state
+static SCHEDULER: Mutex<RefCell<Scheduler>> = Mutex::new(RefCell::new(Scheduler {
+    threads: [const { None }; MAX_THREADS],
+    current: IDLE,
+    running: false,
+}));
+
+/// The EL1 physical timer, which only this module uses
+static TIMER: Mutex<RefCell<GenericTimer>> = Mutex::new(RefCell::new(
+    // Safety: We never make another driver for the physical timer
+    unsafe { GenericTimer::new(Counter::Physical) },
+));
+
+/// Set by the timer interrupt when it's time to switch threads
+static NEED_SWITCH: AtomicBool = AtomicBool::new(false);
+
+extern "C" {
+    /// Save the current registers in `from`, and load the ones in `to`
+    ///
+    /// Returns when something switches back to `from`.
+    fn __switch_thread(from: *mut Context, to: *const Context);
+
+    /// Where a new thread starts, with its slot in `x19`
+    fn __thread_start();
+}
+
+/// Make a thread, which will run `entry` on `stack`
+///
+/// It gets its first turn once [`run`] is called, or straight away if it's
+/// already running.
+pub fn spawn(
+    name: &'static str,
+    entry: Entry,
+    stack: &'static mut ThreadStack,
+) -> Result<ThreadId, Error> {
+    let top = stack.0.as_ptr_range().end as u64;
+    critical_section::with(|cs| {
+        let mut scheduler = SCHEDULER.borrow_ref_mut(cs);
+        let slot = (1..MAX_THREADS)
+            .find(|&slot| scheduler.threads[slot].is_none())
+            .ok_or(Error::TooManyThreads)?;
+        // Safety: we only take the address here. `slot` is in range.
+        let context = unsafe { &raw mut CONTEXTS[slot] };
+        // Safety: the slot is empty, so no thread is using this context, and
+        // we're in a critical section so nothing else is spawning
+        let context = unsafe { &mut *context };
+        *context = Context::empty();
+        context.x[0] = slot as u64;
+        context.x[11] = __thread_start as *const () as usize as u64;
+        context.sp = top & !15;
+        scheduler.threads[slot] = Some(Thread {
+            name,
+            entry,
+            state: State::Ready,
+            switches: 0,
+        });
+        Ok(ThreadId(slot))
+    })
+}
+
+/// Run the threads until they have all finished
+///
+/// The caller becomes the idle thread. We use the EL1 physical timer, and
+/// unmask interrupts. Finished threads are forgotten before we return, so
+/// you can spawn more and call this again.
+pub fn run(gic: &mut Gic) -> Result<(), gic::Error> {
+    critical_section::with(|cs| {
+        let mut scheduler = SCHEDULER.borrow_ref_mut(cs);
+        scheduler.current = IDLE;
+        scheduler.running = true;
+        scheduler.threads[IDLE] = Some(Thread {
+            name: "idle",
+            entry: || {},
+            state: State::Ready,
+            switches: 0,
+        });
+        let mut timer = TIMER.borrow_ref_mut(cs);
+        timer.set_countdown(timer::hz_to_ticks(TIME_SLICE_MS, 1_000));
+        timer.enable_interrupt(true);
+        timer.enable(true);
+    });
+    gic.register(
+        timer::intid(Counter::Physical),
+        gic::DEFAULT_PRIORITY,
+        on_tick,
+    )?;
+    // Safety: the scheduler is ready for the timer interrupt
+    unsafe { crate::enable_interrupts() };
+
+    loop {
+        let (alive, ready) = critical_section::with(|cs| {
+            let scheduler = SCHEDULER.borrow_ref(cs);
+            let mut threads = scheduler.threads[1..].iter().flatten();
+            let alive = threads.clone().any(|t| t.state != State::Finished);
+            (alive, threads.any(|t| t.state == State::Ready))
+        });
+        if !alive {
+            break;
+        }
+        if ready {
+            yield_now();
+        } else {
+            // Safety: waiting for an interrupt has no side-effects
+            unsafe { core::arch::asm!("wfi", options(nomem, nostack, preserves_flags)) };
+        }
+    }
+
+    critical_section::with(|cs| {
+        TIMER.borrow_ref_mut(cs).enable(false);
+        let mut scheduler = SCHEDULER.borrow_ref_mut(cs);
+        scheduler.running = false;
+        scheduler.threads = [const { None }; MAX_THREADS];
+    });
+    gic.unregister(timer::intid(Counter::Physical))
+}
+
+/// The thread which is running
+pub fn current() -> ThreadId {
+    critical_section::with(|cs| ThreadId(SCHEDULER.borrow_ref(cs).current))
+}
+
+/// The name of the thread which is running, or `None` outside [`run`]
+pub fn name() -> Option<&'static str> {
+    critical_section::with(|cs| {
+        let scheduler = SCHEDULER.borrow_ref(cs);
+        scheduler.threads[scheduler.current]
+            .as_ref()
+            .map(|t| t.name)
+    })
+}
+
+/// Let the next thread have a turn
+///
+/// If no other thread is ready, we carry on.
+pub fn yield_now() {
+    switch();
+}
+
+/// Stop this thread for at least `ms` milliseconds
+///
+/// The other threads run in the meantime. Outside a thread, this just
+/// busy-waits.
+pub fn sleep_ms(ms: u32) {
+    sleep_ticks(timer::hz_to_ticks(u64::from(ms), 1_000));
+}
+
+/// Stop this thread for at least `us` microseconds
+///
+/// The other threads run in the meantime. Outside a thread, this just
+/// busy-waits.
+pub fn sleep_us(us: u32) {
+    sleep_ticks(timer::hz_to_ticks(u64::from(us), 1_000_000));
+}
+
+/// Stop this thread until the physical count has gone up by `ticks`
+fn sleep_ticks(ticks: u64) {
+    let deadline = timer::count(Counter::Physical) + ticks;
+    let in_thread = critical_section::with(|cs| {
+        let mut scheduler = SCHEDULER.borrow_ref_mut(cs);
+        if !scheduler.running || scheduler.current == IDLE {
+            return false;
+        }
+        scheduler.current_mut().state = State::Sleeping(deadline);
+        // Wake up in time, if that's before the next tick
+        let mut timer = TIMER.borrow_ref_mut(cs);
+        if deadline < timer.compare() {
+            timer.set_compare(deadline);
+        }
+        true
+    });
+    if in_thread {
+        switch();
+    } else {
+        while timer::count(Counter::Physical) < deadline {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Print each thread's name, state and how many turns it has had, with
+/// defmt
+pub fn report() {
+    critical_section::with(|cs| {
+        let scheduler = SCHEDULER.borrow_ref(cs);
+        for (slot, thread) in scheduler.threads.iter().enumerate() {
+            if let Some(thread) = thread {
+                defmt::info!(
+                    "Thread {=usize} {=str}: {}, {=u32} switches",
+                    slot,
+                    thread.name,
+                    thread.state,
+                    thread.switches
+                );
+            }
+        }
+    });
+}
+
+/// Mark the current thread as waiting for the [`sync`] object at `key`
+///
+/// It stops running at the next [`switch`], and starts again after [`wake`].
+/// Panics outside a thread, where there's nothing to switch to while we
+/// wait.
+fn block(cs: CriticalSection, key: usize) {
+    let mut scheduler = SCHEDULER.borrow_ref_mut(cs);
+    assert!(
+        scheduler.running && scheduler.current != IDLE,
+        "only threads can wait for a lock"
+    );
+    scheduler.current_mut().state = State::Blocked(key);
+}
+
+/// Make the next thread waiting for the [`sync`] object at `key` ready
+///
+/// Returns `false` if no thread was waiting.
+fn wake(cs: CriticalSection, key: usize) -> bool {
+    let mut scheduler = SCHEDULER.borrow_ref_mut(cs);
+    let current = scheduler.current;
+    // Start after the current thread, to keep the round robin fair
+    let waiter = (1..=MAX_THREADS)
+        .map(|offset| (current + offset) % MAX_THREADS)
+        .find(
+            |&slot| matches!(&scheduler.threads[slot], Some(t) if t.state == State::Blocked(key)),
+        );
+    match waiter {
+        Some(slot) => {
+            if let Some(thread) = scheduler.threads[slot].as_mut() {
+                thread.state = State::Ready;
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Switch to the next thread that is ready, if there is one
+///
+/// This masks IRQs, and puts them back when we get our turn again.
+fn switch() {
+    let was_enabled = crate::critical_section::interrupts_enabled();
+    crate::disable_interrupts();
+    let switch = critical_section::with(|cs| SCHEDULER.borrow_ref_mut(cs).pick_next());
+    if let Some((from, to)) = switch {
+        // Safety: each context is only used by its own thread, and only while
+        // it isn't running. IRQs are masked, so nothing else can switch
+        // threads until we're done.
+        unsafe { __switch_thread(&raw mut CONTEXTS[from], &raw const CONTEXTS[to]) };
+    }
+    if was_enabled {
+        // Safety: they were enabled before
+        unsafe { crate::enable_interrupts() };
+    }
+}
+
+/// Switch threads, if the timer says the current one has had its turn
+///
+/// Our IRQ handler calls this after the GIC driver has handled the
+/// interrupt.
+pub(crate) fn preempt() {
+    if NEED_SWITCH.swap(false, Ordering::Relaxed) {
+        switch();
+    }
+}
+
+/// Called by the GIC driver when the physical timer fires
+///
+/// Wakes any sleepers whose time is up, and sets the timer for the end of
+/// the next time slice, or the next sleeper, whichever is sooner.
+fn on_tick() {
+    critical_section::with(|cs| {
+        let now = timer::count(Counter::Physical);
+        let next_wake = SCHEDULER.borrow_ref_mut(cs).wake_sleepers(now);
+        let slice_end = now + timer::hz_to_ticks(TIME_SLICE_MS, 1_000);
+        let next = next_wake.map_or(slice_end, |wake| wake.min(slice_end));
+        // The interrupt is level-sensitive, so moving the compare value into
+        // the future clears it
+        TIMER.borrow_ref_mut(cs).set_compare(next);
+    });
+    NEED_SWITCH.store(true, Ordering::Relaxed);
+}
+
+/// Where a new thread starts, after `__thread_start` passes us its slot
+extern "C" fn thread_main(slot: usize) -> ! {
+    let entry = critical_section::with(|cs| {
+        SCHEDULER.borrow_ref(cs).threads[slot]
+            .as_ref()
+            .expect("started a thread with no slot")
+            .entry
+    });
+    // We were switched to with IRQs masked
+    // Safety: the scheduler is ready for interrupts
+    unsafe { crate::enable_interrupts() };
+    entry();
+
+    critical_section::with(|cs| {
+        let mut scheduler = SCHEDULER.borrow_ref_mut(cs);
+        let thread = scheduler.current_mut();
+        thread.state = State::Finished;
+        defmt::debug!("Thread {=str} finished", thread.name);
+    });
+    switch();
+    unreachable!("switched back to a finished thread");
+}
+
+// Switching threads. See `Context` for the layout.
+core::arch::global_asm!(
+    r#"
+    .section .text.__switch_thread, "ax"
+    .global __switch_thread
+    .type __switch_thread, %function
+__switch_thread:
+    stp     x19, x20, [x0, #0]
+    stp     x21, x22, [x0, #16]
+    stp     x23, x24, [x0, #32]
+    stp     x25, x26, [x0, #48]
+    stp     x27, x28, [x0, #64]
+    stp     x29, x30, [x0, #80]
+    mov     x9, sp
+    mrs     x10, FPCR
+    mrs     x11, FPSR
+    stp     x9, x10, [x0, #96]
+    str     x11, [x0, #112]
+    stp     q0, q1, [x0, #128]
+    stp     q2, q3, [x0, #160]
+    stp     q4, q5, [x0, #192]
+    stp     q6, q7, [x0, #224]
+    stp     q8, q9, [x0, #256]
+    stp     q10, q11, [x0, #288]
+    stp     q12, q13, [x0, #320]
+    stp     q14, q15, [x0, #352]
+    stp     q16, q17, [x0, #384]
+    stp     q18, q19, [x0, #416]
+    stp     q20, q21, [x0, #448]
+    stp     q22, q23, [x0, #480]
+    stp     q24, q25, [x0, #512]
+    stp     q26, q27, [x0, #544]
+    stp     q28, q29, [x0, #576]
+    stp     q30, q31, [x0, #608]
+
+    ldp     x19, x20, [x1, #0]
+    ldp     x21, x22, [x1, #16]
+    ldp     x23, x24, [x1, #32]
+    ldp     x25, x26, [x1, #48]
+    ldp     x27, x28, [x1, #64]
+    ldp     x29, x30, [x1, #80]
+    ldp     x9, x10, [x1, #96]
+    ldr     x11, [x1, #112]
+    mov     sp, x9
+    msr     FPCR, x10
+    msr     FPSR, x11
+    ldp     q0, q1, [x1, #128]
+    ldp     q2, q3, [x1, #160]
+    ldp     q4, q5, [x1, #192]
+    ldp     q6, q7, [x1, #224]
+    ldp     q8, q9, [x1, #256]
+    ldp     q10, q11, [x1, #288]
+    ldp     q12, q13, [x1, #320]
+    ldp     q14, q15, [x1, #352]
+    ldp     q16, q17, [x1, #384]
+    ldp     q18, q19, [x1, #416]
+    ldp     q20, q21, [x1, #448]
+    ldp     q22, q23, [x1, #480]
+    ldp     q24, q25, [x1, #512]
+    ldp     q26, q27, [x1, #544]
+    ldp     q28, q29, [x1, #576]
+    ldp     q30, q31, [x1, #608]
+    ret
+    .size __switch_thread, . - __switch_thread
+
+    .section .text.__thread_start, "ax"
+    .global __thread_start
+    .type __thread_start, %function
+__thread_start:
+    mov     x0, x19
+    mov     x29, xzr
+    mov     x30, xzr
+    b       {thread_main}
+    .size __thread_start, . - __thread_start
+    "#,
+    thread_main = sym thread_main,
+);
+
+// End of file
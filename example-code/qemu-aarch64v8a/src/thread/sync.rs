@@ -0,0 +1,174 @@
+//! A mutex and a semaphore for threads
+//!
+//! These are built on `critical-section`, like everything else that's
+//! shared here, but they only hold it for long enough to update their own
+//! state. A thread which has to wait is marked as blocked and switched out,
+//! so the other threads keep running - including whoever it is waiting for.
+//! When the lock is released or the semaphore is signalled, the next waiting
+//! thread is made ready again.
+//!
+//! Only threads can wait. Taking a free [`Mutex`] or an available
+//! [`Semaphore`] works from anywhere, but having to wait outside a thread
+//! panics, because there's nothing to switch to.
+
+use core::{
+    cell::{Cell, UnsafeCell},
+    ops::{Deref, DerefMut},
+};
+
+/// A mutual exclusion lock, which blocks the threads waiting for it
+pub struct Mutex<T> {
+    locked: critical_section::Mutex<Cell<bool>>,
+    value: UnsafeCell<T>,
+}
+
+// Safety: the lock makes sure only one thread at a time gets at the value
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    /// Make an unlocked mutex holding `value`
+    pub const fn new(value: T) -> Mutex<T> {
+        Mutex {
+            locked: critical_section::Mutex::new(Cell::new(false)),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Take the lock, waiting for it if another thread has it
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        loop {
+            let taken = critical_section::with(|cs| {
+                let locked = self.locked.borrow(cs);
+                if locked.replace(true) {
+                    super::block(cs, self.key());
+                    false
+                } else {
+                    true
+                }
+            });
+            if taken {
+                return MutexGuard { mutex: self };
+            }
+            super::switch();
+        }
+    }
+
+    /// Take the lock if nobody else has it
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        let taken = critical_section::with(|cs| !self.locked.borrow(cs).replace(true));
+        taken.then_some(MutexGuard { mutex: self })
+    }
+
+    /// Get at the value, without locking, because we own the mutex
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    /// What threads waiting for us are blocked on
+    fn key(&self) -> usize {
+        self as *const Self as usize
+    }
+}
+
+/// Holds a [`Mutex`] locked, and gives access to its value
+///
+/// The lock is released when this is dropped.
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: we hold the lock
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: we hold the lock
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        critical_section::with(|cs| {
+            self.mutex.locked.borrow(cs).set(false);
+            super::wake(cs, self.mutex.key());
+        });
+    }
+}
+
+/// A counting semaphore, which blocks the threads waiting for it
+pub struct Semaphore {
+    count: critical_section::Mutex<Cell<u32>>,
+}
+
+impl Semaphore {
+    /// Make a semaphore with `count` permits available
+    pub const fn new(count: u32) -> Semaphore {
+        Semaphore {
+            count: critical_section::Mutex::new(Cell::new(count)),
+        }
+    }
+
+    /// Take a permit, waiting for one if there are none
+    pub fn acquire(&self) {
+        loop {
+            let taken = critical_section::with(|cs| {
+                let taken = self.take(cs);
+                if !taken {
+                    super::block(cs, self.key());
+                }
+                taken
+            });
+            if taken {
+                return;
+            }
+            super::switch();
+        }
+    }
+
+    /// Take a permit if there is one
+    pub fn try_acquire(&self) -> bool {
+        critical_section::with(|cs| self.take(cs))
+    }
+
+    /// Give back a permit, and wake a thread which is waiting for one
+    ///
+    /// This is fine to call from an interrupt handler.
+    pub fn release(&self) {
+        critical_section::with(|cs| {
+            let count = self.count.borrow(cs);
+            count.set(count.get() + 1);
+            super::wake(cs, self.key());
+        });
+    }
+
+    /// How many permits are available
+    pub fn available(&self) -> u32 {
+        critical_section::with(|cs| self.count.borrow(cs).get())
+    }
+
+    /// Take a permit if there is one
+    fn take(&self, cs: critical_section::CriticalSection) -> bool {
+        let count = self.count.borrow(cs);
+        match count.get() {
+            0 => false,
+            n => {
+                count.set(n - 1);
+                true
+            }
+        }
+    }
+
+    /// What threads waiting for us are blocked on
+    fn key(&self) -> usize {
+        self as *const Self as usize
+    }
+}
+
+// End of file
@@ -0,0 +1,111 @@
+//! An `embassy-time` driver, using the virtual generic timer
+//!
+//! `embassy-time` counts in ticks of [`embassy_time_driver::TICK_HZ`] (1 MHz,
+//! set in `Cargo.toml`). We convert to and from the system counter, which
+//! runs much faster, and use the virtual timer's compare value as the alarm
+//! for the next timer in the queue.
+//!
+//! Call [`init`] with the GIC driver before you use anything from
+//! `embassy-time`, and then unmask interrupts.
+
+use core::{cell::RefCell, task::Waker};
+
+use critical_section::Mutex;
+use embassy_time_driver::{Driver, TICK_HZ};
+use embassy_time_queue_utils::Queue;
+
+use crate::{
+    gic::{self, Gic},
+    timer::{self, Counter, GenericTimer},
+};
+
+/// The virtual timer, which only this module uses
+static TIMER: Mutex<RefCell<GenericTimer>> = Mutex::new(RefCell::new(
+    // Safety: We never make another driver for the virtual timer
+    unsafe { GenericTimer::new(Counter::Virtual) },
+));
+
+/// Our implementation of [`Driver`]
+struct GenericTimerDriver {
+    /// The timers embassy has asked us to wake
+    queue: Mutex<RefCell<Queue>>,
+}
+
+impl GenericTimerDriver {
+    /// Program the alarm for tick `at`
+    ///
+    /// Returns `false` if `at` has already passed, so the caller needs to
+    /// process the queue again.
+    fn set_alarm(&self, cs: critical_section::CriticalSection, at: u64) -> bool {
+        let mut timer = TIMER.borrow_ref_mut(cs);
+        if at == u64::MAX {
+            timer.enable(false);
+            return true;
+        }
+        timer.set_compare(timer::hz_to_ticks(at, TICK_HZ));
+        timer.enable_interrupt(true);
+        timer.enable(true);
+        timer::ticks_to_hz(timer.now(), TICK_HZ) < at
+    }
+
+    /// Wake everything whose time has come, and program the next alarm
+    fn on_interrupt(&self) {
+        critical_section::with(|cs| {
+            let mut queue = self.queue.borrow_ref_mut(cs);
+            let mut next = queue.next_expiration(self.now());
+            while !self.set_alarm(cs, next) {
+                next = queue.next_expiration(self.now());
+            }
+        });
+    }
+}
+
+impl Driver for GenericTimerDriver {
+    fn now(&self) -> u64 {
+        let count = critical_section::with(|cs| TIMER.borrow_ref(cs).now());
+        timer::ticks_to_hz(count, TICK_HZ)
+    }
+
+    fn schedule_wake(&self, at: u64, waker: &Waker) {
+        critical_section::with(|cs| {
+            let mut queue = self.queue.borrow_ref_mut(cs);
+            if queue.schedule_wake(at, waker) {
+                let mut next = queue.next_expiration(self.now());
+                while !self.set_alarm(cs, next) {
+                    next = queue.next_expiration(self.now());
+                }
+            }
+        });
+    }
+}
+
+embassy_time_driver::time_driver_impl!(static DRIVER: GenericTimerDriver = GenericTimerDriver {
+    queue: Mutex::new(RefCell::new(Queue::new())),
+});
+
+/// Set up the virtual timer, and register its interrupt with the GIC
+///
+/// The timer interrupt doesn't fire until you unmask interrupts with
+/// [`crate::enable_interrupts`].
+pub fn init(gic: &mut Gic) -> Result<(), gic::Error> {
+    critical_section::with(|cs| {
+        let mut timer = TIMER.borrow_ref_mut(cs);
+        timer.enable(false);
+        timer.enable_interrupt(true);
+    });
+    gic.register(
+        timer::intid(Counter::Virtual),
+        gic::DEFAULT_PRIORITY,
+        on_interrupt,
+    )
+}
+
+/// Called by the GIC driver when the virtual timer fires
+fn on_interrupt() {
+    // The interrupt is level-sensitive, so turn the timer off before we
+    // return. `on_interrupt` will turn it back on if anything else is queued.
+    critical_section::with(|cs| TIMER.borrow_ref_mut(cs).enable(false));
+    DRIVER.on_interrupt();
+}
+
+// End of file
@@ -0,0 +1,397 @@
+//! A driver for the Arm Generic Timer
+//!
+//! Every Armv8-A core has a 64-bit system counter, which ticks at the
+//! frequency in `CNTFRQ_EL0` (62.5 MHz on QEMU's `virt` machine), and a set of
+//! timers which compare against it. At EL1 we can use two of them:
+//!
+//! * the EL1 physical timer (`CNTP_*_EL0`), which compares against the
+//!   physical count in `CNTPCT_EL0`
+//! * the virtual timer (`CNTV_*_EL0`), which compares against the virtual
+//!   count in `CNTVCT_EL0` - that's the physical count minus an offset set by
+//!   the hypervisor, which is zero here
+//!
+//! Each timer raises a level-sensitive Private Peripheral Interrupt while its
+//! condition is met, until you move the compare value, mask the interrupt or
+//! turn the timer off.
+//!
+//! The [`crate::time_driver`] uses the virtual timer, so use the physical one
+//! if you want to run both.
+//!
+//! The async `DelayNs` implementation sleeps until the timer interrupt, so
+//! register [`on_interrupt`] for the timer with the GIC, and unmask
+//! interrupts, before you use it.
+//!
+//! Which interrupts the timers raise comes from the device tree. Until
+//! [`crate::devicetree::Platform::discover`] calls [`set_intids`], we assume
+//! the ones on QEMU's `virt` machine.
+
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU32, Ordering},
+    task::Poll,
+};
+
+use atomic_waker::AtomicWaker;
+
+/// The GIC Interrupt ID of the EL1 physical timer on QEMU's `virt` machine
+/// (PPI 14)
+pub const PHYSICAL_TIMER_INTID: u32 = 30;
+
+/// The GIC Interrupt ID of the virtual timer on QEMU's `virt` machine (PPI 11)
+pub const VIRTUAL_TIMER_INTID: u32 = 27;
+
+/// The GIC Interrupt ID the EL1 physical timer raises
+static PHYSICAL_INTID: AtomicU32 = AtomicU32::new(PHYSICAL_TIMER_INTID);
+
+/// The GIC Interrupt ID the virtual timer raises
+static VIRTUAL_INTID: AtomicU32 = AtomicU32::new(VIRTUAL_TIMER_INTID);
+
+/// Whoever is waiting for the EL1 physical timer
+static PHYSICAL_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// Whoever is waiting for the virtual timer
+static VIRTUAL_WAKER: AtomicWaker = AtomicWaker::new();
+
+/// `CNTx_CTL_EL0` bit which turns the timer on
+const CTL_ENABLE: u64 = 1 << 0;
+
+/// `CNTx_CTL_EL0` bit which stops the timer raising its interrupt
+const CTL_IMASK: u64 = 1 << 1;
+
+/// `CNTx_CTL_EL0` bit which says the timer condition is met
+const CTL_ISTATUS: u64 = 1 << 2;
+
+/// Which of the generic timers to use
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Counter {
+    /// The EL1 physical timer
+    Physical,
+    /// The virtual timer
+    Virtual,
+}
+
+/// Say which GIC Interrupt IDs the EL1 physical and virtual timers raise
+///
+/// The defaults are [`PHYSICAL_TIMER_INTID`] and [`VIRTUAL_TIMER_INTID`].
+/// Set them before you register a timer interrupt with the GIC.
+pub fn set_intids(physical: u32, virtual_: u32) {
+    PHYSICAL_INTID.store(physical, Ordering::Relaxed);
+    VIRTUAL_INTID.store(virtual_, Ordering::Relaxed);
+}
+
+/// The GIC Interrupt ID a timer raises
+pub fn intid(counter: Counter) -> u32 {
+    match counter {
+        Counter::Physical => PHYSICAL_INTID.load(Ordering::Relaxed),
+        Counter::Virtual => VIRTUAL_INTID.load(Ordering::Relaxed),
+    }
+}
+
+/// Call this when a timer's interrupt fires, to finish an async delay
+///
+/// Register it with the GIC for the timer you use for async delays, e.g.
+/// `gic.register(timer.intid(), priority, || timer::on_interrupt(Counter::Physical))`.
+/// It turns the timer off, as the interrupt is level-sensitive, and wakes the
+/// task waiting for it.
+pub fn on_interrupt(counter: Counter) {
+    // Safety: the delay which armed the timer is waiting for us to do this,
+    // and we only turn the timer off
+    let mut timer = unsafe { GenericTimer::new(counter) };
+    timer.enable(false);
+    waker(counter).wake();
+}
+
+/// The waker for an async delay on a timer
+fn waker(counter: Counter) -> &'static AtomicWaker {
+    match counter {
+        Counter::Physical => &PHYSICAL_WAKER,
+        Counter::Virtual => &VIRTUAL_WAKER,
+    }
+}
+
+/// Read the frequency of the system counter, in Hz, from `CNTFRQ_EL0`
+pub fn frequency() -> u32 {
+    let r: u64;
+    // Safety: We only read a register here, which has no side-effects
+    unsafe {
+        core::arch::asm!("mrs {0}, CNTFRQ_EL0", out(reg) r, options(nomem, nostack, preserves_flags))
+    };
+    r as u32
+}
+
+/// Read the current physical or virtual count
+pub fn count(counter: Counter) -> u64 {
+    let r: u64;
+    // Safety: We only read registers here. The `isb` stops the read happening
+    // early, out of order with the code around it.
+    unsafe {
+        match counter {
+            Counter::Physical => core::arch::asm!(
+                "isb",
+                "mrs {0}, CNTPCT_EL0",
+                out(reg) r,
+                options(nomem, nostack, preserves_flags)
+            ),
+            Counter::Virtual => core::arch::asm!(
+                "isb",
+                "mrs {0}, CNTVCT_EL0",
+                out(reg) r,
+                options(nomem, nostack, preserves_flags)
+            ),
+        }
+    }
+    r
+}
+
+/// Convert a number of system counter ticks to a number of `hz` ticks,
+/// rounding down
+pub fn ticks_to_hz(ticks: u64, hz: u64) -> u64 {
+    ((ticks as u128 * hz as u128) / frequency() as u128) as u64
+}
+
+/// Convert a number of `hz` ticks to a number of system counter ticks,
+/// rounding up
+pub fn hz_to_ticks(value: u64, hz: u64) -> u64 {
+    (value as u128 * frequency() as u128).div_ceil(hz as u128) as u64
+}
+
+/// One of the generic timers
+///
+/// Implements the blocking `DelayNs` trait from `embedded-hal`, which
+/// busy-waits on the counter, and the async one from `embedded-hal-async`,
+/// which waits for the timer interrupt (see [`on_interrupt`]).
+#[derive(Debug)]
+pub struct GenericTimer {
+    counter: Counter,
+}
+
+impl GenericTimer {
+    /// Make a driver for one of the generic timers
+    ///
+    /// # Safety
+    ///
+    /// Only make one driver for each [`Counter`] - they share one set of
+    /// registers.
+    pub const unsafe fn new(counter: Counter) -> GenericTimer {
+        GenericTimer { counter }
+    }
+
+    /// Which timer this is
+    pub fn counter(&self) -> Counter {
+        self.counter
+    }
+
+    /// The GIC Interrupt ID this timer raises
+    pub fn intid(&self) -> u32 {
+        intid(self.counter)
+    }
+
+    /// Read the current count
+    pub fn now(&self) -> u64 {
+        count(self.counter)
+    }
+
+    /// Read the compare value
+    pub fn compare(&self) -> u64 {
+        let r: u64;
+        // Safety: We only read a register here, which has no side-effects
+        unsafe {
+            match self.counter {
+                Counter::Physical => core::arch::asm!(
+                    "mrs {0}, CNTP_CVAL_EL0",
+                    out(reg) r,
+                    options(nomem, nostack, preserves_flags)
+                ),
+                Counter::Virtual => core::arch::asm!(
+                    "mrs {0}, CNTV_CVAL_EL0",
+                    out(reg) r,
+                    options(nomem, nostack, preserves_flags)
+                ),
+            }
+        }
+        r
+    }
+
+    /// Set the compare value
+    ///
+    /// The timer condition is met once the count reaches this value.
+    pub fn set_compare(&mut self, value: u64) {
+        // Safety: We own this timer
+        unsafe {
+            match self.counter {
+                Counter::Physical => core::arch::asm!(
+                    "msr CNTP_CVAL_EL0, {0}",
+                    "isb",
+                    in(reg) value,
+                    options(nomem, nostack, preserves_flags)
+                ),
+                Counter::Virtual => core::arch::asm!(
+                    "msr CNTV_CVAL_EL0, {0}",
+                    "isb",
+                    in(reg) value,
+                    options(nomem, nostack, preserves_flags)
+                ),
+            }
+        }
+    }
+
+    /// Set the compare value to `ticks` after the current count
+    pub fn set_countdown(&mut self, ticks: u64) {
+        let now = self.now();
+        self.set_compare(now.wrapping_add(ticks));
+    }
+
+    /// Read the control register
+    fn control(&self) -> u64 {
+        let r: u64;
+        // Safety: We only read a register here, which has no side-effects
+        unsafe {
+            match self.counter {
+                Counter::Physical => core::arch::asm!(
+                    "mrs {0}, CNTP_CTL_EL0",
+                    out(reg) r,
+                    options(nomem, nostack, preserves_flags)
+                ),
+                Counter::Virtual => core::arch::asm!(
+                    "mrs {0}, CNTV_CTL_EL0",
+                    out(reg) r,
+                    options(nomem, nostack, preserves_flags)
+                ),
+            }
+        }
+        r
+    }
+
+    /// Write the control register
+    fn set_control(&mut self, value: u64) {
+        // Safety: We own this timer
+        unsafe {
+            match self.counter {
+                Counter::Physical => core::arch::asm!(
+                    "msr CNTP_CTL_EL0, {0}",
+                    "isb",
+                    in(reg) value,
+                    options(nomem, nostack, preserves_flags)
+                ),
+                Counter::Virtual => core::arch::asm!(
+                    "msr CNTV_CTL_EL0, {0}",
+                    "isb",
+                    in(reg) value,
+                    options(nomem, nostack, preserves_flags)
+                ),
+            }
+        }
+    }
+
+    /// Turn the timer on or off
+    ///
+    /// A timer which is off never meets its condition, so never interrupts.
+    pub fn enable(&mut self, enabled: bool) {
+        let control = self.control() & !CTL_ISTATUS;
+        if enabled {
+            self.set_control(control | CTL_ENABLE);
+        } else {
+            self.set_control(control & !CTL_ENABLE);
+        }
+    }
+
+    /// Control whether the timer raises its interrupt when the condition is
+    /// met
+    pub fn enable_interrupt(&mut self, enabled: bool) {
+        let control = self.control() & !CTL_ISTATUS;
+        if enabled {
+            self.set_control(control & !CTL_IMASK);
+        } else {
+            self.set_control(control | CTL_IMASK);
+        }
+    }
+
+    /// Is the timer on, and has the count reached the compare value?
+    pub fn is_expired(&self) -> bool {
+        let control = self.control();
+        control & CTL_ENABLE != 0 && control & CTL_ISTATUS != 0
+    }
+
+    /// Spin until the count reaches `deadline`
+    fn wait_until(&self, deadline: u64) {
+        while self.now() < deadline {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+impl embedded_hal::delay::DelayNs for GenericTimer {
+    fn delay_ns(&mut self, ns: u32) {
+        let deadline = self.now() + hz_to_ticks(u64::from(ns), 1_000_000_000);
+        self.wait_until(deadline);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        let deadline = self.now() + hz_to_ticks(u64::from(us), 1_000_000);
+        self.wait_until(deadline);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        let deadline = self.now() + hz_to_ticks(u64::from(ms), 1_000);
+        self.wait_until(deadline);
+    }
+}
+
+/// A future which is ready once a counter reaches a deadline
+///
+/// It sets the timer's compare value to the deadline, and sleeps until
+/// [`on_interrupt`] wakes it.
+struct Deadline<'a> {
+    timer: &'a mut GenericTimer,
+    deadline: u64,
+}
+
+impl Future for Deadline<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<()> {
+        if self.timer.now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        waker(self.timer.counter).register(cx.waker());
+        let deadline = self.deadline;
+        self.timer.set_compare(deadline);
+        self.timer.enable_interrupt(true);
+        // If the deadline passed while we did that, the interrupt is pending
+        // and wakes us straight away
+        self.timer.enable(true);
+        Poll::Pending
+    }
+}
+
+impl embedded_hal_async::delay::DelayNs for GenericTimer {
+    async fn delay_ns(&mut self, ns: u32) {
+        let deadline = self.now() + hz_to_ticks(u64::from(ns), 1_000_000_000);
+        Deadline {
+            timer: self,
+            deadline,
+        }
+        .await
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        let deadline = self.now() + hz_to_ticks(u64::from(us), 1_000_000);
+        Deadline {
+            timer: self,
+            deadline,
+        }
+        .await
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        let deadline = self.now() + hz_to_ticks(u64::from(ms), 1_000);
+        Deadline {
+            timer: self,
+            deadline,
+        }
+        .await
+    }
+}
+
+// End of file
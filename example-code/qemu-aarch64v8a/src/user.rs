@@ -0,0 +1,586 @@
+//! Running user tasks at EL0, with system calls
+//!
+//! A [`Task`] is a function which runs at EL0, on its own stack, in its own
+//! [`UserMemory`]. [`run`] takes turns running each task until it exits,
+//! yields or faults. Tasks can only get at the rest of the system through
+//! system calls - [`address_space`] only lets EL0 use our code, our constants
+//! and the task's own memory, and not the devices or the kernel's data.
+//!
+//! # The system call ABI
+//!
+//! `aarch64-rt` only tells our exception handler the return address and the
+//! saved `SPSR`, not the task's registers, so arguments and results go through
+//! a [`Mailbox`] at the bottom of each task's memory. The task is given a
+//! pointer to its mailbox when it starts. To make a system call, a task:
+//!
+//! 1. writes the call number to `number`, and up to four arguments to `args`
+//! 2. executes `svc #0`
+//! 3. reads the result from `result`
+//!
+//! The [`Mailbox`] methods do that for you. A negative result is an error
+//! ([`EFAULT`] or [`ENOSYS`]).
+//!
+//! | Number             | Arguments                  | Result                    |
+//! |--------------------|----------------------------|---------------------------|
+//! | [`SYS_WRITE`]      | pointer, length            | bytes written             |
+//! | [`SYS_EXIT`]       | exit code                  | (does not return)         |
+//! | [`SYS_YIELD`]      | -                          | 0, once it's our turn     |
+//! | [`SYS_GET_TIME`]   | -                          | microseconds since boot   |
+//!
+//! `SYS_WRITE` sends bytes to the console set with [`set_console`], or prints
+//! them with defmt if there isn't one. The kernel checks that the task is
+//! allowed to read every byte first.
+//!
+//! A task switch can happen at any `svc #0`, so the kernel doesn't keep the
+//! task's registers. The `__user_syscall` stub saves the callee-saved ones on
+//! the task's stack, and everything else is clobbered, just like a function
+//! call.
+//!
+//! User code can run code and read constants from our image, but it can't
+//! write to statics, and it can't use defmt (which needs semihosting, which
+//! only works at EL1).
+
+use core::{
+    cell::{Cell, RefCell},
+    ops::Range,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use arm_pl011_uart::Uart;
+use critical_section::Mutex;
+
+use crate::{
+    exception::{self, ExceptionClass, Origin, Syndrome},
+    mmu::{self, AddressSpace},
+    timer::{self, Counter},
+};
+
+/// Write bytes to the console
+pub const SYS_WRITE: u64 = 0;
+
+/// Stop this task, with an exit code
+pub const SYS_EXIT: u64 = 1;
+
+/// Let the other tasks run
+pub const SYS_YIELD: u64 = 2;
+
+/// Get the time since boot, in microseconds
+pub const SYS_GET_TIME: u64 = 3;
+
+/// The task passed a pointer to memory it isn't allowed to use
+pub const EFAULT: i64 = -14;
+
+/// There is no system call with that number
+pub const ENOSYS: i64 = -38;
+
+/// How much memory each task has, including its mailbox
+pub const USER_MEMORY_SIZE: usize = 2 * mmu::PAGE_SIZE;
+
+/// `SPSR_EL1` for a task - EL0, using `SP_EL0`, with interrupts unmasked
+const SPSR_EL0T: u64 = 0b0000;
+
+/// Where a task's arguments and results go when it makes a system call
+#[derive(Debug)]
+#[repr(C)]
+pub struct Mailbox {
+    /// Which system call to make
+    pub number: u64,
+    /// The arguments to the system call
+    pub args: [u64; 4],
+    /// The result of the system call
+    pub result: i64,
+}
+
+impl Mailbox {
+    /// Make a system call
+    pub fn syscall(&mut self, number: u64, args: [u64; 4]) -> i64 {
+        // The kernel reads and writes the mailbox behind the compiler's back,
+        // so these accesses must be volatile
+        // Safety: these are all valid references
+        unsafe {
+            core::ptr::write_volatile(&mut self.number, number);
+            core::ptr::write_volatile(&mut self.args, args);
+            __user_syscall();
+            core::ptr::read_volatile(&self.result)
+        }
+    }
+
+    /// Write a string to the console
+    pub fn write(&mut self, s: &str) -> i64 {
+        self.syscall(SYS_WRITE, [s.as_ptr() as u64, s.len() as u64, 0, 0])
+    }
+
+    /// Stop this task
+    pub fn exit(&mut self, code: i32) -> ! {
+        self.syscall(SYS_EXIT, [code as u64, 0, 0, 0]);
+        unreachable!("SYS_EXIT returned");
+    }
+
+    /// Let the other tasks run
+    pub fn yield_now(&mut self) {
+        self.syscall(SYS_YIELD, [0; 4]);
+    }
+
+    /// Get the time since boot, in microseconds
+    pub fn time_us(&mut self) -> u64 {
+        self.syscall(SYS_GET_TIME, [0; 4]) as u64
+    }
+}
+
+/// The memory a task runs in - its mailbox, and then its stack
+#[repr(C, align(4096))]
+pub struct UserMemory {
+    mailbox: Mailbox,
+    stack: [u8; USER_MEMORY_SIZE - core::mem::size_of::<Mailbox>()],
+}
+
+impl UserMemory {
+    /// Make some empty user memory
+    ///
+    /// Put it in a static, such as a `static_cell::ConstStaticCell`.
+    pub const fn new() -> UserMemory {
+        UserMemory {
+            mailbox: Mailbox {
+                number: 0,
+                args: [0; 4],
+                result: 0,
+            },
+            stack: [0; USER_MEMORY_SIZE - core::mem::size_of::<Mailbox>()],
+        }
+    }
+}
+
+impl Default for UserMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The entry point of a task
+///
+/// It gets a pointer to its mailbox, and must finish with
+/// [`Mailbox::exit`].
+pub type Entry = extern "C" fn(mailbox: &'static mut Mailbox) -> !;
+
+/// Where to start (or carry on) running a task
+///
+/// `__enter_user` relies on this layout.
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+struct UserState {
+    pc: u64,
+    sp: u64,
+    spsr: u64,
+    x0: u64,
+}
+
+/// What a task is doing
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Status {
+    /// It is waiting for its turn
+    Ready,
+    /// It called [`SYS_EXIT`] with this code
+    Exited(i32),
+    /// It caused an exception, and was stopped
+    Faulted,
+}
+
+/// A task which runs at EL0
+pub struct Task {
+    name: &'static str,
+    memory: &'static mut UserMemory,
+    state: UserState,
+    status: Status,
+}
+
+impl Task {
+    /// Make a task, which will run `entry` in `memory`
+    pub fn new(name: &'static str, entry: Entry, memory: &'static mut UserMemory) -> Task {
+        let top = memory.stack.as_ptr_range().end as u64;
+        let mailbox = &raw mut memory.mailbox;
+        Task {
+            name,
+            memory,
+            state: UserState {
+                pc: entry as usize as u64,
+                sp: top & !15,
+                spsr: SPSR_EL0T,
+                x0: mailbox as u64,
+            },
+            status: Status::Ready,
+        }
+    }
+
+    /// The task's name
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// What the task is doing
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// The memory the task runs in
+    pub fn memory_range(&self) -> Range<usize> {
+        let start = &raw const *self.memory as usize;
+        start..start + USER_MEMORY_SIZE
+    }
+}
+
+/// Make an address space for running these tasks
+///
+/// It's [`AddressSpace::for_image`], plus:
+///
+/// * our code, which EL0 can execute
+/// * our read-only data, which EL0 can read
+/// * each task's memory, which EL0 can read and write
+///
+/// Everything else, including the devices, is only for EL1. Activate it
+/// before calling [`run`].
+pub fn address_space(tasks: &[Task]) -> Result<AddressSpace, mmu::Error> {
+    let mut space = AddressSpace::for_image()?;
+    space.map(mmu::text_section(), mmu::USER_EXECUTABLE)?;
+    space.map(mmu::rodata_section(), mmu::USER_READ_ONLY)?;
+    for task in tasks {
+        space.map(task.memory_range(), mmu::USER_READ_WRITE)?;
+    }
+    Ok(space)
+}
+
+/// Where `SYS_WRITE` sends its bytes
+static CONSOLE: Mutex<RefCell<Option<Uart<'static>>>> = Mutex::new(RefCell::new(None));
+
+/// Send `SYS_WRITE` output to this UART, instead of defmt
+pub fn set_console(uart: Uart<'static>) {
+    critical_section::with(|cs| CONSOLE.borrow_ref_mut(cs).replace(uart));
+}
+
+/// The mailbox of the task that is running, or null if no task is running
+static CURRENT_MAILBOX: AtomicPtr<Mailbox> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Where the task that just yielded wants to carry on from
+static YIELDED_AT: Mutex<Cell<Option<UserState>>> = Mutex::new(Cell::new(None));
+
+/// Why we came back from EL0
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Stop {
+    Exited(i32),
+    Yielded,
+    Faulted,
+}
+
+impl Stop {
+    /// Pack into a value `__leave_user` can hand back
+    fn to_raw(self) -> u64 {
+        match self {
+            Stop::Exited(code) => code as u32 as u64,
+            Stop::Yielded => 1 << 32,
+            Stop::Faulted => 2 << 32,
+        }
+    }
+
+    /// Unpack what `__enter_user` returned
+    fn from_raw(value: u64) -> Stop {
+        match value >> 32 {
+            0 => Stop::Exited(value as u32 as i32),
+            1 => Stop::Yielded,
+            _ => Stop::Faulted,
+        }
+    }
+}
+
+/// The kernel's registers, saved while a task runs
+///
+/// `__enter_user` and `__leave_user` rely on this layout.
+#[repr(C)]
+struct KernelContext {
+    /// x19 to x30
+    x: [u64; 12],
+    sp: u64,
+    daif: u64,
+    /// d8 to d15
+    d: [u64; 8],
+}
+
+/// Where we save the kernel's registers while a task runs
+static mut KERNEL_CONTEXT: KernelContext = KernelContext {
+    x: [0; 12],
+    sp: 0,
+    daif: 0,
+    d: [0; 8],
+};
+
+extern "C" {
+    /// Save the kernel's registers, and jump to EL0
+    ///
+    /// Returns the value passed to `__leave_user`.
+    fn __enter_user(kernel: *mut KernelContext, user: *const UserState) -> u64;
+
+    /// Restore the kernel's registers, and return from `__enter_user`
+    fn __leave_user(kernel: *const KernelContext, value: u64) -> !;
+
+    /// Save the callee-saved registers and execute `svc #0`
+    fn __user_syscall();
+}
+
+/// Run the tasks until they have all exited or faulted
+///
+/// Each task runs until it makes a system call which gives up its turn -
+/// [`SYS_YIELD`] or [`SYS_EXIT`] - or until it faults. Then the next task gets
+/// a turn.
+///
+/// Activate the [`address_space`] for these tasks first.
+pub fn run(tasks: &mut [Task]) {
+    assert!(
+        stack_pointer_select() == 1,
+        "the kernel must run on SP_EL1, because tasks use SP_EL0"
+    );
+    while tasks.iter().any(|task| task.status == Status::Ready) {
+        for task in tasks.iter_mut() {
+            if task.status != Status::Ready {
+                continue;
+            }
+            CURRENT_MAILBOX.store(&raw mut task.memory.mailbox, Ordering::Relaxed);
+            // Safety: only `run` uses the kernel context, and the task only
+            // has access to what the address space lets it use
+            let value = unsafe { __enter_user(&raw mut KERNEL_CONTEXT, &task.state) };
+            CURRENT_MAILBOX.store(core::ptr::null_mut(), Ordering::Relaxed);
+            match Stop::from_raw(value) {
+                Stop::Exited(code) => {
+                    defmt::info!("Task {=str} exited with {=i32}", task.name, code);
+                    task.status = Status::Exited(code);
+                }
+                Stop::Yielded => {
+                    let state = critical_section::with(|cs| YIELDED_AT.borrow(cs).take());
+                    task.state = state.expect("yielded without saving state");
+                }
+                Stop::Faulted => {
+                    defmt::error!("Task {=str} faulted, and was stopped", task.name);
+                    task.status = Status::Faulted;
+                }
+            }
+        }
+    }
+}
+
+/// Read `SPSel`, which says which stack pointer EL1 is using
+fn stack_pointer_select() -> u64 {
+    let r: u64;
+    // Safety: We only read a register here, which has no side-effects
+    unsafe {
+        core::arch::asm!("mrs {0}, SPSel", out(reg) r, options(nomem, nostack, preserves_flags))
+    };
+    r
+}
+
+/// Go back to [`run`], abandoning the exception handler we're in
+fn leave(stop: Stop) -> ! {
+    // Safety: we only get here from an exception taken from EL0, which means
+    // `run` called `__enter_user` and filled in the kernel context
+    unsafe { __leave_user(&raw const KERNEL_CONTEXT, stop.to_raw()) }
+}
+
+/// Handle a synchronous exception from EL0
+pub(crate) fn handle_exception(elr: u64, spsr: u64) {
+    let syndrome = Syndrome::read(elr, spsr);
+    let mailbox = CURRENT_MAILBOX.load(Ordering::Relaxed);
+    if mailbox.is_null() {
+        // We don't know how we got to EL0, so we can't go back to `run`
+        exception::report(Origin::LowerEl, elr, spsr);
+    }
+    if syndrome.class() != ExceptionClass::Svc {
+        exception::log(Origin::LowerEl, &syndrome);
+        leave(Stop::Faulted);
+    }
+    // Safety: the mailbox is in the task's memory, which is still there. The
+    // task can't run while we're in here.
+    let mailbox = unsafe { &mut *mailbox };
+    let args = mailbox.args;
+    mailbox.result = match mailbox.number {
+        SYS_WRITE => sys_write(args[0] as usize, args[1] as usize),
+        SYS_EXIT => leave(Stop::Exited(args[0] as i32)),
+        SYS_YIELD => {
+            mailbox.result = 0;
+            let state = UserState {
+                pc: elr,
+                sp: user_stack_pointer(),
+                spsr,
+                x0: 0,
+            };
+            critical_section::with(|cs| YIELDED_AT.borrow(cs).set(Some(state)));
+            leave(Stop::Yielded);
+        }
+        SYS_GET_TIME => timer::ticks_to_hz(timer::count(Counter::Physical), 1_000_000) as i64,
+        _ => ENOSYS,
+    };
+}
+
+/// Read `SP_EL0`, which is the task's stack pointer
+fn user_stack_pointer() -> u64 {
+    let r: u64;
+    // Safety: We only read a register here, which has no side-effects
+    unsafe {
+        core::arch::asm!("mrs {0}, SP_EL0", out(reg) r, options(nomem, nostack, preserves_flags))
+    };
+    r
+}
+
+/// Can EL0 read the byte at `addr`?
+///
+/// We ask the MMU to translate the address as if EL0 was reading it.
+fn user_can_read(addr: usize) -> bool {
+    let par: u64;
+    // Safety: address translation only writes to PAR_EL1
+    unsafe {
+        core::arch::asm!(
+            "at s1e0r, {addr}",
+            "isb",
+            "mrs {par}, PAR_EL1",
+            addr = in(reg) addr,
+            par = out(reg) par,
+            options(nostack, preserves_flags)
+        );
+    }
+    // PAR_EL1.F is set if the translation failed
+    par & 1 == 0
+}
+
+/// Handle [`SYS_WRITE`]
+fn sys_write(ptr: usize, len: usize) -> i64 {
+    if len == 0 {
+        return 0;
+    }
+    let Some(end) = ptr.checked_add(len) else {
+        return EFAULT;
+    };
+    let mut page = mmu::page_align_down(ptr);
+    while page < end {
+        if !user_can_read(page) {
+            return EFAULT;
+        }
+        page += mmu::PAGE_SIZE;
+    }
+    // Safety: we just checked the task can read all of this, so it's mapped,
+    // and the task can't change it while we're in here
+    let bytes = unsafe { core::slice::from_raw_parts(ptr as *const u8, len) };
+    critical_section::with(|cs| match CONSOLE.borrow_ref_mut(cs).as_mut() {
+        Some(uart) => {
+            for byte in bytes {
+                uart.write_word(*byte);
+            }
+        }
+        None => defmt::println!("{=[u8]:a}", bytes),
+    });
+    len as i64
+}
+
+// Switching between EL1 and EL0. See `KernelContext` and `UserState` for the
+// layouts.
+core::arch::global_asm!(
+    r#"
+    .section .text.__enter_user, "ax"
+    .global __enter_user
+    .type __enter_user, %function
+    __enter_user:
+        stp x19, x20, [x0, #0]
+        stp x21, x22, [x0, #16]
+        stp x23, x24, [x0, #32]
+        stp x25, x26, [x0, #48]
+        stp x27, x28, [x0, #64]
+        stp x29, x30, [x0, #80]
+        mov x9, sp
+        mrs x10, DAIF
+        stp x9, x10, [x0, #96]
+        stp d8, d9, [x0, #112]
+        stp d10, d11, [x0, #128]
+        stp d12, d13, [x0, #144]
+        stp d14, d15, [x0, #160]
+        ldp x9, x10, [x1, #0]
+        ldp x11, x0, [x1, #16]
+        msr ELR_EL1, x9
+        msr SP_EL0, x10
+        msr SPSR_EL1, x11
+        // Don't leak kernel values to the task
+        mov x1, xzr
+        mov x2, xzr
+        mov x3, xzr
+        mov x4, xzr
+        mov x5, xzr
+        mov x6, xzr
+        mov x7, xzr
+        mov x8, xzr
+        mov x9, xzr
+        mov x10, xzr
+        mov x11, xzr
+        mov x12, xzr
+        mov x13, xzr
+        mov x14, xzr
+        mov x15, xzr
+        mov x16, xzr
+        mov x17, xzr
+        mov x18, xzr
+        mov x19, xzr
+        mov x20, xzr
+        mov x21, xzr
+        mov x22, xzr
+        mov x23, xzr
+        mov x24, xzr
+        mov x25, xzr
+        mov x26, xzr
+        mov x27, xzr
+        mov x28, xzr
+        mov x29, xzr
+        mov x30, xzr
+        eret
+
+    .section .text.__leave_user, "ax"
+    .global __leave_user
+    .type __leave_user, %function
+    __leave_user:
+        ldp x19, x20, [x0, #0]
+        ldp x21, x22, [x0, #16]
+        ldp x23, x24, [x0, #32]
+        ldp x25, x26, [x0, #48]
+        ldp x27, x28, [x0, #64]
+        ldp x29, x30, [x0, #80]
+        ldp x9, x10, [x0, #96]
+        mov sp, x9
+        ldp d8, d9, [x0, #112]
+        ldp d10, d11, [x0, #128]
+        ldp d12, d13, [x0, #144]
+        ldp d14, d15, [x0, #160]
+        msr DAIF, x10
+        mov x0, x1
+        ret
+
+    .section .text.__user_syscall, "ax"
+    .global __user_syscall
+    .type __user_syscall, %function
+    __user_syscall:
+        stp x29, x30, [sp, #-160]!
+        stp x19, x20, [sp, #16]
+        stp x21, x22, [sp, #32]
+        stp x23, x24, [sp, #48]
+        stp x25, x26, [sp, #64]
+        stp x27, x28, [sp, #80]
+        stp d8, d9, [sp, #96]
+        stp d10, d11, [sp, #112]
+        stp d12, d13, [sp, #128]
+        stp d14, d15, [sp, #144]
+        svc #0
+        ldp x19, x20, [sp, #16]
+        ldp x21, x22, [sp, #32]
+        ldp x23, x24, [sp, #48]
+        ldp x25, x26, [sp, #64]
+        ldp x27, x28, [sp, #80]
+        ldp d8, d9, [sp, #96]
+        ldp d10, d11, [sp, #112]
+        ldp d12, d13, [sp, #128]
+        ldp d14, d15, [sp, #144]
+        ldp x29, x30, [sp], #160
+        ret
+    "#,
+);
+
+// End of file
@@ -0,0 +1,99 @@
+//! A virtio block device
+//!
+//! Give QEMU a disk image with `-drive file=disk.img,if=none,format=raw,id=hd0
+//! -device virtio-blk-device,drive=hd0` (or `cargo run -- --drive disk.img`)
+//! and it appears as a virtio-blk device. [`VirtioBlk`] wraps the driver from
+//! `virtio-drivers` with the same sector-based interface as
+//! `qemu_common::host_disk::HostDisk`.
+
+use virtio_drivers::{
+    device::blk::VirtIOBlk,
+    transport::{mmio::MmioTransport, DeviceType},
+};
+
+use super::{hal::VirtioHal, Error};
+
+/// The size of a disk sector, in bytes
+pub const SECTOR_SIZE: usize = virtio_drivers::device::blk::SECTOR_SIZE;
+
+/// A virtio-blk disk
+pub struct VirtioBlk {
+    inner: VirtIOBlk<VirtioHal, MmioTransport>,
+}
+
+impl VirtioBlk {
+    /// Find the first virtio-blk device, and start a driver for it
+    ///
+    /// # Safety
+    ///
+    /// There must not be another driver for the same device.
+    pub unsafe fn find() -> Result<VirtioBlk, Error> {
+        let device = super::find(DeviceType::Block).ok_or(Error::NotFound)?;
+        defmt::debug!("Found {}", device);
+        // Safety: our caller says there's no other driver
+        let transport = unsafe { device.transport() };
+        Self::new(transport)
+    }
+
+    /// Start a driver on a virtio-blk transport
+    pub fn new(transport: MmioTransport) -> Result<VirtioBlk, Error> {
+        let inner = VirtIOBlk::new(transport)?;
+        defmt::debug!(
+            "virtio-blk has {=u64} sectors{}",
+            inner.capacity(),
+            if inner.readonly() { " (read-only)" } else { "" }
+        );
+        Ok(VirtioBlk { inner })
+    }
+
+    /// The size of the disk, in bytes
+    pub fn len(&self) -> u64 {
+        self.inner.capacity() * SECTOR_SIZE as u64
+    }
+
+    /// Is the disk empty?
+    pub fn is_empty(&self) -> bool {
+        self.inner.capacity() == 0
+    }
+
+    /// The number of whole sectors on the disk
+    pub fn sector_count(&self) -> u32 {
+        u32::try_from(self.inner.capacity()).unwrap_or(u32::MAX)
+    }
+
+    /// Is the disk read-only?
+    pub fn is_read_only(&self) -> bool {
+        self.inner.readonly()
+    }
+
+    /// Read whole sectors, starting at sector `first`
+    pub fn read_sectors(&mut self, first: u32, buffer: &mut [u8]) -> Result<(), Error> {
+        self.check(first, buffer.len())?;
+        self.inner.read_blocks(first as usize, buffer)?;
+        Ok(())
+    }
+
+    /// Write whole sectors, starting at sector `first`
+    pub fn write_sectors(&mut self, first: u32, buffer: &[u8]) -> Result<(), Error> {
+        if self.inner.readonly() {
+            return Err(Error::ReadOnly);
+        }
+        self.check(first, buffer.len())?;
+        self.inner.write_blocks(first as usize, buffer)?;
+        Ok(())
+    }
+
+    /// Check an access is a whole number of sectors, and is on the disk
+    fn check(&self, first: u32, len: usize) -> Result<(), Error> {
+        if !len.is_multiple_of(SECTOR_SIZE) {
+            return Err(Error::NotSectorSized);
+        }
+        let end = u64::from(first) + (len / SECTOR_SIZE) as u64;
+        if end > self.inner.capacity() {
+            return Err(Error::OutOfRange);
+        }
+        Ok(())
+    }
+}
+
+// End of file
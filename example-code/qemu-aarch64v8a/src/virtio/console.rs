@@ -0,0 +1,65 @@
+//! A virtio console
+//!
+//! Give QEMU `-device virtio-serial-device -device virtconsole,chardev=<id>`
+//! (or `cargo run -- --virtio-console`) and it appears as a virtio console
+//! device. It's a second serial port, which doesn't need a baud rate.
+//! [`VirtioConsole`] wraps the driver from `virtio-drivers`, and implements
+//! [`core::fmt::Write`].
+//!
+//! The driver allocates its buffers on the heap, so call
+//! [`GrowableHeap::init`](crate::heap::GrowableHeap::init) on
+//! [`crate::heap::HEAP`] before you start it.
+
+use virtio_drivers::{
+    device::console::VirtIOConsole,
+    transport::{mmio::MmioTransport, DeviceType},
+};
+
+use super::{hal::VirtioHal, Error};
+
+/// A virtio console
+pub struct VirtioConsole {
+    inner: VirtIOConsole<VirtioHal, MmioTransport>,
+}
+
+impl VirtioConsole {
+    /// Find the first virtio console device, and start a driver for it
+    ///
+    /// # Safety
+    ///
+    /// There must not be another driver for the same device.
+    pub unsafe fn find() -> Result<VirtioConsole, Error> {
+        let device = super::find(DeviceType::Console).ok_or(Error::NotFound)?;
+        defmt::debug!("Found {}", device);
+        // Safety: our caller says there's no other driver
+        let transport = unsafe { device.transport() };
+        Self::new(transport)
+    }
+
+    /// Start a driver on a virtio console transport
+    pub fn new(transport: MmioTransport) -> Result<VirtioConsole, Error> {
+        let inner = VirtIOConsole::new(transport)?;
+        Ok(VirtioConsole { inner })
+    }
+
+    /// Send some bytes
+    pub fn write(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for b in bytes {
+            self.inner.send(*b)?;
+        }
+        Ok(())
+    }
+
+    /// Take a received byte, if there is one
+    pub fn read_byte(&mut self) -> Result<Option<u8>, Error> {
+        Ok(self.inner.recv(true)?)
+    }
+}
+
+impl core::fmt::Write for VirtioConsole {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
+// End of file
@@ -0,0 +1,112 @@
+//! The memory services `virtio-drivers` needs from us
+//!
+//! Our page tables are an identity map, so a physical address is the same as
+//! a virtual address and sharing a buffer with a device is free. QEMU's DMA is
+//! coherent with the CPU's caches, so we don't need any cache maintenance
+//! either.
+//!
+//! The virtqueues live in a fixed pool of pages in `.bss`, [`DMA_PAGES`]
+//! long, handed out first-fit.
+
+use core::{
+    cell::{Cell, UnsafeCell},
+    ptr::NonNull,
+};
+
+use critical_section::Mutex;
+use virtio_drivers::{BufferDirection, Hal, PhysAddr, PAGE_SIZE};
+
+/// How many pages of memory we can give to virtio devices
+pub const DMA_PAGES: usize = 32;
+
+/// The memory we give to virtio devices
+#[repr(C, align(4096))]
+struct DmaPool(UnsafeCell<[u8; DMA_PAGES * PAGE_SIZE]>);
+
+// Safety: `ALLOCATED` makes sure each page only has one owner
+unsafe impl Sync for DmaPool {}
+
+static POOL: DmaPool = DmaPool(UnsafeCell::new([0; DMA_PAGES * PAGE_SIZE]));
+
+/// Which pages of [`POOL`] are in use, one bit per page
+static ALLOCATED: Mutex<Cell<u32>> = Mutex::new(Cell::new(0));
+
+// One bit for each page
+const _: () = assert!(DMA_PAGES <= u32::BITS as usize);
+
+/// Our implementation of the [`Hal`] trait from `virtio-drivers`
+pub struct VirtioHal;
+
+impl VirtioHal {
+    /// How many pages of the DMA pool are in use
+    pub fn pages_in_use() -> usize {
+        critical_section::with(|cs| ALLOCATED.borrow(cs).get().count_ones() as usize)
+    }
+}
+
+/// A mask with `pages` bits set, starting at bit `first`
+fn page_mask(first: usize, pages: usize) -> u32 {
+    let bits = if pages >= 32 {
+        u32::MAX
+    } else {
+        (1 << pages) - 1
+    };
+    bits << first
+}
+
+// Safety: we give out each page to one owner at a time, zeroed, and an
+// identity map means the addresses we give out are right
+unsafe impl Hal for VirtioHal {
+    fn dma_alloc(pages: usize, _direction: BufferDirection) -> (PhysAddr, NonNull<u8>) {
+        assert!(
+            pages > 0 && pages <= DMA_PAGES,
+            "Can't allocate {} DMA pages",
+            pages
+        );
+        let first = critical_section::with(|cs| {
+            let allocated = ALLOCATED.borrow(cs);
+            let first = (0..=DMA_PAGES - pages)
+                .find(|&first| allocated.get() & page_mask(first, pages) == 0)?;
+            allocated.set(allocated.get() | page_mask(first, pages));
+            Some(first)
+        });
+        let Some(first) = first else {
+            panic!("Out of virtio DMA memory, wanted {} pages", pages);
+        };
+        let start = POOL.0.get().cast::<u8>().wrapping_add(first * PAGE_SIZE);
+        // Safety: these pages are ours now, and they're in `POOL`
+        unsafe { start.write_bytes(0, pages * PAGE_SIZE) };
+        defmt::trace!(
+            "virtio DMA alloc {=usize} pages at {=usize:#x}",
+            pages,
+            start as usize
+        );
+        (start as PhysAddr, NonNull::new(start).unwrap())
+    }
+
+    unsafe fn dma_dealloc(_paddr: PhysAddr, vaddr: NonNull<u8>, pages: usize) -> i32 {
+        let first = (vaddr.as_ptr() as usize - POOL.0.get() as usize) / PAGE_SIZE;
+        defmt::trace!(
+            "virtio DMA free {=usize} pages at {=usize:#x}",
+            pages,
+            vaddr.as_ptr() as usize
+        );
+        critical_section::with(|cs| {
+            let allocated = ALLOCATED.borrow(cs);
+            allocated.set(allocated.get() & !page_mask(first, pages));
+        });
+        0
+    }
+
+    unsafe fn mmio_phys_to_virt(paddr: PhysAddr, _size: usize) -> NonNull<u8> {
+        NonNull::new(paddr as *mut u8).unwrap()
+    }
+
+    unsafe fn share(buffer: NonNull<[u8]>, _direction: BufferDirection) -> PhysAddr {
+        buffer.as_ptr().cast::<u8>() as PhysAddr
+    }
+
+    unsafe fn unshare(_paddr: PhysAddr, _buffer: NonNull<[u8]>, _direction: BufferDirection) {}
+}
+
+// End of file
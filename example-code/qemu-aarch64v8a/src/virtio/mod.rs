@@ -0,0 +1,152 @@
+//! Support for virtio devices on the QEMU `virt` machine
+//!
+//! virtio is a standard interface for virtual devices - block devices,
+//! consoles, network cards and so on. QEMU's `virt` machine has 32
+//! *virtio-mmio transports*, each a small block of registers at
+//! `0x0a00_0000 + (n * 0x200)`, wired to SPI `16 + n` on the GIC. A transport
+//! with no device behind it reads back a Device ID of zero. QEMU fills the
+//! transports in from the top, so the first `-device` on the command line is
+//! in slot 31.
+//!
+//! [`devices`] finds every transport with a device behind it. The drivers
+//! themselves come from the `virtio-drivers` crate: [`blk`] and `console`
+//! wrap its block and console drivers. They poll the device, so you don't
+//! need to set up the GIC. The console driver needs a heap, so `console` is
+//! only there with the `virtio-console` feature.
+//!
+//! Devices read and write our memory directly (DMA), so `virtio-drivers` asks
+//! us for memory through the [`hal::VirtioHal`] trait implementation.
+
+use core::ptr::NonNull;
+
+use virtio_drivers::transport::{
+    mmio::{MmioTransport, VirtIOHeader},
+    DeviceType, Transport,
+};
+
+pub mod blk;
+#[cfg(feature = "virtio-console")]
+pub mod console;
+pub mod hal;
+
+/// Base address of the first virtio-mmio transport on the QEMU `virt` machine
+pub const VIRTIO_MMIO_ADDRESS: usize = 0x0a00_0000;
+
+/// The size of each virtio-mmio transport's registers
+pub const VIRTIO_MMIO_SIZE: usize = 0x200;
+
+/// How many virtio-mmio transports the QEMU `virt` machine has
+pub const VIRTIO_MMIO_SLOTS: usize = 32;
+
+/// The GIC Interrupt ID of the first virtio-mmio transport (SPI 16)
+pub const VIRTIO_MMIO_INTID: u32 = 32 + 16;
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// There's no device of that type
+    NotFound,
+    /// The access goes past the end of the disk.
+    OutOfRange,
+    /// The buffer is not a whole number of sectors.
+    NotSectorSized,
+    /// The disk is read-only.
+    ReadOnly,
+    /// The device reported an I/O error
+    Io,
+    /// The driver couldn't talk to the device
+    Driver,
+}
+
+impl From<virtio_drivers::Error> for Error {
+    fn from(error: virtio_drivers::Error) -> Error {
+        match error {
+            virtio_drivers::Error::IoError => Error::Io,
+            other => {
+                defmt::warn!("virtio error: {}", defmt::Debug2Format(&other));
+                Error::Driver
+            }
+        }
+    }
+}
+
+/// A virtio-mmio transport with a device behind it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Device {
+    /// Which transport it is, from 0 to 31
+    pub slot: usize,
+    /// What sort of device it is
+    pub device_type: DeviceType,
+}
+
+impl Device {
+    /// The address of the transport's registers
+    pub fn address(&self) -> usize {
+        VIRTIO_MMIO_ADDRESS + self.slot * VIRTIO_MMIO_SIZE
+    }
+
+    /// The GIC Interrupt ID the transport raises
+    pub fn intid(&self) -> u32 {
+        VIRTIO_MMIO_INTID + self.slot as u32
+    }
+
+    /// Get the transport, to hand to a driver
+    ///
+    /// # Safety
+    ///
+    /// Only one transport (and so one driver) may exist for each device at a
+    /// time.
+    pub unsafe fn transport(&self) -> MmioTransport {
+        // Safety: we found a device here, and our caller says nothing else is
+        // using it
+        unsafe { open(self.slot) }.expect("virtio device went away")
+    }
+}
+
+impl defmt::Format for Device {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "virtio {} at {=usize:#010x} (slot {=usize}, INTID {=u32})",
+            defmt::Debug2Format(&self.device_type),
+            self.address(),
+            self.slot,
+            self.intid()
+        );
+    }
+}
+
+/// Open the transport in a slot, if there is a device behind it
+///
+/// # Safety
+///
+/// Only one transport may exist for each slot at a time.
+unsafe fn open(slot: usize) -> Option<MmioTransport> {
+    let address = VIRTIO_MMIO_ADDRESS + slot * VIRTIO_MMIO_SIZE;
+    let header = NonNull::new(address as *mut VirtIOHeader)?;
+    // Safety: this is a virtio-mmio transport on the `virt` machine, which our
+    // identity map covers, and our caller says nothing else is using it
+    unsafe { MmioTransport::new(header, VIRTIO_MMIO_SIZE) }.ok()
+}
+
+/// Find every virtio-mmio transport with a device behind it
+///
+/// They come out in slot order, which is the reverse of the order they were
+/// given to QEMU.
+pub fn devices() -> impl Iterator<Item = Device> {
+    (0..VIRTIO_MMIO_SLOTS).filter_map(|slot| {
+        // Safety: we drop the transport straight away, and only read from it
+        let transport = unsafe { open(slot) }?;
+        Some(Device {
+            slot,
+            device_type: transport.device_type(),
+        })
+    })
+}
+
+/// Find the first device of a given type
+pub fn find(device_type: DeviceType) -> Option<Device> {
+    devices().find(|d| d.device_type == device_type)
+}
+
+// End of file
@@ -0,0 +1,76 @@
+//! On-target tests for finding our hardware
+//!
+//! The test harness doesn't see the device tree pointer QEMU gives `main`, so
+//! we check the defaults and how bad pointers are rejected. Run with
+//! `cargo test --test devicetree`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch64v8a::{
+    devicetree::{self, Error, Platform, Source},
+    gic, psci,
+    timer::{self, Counter},
+};
+
+/// Some RAM that doesn't hold a device tree
+#[repr(align(8))]
+struct NotABlob([u8; 64]);
+
+static NOT_A_BLOB: NotABlob = NotABlob([0; 64]);
+
+qemu_aarch64v8a::test_suite! {
+    fn defaults_match_the_virt_machine(_: &mut ()) {
+        let platform = Platform::qemu_virt();
+        assert_eq!(platform.source, Source::Defaults);
+        assert_eq!(platform.uart, 0x0900_0000);
+        assert_eq!(platform.uart_intid, 33);
+        assert_eq!(platform.gic.distributor, gic::GICD_ADDRESS);
+        assert_eq!(platform.gic.version, gic::detect_version());
+        assert_eq!(platform.virtual_timer_intid, timer::VIRTUAL_TIMER_INTID);
+        assert_eq!(platform.psci, psci::Conduit::Hvc);
+        assert_eq!(platform.ram_start, devicetree::RAM_ADDRESS);
+    }
+
+    fn rejects_a_null_pointer(_: &mut ()) {
+        // Safety: a null pointer is never read
+        assert_eq!(unsafe { Platform::from_dtb(0) }, Err(Error::NoPointer));
+    }
+
+    fn rejects_a_pointer_outside_ram(_: &mut ()) {
+        // Safety: a pointer outside RAM is never read
+        assert_eq!(unsafe { Platform::from_dtb(0x0900_0000) }, Err(Error::NotInRam));
+    }
+
+    fn rejects_something_else(_: &mut ()) {
+        let address = NOT_A_BLOB.0.as_ptr() as usize;
+        // Safety: the header fits in `NOT_A_BLOB`, and it's not valid, so
+        // nothing past it is read
+        assert_eq!(unsafe { Platform::from_dtb(address) }, Err(Error::Invalid));
+    }
+
+    fn falls_back_to_the_defaults(_: &mut ()) {
+        assert_eq!(Platform::discover(0), Platform::qemu_virt());
+    }
+
+    fn discovery_sets_psci_and_the_timer_interrupts(_: &mut ()) {
+        psci::set_conduit(psci::Conduit::Smc);
+        timer::set_intids(0, 0);
+        let platform = Platform::discover(0);
+        assert_eq!(psci::conduit(), platform.psci);
+        assert_eq!(timer::intid(Counter::Physical), platform.physical_timer_intid);
+        assert_eq!(timer::intid(Counter::Virtual), platform.virtual_timer_intid);
+    }
+
+    fn decodes_interrupt_specifiers(_: &mut ()) {
+        // The PL011 is `<GIC_SPI 1 IRQ_TYPE_LEVEL_HIGH>`
+        assert_eq!(devicetree::gic_intid(0, 1), Some(33));
+        // The virtual timer is `<GIC_PPI 11 ...>`
+        assert_eq!(devicetree::gic_intid(1, 11), Some(timer::VIRTUAL_TIMER_INTID));
+        assert_eq!(devicetree::gic_intid(2, 0), None);
+    }
+}
+
+// End of file
@@ -0,0 +1,70 @@
+//! On-target tests for the synchronous exception decoding
+//!
+//! Run with `cargo test --test exception`. We can't come back from a real
+//! fault, so these decode syndrome values we made earlier.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch64v8a::exception::{ExceptionClass, FaultStatus, Syndrome};
+
+/// Make a syndrome from an EL1h exception with the given ESR and FAR
+fn syndrome(esr: u64, far: u64) -> Syndrome {
+    Syndrome {
+        esr,
+        far,
+        elr: 0x4008_0000,
+        spsr: 0x3C5,
+    }
+}
+
+qemu_aarch64v8a::test_suite! {
+    fn decodes_a_translation_fault_on_read(_: &mut ()) {
+        let s = syndrome(0x9600_0005, 0x1_0000_0000);
+        assert_eq!(s.class(), ExceptionClass::DataAbort);
+        assert_eq!(s.fault_status(), Some(FaultStatus::Translation(1)));
+        assert_eq!(s.fault_address(), Some(0x1_0000_0000));
+        assert!(!s.is_write());
+        assert!(s.is_32_bit_instruction());
+        assert_eq!(s.mode(), "EL1h");
+    }
+
+    fn decodes_a_permission_fault_on_write(_: &mut ()) {
+        let s = syndrome(0x9600_004F, 0x4000_1000);
+        assert_eq!(s.fault_status(), Some(FaultStatus::Permission(3)));
+        assert!(s.is_write());
+    }
+
+    fn ignores_an_invalid_far(_: &mut ()) {
+        let s = syndrome(0x9600_0410, 0xDEAD_BEEF);
+        assert_eq!(s.fault_status(), Some(FaultStatus::SynchronousExternal));
+        assert_eq!(s.fault_address(), None);
+    }
+
+    fn decodes_an_alignment_fault(_: &mut ()) {
+        let s = syndrome(0x9600_0021, 0x4000_0001);
+        assert_eq!(s.fault_status(), Some(FaultStatus::Alignment));
+        assert_eq!(s.fault_status().unwrap().level(), None);
+    }
+
+    fn decodes_svc_and_brk_immediates(_: &mut ()) {
+        let svc = syndrome(0x5600_0042, 0);
+        assert_eq!(svc.class(), ExceptionClass::Svc);
+        assert_eq!(svc.immediate(), Some(0x42));
+        assert_eq!(svc.fault_status(), None);
+        let brk = syndrome(0xF200_1234, 0);
+        assert_eq!(brk.class(), ExceptionClass::Brk);
+        assert_eq!(brk.immediate(), Some(0x1234));
+    }
+
+    fn decodes_an_undefined_instruction(_: &mut ()) {
+        let s = syndrome(0x0200_0000, 0);
+        assert_eq!(s.class(), ExceptionClass::Unknown);
+        assert_eq!(s.immediate(), None);
+        assert_eq!(s.fault_address(), None);
+    }
+}
+
+// End of file
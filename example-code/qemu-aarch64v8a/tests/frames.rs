@@ -0,0 +1,116 @@
+//! On-target tests for the physical frame allocator
+//!
+//! These use their own small allocators, over made-up addresses - the
+//! allocator never touches the frames, so they don't need to exist. Run with
+//! `cargo test --test frames`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch64v8a::frames::{FrameAllocator, FRAME_SIZE};
+
+/// Where our made-up RAM starts
+const BASE: usize = 0x8000_0000;
+
+/// An allocator for 128 frames, which all start out free
+type Frames = FrameAllocator<2>;
+
+qemu_aarch64v8a::test_suite! {
+    setup: fn setup() -> Frames {
+        let mut frames = Frames::new();
+        frames.init(BASE..BASE + 128 * FRAME_SIZE);
+        frames
+    }
+
+    fn allocates_from_the_start(frames: &mut Frames) {
+        assert_eq!(frames.allocate(1), Some(BASE));
+        assert_eq!(frames.allocate(2), Some(BASE + FRAME_SIZE));
+        assert_eq!(frames.allocate(1), Some(BASE + 3 * FRAME_SIZE));
+        let stats = frames.stats();
+        assert_eq!(stats.total, 128);
+        assert_eq!(stats.used, 4);
+        assert_eq!(stats.free, 124);
+        assert_eq!(stats.allocations, 3);
+    }
+
+    fn leaves_out_partial_frames(_: &mut Frames) {
+        let mut frames = Frames::new();
+        frames.init(BASE + 1..BASE + 3 * FRAME_SIZE + 1);
+        assert_eq!(frames.stats().total, 2);
+        assert_eq!(frames.allocate(1), Some(BASE + FRAME_SIZE));
+    }
+
+    fn ignores_ram_it_has_no_room_for(_: &mut Frames) {
+        let mut frames = Frames::new();
+        frames.init(BASE..BASE + 1000 * FRAME_SIZE);
+        assert_eq!(frames.stats().total, 128);
+    }
+
+    fn skips_reserved_frames(frames: &mut Frames) {
+        frames.reserve(BASE..BASE + 70 * FRAME_SIZE + 1);
+        assert_eq!(frames.stats().reserved, 71);
+        assert_eq!(frames.allocate(1), Some(BASE + 71 * FRAME_SIZE));
+        // Reserving outside our RAM, or the same frames again, does nothing
+        frames.reserve(0..BASE + 10 * FRAME_SIZE);
+        frames.reserve(BASE + 200 * FRAME_SIZE..BASE + 300 * FRAME_SIZE);
+        assert_eq!(frames.stats().reserved, 71);
+    }
+
+    fn finds_contiguous_runs(frames: &mut Frames) {
+        let a = frames.allocate(1).unwrap();
+        let b = frames.allocate(1).unwrap();
+        frames.allocate(1).unwrap();
+        frames.free(a, 1);
+        frames.free(b, 1);
+        // The hole at the start is big enough, but we look after the last
+        // allocation first
+        assert_eq!(frames.allocate(2), Some(BASE + 3 * FRAME_SIZE));
+        assert_eq!(frames.allocate(3), Some(BASE + 5 * FRAME_SIZE));
+    }
+
+    fn wraps_around_to_freed_frames(frames: &mut Frames) {
+        assert_eq!(frames.allocate(120), Some(BASE));
+        frames.free(BASE, 4);
+        assert_eq!(frames.allocate(5), Some(BASE + 120 * FRAME_SIZE));
+        // There's no room left at the end, so we go back to the start
+        assert_eq!(frames.allocate(4), Some(BASE));
+        assert_eq!(frames.stats().free, 3);
+    }
+
+    fn counts_failures(frames: &mut Frames) {
+        assert_eq!(frames.allocate(0), None);
+        assert_eq!(frames.allocate(129), None);
+        frames.reserve(BASE + 64 * FRAME_SIZE..BASE + 65 * FRAME_SIZE);
+        assert_eq!(frames.allocate(100), None);
+        assert!(frames.allocate(64).is_some());
+        let stats = frames.stats();
+        assert_eq!(stats.failures, 3);
+        assert_eq!(stats.allocations, 1);
+    }
+
+    fn keeps_the_peak(frames: &mut Frames) {
+        let a = frames.allocate(10).unwrap();
+        frames.free(a, 10);
+        frames.allocate(3).unwrap();
+        let stats = frames.stats();
+        assert_eq!(stats.used, 3);
+        assert_eq!(stats.peak, 10);
+        assert_eq!(stats.frees, 1);
+    }
+
+    #[should_panic]
+    fn panics_on_double_free(frames: &mut Frames) {
+        let a = frames.allocate(1).unwrap();
+        frames.free(a, 1);
+        frames.free(a, 1);
+    }
+
+    #[should_panic]
+    fn panics_freeing_frames_it_does_not_manage(frames: &mut Frames) {
+        frames.free(BASE - FRAME_SIZE, 1);
+    }
+}
+
+// End of file
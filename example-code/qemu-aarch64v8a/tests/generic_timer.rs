@@ -0,0 +1,136 @@
+//! On-target tests for the generic timer driver
+//!
+//! Run with `cargo test --test generic_timer`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::{
+    future::Future,
+    pin::pin,
+    sync::atomic::{AtomicU32, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use embedded_hal::delay::DelayNs;
+use qemu_aarch64v8a::{
+    gic::{self, Gic},
+    timer::{self, Counter, GenericTimer},
+};
+
+/// How many times [`timer_handler`] has run
+static COUNT: AtomicU32 = AtomicU32::new(0);
+
+/// Called by the GIC driver when the physical timer fires
+fn timer_handler() {
+    // Safety: the test has finished with its driver by the time this runs,
+    // and we only turn the timer off
+    let mut timer = unsafe { GenericTimer::new(Counter::Physical) };
+    timer.enable(false);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Called by the GIC driver when the physical timer fires during an async delay
+fn async_handler() {
+    timer::on_interrupt(Counter::Physical);
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+qemu_aarch64v8a::test_suite! {
+    setup: fn setup() -> GenericTimer {
+        qemu_aarch64v8a::disable_interrupts();
+        COUNT.store(0, Ordering::Relaxed);
+        // Safety: the harness has finished with the last test's timer before
+        // it calls `setup` again, and `timer_handler` only makes its own while
+        // a test is waiting for it
+        let mut timer = unsafe { GenericTimer::new(Counter::Physical) };
+        timer.enable(false);
+        timer
+    }
+
+    fn has_a_sensible_frequency(_: &mut GenericTimer) {
+        let hz = timer::frequency();
+        assert!(hz >= 1_000_000, "CNTFRQ_EL0 is only {} Hz", hz);
+    }
+
+    fn converts_between_rates(_: &mut GenericTimer) {
+        let hz = u64::from(timer::frequency());
+        assert_eq!(timer::ticks_to_hz(hz, 1_000), 1_000);
+        assert_eq!(timer::hz_to_ticks(1_000, 1_000), hz);
+        // Rounds up, so a delay is never too short
+        assert_eq!(timer::hz_to_ticks(1, 1_000_000_000), 1);
+    }
+
+    fn counts_up(timer: &mut GenericTimer) {
+        let before = timer.now();
+        let after = timer.now();
+        assert!(after >= before);
+    }
+
+    #[timeout(1000)]
+    fn delay_waits_long_enough(timer: &mut GenericTimer) {
+        let start = timer.now();
+        timer.delay_ms(10);
+        let elapsed = timer.now() - start;
+        assert!(timer::ticks_to_hz(elapsed, 1_000) >= 10);
+    }
+
+    fn expires_at_the_compare_value(timer: &mut GenericTimer) {
+        timer.enable_interrupt(false);
+        timer.set_countdown(timer::hz_to_ticks(1, 1_000));
+        timer.enable(true);
+        assert!(!timer.is_expired());
+        timer.delay_ms(2);
+        assert!(timer.is_expired());
+        timer.enable(false);
+        assert!(!timer.is_expired());
+    }
+
+    #[timeout(1000)]
+    fn raises_an_interrupt(timer: &mut GenericTimer) {
+        // Safety: the harness has initialised the GIC (and doing it again
+        // here would turn off the interrupt it checks our timeout with), and
+        // its own driver only touches the virtual timer's interrupt
+        let mut gic = unsafe { Gic::new_virt() }.unwrap();
+        gic.register(timer.intid(), gic::DEFAULT_PRIORITY, timer_handler)
+            .unwrap();
+        timer.set_countdown(timer::hz_to_ticks(1, 1_000));
+        timer.enable_interrupt(true);
+        timer.enable(true);
+        // Safety: our handler only touches the timer and an atomic
+        unsafe { qemu_aarch64v8a::enable_interrupts() };
+        timer.delay_ms(5);
+        qemu_aarch64v8a::disable_interrupts();
+        assert_eq!(COUNT.load(Ordering::Relaxed), 1);
+        gic.unregister(timer.intid()).unwrap();
+    }
+
+    #[timeout(1000)]
+    fn async_delay_waits_for_the_interrupt(timer: &mut GenericTimer) {
+        // Safety: the harness has initialised the GIC, and its own driver
+        // only touches the virtual timer's interrupt
+        let mut gic = unsafe { Gic::new_virt() }.unwrap();
+        gic.register(timer.intid(), gic::DEFAULT_PRIORITY, async_handler)
+            .unwrap();
+        let start = timer.now();
+        let mut cx = Context::from_waker(Waker::noop());
+        // Safety: our handler only touches the timer and an atomic
+        unsafe { qemu_aarch64v8a::enable_interrupts() };
+        {
+            let mut delay = pin!(embedded_hal_async::delay::DelayNs::delay_ms(timer, 5));
+            assert_eq!(delay.as_mut().poll(&mut cx), Poll::Pending);
+            while COUNT.load(Ordering::Relaxed) == 0 {
+                core::hint::spin_loop();
+            }
+            assert_eq!(delay.as_mut().poll(&mut cx), Poll::Ready(()));
+        }
+        qemu_aarch64v8a::disable_interrupts();
+        assert!(timer::ticks_to_hz(timer.now() - start, 1_000) >= 5);
+        assert_eq!(COUNT.load(Ordering::Relaxed), 1);
+        gic.unregister(timer.intid()).unwrap();
+    }
+}
+
+// End of file
@@ -0,0 +1,99 @@
+//! On-target tests for the GIC driver and IRQ dispatch
+//!
+//! Run with `cargo test --test gic`. They pass with either a GICv2 or (with
+//! `--machine virt,gic-version=3`) a GICv3.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use qemu_aarch64v8a::gic::{self, Error, Gic, Trigger};
+
+/// An SPI which nothing on QEMU `virt` is wired to
+const TEST_INTID: u32 = 32 + 100;
+
+/// How many times [`count_handler`] has run
+static COUNT: AtomicU32 = AtomicU32::new(0);
+
+fn count_handler() {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Spin until `COUNT` reaches `expected`, or we give up
+fn wait_for_count(expected: u32) -> u32 {
+    for _ in 0..100_000 {
+        if COUNT.load(Ordering::Relaxed) >= expected {
+            break;
+        }
+        core::hint::spin_loop();
+    }
+    COUNT.load(Ordering::Relaxed)
+}
+
+qemu_aarch64v8a::test_suite! {
+    setup: fn setup() -> Gic {
+        qemu_aarch64v8a::disable_interrupts();
+        COUNT.store(0, Ordering::Relaxed);
+        // Safety: the harness has finished with the last test's GIC driver
+        // before it calls `setup` again, and the tests don't make their own
+        let mut gic = unsafe { Gic::new_virt() }.unwrap();
+        gic.init();
+        gic
+    }
+
+    fn finds_the_right_version(gic: &mut Gic) {
+        assert_eq!(gic.version(), gic::detect_version());
+        assert!(gic.num_intids() as usize >= gic::NUM_INTIDS);
+    }
+
+    fn rejects_out_of_range_intids(gic: &mut Gic) {
+        assert_eq!(gic.enable_interrupt(5000, true), Err(Error::InvalidIntId));
+        assert_eq!(gic.register(1020, 0, count_handler), Err(Error::InvalidIntId));
+    }
+
+    fn sgis_are_always_edge_triggered(gic: &mut Gic) {
+        assert_eq!(gic.set_trigger(3, Trigger::Level), Err(Error::CannotConfigure));
+    }
+
+    fn register_enables_the_interrupt(gic: &mut Gic) {
+        assert!(!gic.is_enabled(TEST_INTID).unwrap());
+        gic.register(TEST_INTID, gic::DEFAULT_PRIORITY, count_handler).unwrap();
+        assert!(gic.is_enabled(TEST_INTID).unwrap());
+        gic.unregister(TEST_INTID).unwrap();
+        assert!(!gic.is_enabled(TEST_INTID).unwrap());
+    }
+
+    #[timeout(1000)]
+    fn pending_interrupt_calls_its_handler(gic: &mut Gic) {
+        gic.set_trigger(TEST_INTID, Trigger::Edge).unwrap();
+        gic.register(TEST_INTID, gic::DEFAULT_PRIORITY, count_handler).unwrap();
+        // Safety: our handler only touches an atomic
+        unsafe { qemu_aarch64v8a::enable_interrupts() };
+        gic.set_pending(TEST_INTID, true).unwrap();
+        assert_eq!(wait_for_count(1), 1);
+        qemu_aarch64v8a::disable_interrupts();
+        assert!(!gic.is_pending(TEST_INTID).unwrap());
+        gic.unregister(TEST_INTID).unwrap();
+    }
+
+    #[timeout(1000)]
+    fn priority_mask_holds_back_interrupts(gic: &mut Gic) {
+        gic.set_trigger(TEST_INTID, Trigger::Edge).unwrap();
+        gic.register(TEST_INTID, 0xA0, count_handler).unwrap();
+        gic.set_priority_mask(0x80);
+        // Safety: our handler only touches an atomic
+        unsafe { qemu_aarch64v8a::enable_interrupts() };
+        gic.set_pending(TEST_INTID, true).unwrap();
+        assert_eq!(wait_for_count(1), 0);
+        assert!(gic.is_pending(TEST_INTID).unwrap());
+        gic.set_priority_mask(0xFF);
+        assert_eq!(wait_for_count(1), 1);
+        qemu_aarch64v8a::disable_interrupts();
+        gic.unregister(TEST_INTID).unwrap();
+    }
+}
+
+// End of file
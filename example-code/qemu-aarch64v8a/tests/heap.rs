@@ -0,0 +1,118 @@
+//! On-target tests for the frame allocator and the growable heap
+//!
+//! The heap is our global allocator here, so every test shares it. The first
+//! test's setup sets it up. Run with `cargo test --test heap`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use alloc::{boxed::Box, vec::Vec};
+use qemu_aarch64v8a::{
+    devicetree::Platform,
+    frames,
+    heap::{self, GROW_SIZE, HEAP_START, MAX_HEAP_SIZE},
+    mmu::AddressSpace,
+};
+
+#[cfg(not(feature = "virtio-console"))]
+#[global_allocator]
+static HEAP: heap::GrowableHeap = heap::GrowableHeap::empty();
+
+// The library brings its own global allocator with this feature on
+#[cfg(feature = "virtio-console")]
+use heap::HEAP;
+
+/// Has the heap been set up yet?
+static READY: AtomicBool = AtomicBool::new(false);
+
+/// Is this address in the heap?
+fn in_heap(addr: usize) -> bool {
+    (HEAP_START..HEAP_START + MAX_HEAP_SIZE).contains(&addr)
+}
+
+qemu_aarch64v8a::test_suite! {
+    setup: fn setup() -> () {
+        if !READY.swap(true, Ordering::Relaxed) {
+            frames::init(&Platform::qemu_virt());
+            let space = AddressSpace::for_image().unwrap();
+            // Safety: `for_image` maps everything this test program uses, and
+            // the heap is the only thing that maps anything at `HEAP_START`
+            unsafe {
+                space.activate();
+                HEAP.init(space).unwrap();
+            }
+        }
+    }
+
+    fn starts_small(_: &mut ()) {
+        let stats = HEAP.stats();
+        assert!(stats.size >= GROW_SIZE);
+        assert!(stats.size < 1024 * 1024);
+    }
+
+    fn reserves_the_image(_: &mut ()) {
+        let stats = frames::stats();
+        assert!(stats.reserved > 0);
+        assert_eq!(stats.total, stats.reserved + stats.used + stats.free);
+    }
+
+    fn allocates_in_the_heap(_: &mut ()) {
+        let value = Box::new(42u32);
+        assert!(in_heap(&raw const *value as usize));
+        assert_eq!(*value, 42);
+    }
+
+    fn grows_for_big_allocations(_: &mut ()) {
+        let small: Vec<u32> = (0..100).collect();
+        let size_before = HEAP.stats().size;
+        let frames_before = frames::stats().used;
+        let big: Vec<u64> = (0..512 * 1024).collect();
+        assert!(in_heap(big.as_ptr() as usize));
+        assert!(HEAP.stats().size >= size_before + 4 * 1024 * 1024);
+        assert!(frames::stats().used >= frames_before + 1024);
+        // Check every page of the new memory, and that growing didn't
+        // disturb what was there already
+        assert!(big.iter().enumerate().all(|(i, &x)| x == i as u64));
+        assert!(small.iter().enumerate().all(|(i, &x)| x == i as u32));
+    }
+
+    fn counts_allocations_and_frees(_: &mut ()) {
+        let before = HEAP.stats();
+        let value = Box::new([0u8; 100]);
+        assert_eq!(HEAP.stats().used, before.used + 100);
+        drop(value);
+        let after = HEAP.stats();
+        assert_eq!(after.used, before.used);
+        assert_eq!(after.allocations, before.allocations + 1);
+        assert_eq!(after.frees, before.frees + 1);
+    }
+
+    fn fails_past_the_maximum_size(_: &mut ()) {
+        let failures = HEAP.stats().failures;
+        let mut too_big: Vec<u8> = Vec::new();
+        assert!(too_big.try_reserve(MAX_HEAP_SIZE + 1).is_err());
+        assert_eq!(HEAP.stats().failures, failures + 1);
+    }
+
+    fn gives_frames_back_when_it_cannot_grow(_: &mut ()) {
+        // More than QEMU's default RAM, but less than the maximum, so growing
+        // runs out of frames (or page tables) part way
+        let before = HEAP.stats();
+        let frames_before = frames::stats().used;
+        let mut too_big: Vec<u8> = Vec::new();
+        assert!(too_big.try_reserve(256 * 1024 * 1024).is_err());
+        assert_eq!(HEAP.stats().size, before.size);
+        assert_eq!(frames::stats().used, frames_before);
+        // The heap still works
+        let value = Box::new(7u32);
+        assert_eq!(*value, 7);
+    }
+}
+
+// End of file
@@ -0,0 +1,87 @@
+//! On-target tests for the runtime page tables
+//!
+//! Run with `cargo test --test mmu`. The page table pool is small and never
+//! gets tables back, so each test should only build what it needs.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch64v8a::mmu::{self, AddressSpace, Error};
+
+/// Something to read once the new tables are active
+static ANSWER: u32 = 42;
+
+qemu_aarch64v8a::test_suite! {
+    fn rejects_misaligned_ranges(_: &mut ()) {
+        let mut space = AddressSpace::new(2).unwrap();
+        assert_eq!(space.map(0x1000..0x1800, mmu::READ_ONLY), Err(Error::Misaligned));
+        assert_eq!(
+            space.map(0..0x100_0000_0000, mmu::READ_ONLY),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    fn splits_blocks_when_unmapping(_: &mut ()) {
+        const BLOCK: usize = 0x8000_0000;
+        let mut space = AddressSpace::new(3).unwrap();
+        space.map(BLOCK..BLOCK + 0x20_0000, mmu::READ_WRITE).unwrap();
+        assert_eq!(space.attributes(BLOCK + 0x1234), Some(mmu::READ_WRITE));
+        space.unmap(BLOCK + 0x1000..BLOCK + 0x2000).unwrap();
+        assert_eq!(space.attributes(BLOCK), Some(mmu::READ_WRITE));
+        assert_eq!(space.attributes(BLOCK + 0x1000), None);
+        assert_eq!(space.attributes(BLOCK + 0x2000), Some(mmu::READ_WRITE));
+        assert_eq!(space.attributes(BLOCK + 0x20_0000), None);
+    }
+
+    fn maps_to_other_physical_addresses(_: &mut ()) {
+        const VIRT: usize = 0x1_0000_0000;
+        const PHYS: usize = 0x4800_0000;
+        let mut space = AddressSpace::new(4).unwrap();
+        // Both ends are 2 MiB aligned, so this can use blocks
+        space.map_at(VIRT..VIRT + 0x40_0000, PHYS, mmu::READ_WRITE).unwrap();
+        assert_eq!(space.translate(VIRT + 0x1234), Some(PHYS + 0x1234));
+        assert_eq!(space.translate(VIRT + 0x30_0000), Some(PHYS + 0x30_0000));
+        assert_eq!(space.translate(PHYS), None);
+        // This one can't, so it uses pages
+        space
+            .map_at(VIRT + 0x40_0000..VIRT + 0x60_0000, PHYS + 0x1000, mmu::READ_ONLY)
+            .unwrap();
+        assert_eq!(space.translate(VIRT + 0x5F_F010), Some(PHYS + 0x20_0010));
+        assert_eq!(space.attributes(VIRT + 0x40_0000), Some(mmu::READ_ONLY));
+        assert_eq!(
+            space.map_at(VIRT..VIRT + 0x1000, PHYS + 0x800, mmu::READ_WRITE),
+            Err(Error::Misaligned)
+        );
+    }
+
+    fn maps_the_image_with_the_right_permissions(_: &mut ()) {
+        let space = AddressSpace::for_image().unwrap();
+        let code = maps_the_image_with_the_right_permissions as *const () as usize;
+        assert_eq!(space.attributes(code), Some(mmu::EXECUTABLE));
+        assert_eq!(space.attributes(&raw const ANSWER as usize), Some(mmu::READ_ONLY));
+        let stack = qemu_aarch64v8a::stack::stack();
+        assert_eq!(space.attributes(stack.bottom()), Some(mmu::READ_WRITE));
+        assert_eq!(space.attributes(stack.top() - 1), Some(mmu::READ_WRITE));
+        let [below, above] = qemu_aarch64v8a::stack::guard_pages();
+        assert_eq!(below.end, stack.bottom());
+        assert_eq!(above.start, stack.top());
+        assert_eq!(space.attributes(below.start), None);
+        assert_eq!(space.attributes(above.start), None);
+        assert_eq!(space.attributes(above.end - 1), None);
+        assert_eq!(space.attributes(0x0900_0000), Some(mmu::DEVICE));
+    }
+
+    fn keeps_running_after_activating(_: &mut ()) {
+        let space = AddressSpace::for_image().unwrap();
+        assert!(!space.is_active());
+        // Safety: `for_image` maps everything this test program uses
+        unsafe { space.activate() };
+        assert!(space.is_active());
+        assert_eq!(core::hint::black_box(&ANSWER), &42);
+        defmt::info!("{} page tables left", mmu::tables_free());
+    }
+}
+
+// End of file
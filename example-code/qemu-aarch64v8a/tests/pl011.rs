@@ -0,0 +1,136 @@
+//! On-target tests for the interrupt-driven PL011 drivers
+//!
+//! Nothing types into QEMU's UART while the tests run, so we can only check
+//! that receiving waits properly. QEMU sends each byte as soon as it is
+//! written, so sending never has to wait for long. Run with
+//! `cargo test --test pl011`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use arm_pl011_uart::{DataBits, LineConfig, Parity, StopBits, Uart};
+use embedded_io::ReadReady as _;
+use qemu_aarch64v8a::{
+    devicetree::Platform,
+    gic::{self, Gic},
+    pl011::{asynch, BufferedUart},
+};
+
+/// Smaller than some of the writes, so the queue fills up
+const QLEN: usize = 64;
+
+static BUFFERED: BufferedUart<QLEN> = BufferedUart::empty();
+
+static ASYNC_STATE: asynch::State = asynch::State::new();
+
+fn buffered_irq() {
+    BUFFERED.on_interrupt();
+}
+
+fn async_irq() {
+    ASYNC_STATE.on_interrupt();
+}
+
+/// Make a fresh driver for the first UART
+fn uart() -> Uart<'static> {
+    // Safety: the only other driver for the UART was given to the last test,
+    // which has finished
+    let mut uart = unsafe { Platform::qemu_virt().uart() };
+    let line_config = LineConfig {
+        data_bits: DataBits::Bits8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+    uart.enable(line_config, 115_200, 16_000_000).unwrap();
+    uart
+}
+
+/// Poll `future` until it's done, spinning in between
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        core::hint::spin_loop();
+    }
+}
+
+qemu_aarch64v8a::test_suite! {
+    setup: fn setup() -> Gic {
+        qemu_aarch64v8a::disable_interrupts();
+        // Safety: the harness has finished with the last test's GIC driver
+        // before it calls `setup` again, and the tests don't make their own
+        let mut gic = unsafe { Gic::new_virt() }.unwrap();
+        gic.init();
+        gic
+    }
+
+    #[should_panic]
+    fn buffered_needs_init(_gic: &mut Gic) {
+        static EMPTY: BufferedUart<QLEN> = BufferedUart::empty();
+        EMPTY.read(&mut [0; 4]);
+    }
+
+    fn buffered_sends_more_than_it_can_queue(gic: &mut Gic) {
+        BUFFERED.init(uart());
+        gic.register(Platform::qemu_virt().uart_intid, gic::DEFAULT_PRIORITY, buffered_irq)
+            .unwrap();
+        // Safety: the UART is ready for its interrupt handler
+        unsafe { qemu_aarch64v8a::enable_interrupts() };
+        for _ in 0..4 {
+            BUFFERED.tx_blocking(&[b'.'; QLEN]);
+        }
+        BUFFERED.tx_blocking(b"\r\n");
+        BUFFERED.flush();
+    }
+
+    fn buffered_sends_with_interrupts_masked(_gic: &mut Gic) {
+        BUFFERED.init(uart());
+        critical_section::with(|_| {
+            BUFFERED.tx_blocking(&[b'-'; QLEN * 2]);
+            BUFFERED.tx_blocking(b"\r\n");
+        });
+        BUFFERED.flush();
+    }
+
+    fn buffered_has_nothing_to_read(_gic: &mut Gic) {
+        BUFFERED.init(uart());
+        let mut buffer = [0; 4];
+        assert_eq!(BUFFERED.read(&mut buffer), 0);
+        assert_eq!((&BUFFERED).read_ready(), Ok(false));
+    }
+
+    fn async_sends_everything(gic: &mut Gic) {
+        let (mut tx, _rx) = ASYNC_STATE.init(uart());
+        gic.register(Platform::qemu_virt().uart_intid, gic::DEFAULT_PRIORITY, async_irq)
+            .unwrap();
+        // Safety: the UART is ready for its interrupt handler
+        unsafe { qemu_aarch64v8a::enable_interrupts() };
+        assert_eq!(block_on(tx.write(&[])), 0);
+        assert!(block_on(tx.write(b"async\r\n")) > 0);
+        block_on(tx.write_all(&[b'~'; 100]));
+        block_on(tx.write_all(b"\r\n"));
+        block_on(tx.flush());
+    }
+
+    fn async_read_waits_for_data(_gic: &mut Gic) {
+        let (_tx, mut rx) = ASYNC_STATE.init(uart());
+        let mut buffer = [0; 4];
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut read = pin!(rx.read(&mut buffer));
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(read.as_mut().poll(&mut cx), Poll::Pending);
+    }
+}
+
+// End of file
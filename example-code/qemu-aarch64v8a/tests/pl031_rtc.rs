@@ -0,0 +1,76 @@
+//! On-target tests for the PL031 RTC driver and its date/time conversions
+//!
+//! Run with `cargo test --test pl031_rtc`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch64v8a::rtc::{self, DateTime, Rtc, Weekday};
+
+qemu_aarch64v8a::test_suite! {
+    setup: fn setup() -> Rtc {
+        // Safety: `RTC_ADDRESS` is the PL031 on the `virt` machine, and the
+        // harness has finished with the last test's driver before it calls
+        // `setup` again
+        unsafe { Rtc::new_with_raw_addr(rtc::RTC_ADDRESS) }
+    }
+
+    fn passes_id_check(rtc: &mut Rtc) {
+        rtc.check().unwrap();
+    }
+
+    fn epoch_is_a_thursday(_: &mut Rtc) {
+        let epoch = DateTime::from_timestamp(0);
+        assert_eq!(epoch, DateTime::new(1970, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(epoch.weekday(), Weekday::Thursday);
+    }
+
+    fn round_trips_a_leap_day(_: &mut Rtc) {
+        let leap_day = DateTime::new(2024, 2, 29, 12, 34, 56).unwrap();
+        let timestamp = leap_day.to_timestamp().unwrap();
+        assert_eq!(timestamp, 1_709_210_096);
+        assert_eq!(DateTime::from_timestamp(timestamp), leap_day);
+    }
+
+    fn round_trips_the_last_second(_: &mut Rtc) {
+        let last = DateTime::from_timestamp(u32::MAX);
+        assert_eq!(last, DateTime::new(2106, 2, 7, 6, 28, 15).unwrap());
+        assert_eq!(last.to_timestamp().unwrap(), u32::MAX);
+    }
+
+    fn rejects_invalid_dates(_: &mut Rtc) {
+        assert!(DateTime::new(2023, 2, 29, 0, 0, 0).is_err());
+        assert!(DateTime::new(2024, 13, 1, 0, 0, 0).is_err());
+        assert!(DateTime::new(1969, 12, 31, 23, 59, 59).is_err());
+        assert!(DateTime::new(2106, 2, 7, 6, 28, 16).is_err());
+    }
+
+    #[should_panic]
+    fn unwrapping_an_invalid_date_panics(_: &mut Rtc) {
+        DateTime::new(2024, 4, 31, 0, 0, 0).unwrap();
+    }
+
+    #[timeout(3000)]
+    fn counts_seconds(rtc: &mut Rtc) {
+        rtc.enable();
+        let start = rtc.timestamp();
+        while rtc.timestamp() == start {
+            core::hint::spin_loop();
+        }
+        assert_eq!(rtc.timestamp(), start + 1);
+    }
+
+    fn set_time_reads_back(rtc: &mut Rtc) {
+        let original = rtc.timestamp();
+        let when = DateTime::new(2030, 6, 15, 8, 0, 0).unwrap();
+        rtc.set_time(&when).unwrap();
+        let now = rtc.now();
+        // allow for the clock ticking over whilst we check
+        assert!(now.to_timestamp().unwrap() - when.to_timestamp().unwrap() <= 1);
+        rtc.set_timestamp(original);
+    }
+}
+
+// End of file
@@ -0,0 +1,65 @@
+//! On-target tests for PSCI and starting secondary cores
+//!
+//! QEMU only gives us one core when running tests, so we can only check what
+//! happens when there's nothing to start. Run with `cargo test --test smp`,
+//! and add `--features multicore` to test that critical section instead.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch64v8a::{psci, smp};
+
+/// A secondary core entry point which should never run
+fn never(_core: usize) -> ! {
+    panic!("a secondary core started");
+}
+
+qemu_aarch64v8a::test_suite! {
+    fn has_psci_0_2_or_later(_: &mut ()) {
+        let (major, minor) = psci::version();
+        assert!((major, minor) >= (0, 2), "PSCI version is {}.{}", major, minor);
+    }
+
+    fn runs_on_core_0(_: &mut ()) {
+        assert_eq!(smp::core_id(), 0);
+    }
+
+    fn cannot_start_core_0(_: &mut ()) {
+        assert_eq!(smp::start_core(0, never), Err(smp::Error::InvalidCore));
+    }
+
+    fn cannot_start_too_many_cores(_: &mut ()) {
+        assert_eq!(smp::start_core(smp::MAX_CORES, never), Err(smp::Error::InvalidCore));
+    }
+
+    fn reports_missing_cores(_: &mut ()) {
+        assert_eq!(
+            smp::start_core(1, never),
+            Err(smp::Error::Psci(psci::Error::InvalidParameters))
+        );
+        // A failed start can be tried again
+        assert_eq!(
+            smp::start_core(1, never),
+            Err(smp::Error::Psci(psci::Error::InvalidParameters))
+        );
+    }
+
+    fn critical_sections_nest(_: &mut ()) {
+        // Safety: the test puts interrupts back how they were
+        unsafe { qemu_aarch64v8a::enable_interrupts() };
+        critical_section::with(|_| {
+            assert!(!qemu_aarch64v8a::critical_section::interrupts_enabled());
+            critical_section::with(|_| {
+                assert!(!qemu_aarch64v8a::critical_section::interrupts_enabled());
+            });
+            // Leaving the inner one mustn't end the outer one
+            assert!(!qemu_aarch64v8a::critical_section::interrupts_enabled());
+        });
+        assert!(qemu_aarch64v8a::critical_section::interrupts_enabled());
+        qemu_aarch64v8a::disable_interrupts();
+    }
+}
+
+// End of file
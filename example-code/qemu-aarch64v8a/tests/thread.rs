@@ -0,0 +1,180 @@
+//! On-target tests for the preemptive thread scheduler
+//!
+//! Each test spawns its own threads and runs them to completion. Stacks are
+//! never given back, so each thread needs one of its own. Run with
+//! `cargo test --test thread`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+
+use qemu_aarch64v8a::{
+    gic::Gic,
+    thread::{
+        self,
+        sync::{Mutex, Semaphore},
+        ThreadId, ThreadStack, MAX_THREADS,
+    },
+    timer::{self, Counter},
+};
+use static_cell::ConstStaticCell;
+
+/// Make a fresh stack, each time this is expanded
+macro_rules! stack {
+    () => {{
+        static STACK: ConstStaticCell<ThreadStack> = ConstStaticCell::new(ThreadStack::new());
+        STACK.take()
+    }};
+}
+
+/// Counts what the threads did
+static COUNT: AtomicU32 = AtomicU32::new(0);
+
+fn count() {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Set by [`spin_a`] and [`spin_b`] once they are running
+static A_STARTED: AtomicBool = AtomicBool::new(false);
+static B_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Spins until [`spin_b`] has started, which it can only do if we're
+/// preempted
+fn spin_a() {
+    A_STARTED.store(true, Ordering::Relaxed);
+    while !B_STARTED.load(Ordering::Relaxed) {
+        core::hint::spin_loop();
+    }
+    count();
+}
+
+/// Spins until [`spin_a`] has started
+fn spin_b() {
+    B_STARTED.store(true, Ordering::Relaxed);
+    while !A_STARTED.load(Ordering::Relaxed) {
+        core::hint::spin_loop();
+    }
+    count();
+}
+
+/// How long [`sleeper`] actually slept, in microseconds
+static SLEPT_US: AtomicU64 = AtomicU64::new(0);
+
+fn sleeper() {
+    let start = timer::count(Counter::Physical);
+    thread::sleep_ms(20);
+    let elapsed = timer::count(Counter::Physical) - start;
+    SLEPT_US.store(timer::ticks_to_hz(elapsed, 1_000_000), Ordering::Relaxed);
+}
+
+/// A counter which isn't atomic, so only the mutex keeps it right
+static SHARED: Mutex<u32> = Mutex::new(0);
+
+/// Adds to [`SHARED`] one slow step at a time, yielding while it holds the
+/// lock
+fn add_slowly() {
+    for _ in 0..10 {
+        let mut shared = SHARED.lock();
+        let value = *shared;
+        thread::yield_now();
+        *shared = value + 1;
+    }
+}
+
+/// Released by [`signaller`], acquired by [`waiter`]
+static SIGNAL: Semaphore = Semaphore::new(0);
+
+/// Which order things happened in
+static STEP: AtomicU32 = AtomicU32::new(0);
+
+/// What [`STEP`] was when [`waiter`] got the signal
+static WAITER_STEP: AtomicU32 = AtomicU32::new(0);
+
+fn waiter() {
+    SIGNAL.acquire();
+    WAITER_STEP.store(STEP.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+fn signaller() {
+    thread::sleep_ms(10);
+    STEP.store(1, Ordering::Relaxed);
+    SIGNAL.release();
+}
+
+qemu_aarch64v8a::test_suite! {
+    setup: fn setup() -> Gic {
+        qemu_aarch64v8a::disable_interrupts();
+        COUNT.store(0, Ordering::Relaxed);
+        // Safety: the harness has finished with the last test's GIC driver
+        // before it calls `setup` again, and the scheduler doesn't make one
+        let mut gic = unsafe { Gic::new_virt() }.unwrap();
+        gic.init();
+        gic
+    }
+
+    fn runs_every_thread(gic: &mut Gic) {
+        thread::spawn("one", count, stack!()).unwrap();
+        thread::spawn("two", count, stack!()).unwrap();
+        thread::spawn("three", count, stack!()).unwrap();
+        thread::run(gic).unwrap();
+        assert_eq!(COUNT.load(Ordering::Relaxed), 3);
+    }
+
+    fn runs_with_no_threads(gic: &mut Gic) {
+        thread::run(gic).unwrap();
+        assert_eq!(thread::current(), ThreadId::IDLE);
+        assert_eq!(thread::name(), None);
+    }
+
+    #[timeout(1000)]
+    fn preempts_threads_which_never_yield(gic: &mut Gic) {
+        thread::spawn("a", spin_a, stack!()).unwrap();
+        thread::spawn("b", spin_b, stack!()).unwrap();
+        thread::run(gic).unwrap();
+        assert_eq!(COUNT.load(Ordering::Relaxed), 2);
+    }
+
+    fn sleeps_for_long_enough(gic: &mut Gic) {
+        thread::spawn("sleeper", sleeper, stack!()).unwrap();
+        thread::run(gic).unwrap();
+        let slept_us = SLEPT_US.load(Ordering::Relaxed);
+        assert!(slept_us >= 20_000, "slept for {} us", slept_us);
+        assert!(slept_us < 100_000, "slept for {} us", slept_us);
+    }
+
+    fn mutex_keeps_threads_out(gic: &mut Gic) {
+        thread::spawn("adder 1", add_slowly, stack!()).unwrap();
+        thread::spawn("adder 2", add_slowly, stack!()).unwrap();
+        thread::spawn("adder 3", add_slowly, stack!()).unwrap();
+        thread::run(gic).unwrap();
+        assert_eq!(*SHARED.lock(), 30);
+    }
+
+    fn semaphore_blocks_until_released(gic: &mut Gic) {
+        thread::spawn("waiter", waiter, stack!()).unwrap();
+        thread::spawn("signaller", signaller, stack!()).unwrap();
+        thread::run(gic).unwrap();
+        assert_eq!(WAITER_STEP.load(Ordering::Relaxed), 1);
+        assert_eq!(SIGNAL.available(), 0);
+    }
+
+    fn has_a_limit(gic: &mut Gic) {
+        static STACKS: [ConstStaticCell<ThreadStack>; MAX_THREADS] =
+            [const { ConstStaticCell::new(ThreadStack::new()) }; MAX_THREADS];
+        let mut stacks = STACKS.iter();
+        for _ in 1..MAX_THREADS {
+            thread::spawn("filler", count, stacks.next().unwrap().take()).unwrap();
+        }
+        assert_eq!(
+            thread::spawn("one too many", count, stacks.next().unwrap().take()),
+            Err(thread::Error::TooManyThreads)
+        );
+        thread::run(gic).unwrap();
+        assert_eq!(COUNT.load(Ordering::Relaxed), MAX_THREADS as u32 - 1);
+    }
+}
+
+// End of file
@@ -0,0 +1,102 @@
+//! On-target tests for user tasks and system calls
+//!
+//! Run with `cargo test --test user`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use qemu_aarch64v8a::{
+    mmu::{self, AddressSpace},
+    user::{self, Mailbox, Status, Task, UserMemory},
+};
+use static_cell::ConstStaticCell;
+
+/// Memory for each test's task
+static MEMORY: [ConstStaticCell<UserMemory>; 4] =
+    [const { ConstStaticCell::new(UserMemory::new()) }; 4];
+
+/// Have we switched to the user address space yet?
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Something only the kernel can read - it's in `.data`, not `.rodata`
+static SECRET: AtomicU32 = AtomicU32::new(0x5EC2_E700);
+
+/// Make a task, and make sure every task's memory is mapped
+///
+/// The page table pool is too small for an address space per test, so the
+/// first call maps all of the test memory.
+fn make_task(index: usize, entry: user::Entry) -> Task {
+    if !ACTIVE.swap(true, Ordering::Relaxed) {
+        let memory = &raw const MEMORY as usize;
+        let mut space = AddressSpace::for_image().unwrap();
+        space
+            .map(mmu::text_section(), mmu::USER_EXECUTABLE)
+            .unwrap();
+        space
+            .map(mmu::rodata_section(), mmu::USER_READ_ONLY)
+            .unwrap();
+        space
+            .map(
+                memory..memory + core::mem::size_of_val(&MEMORY),
+                mmu::USER_READ_WRITE,
+            )
+            .unwrap();
+        // Safety: the address space maps everything the test harness uses
+        unsafe { space.activate() };
+    }
+    Task::new("test", entry, MEMORY[index].take())
+}
+
+extern "C" fn exit_seven(sys: &'static mut Mailbox) -> ! {
+    sys.exit(7);
+}
+
+extern "C" fn write_secret(sys: &'static mut Mailbox) -> ! {
+    let result = sys.syscall(user::SYS_WRITE, [SECRET.as_ptr() as u64, 4, 0, 0]);
+    sys.exit(result as i32);
+}
+
+extern "C" fn unknown_syscall(sys: &'static mut Mailbox) -> ! {
+    let result = sys.syscall(99, [0; 4]);
+    sys.exit(result as i32);
+}
+
+extern "C" fn touch_uart(sys: &'static mut Mailbox) -> ! {
+    // Safety: the UART isn't mapped for EL0, so this read faults before it
+    // reaches the device, and `user::run` stops the task rather than
+    // resuming it. Nothing else in this test is using the UART.
+    let value = unsafe { (0x0900_0000 as *const u32).read_volatile() };
+    sys.exit(value as i32);
+}
+
+qemu_aarch64v8a::test_suite! {
+    fn task_exits_with_its_code(_: &mut ()) {
+        let mut tasks = [make_task(0, exit_seven)];
+        user::run(&mut tasks);
+        assert_eq!(tasks[0].status(), Status::Exited(7));
+    }
+
+    fn write_rejects_kernel_memory(_: &mut ()) {
+        let mut tasks = [make_task(1, write_secret)];
+        user::run(&mut tasks);
+        assert_eq!(tasks[0].status(), Status::Exited(user::EFAULT as i32));
+    }
+
+    fn unknown_syscall_fails(_: &mut ()) {
+        let mut tasks = [make_task(2, unknown_syscall)];
+        user::run(&mut tasks);
+        assert_eq!(tasks[0].status(), Status::Exited(user::ENOSYS as i32));
+    }
+
+    fn task_cannot_touch_devices(_: &mut ()) {
+        let mut tasks = [make_task(3, touch_uart)];
+        user::run(&mut tasks);
+        assert_eq!(tasks[0].status(), Status::Faulted);
+    }
+}
+
+// End of file
@@ -0,0 +1,78 @@
+//! On-target tests for the virtio support
+//!
+//! `cargo test` doesn't give QEMU any virtio devices, so we can only check
+//! the empty transports and the DMA memory pool. Run with
+//! `cargo test --test virtio`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_aarch64v8a::virtio::{
+    self,
+    blk::VirtioBlk,
+    hal::{VirtioHal, DMA_PAGES},
+    Error,
+};
+use virtio_drivers::{BufferDirection, Hal, PAGE_SIZE};
+
+qemu_aarch64v8a::test_suite! {
+    fn finds_no_devices(_: &mut ()) {
+        assert_eq!(virtio::devices().count(), 0);
+    }
+
+    fn no_disk_is_not_found(_: &mut ()) {
+        // Safety: there's no disk to have a driver for
+        assert!(matches!(unsafe { VirtioBlk::find() }, Err(Error::NotFound)));
+    }
+
+    fn transport_addresses(_: &mut ()) {
+        let device = virtio::Device {
+            slot: 31,
+            device_type: virtio_drivers::transport::DeviceType::Block,
+        };
+        assert_eq!(device.address(), 0x0a00_3e00);
+        assert_eq!(device.intid(), 79);
+    }
+
+    fn dma_memory_is_zeroed_and_aligned(_: &mut ()) {
+        let (paddr, vaddr) = VirtioHal::dma_alloc(2, BufferDirection::Both);
+        assert_eq!(paddr % PAGE_SIZE, 0);
+        assert_eq!(paddr, vaddr.as_ptr() as usize);
+        // Safety: we were just given these two pages
+        let memory = unsafe { core::slice::from_raw_parts_mut(vaddr.as_ptr(), 2 * PAGE_SIZE) };
+        assert!(memory.iter().all(|b| *b == 0));
+        memory.fill(0xAA);
+        // Safety: we're giving back what we were given
+        unsafe { VirtioHal::dma_dealloc(paddr, vaddr, 2) };
+        // We get the same zeroed pages back
+        let (again, vaddr) = VirtioHal::dma_alloc(2, BufferDirection::Both);
+        assert_eq!(again, paddr);
+        // Safety: we were just given these two pages
+        let memory = unsafe { core::slice::from_raw_parts(vaddr.as_ptr(), 2 * PAGE_SIZE) };
+        assert!(memory.iter().all(|b| *b == 0));
+        // Safety: we're giving back what we were given
+        unsafe { VirtioHal::dma_dealloc(again, vaddr, 2) };
+    }
+
+    fn dma_allocations_do_not_overlap(_: &mut ()) {
+        let (a, a_ptr) = VirtioHal::dma_alloc(1, BufferDirection::Both);
+        let (b, b_ptr) = VirtioHal::dma_alloc(3, BufferDirection::Both);
+        assert!(b >= a + PAGE_SIZE || a >= b + 3 * PAGE_SIZE);
+        assert_eq!(VirtioHal::pages_in_use(), 4);
+        // Safety: we're giving back what we were given
+        unsafe {
+            VirtioHal::dma_dealloc(a, a_ptr, 1);
+            VirtioHal::dma_dealloc(b, b_ptr, 3);
+        }
+        assert_eq!(VirtioHal::pages_in_use(), 0);
+    }
+
+    #[should_panic]
+    fn dma_pool_runs_out(_: &mut ()) {
+        let _ = VirtioHal::dma_alloc(DMA_PAGES + 1, BufferDirection::Both);
+    }
+}
+
+// End of file
@@ -0,0 +1,107 @@
+#!/usr/bin/env bash
+#
+# A cargo runner which passes command-line arguments through to the firmware.
+#
+# Put it in front of `qemu-run` in `.cargo/config.toml`. Cargo calls it with
+# the qemu-run command and options, then the ELF file, then anything you put
+# after `--` in `cargo run`. `--uart-telnet` is a qemu-run option. These
+# ones add to the QEMU machine:
+#
+# * `--smp <n>` sets how many cores it has
+# * `--drive <file>` adds a virtio-blk disk, backed by a raw disk image
+# * `--virtio-console` adds a virtio console, which prints to stderr
+#
+# Everything else is for the firmware, which can fetch it with
+# `qemu_common::host_env`.
+#
+# qemu-run has no way to set the semihosting command line or add to the
+# machine, so if there are any firmware arguments or machine options we start
+# QEMU ourselves and decode the defmt logs with `defmt-print`
+# (`cargo install defmt-print`). Otherwise we just run qemu-run.
+
+set -euo pipefail
+
+qemu_run=("$1")
+shift
+qemu=qemu-system-arm
+machine=
+cpu=
+log_format=
+elf=
+uart_telnet=
+qemu_args=()
+drives=0
+firmware_args=()
+
+# The qemu-run options from `.cargo/config.toml`, up to the ELF file
+while [[ $# -gt 0 && -z "${elf}" ]]; do
+    case "$1" in
+    --machine) machine="$2"; qemu_run+=("$1" "$2"); shift 2 ;;
+    --cpu) cpu="$2"; qemu_run+=("$1" "$2"); shift 2 ;;
+    --log-format) log_format="$2"; qemu_run+=("$1" "$2"); shift 2 ;;
+    --aarch64) qemu=qemu-system-aarch64; qemu_run+=("$1"); shift ;;
+    -*) qemu_run+=("$1"); shift ;;
+    *) elf="$1"; shift ;;
+    esac
+done
+
+# Stops with a usage message if the option in $1 has no value after it
+need_value() {
+    if [[ $# -lt 2 ]]; then
+        echo "$1 needs a value" >&2
+        echo "usage: cargo run -- [--uart-telnet] [--smp <n>] [--drive <file>] [--virtio-console] [firmware args...]" >&2
+        exit 2
+    fi
+}
+
+# The arguments given to `cargo run`
+while [[ $# -gt 0 ]]; do
+    case "$1" in
+    --uart-telnet) uart_telnet=1; shift ;;
+    --smp) need_value "$@"; qemu_args+=(-smp "$2"); shift 2 ;;
+    --drive)
+        need_value "$@"
+        qemu_args+=(-drive "file=${2//,/,,},if=none,format=raw,id=disk${drives}")
+        qemu_args+=(-device "virtio-blk-device,drive=disk${drives}")
+        drives=$((drives + 1))
+        shift 2
+        ;;
+    --virtio-console)
+        qemu_args+=(-chardev file,id=virtio-console,path=/dev/stderr)
+        qemu_args+=(-device virtio-serial-device -device virtconsole,chardev=virtio-console)
+        shift
+        ;;
+    *) firmware_args+=("$1"); shift ;;
+    esac
+done
+
+if [[ ${#firmware_args[@]} -eq 0 && ${#qemu_args[@]} -eq 0 ]]; then
+    exec "${qemu_run[@]}" "${elf}" ${uart_telnet:+--uart-telnet}
+fi
+
+# QEMU separates sub-options with commas, so any commas in the arguments
+# have to be doubled
+semihosting_config="enable=on,target=native,arg=$(basename "${elf}")"
+for arg in "${firmware_args[@]}"; do
+    semihosting_config+=",arg=${arg//,/,,}"
+done
+
+if [[ -n "${uart_telnet}" ]]; then
+    echo "Starting telnet server on localhost:4321. Connect to interact with the UART." >&2
+    serial=(-chardev socket,id=sock0,server=on,telnet=on,port=4321,host=localhost -serial chardev:sock0)
+else
+    serial=(-serial null)
+fi
+
+"${qemu}" \
+    -machine "${machine}" \
+    ${cpu:+-cpu "${cpu}"} \
+    ${qemu_args[@]+"${qemu_args[@]}"} \
+    -display none \
+    -monitor none \
+    "${serial[@]}" \
+    -semihosting-config "${semihosting_config}" \
+    -kernel "${elf}" |
+    defmt-print -e "${elf}" ${log_format:+--log-format "${log_format}"}
+# Exit with QEMU's status, not defmt-print's
+exit "${PIPESTATUS[0]}"
@@ -0,0 +1,229 @@
+//! A header for firmware images, so a bootloader can check them before it
+//! runs them
+//!
+//! An image starts with [`HEADER_LEN`] bytes of header, followed by the
+//! application's vector table and everything after it. The firmware reserves
+//! space for the header with [`ImageHeader::unsigned`], which fills in
+//! everything except the length and the checksums, and the `qemu-image` tool
+//! fills those in after linking.
+//!
+//! The bootloader then calls [`check`] on each slot to find an image it can
+//! trust.
+//!
+//! This module builds for the host too, so `qemu-image` uses the same header
+//! layout and CRC as the bootloader.
+
+/// Identifies an image header ("QIMG" in little-endian byte order)
+pub const MAGIC: u32 = 0x474D_4951;
+
+/// The version of the header format
+pub const FORMAT: u32 = 1;
+
+/// The space reserved for the header, in bytes
+///
+/// The vector table comes straight after the header, and VTOR needs it to be
+/// aligned to the next power of two up from its size. 1 KiB is enough for 256
+/// vectors.
+pub const HEADER_LEN: usize = 1024;
+
+/// How many bytes at the start of the header are covered by `header_crc32`
+const HEADER_CRC_LEN: usize = 24;
+
+/// How many bytes at the start of the header hold its fields
+///
+/// The rest of the [`HEADER_LEN`] bytes are padding.
+pub const FIELDS_LEN: usize = HEADER_CRC_LEN + 4;
+
+/// Why an image isn't fit to boot
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// There's no header (the slot is probably empty)
+    BadMagic(u32),
+    /// The header is in a format we don't understand
+    BadFormat(u32),
+    /// The header has been corrupted
+    BadHeaderCrc,
+    /// The image says it is bigger than the slot
+    TooLong(u32),
+    /// The image has been corrupted
+    BadImageCrc {
+        /// The CRC in the header
+        expected: u32,
+        /// The CRC of the image in memory
+        actual: u32,
+    },
+}
+
+/// The version of an image
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+pub struct Version {
+    /// Major version
+    pub major: u8,
+    /// Minor version
+    pub minor: u8,
+    /// Patch version
+    pub patch: u16,
+}
+
+impl Version {
+    /// Pack the version into a word, so that later versions are bigger
+    pub const fn to_bits(self) -> u32 {
+        (self.major as u32) << 24 | (self.minor as u32) << 16 | self.patch as u32
+    }
+
+    /// Unpack a version from a word
+    pub const fn from_bits(bits: u32) -> Version {
+        Version {
+            major: (bits >> 24) as u8,
+            minor: (bits >> 16) as u8,
+            patch: bits as u16,
+        }
+    }
+}
+
+impl core::fmt::Display for Version {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The header at the start of every image
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(C)]
+pub struct ImageHeader {
+    /// Always [`MAGIC`]
+    pub magic: u32,
+    /// Always [`FORMAT`]
+    pub format: u32,
+    /// How much space the header takes up, in bytes
+    pub header_len: u32,
+    /// The length of the image after the header, in bytes
+    pub image_len: u32,
+    /// CRC-32 of the image after the header
+    pub image_crc32: u32,
+    /// The version of the image, from [`Version::to_bits`]
+    pub version: u32,
+    /// CRC-32 of all the fields above
+    pub header_crc32: u32,
+}
+
+impl ImageHeader {
+    /// A header for an image which hasn't been through `qemu-image` yet
+    ///
+    /// The bootloader won't boot it until it has.
+    pub const fn unsigned(version: Version) -> ImageHeader {
+        ImageHeader {
+            magic: MAGIC,
+            format: FORMAT,
+            header_len: HEADER_LEN as u32,
+            image_len: 0,
+            image_crc32: 0,
+            version: version.to_bits(),
+            header_crc32: 0,
+        }
+    }
+
+    /// The version of the image
+    pub const fn version(&self) -> Version {
+        Version::from_bits(self.version)
+    }
+
+    /// Read a header from the first [`FIELDS_LEN`] bytes of `bytes`
+    ///
+    /// Returns `None` if there aren't enough bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<ImageHeader> {
+        let bytes = bytes.get(..FIELDS_LEN)?;
+        let word = |index: usize| u32::from_le_bytes(bytes[index * 4..][..4].try_into().unwrap());
+        Some(ImageHeader {
+            magic: word(0),
+            format: word(1),
+            header_len: word(2),
+            image_len: word(3),
+            image_crc32: word(4),
+            version: word(5),
+            header_crc32: word(6),
+        })
+    }
+
+    /// The header's fields, as they are laid out in memory
+    pub fn to_bytes(&self) -> [u8; FIELDS_LEN] {
+        let mut bytes = [0u8; FIELDS_LEN];
+        let words = [
+            self.magic,
+            self.format,
+            self.header_len,
+            self.image_len,
+            self.image_crc32,
+            self.version,
+            self.header_crc32,
+        ];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Fill in the length and checksums, for the image that follows the
+    /// header
+    pub fn sign(&mut self, image: &[u8]) {
+        self.image_len = image.len() as u32;
+        self.image_crc32 = crc32(image);
+        self.header_crc32 = self.expected_header_crc32();
+    }
+
+    /// What `header_crc32` should be, given the other fields
+    pub fn expected_header_crc32(&self) -> u32 {
+        crc32(&self.to_bytes()[..HEADER_CRC_LEN])
+    }
+}
+
+/// Check the image in a slot, and return its header if it's fit to boot
+///
+/// # Safety
+///
+/// `slot` must point to `slot_len` bytes of readable memory.
+pub unsafe fn check(slot: *const u8, slot_len: usize) -> Result<ImageHeader, Error> {
+    // Safety: the caller promised us the slot is readable, and the header is
+    // just integers
+    let header = unsafe { slot.cast::<ImageHeader>().read_volatile() };
+    if header.magic != MAGIC {
+        return Err(Error::BadMagic(header.magic));
+    }
+    if header.format != FORMAT || header.header_len != HEADER_LEN as u32 {
+        return Err(Error::BadFormat(header.format));
+    }
+    if header.expected_header_crc32() != header.header_crc32 {
+        return Err(Error::BadHeaderCrc);
+    }
+    if header.image_len as usize > slot_len - HEADER_LEN {
+        return Err(Error::TooLong(header.image_len));
+    }
+    // Safety: we just checked the image fits in the slot
+    let image =
+        unsafe { core::slice::from_raw_parts(slot.add(HEADER_LEN), header.image_len as usize) };
+    let actual = crc32(image);
+    if actual != header.image_crc32 {
+        return Err(Error::BadImageCrc {
+            expected: header.image_crc32,
+            actual,
+        });
+    }
+    Ok(header)
+}
+
+/// The standard CRC-32 (as used by zip, Ethernet, etc)
+///
+/// This does a bit at a time, which is slow but small.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+// End of file
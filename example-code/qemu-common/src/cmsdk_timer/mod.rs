@@ -93,6 +93,11 @@ impl DelayTimer {
     pub fn new(timer: Timer, sys_clk_hz: u32) -> Self {
         Self { timer, sys_clk_hz }
     }
+
+    /// The system clock frequency the timer runs at
+    pub fn sys_clk_hz(&self) -> u32 {
+        self.sys_clk_hz
+    }
 }
 
 impl embedded_hal::delay::DelayNs for DelayTimer {
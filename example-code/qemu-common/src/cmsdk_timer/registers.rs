@@ -48,6 +48,7 @@ pub struct Interrupt {
     interrupt_bit: bool,
 }
 
+#[cfg(feature = "embassy-time")]
 impl From<MmioRegisters<'_>> for embassy_time::driver_cmsdk::regs::MmioRegisters<'_> {
     fn from(value: MmioRegisters<'_>) -> Self {
         unsafe { embassy_time::driver_cmsdk::regs::Registers::new_mmio_at(value.ptr as usize) }
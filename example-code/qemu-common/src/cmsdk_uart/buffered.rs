@@ -270,4 +270,16 @@ impl<const QLEN: usize> embedded_io::Read for &BufferedUart<QLEN> {
     }
 }
 
+impl<const QLEN: usize> embedded_io::ReadReady for BufferedUart<QLEN> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.with(|inner| !inner.rx_buffer.is_empty()))
+    }
+}
+
+impl<const QLEN: usize> embedded_io::ReadReady for &BufferedUart<QLEN> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.with(|inner| !inner.rx_buffer.is_empty()))
+    }
+}
+
 // End of file
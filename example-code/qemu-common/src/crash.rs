@@ -0,0 +1,325 @@
+//! Crash records which survive a warm reset
+//!
+//! Printing a panic over semihosting is fine when you're watching, but a
+//! device in the field has nobody watching. Instead, the panic (or fault)
+//! handler can write a [`CrashRecord`] into a small piece of RAM which the
+//! startup code doesn't touch, and then reset the chip. On the next boot, the
+//! application calls [`CrashLog::take`] to find out what went wrong last time.
+//!
+//! The record is protected by a magic number and a checksum, so after a power
+//! cycle (when RAM holds garbage) you get `None` rather than nonsense.
+//!
+//! Each firmware crate reserves [`CRASH_LOG_SIZE`] bytes of RAM for the log in
+//! its linker script. It has to sit outside every section in the ELF file,
+//! because QEMU re-loads the ELF file when the machine resets, zeroing out
+//! `.bss` (and anything like it) as it goes.
+
+use core::{
+    fmt::Write,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// How many bytes of RAM a firmware crate must reserve for the crash log
+pub const CRASH_LOG_SIZE: usize = 1024;
+
+/// The most bytes of panic message we keep
+pub const MESSAGE_LEN: usize = 128;
+
+/// The most bytes of file name we keep
+pub const FILE_LEN: usize = 64;
+
+/// The most fault registers we keep
+pub const MAX_REGISTERS: usize = 8;
+
+/// Marks the crash log as having been written by us
+const MAGIC: u32 = 0xC0FF_EE42;
+
+/// Should the panic and fault handlers reset, rather than exit QEMU?
+static RESET_ON_CRASH: AtomicBool = AtomicBool::new(false);
+
+/// Choose whether the panic and fault handlers store a crash record and reset
+/// the machine (`true`), or just exit QEMU (`false`, the default)
+pub fn set_reset_on_crash(enabled: bool) {
+    RESET_ON_CRASH.store(enabled, Ordering::Relaxed);
+}
+
+/// Should the panic and fault handlers store a crash record and reset?
+pub fn reset_on_crash() -> bool {
+    RESET_ON_CRASH.load(Ordering::Relaxed)
+}
+
+/// What sort of crash a [`CrashRecord`] describes
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum CrashKind {
+    /// The application called `panic!`
+    Panic,
+    /// The processor took a fault exception
+    Fault,
+}
+
+impl CrashKind {
+    /// Convert to the value we store in RAM (zero means "no crash")
+    const fn to_raw(self) -> u32 {
+        match self {
+            CrashKind::Panic => 1,
+            CrashKind::Fault => 2,
+        }
+    }
+
+    /// Convert from the value we store in RAM
+    const fn from_raw(raw: u32) -> Option<CrashKind> {
+        match raw {
+            1 => Some(CrashKind::Panic),
+            2 => Some(CrashKind::Fault),
+            _ => None,
+        }
+    }
+}
+
+/// Everything we know about a crash
+///
+/// This is stored in RAM exactly as laid out here, so it has no padding and
+/// every field is valid for any bit pattern.
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct CrashRecord {
+    registers: [u64; MAX_REGISTERS],
+    kind: u32,
+    line: u32,
+    reset_count: u32,
+    register_count: u32,
+    file_len: u32,
+    message_len: u32,
+    file: [u8; FILE_LEN],
+    message: [u8; MESSAGE_LEN],
+}
+
+impl CrashRecord {
+    /// A record which says there was no crash
+    const EMPTY: CrashRecord = CrashRecord {
+        registers: [0; MAX_REGISTERS],
+        kind: 0,
+        line: 0,
+        reset_count: 0,
+        register_count: 0,
+        file_len: 0,
+        message_len: 0,
+        file: [0; FILE_LEN],
+        message: [0; MESSAGE_LEN],
+    };
+
+    /// What sort of crash this was
+    pub fn kind(&self) -> CrashKind {
+        // `CrashLog::load` only hands out records with a valid kind
+        CrashKind::from_raw(self.kind).unwrap_or(CrashKind::Fault)
+    }
+
+    /// The panic message, or the name of the fault
+    ///
+    /// Long messages are cut short.
+    pub fn message(&self) -> &str {
+        text(&self.message, self.message_len)
+    }
+
+    /// Where the panic happened, if we know
+    pub fn location(&self) -> Option<(&str, u32)> {
+        (self.file_len != 0).then(|| (text(&self.file, self.file_len), self.line))
+    }
+
+    /// The fault registers (which ones depends on the architecture)
+    pub fn registers(&self) -> &[u64] {
+        &self.registers[..(self.register_count as usize).min(MAX_REGISTERS)]
+    }
+
+    /// How many times we have reset because of a crash, including this one
+    ///
+    /// This counts up from one after a power cycle.
+    pub fn reset_count(&self) -> u32 {
+        self.reset_count
+    }
+}
+
+impl defmt::Format for CrashRecord {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "{} #{=u32}: {=str}",
+            self.kind(),
+            self.reset_count,
+            self.message()
+        );
+        if let Some((file, line)) = self.location() {
+            defmt::write!(fmt, " at {=str}:{=u32}", file, line);
+        }
+        if !self.registers().is_empty() {
+            defmt::write!(fmt, ", registers {:#x}", self.registers());
+        }
+    }
+}
+
+/// What we actually store in RAM
+#[repr(C)]
+struct Stored {
+    magic: u32,
+    checksum: u32,
+    record: CrashRecord,
+}
+
+const _: () = assert!(core::mem::size_of::<Stored>() <= CRASH_LOG_SIZE);
+
+/// A handle to the crash log in RAM
+///
+/// It's just a pointer, so it's fine to make a new one whenever you need it.
+pub struct CrashLog {
+    stored: *mut Stored,
+}
+
+impl CrashLog {
+    /// Use the [`CRASH_LOG_SIZE`] bytes of RAM at `address` as the crash log
+    ///
+    /// # Safety
+    ///
+    /// The memory must be reserved for the crash log, aligned to 8 bytes, and
+    /// not touched by anything else (including the startup code).
+    pub const unsafe fn new(address: *mut u8) -> CrashLog {
+        CrashLog {
+            stored: address.cast(),
+        }
+    }
+
+    /// Store a crash record for a panic
+    pub fn record_panic(&self, info: &core::panic::PanicInfo) {
+        let mut record = CrashRecord::EMPTY;
+        record.kind = CrashKind::Panic.to_raw();
+        let mut message = Truncate::new(&mut record.message);
+        let _ = write!(message, "{}", info.message());
+        record.message_len = message.len as u32;
+        if let Some(location) = info.location() {
+            let mut file = Truncate::new(&mut record.file);
+            let _ = file.write_str(location.file());
+            record.file_len = file.len as u32;
+            record.line = location.line();
+        }
+        self.store(record);
+    }
+
+    /// Store a crash record for a fault
+    ///
+    /// Only the first [`MAX_REGISTERS`] registers are kept.
+    pub fn record_fault(&self, name: &str, registers: &[u64]) {
+        let mut record = CrashRecord::EMPTY;
+        record.kind = CrashKind::Fault.to_raw();
+        let mut message = Truncate::new(&mut record.message);
+        let _ = message.write_str(name);
+        record.message_len = message.len as u32;
+        for (slot, value) in record.registers.iter_mut().zip(registers) {
+            *slot = *value;
+            record.register_count += 1;
+        }
+        self.store(record);
+    }
+
+    /// Get the crash record from before the last reset, if there is one, and
+    /// clear it
+    pub fn take(&self) -> Option<CrashRecord> {
+        critical_section::with(|_| {
+            let record = self.load()?;
+            CrashKind::from_raw(record.kind)?;
+            // Keep the reset count, but forget the crash
+            let mut cleared = CrashRecord::EMPTY;
+            cleared.reset_count = record.reset_count;
+            self.write(&cleared);
+            Some(record)
+        })
+    }
+
+    /// How many times we have reset because of a crash since the last power
+    /// cycle
+    pub fn reset_count(&self) -> u32 {
+        critical_section::with(|_| self.load().map_or(0, |r| r.reset_count))
+    }
+
+    /// Store a record, counting one more reset
+    fn store(&self, mut record: CrashRecord) {
+        critical_section::with(|_| {
+            record.reset_count = self.load().map_or(0, |r| r.reset_count) + 1;
+            self.write(&record);
+        })
+    }
+
+    /// Read the record, if the magic number and checksum are OK
+    fn load(&self) -> Option<CrashRecord> {
+        // Safety: the caller of `new` promised this memory is ours. It may
+        // hold any bit pattern, but every field of `Stored` is an integer, so
+        // any bit pattern is valid. It might have been written before the
+        // reset, so don't let the compiler assume anything about it.
+        let stored = unsafe { self.stored.read_volatile() };
+        (stored.magic == MAGIC && stored.checksum == checksum(&stored.record))
+            .then_some(stored.record)
+    }
+
+    /// Write a record, with the magic number and checksum
+    fn write(&self, record: &CrashRecord) {
+        let stored = Stored {
+            magic: MAGIC,
+            checksum: checksum(record),
+            record: *record,
+        };
+        // Safety: the caller of `new` promised this memory is ours
+        unsafe { self.stored.write_volatile(stored) };
+    }
+}
+
+/// A 32-bit FNV-1a hash of a record
+fn checksum(record: &CrashRecord) -> u32 {
+    // Safety: `CrashRecord` has no padding, so every byte is initialised
+    let bytes = unsafe {
+        core::slice::from_raw_parts(
+            (record as *const CrashRecord).cast::<u8>(),
+            core::mem::size_of::<CrashRecord>(),
+        )
+    };
+    bytes.iter().fold(0x811C_9DC5, |hash, byte| {
+        (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Get a string back out of a stored buffer
+///
+/// We only store valid UTF-8, but the checksum can't catch everything, so we
+/// check again.
+fn text(buffer: &[u8], len: u32) -> &str {
+    let bytes = &buffer[..(len as usize).min(buffer.len())];
+    match core::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => core::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+    }
+}
+
+/// Writes text into a fixed buffer, dropping whatever doesn't fit
+struct Truncate<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> Truncate<'a> {
+    fn new(buffer: &'a mut [u8]) -> Truncate<'a> {
+        Truncate { buffer, len: 0 }
+    }
+}
+
+impl Write for Truncate<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let space = self.buffer.len() - self.len;
+        // Don't cut a character in half
+        let mut take = s.len().min(space);
+        while !s.is_char_boundary(take) {
+            take -= 1;
+        }
+        self.buffer[self.len..self.len + take].copy_from_slice(&s.as_bytes()[..take]);
+        self.len += take;
+        Ok(())
+    }
+}
+
+// End of file
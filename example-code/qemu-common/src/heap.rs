@@ -0,0 +1,220 @@
+//! A global allocator that keeps statistics
+//!
+//! [`Heap`] wraps the allocator from `linked_list_allocator`, and counts what
+//! goes through it: how many bytes are in use now, the most that have ever
+//! been in use, how many allocations and frees there have been, and how many
+//! allocations failed. Call [`Heap::report`] to print them with defmt, or
+//! [`Heap::stats`] to get them as a [`Stats`].
+//!
+//! When an allocation fails we call a hook with the layout we couldn't
+//! satisfy and the current statistics. The default hook,
+//! [`log_failure`], logs them with defmt. Use [`Heap::set_failure_hook`] to
+//! do something else. The hook runs inside the allocator, so it must not
+//! allocate.
+//!
+//! Returning null from the allocator makes Rust call its allocation error
+//! handler, which panics (unless the caller used something like
+//! `Vec::try_reserve`, which just returns an error). So the hook is the place
+//! to find out *why*, before the panic handler takes over.
+//!
+//! ```rust ignore
+//! #[global_allocator]
+//! static HEAP: qemu_common::heap::Heap = qemu_common::heap::Heap::empty();
+//! ```
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::{Cell, RefCell},
+    ptr::NonNull,
+};
+
+use critical_section::Mutex;
+
+/// Called when an allocation fails
+pub type FailureHook = fn(Layout, &Stats);
+
+/// A snapshot of the heap statistics
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct Stats {
+    /// The size of the heap, in bytes
+    pub size: usize,
+    /// Bytes currently allocated (as requested, not counting the
+    /// allocator's own overhead)
+    pub used: usize,
+    /// The most bytes that have been allocated at once
+    pub peak: usize,
+    /// How many allocations have succeeded
+    pub allocations: u32,
+    /// How many allocations have been freed
+    pub frees: u32,
+    /// How many allocations have failed
+    pub failures: u32,
+    /// The biggest allocation that would succeed right now
+    pub largest_free: usize,
+}
+
+/// The numbers we keep up to date as we go
+#[derive(Copy, Clone)]
+struct Counters {
+    size: usize,
+    used: usize,
+    peak: usize,
+    allocations: u32,
+    frees: u32,
+    failures: u32,
+}
+
+/// The linked-list heap from `linked_list_allocator`
+struct Inner(linked_list_allocator::Heap);
+
+// Safety: the heap's pointers are into memory only the heap uses, so it can
+// be used from any core
+unsafe impl Send for Inner {}
+
+/// A linked-list heap which keeps statistics
+pub struct Heap {
+    inner: Mutex<RefCell<Inner>>,
+    counters: Mutex<Cell<Counters>>,
+    on_failure: Mutex<Cell<FailureHook>>,
+}
+
+impl Heap {
+    /// Make an empty heap
+    ///
+    /// Call [`Heap::init`] before you allocate anything.
+    pub const fn empty() -> Heap {
+        Heap {
+            inner: Mutex::new(RefCell::new(Inner(linked_list_allocator::Heap::empty()))),
+            counters: Mutex::new(Cell::new(Counters {
+                size: 0,
+                used: 0,
+                peak: 0,
+                allocations: 0,
+                frees: 0,
+                failures: 0,
+            })),
+            on_failure: Mutex::new(Cell::new(log_failure)),
+        }
+    }
+
+    /// Give the heap some memory to work with
+    ///
+    /// # Safety
+    ///
+    /// Call this once, before the first allocation. The `size` bytes from
+    /// `start` must be valid, and not used for anything else.
+    pub unsafe fn init(&self, start: usize, size: usize) {
+        critical_section::with(|cs| {
+            // Safety: passed on to our caller
+            unsafe { self.inner.borrow_ref_mut(cs).0.init(start as *mut u8, size) };
+        });
+        self.update(|counters| counters.size = size);
+    }
+
+    /// Change what happens when an allocation fails
+    pub fn set_failure_hook(&self, hook: FailureHook) {
+        critical_section::with(|cs| self.on_failure.borrow(cs).set(hook));
+    }
+
+    /// Get the current statistics
+    pub fn stats(&self) -> Stats {
+        critical_section::with(|cs| {
+            let counters = self.counters.borrow(cs).get();
+            Stats {
+                size: counters.size,
+                used: counters.used,
+                peak: counters.peak,
+                allocations: counters.allocations,
+                frees: counters.frees,
+                failures: counters.failures,
+                largest_free: largest_free(&mut self.inner.borrow_ref_mut(cs).0),
+            }
+        })
+    }
+
+    /// Print the current statistics with defmt
+    pub fn report(&self) {
+        defmt::info!("Heap: {}", self.stats());
+    }
+
+    /// Change the counters
+    fn update(&self, f: impl FnOnce(&mut Counters)) {
+        critical_section::with(|cs| {
+            let cell = self.counters.borrow(cs);
+            let mut counters = cell.get();
+            f(&mut counters);
+            cell.set(counters);
+        });
+    }
+}
+
+// Safety: we pass everything through to `linked_list_allocator`, which is a
+// sound allocator, and only count what happens
+unsafe impl GlobalAlloc for Heap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = critical_section::with(|cs| {
+            let mut inner = self.inner.borrow_ref_mut(cs);
+            inner.0.allocate_first_fit(layout).map(NonNull::as_ptr)
+        });
+        let Ok(ptr) = ptr else {
+            self.update(|counters| counters.failures += 1);
+            let hook = critical_section::with(|cs| self.on_failure.borrow(cs).get());
+            hook(layout, &self.stats());
+            return core::ptr::null_mut();
+        };
+        self.update(|counters| {
+            counters.allocations += 1;
+            counters.used += layout.size();
+            counters.peak = counters.peak.max(counters.used);
+        });
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        critical_section::with(|cs| {
+            let mut inner = self.inner.borrow_ref_mut(cs);
+            // Safety: our caller says we allocated `ptr`, with this layout
+            unsafe { inner.0.deallocate(NonNull::new_unchecked(ptr), layout) };
+        });
+        self.update(|counters| {
+            counters.frees += 1;
+            counters.used -= layout.size();
+        });
+    }
+}
+
+/// Find the biggest allocation that would succeed right now
+///
+/// The allocator doesn't tell us about its free list, so we find out by
+/// trying allocations (and freeing them again), homing in on the answer.
+pub fn largest_free(heap: &mut linked_list_allocator::Heap) -> usize {
+    let mut lower = 0;
+    let mut upper = heap.free();
+    while lower < upper {
+        let size = lower + (upper - lower).div_ceil(2);
+        let Ok(layout) = Layout::from_size_align(size, 1) else {
+            break;
+        };
+        match heap.allocate_first_fit(layout) {
+            Ok(ptr) => {
+                // Safety: we just allocated this, with this layout
+                unsafe { heap.deallocate(ptr, layout) };
+                lower = size;
+            }
+            Err(()) => upper = size - 1,
+        }
+    }
+    lower
+}
+
+/// The default failure hook, which logs the failure with defmt
+pub fn log_failure(layout: Layout, stats: &Stats) {
+    defmt::error!(
+        "Failed to allocate {=usize} bytes (aligned to {=usize}). Heap: {}",
+        layout.size(),
+        layout.align(),
+        stats
+    );
+}
+
+// End of file
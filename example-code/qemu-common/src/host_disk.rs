@@ -0,0 +1,177 @@
+//! A virtual block device, backed by a file on the host
+//!
+//! QEMU's semihosting support lets the guest open, seek, read and write files
+//! on the machine running QEMU (relative to the directory QEMU was started
+//! in). [`HostDisk`] uses that to treat a disk image file as a block device,
+//! so you can try out a filesystem crate without any real storage hardware.
+//!
+//! It implements the [`embedded_storage::ReadStorage`] and
+//! [`embedded_storage::Storage`] traits for byte-addressed access, and also
+//! has methods for reading and writing whole 512 byte sectors.
+//!
+//! Semihosting calls are slow - each one stops the guest CPU whilst QEMU
+//! does the work - so don't expect great performance.
+
+use core::ffi::CStr;
+
+use semihosting::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+};
+
+/// The size of a disk sector, in bytes
+pub const SECTOR_SIZE: usize = 512;
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// The host reported an error, with this `errno` value (if it gave one)
+    Host(Option<i32>),
+    /// The access goes past the end of the disk image.
+    OutOfRange,
+    /// The buffer is not a whole number of sectors.
+    NotSectorSized,
+    /// The disk was opened read-only.
+    ReadOnly,
+}
+
+impl From<semihosting::io::Error> for Error {
+    fn from(error: semihosting::io::Error) -> Error {
+        Error::Host(error.raw_os_error())
+    }
+}
+
+/// A disk image on the host, accessed via semihosting
+pub struct HostDisk {
+    file: File,
+    capacity: u64,
+    writable: bool,
+}
+
+impl HostDisk {
+    /// Open an existing disk image for reading and writing
+    ///
+    /// The image is never resized - writes past the end fail.
+    pub fn open(path: &CStr) -> Result<HostDisk, Error> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        Self::new(file, true)
+    }
+
+    /// Open an existing disk image for reading only
+    pub fn open_read_only(path: &CStr) -> Result<HostDisk, Error> {
+        let file = File::open(path)?;
+        Self::new(file, false)
+    }
+
+    /// Wrap an open file
+    fn new(file: File, writable: bool) -> Result<HostDisk, Error> {
+        let capacity = file.metadata()?.len();
+        defmt::debug!("Opened host disk, {=u64} bytes", capacity);
+        Ok(HostDisk {
+            file,
+            capacity,
+            writable,
+        })
+    }
+
+    /// The size of the disk image, in bytes
+    pub fn len(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Is the disk image empty?
+    pub fn is_empty(&self) -> bool {
+        self.capacity == 0
+    }
+
+    /// The number of whole sectors in the disk image
+    pub fn sector_count(&self) -> u32 {
+        (self.capacity / SECTOR_SIZE as u64) as u32
+    }
+
+    /// Can we write to this disk?
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    /// Read whole sectors, starting at sector number `first_sector`
+    ///
+    /// The length of `buffer` must be a multiple of [`SECTOR_SIZE`].
+    pub fn read_sectors(&mut self, first_sector: u32, buffer: &mut [u8]) -> Result<(), Error> {
+        if buffer.len() % SECTOR_SIZE != 0 {
+            return Err(Error::NotSectorSized);
+        }
+        self.read_at(u64::from(first_sector) * SECTOR_SIZE as u64, buffer)
+    }
+
+    /// Write whole sectors, starting at sector number `first_sector`
+    ///
+    /// The length of `buffer` must be a multiple of [`SECTOR_SIZE`].
+    pub fn write_sectors(&mut self, first_sector: u32, buffer: &[u8]) -> Result<(), Error> {
+        if buffer.len() % SECTOR_SIZE != 0 {
+            return Err(Error::NotSectorSized);
+        }
+        self.write_at(u64::from(first_sector) * SECTOR_SIZE as u64, buffer)
+    }
+
+    /// Read `buffer.len()` bytes, starting at byte `offset`
+    pub fn read_at(&mut self, offset: u64, buffer: &mut [u8]) -> Result<(), Error> {
+        self.check_range(offset, buffer.len())?;
+        self.file.seek(SeekFrom::Start(offset))?;
+        // The host is allowed to give us less than we asked for
+        let mut done = 0;
+        while done < buffer.len() {
+            match self.file.read(&mut buffer[done..])? {
+                0 => return Err(Error::OutOfRange),
+                n => done += n,
+            }
+        }
+        Ok(())
+    }
+
+    /// Write all of `buffer`, starting at byte `offset`
+    pub fn write_at(&mut self, offset: u64, buffer: &[u8]) -> Result<(), Error> {
+        if !self.writable {
+            return Err(Error::ReadOnly);
+        }
+        self.check_range(offset, buffer.len())?;
+        self.file.seek(SeekFrom::Start(offset))?;
+        let mut done = 0;
+        while done < buffer.len() {
+            match self.file.write(&buffer[done..])? {
+                0 => return Err(Error::OutOfRange),
+                n => done += n,
+            }
+        }
+        Ok(())
+    }
+
+    /// Check an access fits inside the disk image
+    fn check_range(&self, offset: u64, len: usize) -> Result<(), Error> {
+        match offset.checked_add(len as u64) {
+            Some(end) if end <= self.capacity => Ok(()),
+            _ => Err(Error::OutOfRange),
+        }
+    }
+}
+
+impl embedded_storage::ReadStorage for HostDisk {
+    type Error = Error;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Error> {
+        self.read_at(u64::from(offset), bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        // embedded-storage uses `u32` offsets, so that's as far as we can go
+        self.capacity.min(u64::from(u32::MAX)) as usize
+    }
+}
+
+impl embedded_storage::Storage for HostDisk {
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Error> {
+        self.write_at(u64::from(offset), bytes)
+    }
+}
+
+// End of file
@@ -0,0 +1,141 @@
+//! Command-line arguments and time from the host, via semihosting
+//!
+//! QEMU gives the guest a semihosting command line, which our runner fills in
+//! with whatever comes after the `--` in `cargo run` (see the README). Call
+//! [`CommandLine::fetch`] to get it. The first word is the program name, and
+//! [`CommandLine::args`] gives you the rest:
+//!
+//! ```rust ignore
+//! let cmdline = CommandLine::fetch().unwrap();
+//! let baud: u32 = cmdline.value_of("--baud").and_then(|s| s.parse().ok()).unwrap_or(115_200);
+//! ```
+//!
+//! QEMU joins the arguments with spaces and doesn't quote them, so an argument
+//! which contains a space comes out as two arguments.
+//!
+//! We also expose the semihosting `SYS_CLOCK` and `SYS_TIME` calls. They are a
+//! coarse time source (centiseconds and seconds respectively) which needs no
+//! timer peripheral at all.
+//!
+//! The calls go through the `semihosting` crate, which knows which trap
+//! instruction each target needs - `hlt` on A64, `bkpt` on M-profile and `svc`
+//! on A and R-profile Arm.
+
+use semihosting::sys::arm_compat::syscall::{OperationNumber, ParamRegW, syscall};
+
+/// The longest command line we can fetch, in bytes, including the null
+pub const MAX_CMDLINE_LEN: usize = 256;
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// The host refused the call (or the command line didn't fit)
+    Host,
+    /// The command line was not valid UTF-8
+    NotUtf8,
+}
+
+/// The command line our program was started with
+pub struct CommandLine {
+    buffer: [u8; MAX_CMDLINE_LEN],
+    len: usize,
+}
+
+impl CommandLine {
+    /// Ask the host for the command line
+    pub fn fetch() -> Result<CommandLine, Error> {
+        let mut buffer = [0u8; MAX_CMDLINE_LEN];
+        // The host writes the length of the command line back into the
+        // second word of the parameter block
+        let mut block = [buffer.as_mut_ptr() as usize, MAX_CMDLINE_LEN];
+        // Safety: the parameter block points at a buffer which is as long as
+        // it says it is, and both live until the call returns
+        let result = unsafe {
+            syscall(
+                OperationNumber::SYS_GET_CMDLINE,
+                ParamRegW::ptr(block.as_mut_ptr()),
+            )
+        }
+        .usize();
+        if result != 0 {
+            return Err(Error::Host);
+        }
+        let len = block[1].min(MAX_CMDLINE_LEN);
+        if core::str::from_utf8(&buffer[..len]).is_err() {
+            return Err(Error::NotUtf8);
+        }
+        Ok(CommandLine { buffer, len })
+    }
+
+    /// The whole command line, including the program name
+    pub fn as_str(&self) -> &str {
+        // Safety: we checked this was UTF-8 in `fetch`
+        unsafe { core::str::from_utf8_unchecked(&self.buffer[..self.len]) }
+    }
+
+    /// The program name, if the host gave us one
+    pub fn program(&self) -> Option<&str> {
+        self.as_str().split_ascii_whitespace().next()
+    }
+
+    /// The arguments, not including the program name
+    pub fn args(&self) -> impl Iterator<Item = &str> {
+        self.as_str().split_ascii_whitespace().skip(1)
+    }
+
+    /// Was `flag` (e.g. `--verbose`) given?
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.args().any(|arg| arg == flag)
+    }
+
+    /// Get the value of an option, given as `--name value` or `--name=value`
+    pub fn value_of(&self, name: &str) -> Option<&str> {
+        let mut args = self.args();
+        while let Some(arg) = args.next() {
+            if arg == name {
+                return args.next();
+            }
+            let value = arg
+                .strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix('='));
+            if value.is_some() {
+                return value;
+            }
+        }
+        None
+    }
+}
+
+impl defmt::Format for CommandLine {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "{=str}", self.as_str())
+    }
+}
+
+/// How long QEMU has been running, in hundredths of a second
+pub fn clock_cs() -> Result<u32, Error> {
+    // Safety: SYS_CLOCK takes no parameters
+    let result = unsafe { syscall(OperationNumber::SYS_CLOCK, ParamRegW::usize(0)) }.usize();
+    // -1 means the host has no clock
+    if result as isize == -1 {
+        Err(Error::Host)
+    } else {
+        Ok(result as u32)
+    }
+}
+
+/// How long QEMU has been running, in milliseconds
+///
+/// This only goes up in 10 ms steps.
+pub fn uptime_ms() -> Result<u64, Error> {
+    clock_cs().map(|cs| u64::from(cs) * 10)
+}
+
+/// The host's wall-clock time, in seconds since 1970-01-01 00:00:00 UTC
+pub fn unix_time() -> Result<u32, Error> {
+    // Safety: SYS_TIME takes no parameters
+    let result = unsafe { syscall(OperationNumber::SYS_TIME, ParamRegW::usize(0)) }.usize();
+    Ok(result as u32)
+}
+
+// End of file
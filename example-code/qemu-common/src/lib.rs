@@ -1,7 +1,25 @@
 //! Shared code for our QEMU examples
+//!
+//! The CMSDK UART and timer drivers are only built with the `cmsdk` feature,
+//! because only the MPS2 and MPS3 machines have them. The `embassy-time`
+//! feature lets our CMSDK timer registers drive embassy-time's CMSDK time
+//! driver. The semihosting modules are only built for bare-metal targets, so
+//! the rest builds on the host too.
 
 #![no_std]
 #![deny(missing_docs)]
 
+pub mod boot_image;
+#[cfg(feature = "cmsdk")]
 pub mod cmsdk_timer;
+#[cfg(feature = "cmsdk")]
 pub mod cmsdk_uart;
+pub mod crash;
+pub mod heap;
+#[cfg(target_os = "none")]
+pub mod host_disk;
+#[cfg(target_os = "none")]
+pub mod host_env;
+pub mod stack;
+pub mod testing;
+pub mod xmodem;
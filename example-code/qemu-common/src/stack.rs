@@ -0,0 +1,135 @@
+//! Stack painting and high-water marks
+//!
+//! At startup, each of our QEMU crates fills the unused part of its stack with
+//! [`PAINT`]. Later on, we look for the lowest word that doesn't hold the paint
+//! any more. Everything from there up to the top of the stack has been used at
+//! some point, so that's the most stack the program has needed so far - its
+//! *high-water mark*.
+//!
+//! This can under-report, if a function reserves stack it never writes to, or
+//! happens to write the paint value itself. In practice it's close, and it
+//! costs nothing until you ask.
+//!
+//! ```rust ignore
+//! // Safety: this is our stack, and it's in RAM
+//! let stack = unsafe { Stack::new(bottom, top) };
+//! // Safety: interrupts are off, so nothing else is using the stack
+//! unsafe { stack.paint() };
+//! // ... later ...
+//! stack.report();
+//! ```
+
+/// The value we fill unused stack with
+///
+/// This is the same value as `cortex-m-rt` uses for its `paint-stack`
+/// feature, so debuggers which know about that will understand ours too.
+pub const PAINT: u32 = 0xCCCC_CCCC;
+
+/// How much space we leave below our own stack frame when painting
+///
+/// [`Stack::paint`] can't paint the memory it is using, or the memory any
+/// function it calls might use.
+const MARGIN: usize = 256;
+
+/// How much of a stack has been used
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct Usage {
+    /// The size of the stack, in bytes
+    pub size: usize,
+    /// The most bytes that have been in use at once
+    pub used: usize,
+}
+
+impl Usage {
+    /// How many bytes of stack have never been used
+    pub fn free(&self) -> usize {
+        self.size - self.used
+    }
+}
+
+/// A stack, which grows down from `top` towards `bottom`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Stack {
+    bottom: usize,
+    top: usize,
+}
+
+impl Stack {
+    /// Describe a stack
+    ///
+    /// `bottom` is the lowest address the stack may use, and `top` is the
+    /// address just above the highest.
+    ///
+    /// # Safety
+    ///
+    /// `bottom..top` must be RAM which is valid to read and write.
+    pub const unsafe fn new(bottom: usize, top: usize) -> Stack {
+        Stack {
+            // Round inwards to whole words
+            bottom: (bottom + 3) & !3,
+            top: top & !3,
+        }
+    }
+
+    /// The lowest address the stack may use
+    pub fn bottom(&self) -> usize {
+        self.bottom
+    }
+
+    /// The address just above the highest address the stack may use
+    pub fn top(&self) -> usize {
+        self.top
+    }
+
+    /// The size of the stack, in bytes
+    pub fn size(&self) -> usize {
+        self.top - self.bottom
+    }
+
+    /// Fill the stack with [`PAINT`], from the bottom up to a little below
+    /// our own stack frame
+    ///
+    /// # Safety
+    ///
+    /// This must be the stack we are running on, and nothing else may be
+    /// using the part below our stack frame - so call it with interrupts
+    /// turned off, or before they are turned on.
+    #[inline(never)]
+    pub unsafe fn paint(&self) {
+        let marker = 0u32;
+        let here = core::ptr::addr_of!(marker) as usize;
+        let limit = here.saturating_sub(MARGIN).clamp(self.bottom, self.top) & !3;
+        let mut addr = self.bottom;
+        while addr < limit {
+            // Safety: the caller says this is our stack, and this part of it
+            // is below anything we are using
+            unsafe { core::ptr::write_volatile(addr as *mut u32, PAINT) };
+            addr += 4;
+        }
+    }
+
+    /// Work out how much of the stack has been used
+    ///
+    /// If the stack was never painted, this says it has all been used.
+    pub fn usage(&self) -> Usage {
+        let mut addr = self.bottom;
+        while addr < self.top {
+            // Safety: `Stack::new` says we can read all of this
+            if unsafe { core::ptr::read_volatile(addr as *const u32) } != PAINT {
+                break;
+            }
+            addr += 4;
+        }
+        Usage {
+            size: self.size(),
+            used: self.top - addr,
+        }
+    }
+
+    /// Print the stack usage with defmt
+    pub fn report(&self) {
+        defmt::info!("Stack: {}", self.usage());
+    }
+}
+
+// End of file
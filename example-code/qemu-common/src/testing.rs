@@ -0,0 +1,436 @@
+//! A minimal on-target test harness
+//!
+//! The standard library's test harness needs an operating system, so on
+//! bare-metal we bring our own. A test binary is a normal `#![no_main]`
+//! firmware image, with `harness = false` set in `Cargo.toml`, and it lists its
+//! tests with the `test_suite!` macro from the firmware crate (which wraps
+//! [`test_suite!`](crate::test_suite) and adds an entry point):
+//!
+//! ```rust ignore
+//! qemu_thumbv7em::test_suite! {
+//!     setup: fn setup() -> Uart {
+//!         Uart::new(...)
+//!     }
+//!
+//!     fn writes_a_byte(uart: &mut Uart) {
+//!         uart.write(b'x').unwrap();
+//!     }
+//!
+//!     #[should_panic]
+//!     fn bad_index(_: &mut Uart) {
+//!         let _ = [0u8; 2][core::hint::black_box(5)];
+//!     }
+//!
+//!     #[timeout(100)]
+//!     fn is_quick(uart: &mut Uart) {
+//!         ...
+//!     }
+//! }
+//! ```
+//!
+//! `cargo test` then runs the binary in QEMU. We run each test in turn,
+//! calling the `setup` function to make a fresh state for each one, and print
+//! the results with `defmt`. The binary exits through semihosting with code 0
+//! if every test passed, and code 1 otherwise, which `cargo test` sees as the
+//! test binary passing or failing.
+//!
+//! There is no unwinding, so a test which panics never returns. Before the
+//! first test we take a [`Checkpoint`] of the stack pointer and whether
+//! interrupts are enabled. If a test marked `#[should_panic]` panics, the
+//! panic handler calls [`on_panic`], which asks the platform to go back to
+//! that checkpoint - throwing away whatever the test left on the stack, and
+//! undoing any interrupt masking it was in the middle of - and carries on with
+//! the next test. Any other panic, or a test exceeding its `#[timeout(ms)]`,
+//! stops the run.
+//!
+//! Tests can have doc comments and other attributes as well as the ones the
+//! harness understands.
+//!
+//! Timeouts are checked when a test returns. If the platform has a periodic
+//! interrupt, it can call [`check_timeout`] from it to also catch tests which
+//! never return.
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+
+/// The things the harness needs from the platform it is running on
+pub trait Platform {
+    /// Called once, before the first test runs
+    fn init() {}
+
+    /// Called before each test runs, after its `setup` function
+    ///
+    /// A platform can use this to put back anything `setup` might have
+    /// undone, such as the interrupt which calls [`check_timeout`].
+    fn before_test() {}
+
+    /// A monotonic clock, in microseconds
+    fn now_us() -> u64;
+
+    /// Exit QEMU with the given status code
+    fn exit(code: i32) -> !;
+
+    /// Record the current stack pointer, and whether interrupts are enabled
+    fn checkpoint() -> Checkpoint;
+
+    /// Go back to `checkpoint`, and call `resume(next)`
+    ///
+    /// Implementations set the stack pointer, enable or disable interrupts
+    /// and undo anything else the platform needs to (such as a critical
+    /// section which a panicking test never left), then jump to `resume`.
+    ///
+    /// # Safety
+    ///
+    /// Everything on the stack below the checkpoint is thrown away, so
+    /// nothing may still be using it.
+    unsafe fn restart(checkpoint: Checkpoint, resume: Resume, next: usize) -> !;
+}
+
+/// Where to go back to, after a test which panics
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The stack pointer, before the first test ran
+    pub stack_pointer: usize,
+    /// Whether interrupts were enabled, before the first test ran
+    pub interrupts_enabled: bool,
+}
+
+/// A single test
+pub struct Test<S> {
+    name: &'static str,
+    func: fn(&mut S),
+    should_panic: bool,
+    timeout_ms: Option<u32>,
+    ignored: bool,
+}
+
+impl<S> Test<S> {
+    /// Create a test which runs `func`
+    pub const fn new(name: &'static str, func: fn(&mut S)) -> Test<S> {
+        Test {
+            name,
+            func,
+            should_panic: false,
+            timeout_ms: None,
+            ignored: false,
+        }
+    }
+
+    /// Only pass this test if it panics
+    pub const fn should_panic(mut self) -> Test<S> {
+        self.should_panic = true;
+        self
+    }
+
+    /// Fail this test if it runs for longer than `ms` milliseconds
+    pub const fn timeout(mut self, ms: u32) -> Test<S> {
+        self.timeout_ms = Some(ms);
+        self
+    }
+
+    /// Skip this test
+    pub const fn ignore(mut self) -> Test<S> {
+        self.ignored = true;
+        self
+    }
+}
+
+/// A set of tests, which share a setup function
+pub struct Suite<S: 'static> {
+    /// Creates the state each test is given
+    pub setup: fn() -> S,
+    /// The tests, in the order they are run
+    pub tests: &'static [Test<S>],
+}
+
+/// Called to run the tests from a given index onwards
+///
+/// The [`test_suite!`](crate::test_suite) macro makes one of these for each
+/// suite, so that [`on_panic`] can carry on without knowing the state type.
+/// Platforms jump to it from assembly with the index in the first argument
+/// register, so it uses the C calling convention.
+pub type Resume = extern "C" fn(usize) -> !;
+
+/// How [`on_panic`] gets back to the checkpoint, without knowing the platform
+///
+/// This is [`Platform::restart`], which hands a [`Resume`] on to assembly.
+type Restart = unsafe fn(Checkpoint, Resume, usize) -> !;
+
+/// The test which is currently running
+#[derive(Copy, Clone)]
+struct Running {
+    index: usize,
+    name: &'static str,
+    should_panic: bool,
+    deadline_us: Option<u64>,
+}
+
+/// Everything we need to remember across a panic
+#[derive(Copy, Clone)]
+struct Progress {
+    running: Option<Running>,
+    resume: Option<(Resume, Restart, Checkpoint)>,
+    total: usize,
+    passed: usize,
+    ignored: usize,
+}
+
+static PROGRESS: Mutex<Cell<Progress>> = Mutex::new(Cell::new(Progress {
+    running: None,
+    resume: None,
+    total: 0,
+    passed: 0,
+    ignored: 0,
+}));
+
+/// Update our progress record
+fn update<F>(f: F) -> Progress
+where
+    F: FnOnce(&mut Progress),
+{
+    critical_section::with(|cs| {
+        let cell = PROGRESS.borrow(cs);
+        let mut progress = cell.get();
+        f(&mut progress);
+        cell.set(progress);
+        progress
+    })
+}
+
+/// Run the tests in `suite`, starting at `start`, and then exit QEMU
+///
+/// You don't normally call this yourself - use the
+/// [`test_suite!`](crate::test_suite) macro.
+pub fn run<P, S>(suite: &Suite<S>, resume: Resume, start: usize) -> !
+where
+    P: Platform,
+{
+    if start == 0 {
+        P::init();
+        update(|p| {
+            p.resume = Some((resume, P::restart, P::checkpoint()));
+            p.total = suite.tests.len();
+        });
+        defmt::println!("running {=usize} tests", suite.tests.len());
+    }
+
+    for (index, test) in suite.tests.iter().enumerate().skip(start) {
+        if test.ignored {
+            defmt::println!("test {=str} ... ignored", test.name);
+            update(|p| p.ignored += 1);
+            continue;
+        }
+        let mut state = (suite.setup)();
+        P::before_test();
+        let started_us = P::now_us();
+        update(|p| {
+            p.running = Some(Running {
+                index,
+                name: test.name,
+                should_panic: test.should_panic,
+                deadline_us: test.timeout_ms.map(|ms| started_us + u64::from(ms) * 1000),
+            })
+        });
+        (test.func)(&mut state);
+        update(|p| p.running = None);
+        let elapsed_us = P::now_us() - started_us;
+
+        if test.should_panic {
+            defmt::error!("test {=str} ... FAILED (it did not panic)", test.name);
+            finish::<P>(false);
+        }
+        let too_slow = |ms: &u32| elapsed_us > u64::from(*ms) * 1000;
+        if let Some(timeout_ms) = test.timeout_ms.filter(too_slow) {
+            defmt::error!(
+                "test {=str} ... FAILED (took {=u64} ms, limit is {=u32} ms)",
+                test.name,
+                elapsed_us / 1000,
+                timeout_ms
+            );
+            finish::<P>(false);
+        }
+        defmt::println!("test {=str} ... ok", test.name);
+        update(|p| p.passed += 1);
+    }
+
+    finish::<P>(true)
+}
+
+/// Call this from your panic handler, after reporting the panic
+///
+/// If the test that is running was expected to panic, this marks it as passed
+/// and goes back to the [`Checkpoint`] to run the rest of the tests (so it
+/// does not return). Otherwise, it
+/// marks the test as failed and returns, and your panic handler should exit
+/// QEMU with a non-zero status as normal.
+///
+/// Does nothing if no test is running.
+pub fn on_panic() {
+    let progress = update(|p| {
+        if p.running.is_some_and(|r| r.should_panic) {
+            p.passed += 1;
+        }
+    });
+    let Some(running) = progress.running else {
+        return;
+    };
+    if running.should_panic {
+        defmt::println!("test {=str} ... ok", running.name);
+        update(|p| p.running = None);
+        if let Some((resume, restart, checkpoint)) = progress.resume {
+            // Safety: the panicking test is all that is below the
+            // checkpoint, and it is never coming back
+            unsafe { restart(checkpoint, resume, running.index + 1) };
+        }
+    } else {
+        defmt::error!("test {=str} ... FAILED", running.name);
+        print_summary(&progress, false);
+    }
+}
+
+/// Call this periodically (e.g. from a timer interrupt) to catch tests which
+/// run past their timeout and never return
+pub fn check_timeout<P>()
+where
+    P: Platform,
+{
+    let progress = critical_section::with(|cs| PROGRESS.borrow(cs).get());
+    let Some(running) = progress.running else {
+        return;
+    };
+    let Some(deadline_us) = running.deadline_us else {
+        return;
+    };
+    if P::now_us() > deadline_us {
+        defmt::error!("test {=str} ... FAILED (timed out)", running.name);
+        print_summary(&progress, false);
+        P::exit(1);
+    }
+}
+
+/// Print the summary and exit QEMU
+fn finish<P>(ok: bool) -> !
+where
+    P: Platform,
+{
+    let progress = critical_section::with(|cs| PROGRESS.borrow(cs).get());
+    print_summary(&progress, ok);
+    P::exit(if ok { 0 } else { 1 })
+}
+
+/// Print the line at the end of a test run
+fn print_summary(progress: &Progress, ok: bool) {
+    let failed = usize::from(!ok);
+    let not_run = progress.total - progress.passed - progress.ignored - failed;
+    defmt::println!(
+        "test result: {=str}. {=usize} passed; {=usize} failed; {=usize} ignored; {=usize} not run",
+        if ok { "ok" } else { "FAILED" },
+        progress.passed,
+        failed,
+        progress.ignored,
+        not_run
+    );
+}
+
+/// Declare a set of tests, and a function to run them
+///
+/// This produces a function called `__run_tests`, which takes the index of the
+/// first test to run (normally zero) and never returns. Your entry point
+/// should call it. The firmware crates each have a `test_suite!` macro which
+/// does this for you, so you probably want to use that instead.
+///
+/// The `setup` function is optional - leave it out and each test gets a
+/// `&mut ()`. Each test can be marked with `#[should_panic]`, `#[ignore]`
+/// and/or `#[timeout(ms)]`. Any other attributes, including doc comments, are
+/// kept on the test function.
+#[macro_export]
+macro_rules! test_suite {
+    (
+        platform: $platform:ty;
+        setup: fn $setup:ident() -> $state:ty $setup_body:block
+        $(
+            $(#[$($attr:tt)*])*
+            fn $name:ident($arg_name:tt: &mut $arg_ty:ty) $body:block
+        )*
+    ) => {
+        fn $setup() -> $state $setup_body
+
+        $(
+            $crate::__test_fn! {
+                [] $([$($attr)*])*;
+                fn $name($arg_name: &mut $arg_ty) $body
+            }
+        )*
+
+        static __TEST_SUITE: $crate::testing::Suite<$state> = $crate::testing::Suite {
+            setup: $setup,
+            tests: &[
+                $(
+                    $crate::__test_case!(
+                        $crate::testing::Test::new(stringify!($name), $name);
+                        $([$($attr)*])*
+                    )
+                ),*
+            ],
+        };
+
+        extern "C" fn __run_tests(start: usize) -> ! {
+            $crate::testing::run::<$platform, $state>(&__TEST_SUITE, __run_tests, start)
+        }
+    };
+    (
+        platform: $platform:ty;
+        $($tests:tt)*
+    ) => {
+        $crate::test_suite! {
+            platform: $platform;
+            setup: fn __setup() -> () {}
+            $($tests)*
+        }
+    };
+}
+
+/// Emit a test function, without the attributes only the harness understands
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __test_fn {
+    ([$($keep:tt)*] [should_panic] $($rest:tt)*) => {
+        $crate::__test_fn! { [$($keep)*] $($rest)* }
+    };
+    ([$($keep:tt)*] [ignore] $($rest:tt)*) => {
+        $crate::__test_fn! { [$($keep)*] $($rest)* }
+    };
+    ([$($keep:tt)*] [timeout($ms:expr)] $($rest:tt)*) => {
+        $crate::__test_fn! { [$($keep)*] $($rest)* }
+    };
+    ([$($keep:tt)*] [$($other:tt)*] $($rest:tt)*) => {
+        $crate::__test_fn! { [$($keep)* #[$($other)*]] $($rest)* }
+    };
+    ([$($keep:tt)*]; $($func:tt)*) => {
+        $($keep)* $($func)*
+    };
+}
+
+/// Turn the attributes the harness understands into calls on a [`Test`]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __test_case {
+    ($test:expr; [should_panic] $($rest:tt)*) => {
+        $crate::__test_case!($test.should_panic(); $($rest)*)
+    };
+    ($test:expr; [ignore] $($rest:tt)*) => {
+        $crate::__test_case!($test.ignore(); $($rest)*)
+    };
+    ($test:expr; [timeout($ms:expr)] $($rest:tt)*) => {
+        $crate::__test_case!($test.timeout($ms); $($rest)*)
+    };
+    ($test:expr; [$($other:tt)*] $($rest:tt)*) => {
+        $crate::__test_case!($test; $($rest)*)
+    };
+    ($test:expr;) => {
+        $test
+    };
+}
+
+// End of file
@@ -0,0 +1,497 @@
+//! An XMODEM-1K and YMODEM receiver, for uploading files over a UART
+//!
+//! Both protocols send a file as a series of numbered blocks of 128 or 1024
+//! bytes, each followed by a CRC-16. We acknowledge each good block, ask for
+//! any bad block to be sent again, and write the data into a caller-supplied
+//! [`embedded_storage::Storage`] - such as a [`MemoryStorage`] covering a
+//! spare region of RAM, or a [`HostDisk`](crate::host_disk::HostDisk).
+//!
+//! YMODEM adds a block zero holding the file name and length, so we know how
+//! long the file really is. XMODEM pads the last block with `0x1A` bytes, and
+//! the length we report includes that padding.
+//!
+//! We need to know when the sender has gone quiet, so as well as a UART
+//! (anything implementing [`embedded_io::Read`], [`embedded_io::ReadReady`]
+//! and [`embedded_io::Write`]) you pass in something that implements
+//! [`Timeout`], such as the CMSDK `DelayTimer` (with the `cmsdk` feature).
+//!
+//! The `qemu-xmodem` tool in `../qemu-xmodem` can send files to a QEMU
+//! machine's UART telnet server.
+
+use embedded_io::{Read, ReadReady, Write};
+use embedded_storage::Storage;
+
+/// Start of a 128 byte block
+const SOH: u8 = 0x01;
+/// Start of a 1024 byte block
+const STX: u8 = 0x02;
+/// End of transmission
+const EOT: u8 = 0x04;
+/// Block received OK
+const ACK: u8 = 0x06;
+/// Block not received OK - please send it again
+const NAK: u8 = 0x15;
+/// Cancel the transfer (sent twice)
+const CAN: u8 = 0x18;
+/// Asks the sender to use CRC-16 rather than the old 8-bit checksum
+const CRC_MODE: u8 = b'C';
+
+/// How long we wait for the sender to start, between each `C`, in ms
+const START_TIMEOUT_MS: u32 = 3000;
+/// How many times we send `C` before giving up on the sender
+const START_ATTEMPTS: u32 = 20;
+/// How long we wait for the next block to start, in ms
+const BLOCK_TIMEOUT_MS: u32 = 10_000;
+/// How long we wait for each byte within a block, in ms
+const BYTE_TIMEOUT_MS: u32 = 1000;
+/// How long the line has to go quiet before we send a NAK, in ms
+const PURGE_TIMEOUT_MS: u32 = 200;
+/// How many bad blocks in a row we put up with
+const MAX_RETRIES: u32 = 10;
+
+/// The longest YMODEM file name we keep
+pub const MAX_NAME_LEN: usize = 64;
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// Reading from or writing to the UART failed
+    Port,
+    /// Writing to the storage failed
+    Storage,
+    /// The sender never started
+    NoSender,
+    /// The sender cancelled the transfer
+    Cancelled,
+    /// Too many bad blocks in a row
+    TooManyErrors,
+    /// The sender skipped a block
+    OutOfSequence,
+    /// The file doesn't fit in the storage
+    TooBig,
+    /// The YMODEM header block didn't make sense
+    BadHeader,
+}
+
+/// Something that can tell us when some time has passed
+pub trait Timeout {
+    /// Start (or restart) the countdown
+    fn start(&mut self, ms: u32);
+
+    /// Has the time passed since we called [`start`](Timeout::start)?
+    fn expired(&mut self) -> bool;
+}
+
+#[cfg(feature = "cmsdk")]
+impl Timeout for crate::cmsdk_timer::DelayTimer {
+    fn start(&mut self, ms: u32) {
+        let ticks = u64::from(ms) * u64::from(self.sys_clk_hz()) / 1000;
+        self.timer.disable();
+        self.timer.clear_interrupt();
+        // The interrupt flag only gets set if the interrupt is enabled in the
+        // timer. We don't unmask it in the NVIC.
+        self.timer.enable_interrupt(true);
+        self.timer
+            .write_value(ticks.min(u64::from(u32::MAX)) as u32);
+        self.timer.enable();
+    }
+
+    fn expired(&mut self) -> bool {
+        self.timer.interrupt_fired()
+    }
+}
+
+/// Which protocol the sender is using
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Protocol {
+    /// XMODEM, with CRC-16 and 128 or 1024 byte blocks
+    Xmodem,
+    /// YMODEM - we take the first file of a batch
+    Ymodem,
+}
+
+/// A file we received
+#[derive(Debug, Clone, PartialEq, Eq, defmt::Format)]
+pub struct Received {
+    /// How many bytes we wrote to the storage
+    pub len: usize,
+    /// The file name, for YMODEM transfers
+    pub name: heapless::String<MAX_NAME_LEN>,
+}
+
+/// A region of memory that you can receive a file into
+pub struct MemoryStorage<'a> {
+    memory: &'a mut [u8],
+}
+
+impl<'a> MemoryStorage<'a> {
+    /// Wrap a slice
+    pub fn new(memory: &'a mut [u8]) -> MemoryStorage<'a> {
+        MemoryStorage { memory }
+    }
+
+    /// Get the contents back
+    pub fn as_slice(&self) -> &[u8] {
+        self.memory
+    }
+}
+
+impl embedded_storage::ReadStorage for MemoryStorage<'_> {
+    type Error = Error;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Error> {
+        let start = offset as usize;
+        let source = self
+            .memory
+            .get(start..start + bytes.len())
+            .ok_or(Error::TooBig)?;
+        bytes.copy_from_slice(source);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        self.memory.len()
+    }
+}
+
+impl Storage for MemoryStorage<'_> {
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Error> {
+        let start = offset as usize;
+        let dest = self
+            .memory
+            .get_mut(start..start + bytes.len())
+            .ok_or(Error::TooBig)?;
+        dest.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// What arrived when we were waiting for a block
+enum Packet {
+    /// A block with a good CRC
+    Block { number: u8, len: usize },
+    /// The sender has finished
+    EndOfTransmission,
+    /// Something went wrong, and we should ask again
+    Bad,
+}
+
+/// Receives files over a UART
+pub struct Receiver<P, T> {
+    port: P,
+    timer: T,
+    block: [u8; 1024],
+}
+
+impl<P, T> Receiver<P, T>
+where
+    P: Read + ReadReady + Write,
+    T: Timeout,
+{
+    /// Make a receiver, using this UART and timer
+    pub fn new(port: P, timer: T) -> Receiver<P, T> {
+        Receiver {
+            port,
+            timer,
+            block: [0; 1024],
+        }
+    }
+
+    /// Give back the UART and the timer
+    pub fn free(self) -> (P, T) {
+        (self.port, self.timer)
+    }
+
+    /// Receive one file into `storage`, starting at offset zero
+    ///
+    /// If anything goes wrong we cancel the transfer, so the sender doesn't
+    /// sit there waiting for us.
+    pub fn receive<S>(&mut self, protocol: Protocol, storage: &mut S) -> Result<Received, Error>
+    where
+        S: Storage,
+    {
+        let result = self.receive_inner(protocol, storage);
+        // Don't bother cancelling if the sender has gone, or already did
+        if result
+            .as_ref()
+            .is_err_and(|e| !matches!(e, Error::Port | Error::NoSender | Error::Cancelled))
+        {
+            self.cancel();
+        }
+        result
+    }
+
+    fn receive_inner<S>(&mut self, protocol: Protocol, storage: &mut S) -> Result<Received, Error>
+    where
+        S: Storage,
+    {
+        let mut received = Received {
+            len: 0,
+            name: heapless::String::new(),
+        };
+        let mut file_len = None;
+        if protocol == Protocol::Ymodem {
+            let (name, size) = self.wait_for_header()?;
+            if name.is_empty() {
+                // An empty batch - there's no file
+                self.send(ACK)?;
+                return Ok(received);
+            }
+            if size.is_some_and(|size| size > storage.capacity()) {
+                return Err(Error::TooBig);
+            }
+            defmt::debug!("YMODEM file {}, length {}", name.as_str(), size);
+            received.name = name;
+            file_len = size;
+            self.send(ACK)?;
+        }
+
+        // Now the data blocks, which count up from one. For YMODEM, the
+        // sender needs another `C` before it starts.
+        let mut expected = 1u8;
+        let mut next = self.wait_for_data(expected)?;
+        while let Some(len) = next {
+            let data = &self.block[..len];
+            let keep = match file_len {
+                Some(file_len) => data.len().min(file_len - received.len),
+                None => data.len(),
+            };
+            if received.len + keep > storage.capacity() {
+                return Err(Error::TooBig);
+            }
+            storage
+                .write(received.len as u32, &data[..keep])
+                .map_err(|_| Error::Storage)?;
+            received.len += keep;
+            self.send(ACK)?;
+            expected = expected.wrapping_add(1);
+            next = self.next_block(expected)?;
+        }
+        self.finish(protocol)?;
+        Ok(received)
+    }
+
+    /// Wait for block `expected`, sending `C` until the sender starts
+    ///
+    /// Returns `None` if the sender sent EOT instead.
+    fn wait_for_data(&mut self, expected: u8) -> Result<Option<usize>, Error> {
+        let mut reply = CRC_MODE;
+        let mut retries = 0;
+        for _ in 0..START_ATTEMPTS {
+            self.send(reply)?;
+            match self.read_packet(START_TIMEOUT_MS)? {
+                Some(Packet::Block { number, len }) if number == expected => {
+                    return Ok(Some(len));
+                }
+                Some(Packet::Block { .. }) => return Err(Error::OutOfSequence),
+                Some(Packet::EndOfTransmission) => return Ok(None),
+                Some(Packet::Bad) => {
+                    // The sender has started, so ask for the block again
+                    retries += 1;
+                    if retries > MAX_RETRIES {
+                        return Err(Error::TooManyErrors);
+                    }
+                    self.purge()?;
+                    reply = NAK;
+                }
+                None => reply = CRC_MODE,
+            }
+        }
+        Err(Error::NoSender)
+    }
+
+    /// Wait for a YMODEM header block, and get the file name and length
+    fn wait_for_header(
+        &mut self,
+    ) -> Result<(heapless::String<MAX_NAME_LEN>, Option<usize>), Error> {
+        match self.wait_for_data(0)? {
+            Some(len) => parse_header(&self.block[..len]),
+            None => Err(Error::BadHeader),
+        }
+    }
+
+    /// Wait for the next block, asking for it again if it goes wrong
+    ///
+    /// Returns `None` when the sender sends EOT.
+    fn next_block(&mut self, expected: u8) -> Result<Option<usize>, Error> {
+        let mut retries = 0;
+        loop {
+            match self.read_packet(BLOCK_TIMEOUT_MS)? {
+                Some(Packet::Block { number, len }) if number == expected => {
+                    return Ok(Some(len));
+                }
+                Some(Packet::Block { number, .. }) if number == expected.wrapping_sub(1) => {
+                    // Our ACK got lost, and they sent the last block again
+                    defmt::debug!("Duplicate block {}", number);
+                    self.send(ACK)?;
+                    continue;
+                }
+                Some(Packet::Block { .. }) => return Err(Error::OutOfSequence),
+                Some(Packet::EndOfTransmission) => return Ok(None),
+                Some(Packet::Bad) | None => {
+                    retries += 1;
+                    if retries > MAX_RETRIES {
+                        return Err(Error::TooManyErrors);
+                    }
+                    self.purge()?;
+                    self.send(NAK)?;
+                }
+            }
+        }
+    }
+
+    /// Deal with the end of the file, once the sender has sent EOT
+    fn finish(&mut self, protocol: Protocol) -> Result<(), Error> {
+        if protocol == Protocol::Xmodem {
+            return self.send(ACK);
+        }
+        // YMODEM senders expect a NAK for the first EOT, then send it again
+        self.send(NAK)?;
+        match self.read_packet(BYTE_TIMEOUT_MS)? {
+            Some(Packet::EndOfTransmission) => self.send(ACK)?,
+            _ => return Err(Error::TooManyErrors),
+        }
+        // Then they offer the next file in the batch. We only take one, so
+        // we accept the empty header that ends the batch, or cancel if there
+        // is another file.
+        let (name, _) = self.wait_for_header()?;
+        if name.is_empty() {
+            self.send(ACK)?;
+        } else {
+            defmt::warn!("Ignoring the rest of the YMODEM batch");
+            self.cancel();
+        }
+        Ok(())
+    }
+
+    /// Read a block (or an EOT), and check its CRC
+    ///
+    /// Returns `None` if nothing arrived in time.
+    fn read_packet(&mut self, first_byte_timeout_ms: u32) -> Result<Option<Packet>, Error> {
+        let Some(start) = self.read_byte(first_byte_timeout_ms)? else {
+            return Ok(None);
+        };
+        let len = match start {
+            SOH => 128,
+            STX => 1024,
+            EOT => return Ok(Some(Packet::EndOfTransmission)),
+            CAN => {
+                // Two CANs in a row mean the sender has given up
+                return match self.read_byte(BYTE_TIMEOUT_MS)? {
+                    Some(CAN) => Err(Error::Cancelled),
+                    _ => Ok(Some(Packet::Bad)),
+                };
+            }
+            other => {
+                defmt::debug!("Unexpected byte {=u8:#04x}", other);
+                return Ok(Some(Packet::Bad));
+            }
+        };
+        let mut header = [0u8; 2];
+        let mut crc = [0u8; 2];
+        for byte in header.iter_mut() {
+            let Some(b) = self.read_byte(BYTE_TIMEOUT_MS)? else {
+                return Ok(Some(Packet::Bad));
+            };
+            *byte = b;
+        }
+        for index in 0..len {
+            let Some(b) = self.read_byte(BYTE_TIMEOUT_MS)? else {
+                return Ok(Some(Packet::Bad));
+            };
+            self.block[index] = b;
+        }
+        for byte in crc.iter_mut() {
+            let Some(b) = self.read_byte(BYTE_TIMEOUT_MS)? else {
+                return Ok(Some(Packet::Bad));
+            };
+            *byte = b;
+        }
+        if header[0] != !header[1] {
+            defmt::debug!("Bad block number {=[u8]:#04x}", header);
+            return Ok(Some(Packet::Bad));
+        }
+        if crc16(&self.block[..len]) != u16::from_be_bytes(crc) {
+            defmt::debug!("Bad CRC on block {}", header[0]);
+            return Ok(Some(Packet::Bad));
+        }
+        Ok(Some(Packet::Block {
+            number: header[0],
+            len,
+        }))
+    }
+
+    /// Read one byte, or `None` if nothing arrives in time
+    fn read_byte(&mut self, timeout_ms: u32) -> Result<Option<u8>, Error> {
+        self.timer.start(timeout_ms);
+        loop {
+            if self.port.read_ready().map_err(|_| Error::Port)? {
+                let mut byte = [0u8; 1];
+                if self.port.read(&mut byte).map_err(|_| Error::Port)? == 1 {
+                    return Ok(Some(byte[0]));
+                }
+            }
+            if self.timer.expired() {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Throw away anything that arrives until the line goes quiet
+    fn purge(&mut self) -> Result<(), Error> {
+        while self.read_byte(PURGE_TIMEOUT_MS)?.is_some() {}
+        Ok(())
+    }
+
+    /// Send one control byte
+    fn send(&mut self, byte: u8) -> Result<(), Error> {
+        self.port.write_all(&[byte]).map_err(|_| Error::Port)?;
+        self.port.flush().map_err(|_| Error::Port)
+    }
+
+    /// Tell the sender we're giving up
+    fn cancel(&mut self) {
+        // Best effort - if the UART is broken there's nothing more we can do
+        let _ = self.port.write_all(&[CAN; 3]);
+        let _ = self.port.flush();
+    }
+}
+
+/// Parse a YMODEM header block: the file name, a NUL, then the length in
+/// decimal (followed by optional fields we ignore)
+fn parse_header(block: &[u8]) -> Result<(heapless::String<MAX_NAME_LEN>, Option<usize>), Error> {
+    let mut fields = block.split(|b| *b == 0);
+    let name = fields.next().ok_or(Error::BadHeader)?;
+    let mut name_out = heapless::String::new();
+    let name = core::str::from_utf8(name).map_err(|_| Error::BadHeader)?;
+    // Keep as much of the name as fits
+    for c in name.chars() {
+        if name_out.push(c).is_err() {
+            break;
+        }
+    }
+    let size = fields
+        .next()
+        .and_then(|info| info.split(|b| *b == b' ').next())
+        .and_then(|size| core::str::from_utf8(size).ok())
+        .and_then(|size| size.parse().ok());
+    Ok((name_out, size))
+}
+
+/// CRC-16/XMODEM (polynomial 0x1021, starting from zero)
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+// End of file
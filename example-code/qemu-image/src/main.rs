@@ -0,0 +1,219 @@
+//! Signs application images for the qemu-thumbv7em bootloader
+//!
+//! An application built with the `slot-a` or `slot-b` feature has an image
+//! header at the start of its slot, but the firmware can't know its own
+//! length or checksum. This tool works out what QEMU will load into the slot,
+//! fills in the header, and writes it back into the ELF file:
+//!
+//! ```console
+//! $ qemu-image sign ../qemu-thumbv7em/target/thumbv7em-none-eabihf/debug/defmt
+//! ```
+//!
+//! `qemu-image info <elf-file>` prints the header without changing anything.
+//!
+//! The header format, and the CRC, come from `qemu_common::boot_image`, which
+//! the bootloader uses to check the image.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+use object::{
+    Endianness, Object, ObjectSection,
+    elf::{FileHeader32, PT_LOAD},
+    read::elf::{FileHeader, ProgramHeader},
+};
+use qemu_common::boot_image::{FIELDS_LEN, FORMAT, HEADER_LEN, ImageHeader, MAGIC, crc32};
+
+/// The name of the section holding the header
+const HEADER_SECTION: &str = ".image_header";
+
+/// What to do when things go wrong
+type Error = Box<dyn std::error::Error>;
+
+/// Where the header is, in memory and in the ELF file
+struct HeaderLocation {
+    address: u32,
+    file_offset: usize,
+}
+
+/// Find the image header section
+fn find_header(elf: &[u8]) -> Result<HeaderLocation, Error> {
+    let file = object::File::parse(elf)?;
+    let section = file
+        .section_by_name(HEADER_SECTION)
+        .ok_or("no .image_header section - was this built with the slot-a or slot-b feature?")?;
+    let (offset, size) = section
+        .file_range()
+        .ok_or(".image_header section has no data")?;
+    if size < FIELDS_LEN as u64 {
+        return Err(".image_header section is too small".into());
+    }
+    Ok(HeaderLocation {
+        address: section.address() as u32,
+        file_offset: offset as usize,
+    })
+}
+
+/// Build the flat image QEMU will load after the header
+///
+/// We use the physical (load) addresses, because that's where QEMU puts
+/// each segment - for `.data` that's in FLASH, not RAM. Gaps between
+/// segments are filled with zeros, like QEMU leaves them.
+fn flat_image(elf: &[u8], start: u32) -> Result<Vec<u8>, Error> {
+    let header = FileHeader32::<Endianness>::parse(elf)?;
+    let endian = header.endian()?;
+    let mut image = Vec::new();
+    for segment in header.program_headers(endian, elf)? {
+        let address = segment.p_paddr(endian);
+        let data = segment
+            .data(endian, elf)
+            .map_err(|_| "segment data is out of bounds")?;
+        if segment.p_type(endian) != PT_LOAD || data.is_empty() || address < start {
+            continue;
+        }
+        let offset = (address - start) as usize;
+        let end = offset + data.len();
+        if image.len() < end {
+            image.resize(end, 0);
+        }
+        image[offset..end].copy_from_slice(data);
+    }
+    Ok(image)
+}
+
+/// Read the header an application reserved, and fill it in for `image`
+///
+/// `fields` is the start of the header, and gets the signed header back.
+fn sign_header(fields: &mut [u8], image: &[u8]) -> Result<ImageHeader, Error> {
+    let mut header = ImageHeader::from_bytes(fields).ok_or("the image header is too short")?;
+    if header.magic != MAGIC || header.format != FORMAT || header.header_len != HEADER_LEN as u32 {
+        return Err("the image header isn't in a format we understand".into());
+    }
+    header.sign(image);
+    fields[..FIELDS_LEN].copy_from_slice(&header.to_bytes());
+    Ok(header)
+}
+
+/// Fill in the image header in an ELF file
+fn sign(path: &str) -> Result<(), Error> {
+    let mut elf = std::fs::read(path)?;
+    let location = find_header(&elf)?;
+    let image = flat_image(&elf, location.address + HEADER_LEN as u32)?;
+
+    let header = sign_header(&mut elf[location.file_offset..][..FIELDS_LEN], &image)?;
+    println!(
+        "Signed {path}: version {}, {} bytes at {:#010x}",
+        header.version(),
+        image.len(),
+        location.address
+    );
+
+    std::fs::write(path, &elf)?;
+    Ok(())
+}
+
+/// Print the image header in an ELF file, and whether it's right
+fn info(path: &str) -> Result<(), Error> {
+    let elf = std::fs::read(path)?;
+    let location = find_header(&elf)?;
+    let image = flat_image(&elf, location.address + HEADER_LEN as u32)?;
+    let header = ImageHeader::from_bytes(&elf[location.file_offset..])
+        .ok_or("the image header is too short")?;
+
+    println!("Slot address: {:#010x}", location.address);
+    println!("Magic:        {:#010x}", header.magic);
+    println!("Format:       {}", header.format);
+    println!("Version:      {}", header.version());
+    println!(
+        "Image length: {} (actual {})",
+        header.image_len,
+        image.len()
+    );
+    println!(
+        "Image CRC:    {:#010x} (actual {:#010x})",
+        header.image_crc32,
+        crc32(&image)
+    );
+    println!(
+        "Header CRC:   {:#010x} (actual {:#010x})",
+        header.header_crc32,
+        header.expected_header_crc32()
+    );
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.as_slice() {
+        [command, path] if command == "sign" => sign(path),
+        [command, path] if command == "info" => info(path),
+        _ => {
+            eprintln!("Usage: qemu-image sign <elf-file>");
+            eprintln!("       qemu-image info <elf-file>");
+            std::process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use qemu_common::boot_image::{self, Version};
+
+    use super::*;
+
+    /// An unsigned header, padded out to [`HEADER_LEN`], as the firmware
+    /// reserves it
+    fn unsigned_slot() -> Vec<u8> {
+        let version = Version {
+            major: 1,
+            minor: 2,
+            patch: 3,
+        };
+        let mut slot = ImageHeader::unsigned(version).to_bytes().to_vec();
+        slot.resize(HEADER_LEN, 0xFF);
+        slot
+    }
+
+    #[test]
+    fn crc32_matches_the_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn signed_header_passes_the_bootloader_check() {
+        let image: Vec<u8> = (0..3000).map(|i| (i * 7) as u8).collect();
+        let mut slot = unsigned_slot();
+        let signed = sign_header(&mut slot, &image).unwrap();
+        slot.extend_from_slice(&image);
+
+        assert_eq!(ImageHeader::from_bytes(&slot), Some(signed));
+        assert_eq!(signed.image_len, 3000);
+        assert_eq!(signed.image_crc32, crc32(&image));
+        assert_eq!(signed.version().to_string(), "1.2.3");
+        // Safety: `slot` is a readable buffer of `slot.len()` bytes
+        let checked = unsafe { boot_image::check(slot.as_ptr(), slot.len()) };
+        assert_eq!(checked, Ok(signed));
+    }
+
+    #[test]
+    fn header_fields_are_little_endian_words() {
+        let mut slot = unsigned_slot();
+        sign_header(&mut slot, b"image").unwrap();
+        let word = |offset: usize| u32::from_le_bytes(slot[offset..offset + 4].try_into().unwrap());
+        assert_eq!(word(0), MAGIC);
+        assert_eq!(word(4), FORMAT);
+        assert_eq!(word(8), HEADER_LEN as u32);
+        assert_eq!(word(12), 5);
+        assert_eq!(word(16), crc32(b"image"));
+        assert_eq!(word(20), 0x0102_0003);
+        assert_eq!(word(24), crc32(&slot[..24]));
+    }
+
+    #[test]
+    fn refuses_to_sign_something_else() {
+        let mut slot = vec![0; HEADER_LEN];
+        assert!(sign_header(&mut slot, b"image").is_err());
+        assert!(sign_header(&mut [0; 8], b"image").is_err());
+    }
+}
+
+// End of file
@@ -0,0 +1,344 @@
+//! Symbolises profiles taken by the qemu-thumbv7em sampling profiler
+//!
+//! The firmware writes a table of (PC, LR, count) samples to a file. We look
+//! up which function each address is in, using the symbol table in the ELF
+//! file, and print either a flat profile:
+//!
+//! ```console
+//! $ qemu-profile ../qemu-thumbv7em/target/thumbv7em-none-eabihf/debug/profile profile.bin
+//! ```
+//!
+//! or folded stacks, ready for `inferno-flamegraph` or `flamegraph.pl`:
+//!
+//! ```console
+//! $ qemu-profile --folded ../qemu-thumbv7em/target/thumbv7em-none-eabihf/debug/profile profile.bin | inferno-flamegraph > profile.svg
+//! ```
+//!
+//! We only know the PC and the LR for each sample, so the stacks are at most
+//! two functions deep. LR is only the caller's return address if the function
+//! that was running hasn't re-used it, so treat the callers as a hint.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+use std::{collections::BTreeMap, io::Write};
+
+use object::{Object, ObjectSymbol, SymbolKind};
+
+/// Identifies a profile file
+const FILE_MAGIC: &[u8; 4] = b"QPRF";
+
+/// The version of the file format we understand
+const FILE_VERSION: u32 = 1;
+
+/// The size of the file header, in bytes
+const HEADER_LEN: usize = 24;
+
+/// The size of each sample in the file, in bytes
+const SAMPLE_LEN: usize = 12;
+
+/// What to do when things go wrong
+type Error = Box<dyn std::error::Error>;
+
+/// A profile, as read from the file the firmware wrote
+struct Profile {
+    rate_hz: u32,
+    total: u32,
+    dropped: u32,
+    samples: Vec<Sample>,
+}
+
+/// One entry from the profile table
+struct Sample {
+    pc: u32,
+    lr: u32,
+    count: u32,
+}
+
+impl Profile {
+    /// Parse a profile file
+    fn parse(data: &[u8]) -> Result<Profile, Error> {
+        let word = |offset: usize| -> Result<u32, Error> {
+            let bytes = data
+                .get(offset..offset + 4)
+                .ok_or("profile file is truncated")?;
+            Ok(u32::from_le_bytes(bytes.try_into()?))
+        };
+        if data.get(0..4) != Some(FILE_MAGIC) {
+            return Err("not a profile file (bad magic number)".into());
+        }
+        let version = word(4)?;
+        if version != FILE_VERSION {
+            return Err(
+                format!("profile file is version {version}, we need {FILE_VERSION}").into(),
+            );
+        }
+        let unique = word(20)? as usize;
+        let samples = (0..unique)
+            .map(|i| {
+                let offset = HEADER_LEN + i * SAMPLE_LEN;
+                Ok(Sample {
+                    pc: word(offset)?,
+                    lr: word(offset + 4)?,
+                    count: word(offset + 8)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Profile {
+            rate_hz: word(8)?,
+            total: word(12)?,
+            dropped: word(16)?,
+            samples,
+        })
+    }
+}
+
+/// The functions in an ELF file, sorted by address
+struct Symbols {
+    functions: Vec<Function>,
+}
+
+/// One function from the ELF symbol table
+struct Function {
+    start: u32,
+    end: u32,
+    name: String,
+}
+
+impl Symbols {
+    /// Read the function symbols from an ELF file
+    fn load(elf: &[u8]) -> Result<Symbols, Error> {
+        let file = object::File::parse(elf)?;
+        let mut functions: Vec<Function> = file
+            .symbols()
+            .filter(|sym| sym.kind() == SymbolKind::Text)
+            .filter_map(|sym| {
+                // Thumb function addresses have the bottom bit set
+                let start = sym.address() as u32 & !1;
+                let name = rustc_demangle::demangle(sym.name().ok()?);
+                Some(Function {
+                    start,
+                    end: start + sym.size() as u32,
+                    name: format!("{name:#}"),
+                })
+            })
+            .collect();
+        functions.sort_by_key(|f| f.start);
+        Ok(Symbols { functions })
+    }
+
+    /// Which function is this address in?
+    fn lookup(&self, address: u32) -> Option<&str> {
+        let index = self.functions.partition_point(|f| f.start <= address);
+        let function = self.functions.get(index.checked_sub(1)?)?;
+        (address < function.end).then_some(function.name.as_str())
+    }
+
+    /// Which function called us, going by the LR value?
+    fn caller(&self, lr: u32) -> Option<&str> {
+        // An EXC_RETURN value means we interrupted an exception handler just
+        // after it started, before it called anything
+        if lr >= 0xF000_0000 {
+            return Some("[exception]");
+        }
+        // LR points at the instruction after the call, which might be in the
+        // next function if the call was the last instruction
+        self.lookup((lr & !1).checked_sub(1)?)
+    }
+}
+
+/// Print the functions we found the PC in most often
+fn print_flat(out: &mut impl Write, profile: &Profile, symbols: &Symbols) -> std::io::Result<()> {
+    let mut by_function: BTreeMap<&str, u32> = BTreeMap::new();
+    for sample in &profile.samples {
+        let name = symbols.lookup(sample.pc).unwrap_or("[unknown]");
+        *by_function.entry(name).or_default() += sample.count;
+    }
+    let mut rows: Vec<_> = by_function.into_iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let counted = profile.total.saturating_sub(profile.dropped);
+    writeln!(
+        out,
+        "{} samples at {} Hz ({} dropped)",
+        profile.total, profile.rate_hz, profile.dropped
+    )?;
+    writeln!(out, "{:>7} {:>8}  function", "%", "samples")?;
+    for (name, count) in rows {
+        let percent = 100.0 * f64::from(count) / f64::from(counted.max(1));
+        writeln!(out, "{percent:>7.2} {count:>8}  {name}")?;
+    }
+    Ok(())
+}
+
+/// Print `caller;callee count` lines, for making a flame graph
+fn print_folded(out: &mut impl Write, profile: &Profile, symbols: &Symbols) -> std::io::Result<()> {
+    let mut stacks: BTreeMap<String, u32> = BTreeMap::new();
+    for sample in &profile.samples {
+        let callee = symbols.lookup(sample.pc).unwrap_or("[unknown]");
+        let stack = match symbols.caller(sample.lr) {
+            Some(caller) if caller != callee => format!("{caller};{callee}"),
+            _ => callee.to_string(),
+        };
+        *stacks.entry(stack).or_default() += sample.count;
+    }
+    for (stack, count) in stacks {
+        writeln!(out, "{stack} {count}")?;
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let folded = args.first().is_some_and(|arg| arg == "--folded");
+    if folded {
+        args.remove(0);
+    }
+    let [elf_path, profile_path] = args.as_slice() else {
+        eprintln!("Usage: qemu-profile [--folded] <elf-file> <profile-file>");
+        std::process::exit(2);
+    };
+
+    let symbols = Symbols::load(&std::fs::read(elf_path)?)?;
+    let profile = Profile::parse(&std::fs::read(profile_path)?)?;
+    let mut out = std::io::stdout().lock();
+    if folded {
+        print_folded(&mut out, &profile, &symbols)?;
+    } else {
+        print_flat(&mut out, &profile, &symbols)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A profile file holding `samples`, as (PC, LR, count)
+    fn profile_file(samples: &[(u32, u32, u32)]) -> Vec<u8> {
+        let total: u32 = samples.iter().map(|s| s.2).sum();
+        let mut data = FILE_MAGIC.to_vec();
+        for word in [FILE_VERSION, 1000, total + 2, 2, samples.len() as u32] {
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        for (pc, lr, count) in samples {
+            for word in [pc, lr, count] {
+                data.extend_from_slice(&word.to_le_bytes());
+            }
+        }
+        data
+    }
+
+    /// Three functions, one after the other
+    fn symbols() -> Symbols {
+        let function = |start, end, name: &str| Function {
+            start,
+            end,
+            name: name.to_string(),
+        };
+        Symbols {
+            functions: vec![
+                function(0x100, 0x140, "main"),
+                function(0x140, 0x180, "app::work"),
+                function(0x180, 0x190, "app::idle"),
+            ],
+        }
+    }
+
+    /// Run a printer over a profile with a bit of everything in it
+    fn output(print: fn(&mut Vec<u8>, &Profile, &Symbols) -> std::io::Result<()>) -> String {
+        let data = profile_file(&[
+            // in `app::work`, called from `main`
+            (0x150, 0x121, 6),
+            // in `app::idle`, in an exception handler that has just started
+            (0x184, 0xFFFF_FFF9, 3),
+            // in `app::work` again, with an LR it got from its own call
+            (0x160, 0x171, 1),
+            // somewhere we have no symbol for
+            (0x2000, 0x121, 2),
+        ]);
+        let profile = Profile::parse(&data).unwrap();
+        let mut out = Vec::new();
+        print(&mut out, &profile, &symbols()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut data = profile_file(&[]);
+        data[0] = b'X';
+        let error = Profile::parse(&data).err().unwrap();
+        assert!(error.to_string().contains("bad magic"), "{error}");
+    }
+
+    #[test]
+    fn rejects_the_wrong_version() {
+        let mut data = profile_file(&[]);
+        data[4..8].copy_from_slice(&2u32.to_le_bytes());
+        let error = Profile::parse(&data).err().unwrap();
+        assert!(error.to_string().contains("version 2"), "{error}");
+    }
+
+    #[test]
+    fn rejects_a_truncated_sample_table() {
+        let mut data = profile_file(&[(0x150, 0x121, 6), (0x184, 0x121, 3)]);
+        data.truncate(data.len() - 4);
+        let error = Profile::parse(&data).err().unwrap();
+        assert!(error.to_string().contains("truncated"), "{error}");
+    }
+
+    #[test]
+    fn parses_the_header_and_samples() {
+        let profile = Profile::parse(&profile_file(&[(0x150, 0x121, 6)])).unwrap();
+        assert_eq!(profile.rate_hz, 1000);
+        assert_eq!(profile.total, 8);
+        assert_eq!(profile.dropped, 2);
+        assert_eq!(profile.samples.len(), 1);
+        let sample = &profile.samples[0];
+        assert_eq!((sample.pc, sample.lr, sample.count), (0x150, 0x121, 6));
+    }
+
+    #[test]
+    fn looks_up_functions_and_callers() {
+        let symbols = symbols();
+        assert_eq!(symbols.lookup(0xFF), None);
+        assert_eq!(symbols.lookup(0x100), Some("main"));
+        assert_eq!(symbols.lookup(0x18F), Some("app::idle"));
+        assert_eq!(symbols.lookup(0x190), None);
+        // A call as the last instruction of `main` returns to `app::work`
+        assert_eq!(symbols.caller(0x141), Some("main"));
+        assert_eq!(symbols.caller(0xFFFF_FFF9), Some("[exception]"));
+    }
+
+    #[test]
+    fn prints_a_flat_profile() {
+        let out = output(print_flat);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "14 samples at 1000 Hz (2 dropped)",
+                "      %  samples  function",
+                "  58.33        7  app::work",
+                "  25.00        3  app::idle",
+                "  16.67        2  [unknown]",
+            ]
+        );
+    }
+
+    #[test]
+    fn prints_folded_stacks() {
+        let out = output(print_folded);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "[exception];app::idle 3",
+                "app::work 1",
+                "main;[unknown] 2",
+                "main;app::work 6",
+            ]
+        );
+    }
+}
+
+// End of file
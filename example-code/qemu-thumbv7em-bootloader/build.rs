@@ -0,0 +1,43 @@
+//! # Build script for the QEMU Armv7E-M bootloader
+//!
+//! This script only executes when using `cargo` to build the project.
+
+use std::io::Write;
+
+/// The machines we support, and where their FLASH and RAM start
+const MACHINES: [(&str, u32, u32); 4] = [
+    ("an385", 0x0000_0000, 0x2000_0000),
+    ("an386", 0x0000_0000, 0x2000_0000),
+    ("an500", 0x0000_0000, 0x2000_0000),
+    ("an505", 0x1000_0000, 0x3800_0000),
+];
+
+fn main() {
+    let selected: Vec<_> = MACHINES
+        .iter()
+        .filter(|(feature, _, _)| {
+            std::env::var_os(format!("CARGO_FEATURE_{}", feature.to_uppercase())).is_some()
+        })
+        .collect();
+    let [(machine, flash, ram)] = selected.as_slice() else {
+        panic!("Enable exactly one of the an385, an386, an500 or an505 features");
+    };
+
+    // We get the first 64 KiB of FLASH - the application slots come after
+    // that. We leave the last 1 KiB of RAM alone, because the application
+    // keeps its crash log there.
+    let out = &std::path::PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+    let mut memory_file = std::fs::File::create(out.join("memory.x")).unwrap();
+    write!(
+        memory_file,
+        "/* Generated by build.rs for the {machine} */\n\
+        MEMORY\n\
+        {{\n  \
+          FLASH : ORIGIN = {flash:#010x}, LENGTH = 64K\n    \
+            RAM : ORIGIN = {ram:#010x}, LENGTH = 4M - 1K\n\
+        }}\n"
+    )
+    .unwrap();
+    println!("cargo:rustc-link-arg=-Tlink.x");
+    println!("cargo:rustc-link-search={}", out.display());
+}
@@ -0,0 +1,88 @@
+//! A second-stage bootloader for QEMU's Armv7E-M Virtual Machine
+//!
+//! We sit in the first 64 KiB of FLASH. After us come two slots, A and B,
+//! each of which can hold an application image with a header on the front
+//! (see `qemu_common::boot_image`). We check both slots, pick the valid image
+//! with the highest version (or slot A, if they're the same), point VTOR at
+//! its vector table, and jump to it. If one slot is empty or corrupt we run
+//! the other one, and if both are, we give up.
+//!
+//! We print to stderr using semihosting, so we don't get mixed up with the
+//! application's defmt logs on stdout.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_common::boot_image::{self, HEADER_LEN, ImageHeader};
+use semihosting::eprintln;
+
+/// Where FLASH starts on this machine
+#[cfg(not(feature = "an505"))]
+const FLASH_ORIGIN: usize = 0x0000_0000;
+
+/// Where FLASH starts on this machine
+#[cfg(feature = "an505")]
+const FLASH_ORIGIN: usize = 0x1000_0000;
+
+/// The size of each slot, including the image header
+///
+/// This must match `qemu-thumbv7em/build.rs`.
+const SLOT_LEN: usize = 0x0018_0000;
+
+/// Our slots, and where they start
+const SLOTS: [(&str, usize); 2] = [
+    ("A", FLASH_ORIGIN + 0x0001_0000),
+    ("B", FLASH_ORIGIN + 0x0001_0000 + SLOT_LEN),
+];
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    eprintln!("Bootloader: checking slots");
+    let mut chosen: Option<(&str, usize, ImageHeader)> = None;
+    for (name, start) in SLOTS {
+        // Safety: every slot is inside FLASH, which we can always read
+        match unsafe { boot_image::check(start as *const u8, SLOT_LEN) } {
+            Ok(header) => {
+                eprintln!(
+                    "Bootloader: slot {} has version {} ({} bytes)",
+                    name,
+                    header.version(),
+                    header.image_len
+                );
+                if chosen.is_none_or(|(_, _, best)| header.version() > best.version()) {
+                    chosen = Some((name, start, header));
+                }
+            }
+            Err(e) => eprintln!("Bootloader: slot {} is not bootable: {:?}", name, e),
+        }
+    }
+
+    let Some((name, start, header)) = chosen else {
+        eprintln!("Bootloader: no bootable image!");
+        semihosting::process::exit(1);
+    };
+    eprintln!(
+        "Bootloader: booting slot {} (version {})",
+        name,
+        header.version()
+    );
+    let vector_table = (start + HEADER_LEN) as *const u32;
+    // Safety: we checked the image, so there's a vector table here, and VTOR
+    // needs it 1 KiB aligned which `HEADER_LEN` guarantees. We haven't
+    // enabled any interrupts, so nothing of ours can run once we leave.
+    unsafe {
+        let scb = &*cortex_m::peripheral::SCB::PTR;
+        scb.vtor.write(vector_table as u32);
+        cortex_m::asm::bootload(vector_table)
+    }
+}
+
+#[panic_handler]
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    eprintln!("Bootloader: {}", info);
+    semihosting::process::exit(1);
+}
+
+// End of file
@@ -4,21 +4,124 @@
 
 use std::io::Write;
 
+/// The machines we support, the memory map for each one, and where its FLASH
+/// starts
+const MACHINES: [(&str, &[u8], u32); 4] = [
+    ("an385", include_bytes!("memory/an385.x"), 0x0000_0000),
+    ("an386", include_bytes!("memory/an386.x"), 0x0000_0000),
+    ("an500", include_bytes!("memory/an500.x"), 0x0000_0000),
+    ("an505", include_bytes!("memory/an505.x"), 0x1000_0000),
+];
+
+/// The bootloader slots we can build an image for, and where each one starts
+/// relative to the start of FLASH
+///
+/// These must match `qemu-thumbv7em-bootloader`.
+const SLOTS: [(&str, u32); 2] = [("slot-a", 0x0001_0000), ("slot-b", 0x0019_0000)];
+
+/// The size of each bootloader slot
+const SLOT_LEN: u32 = 0x0018_0000;
+
+/// The space at the start of a slot for the image header
+///
+/// This must match `qemu_common::boot_image::HEADER_LEN`.
+const HEADER_LEN: u32 = 0x400;
+
+/// Is this cargo feature enabled?
+fn feature_enabled(feature: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+    std::env::var_os(var).is_some()
+}
+
 fn main() {
-    // Put `memory.ld` file in our output directory and ensure it's on the
-    // linker search path.
+    let selected: Vec<_> = MACHINES
+        .iter()
+        .filter(|(feature, _, _)| feature_enabled(feature))
+        .collect();
+    let (memory_x, flash_origin) = match selected.as_slice() {
+        [(_, memory_x, flash_origin)] => (*memory_x, *flash_origin),
+        [] => panic!("Enable one of the an385, an386, an500 or an505 features"),
+        _ => panic!("The an385, an386, an500 and an505 features are mutually exclusive"),
+    };
+    let slots: Vec<_> = SLOTS
+        .iter()
+        .filter(|(feature, _)| feature_enabled(feature))
+        .collect();
+
+    // Put the selected memory map in our output directory as `memory.x` and
+    // ensure it's on the linker search path.
     let out = &std::path::PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
-    std::fs::File::create(out.join("memory.x"))
-        .unwrap()
-        .write_all(include_bytes!("memory.x"))
-        .unwrap();
-    println!("cargo::rerun-if-changed=memory.x");
-    std::fs::File::create(out.join("device.x"))
+    let mut memory_file = std::fs::File::create(out.join("memory.x")).unwrap();
+    match slots.as_slice() {
+        [] => {
+            memory_file.write_all(memory_x).unwrap();
+        }
+        [(feature, offset)] => {
+            // We're building an image for the bootloader. Rename the whole of
+            // FLASH, and make a new FLASH region inside the slot, after the
+            // image header.
+            let memory_x = std::str::from_utf8(memory_x).unwrap();
+            let renamed = memory_x.replace("  FLASH :", "  ALL_FLASH :");
+            assert_ne!(renamed, memory_x, "Couldn't find FLASH in memory.x");
+            let slot = flash_origin + offset;
+            write!(
+                memory_file,
+                "{renamed}\n\
+                /* Added by build.rs, for the {feature} feature */\n\
+                MEMORY\n\
+                {{\n  \
+                  IMAGE_HEADER : ORIGIN = {slot:#010x}, LENGTH = {HEADER_LEN:#x}\n         \
+                         FLASH : ORIGIN = {:#010x}, LENGTH = {:#x}\n\
+                }}\n",
+                slot + HEADER_LEN,
+                SLOT_LEN - HEADER_LEN
+            )
+            .unwrap();
+            std::fs::File::create(out.join("image.x"))
+                .unwrap()
+                .write_all(include_bytes!("image.x"))
+                .unwrap();
+            println!("cargo:rustc-link-arg=-Timage.x");
+        }
+        _ => panic!("The slot-a and slot-b features are mutually exclusive"),
+    }
+    write_image_version(out);
+
+    std::fs::File::create(out.join("crash_log.x"))
         .unwrap()
-        .write_all(include_bytes!("device.x"))
+        .write_all(include_bytes!("crash_log.x"))
         .unwrap();
-    println!("cargo::rerun-if-changed=device.x");
+    println!("cargo::rerun-if-changed=memory");
+    println!("cargo::rerun-if-changed=crash_log.x");
+    println!("cargo::rerun-if-changed=image.x");
+    // `device.x` comes from the `mps2-pac` crate's build script
     println!("cargo:rustc-link-arg=-Tlink.x");
+    println!("cargo:rustc-link-arg=-Tcrash_log.x");
     println!("cargo:rustc-link-arg=-Tdefmt.x");
     println!("cargo:rustc-link-search={}", out.display());
 }
+
+/// Write out the version to put in the image header
+///
+/// This is the package version, unless you set `IMAGE_VERSION` (e.g. to
+/// `1.2.3`) when you build.
+fn write_image_version(out: &std::path::Path) {
+    println!("cargo::rerun-if-env-changed=IMAGE_VERSION");
+    let version = std::env::var("IMAGE_VERSION")
+        .unwrap_or_else(|_| std::env::var("CARGO_PKG_VERSION").unwrap());
+    let parts: Vec<u32> = version
+        .split('.')
+        .map(|part| part.parse().expect("IMAGE_VERSION should look like 1.2.3"))
+        .collect();
+    let [major, minor, patch] = parts.as_slice() else {
+        panic!("IMAGE_VERSION should look like 1.2.3, not {version:?}");
+    };
+    std::fs::write(
+        out.join("image_version.rs"),
+        format!(
+            "/// The version of this image\n\
+            pub const IMAGE_VERSION: Version = Version {{ major: {major}, minor: {minor}, patch: {patch} }};\n"
+        ),
+    )
+    .unwrap();
+}
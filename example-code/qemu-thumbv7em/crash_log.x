@@ -0,0 +1,6 @@
+/* Reserve the top 1 KiB of RAM for the crash log (see `src/crash.rs`), and
+start the stack below it. The crash log sits outside every section, so neither
+the startup code nor QEMU's ELF loader will clear it when the machine resets. */
+
+_crash_log = ORIGIN(RAM) + LENGTH(RAM) - 1K;
+_stack_start = _crash_log;
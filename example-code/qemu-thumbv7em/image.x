@@ -0,0 +1,17 @@
+/* Linker script fragment for building an application image for the
+bootloader, used when the `slot-a` or `slot-b` feature is enabled.
+
+It puts the image header (see `src/image.rs`) at the start of the slot, in the
+IMAGE_HEADER region which `build.rs` adds to `memory.x`. The vector table, and
+everything else, follows in FLASH. */
+
+EXTERN(__IMAGE_HEADER);
+
+SECTIONS
+{
+  .image_header ORIGIN(IMAGE_HEADER) :
+  {
+    KEEP(*(.image_header));
+  } > IMAGE_HEADER
+}
+INSERT BEFORE .vector_table;
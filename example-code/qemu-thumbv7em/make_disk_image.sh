@@ -0,0 +1,19 @@
+#!/usr/bin/env bash
+#
+# Makes `disk.img`, a 16 MiB disk image with an MBR partition table and a
+# single FAT16 partition, for the `fat_disk` example.
+#
+# Needs `sfdisk` (from util-linux), `mkfs.fat` (from dosfstools) and `mcopy`
+# (from mtools).
+
+set -euo pipefail
+
+IMAGE=disk.img
+
+rm -f "${IMAGE}"
+truncate -s 16M "${IMAGE}"
+# One FAT16 partition, starting at 1 MiB
+echo 'start=2048, type=06' | sfdisk --quiet "${IMAGE}"
+mkfs.fat -F 16 --offset 2048 -n QEMUDISK "${IMAGE}" > /dev/null
+echo "This file lives on a FAT16 filesystem, on your host." | mcopy -i "${IMAGE}@@1M" - ::README.TXT
+echo "Created ${IMAGE}"
@@ -0,0 +1,12 @@
+/* Memory Configuration Linker Script for the MPS2-AN386 (Cortex-M4)
+
+This file is copied to `memory.x` by our build script when the `an386`
+feature is enabled. It is imported by cortex-m-rt's link.x script.
+*/
+
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 4M
+    RAM : ORIGIN = 0x20000000, LENGTH = 4M
+  PSRAM : ORIGIN = 0x21000000, LENGTH = 16M
+}
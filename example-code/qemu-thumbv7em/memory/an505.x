@@ -0,0 +1,17 @@
+/* Memory Configuration Linker Script for the MPS2-AN505 (Cortex-M33)
+
+This file is copied to `memory.x` by our build script when the `an505`
+feature is enabled. It is imported by cortex-m-rt's link.x script.
+*/
+
+/*
+We run in Secure state, so we use the Secure aliases of the SRAMs (the
+Non-secure address with bit 28 set). "FLASH" is really ZBT SSRAM1, and RAM is
+ZBT SSRAM2 and SSRAM3, which sit next to each other.
+*/
+MEMORY
+{
+  FLASH : ORIGIN = 0x10000000, LENGTH = 4M
+    RAM : ORIGIN = 0x38000000, LENGTH = 4M
+  PSRAM : ORIGIN = 0x80000000, LENGTH = 16M
+}
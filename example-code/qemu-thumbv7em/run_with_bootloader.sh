@@ -0,0 +1,69 @@
+#!/usr/bin/env bash
+#
+# A cargo runner which boots an application image through
+# `qemu-thumbv7em-bootloader`.
+#
+# The `run-slot-a` and `run-slot-b` aliases in `.cargo/config.toml` use this.
+# Cargo calls it with our options (the same ones qemu-run takes), then the
+# ELF file, then anything you put after `--` in `cargo run` - which here is
+# more signed images to load alongside it, such as a build for the other slot.
+#
+# We sign the ELF file with `qemu-image`, build the bootloader for the same
+# machine, then start QEMU with the bootloader as the kernel and the images
+# loaded into FLASH after it. The defmt logs are decoded using the ELF file
+# cargo gave us, so they only make sense if the bootloader picks that one.
+# Needs `defmt-print` (`cargo install defmt-print`).
+
+set -euo pipefail
+
+here="$(cd "$(dirname "$0")" && pwd)"
+machine=
+cpu=
+log_format=
+elf=
+
+while [[ $# -gt 0 && -z "${elf}" ]]; do
+    case "$1" in
+    --machine) machine="$2"; shift 2 ;;
+    --cpu) cpu="$2"; shift 2 ;;
+    --log-format) log_format="$2"; shift 2 ;;
+    *) elf="$(realpath "$1")"; shift ;;
+    esac
+done
+extra_images=()
+for image in "$@"; do
+    extra_images+=("$(realpath "${image}")")
+done
+
+case "${machine}" in
+mps2-an385) feature=an385; target=thumbv7m-none-eabi ;;
+mps2-an386) feature=an386; target=thumbv7em-none-eabihf ;;
+mps2-an500) feature=an500; target=thumbv7em-none-eabihf ;;
+mps2-an505) feature=an505; target=thumbv8m.main-none-eabihf ;;
+*) echo "Unsupported machine ${machine:-(none)}" >&2; exit 1 ;;
+esac
+
+# Run the tools from their own directories, so they pick up their own
+# `.cargo/config.toml` instead of ours
+(cd "${here}/../qemu-image" && cargo run --quiet --release -- sign "${elf}") >&2
+(cd "${here}/../qemu-thumbv7em-bootloader" &&
+    cargo build --quiet --release --target "${target}" --no-default-features --features "${feature}")
+bootloader="${here}/../qemu-thumbv7em-bootloader/target/${target}/release/qemu-thumbv7em-bootloader"
+
+loaders=(-device "loader,file=${elf}")
+for image in "${extra_images[@]}"; do
+    loaders+=(-device "loader,file=${image}")
+done
+
+qemu-system-arm \
+    -machine "${machine}" \
+    ${cpu:+-cpu "${cpu}"} \
+    -display none \
+    -monitor none \
+    -serial null \
+    -semihosting-config enable=on,target=native \
+    -kernel "${bootloader}" \
+    "${loaders[@]}" |
+    defmt-print -e "${elf}" ${log_format:+--log-format "${log_format}"}
+# Exit with QEMU's status, not defmt-print's
+exit "${PIPESTATUS[0]}"
@@ -0,0 +1,41 @@
+//! An example program for QEMU's Armv7E-M Virtual Machine
+//!
+//! Panics, then faults, resetting the machine each time, and picks up the
+//! crash record from the previous run when it boots again.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use defmt_semihosting as _;
+use qemu_thumbv7em::crash;
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    defmt::println!("This is the crash_reset example.");
+    match crash::take_previous() {
+        Some(record) => defmt::println!("Last run crashed: {}", record),
+        None => defmt::println!("No crash record - this must be a cold boot"),
+    }
+
+    crash::set_reset_on_crash(true);
+    match crash::reset_count() {
+        0 => panic!("Deliberate panic, to get a crash record"),
+        1 => {
+            // Safety: not safe at all! This address is not mapped, so we
+            // get a HardFault
+            let value = unsafe { core::ptr::read_volatile(0xF000_0000 as *const u32) };
+            defmt::println!("Read {=u32:#x}?", value);
+        }
+        _ => {}
+    }
+
+    defmt::println!(
+        "Survived {=u32} resets - that's enough",
+        crash::reset_count()
+    );
+    qemu_thumbv7em::exit(0);
+}
+
+// End of file
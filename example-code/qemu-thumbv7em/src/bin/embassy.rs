@@ -12,7 +12,7 @@ use embassy_time::Delay;
 use embedded_hal_async::delay::DelayNs as _;
 
 use embassy_executor::{Spawner, main};
-use qemu_thumbv7em::{SYSTEM_CLOCK, interrupt, interrupts::Interrupts};
+use qemu_thumbv7em::{Interrupt, SYSTEM_CLOCK, interrupt};
 
 #[main]
 async fn main(_spawner: Spawner) -> ! {
@@ -24,26 +24,27 @@ async fn main(_spawner: Spawner) -> ! {
     let peripherals = qemu_thumbv7em::Peripherals::take().unwrap();
     embassy_time::driver_cmsdk::init_cortex_m(
         SYSTEM_CLOCK,
-        Interrupts::Timer0,
+        Interrupt::TIMER0,
         peripherals.timer0.into(),
-        Interrupts::Timer1,
+        Interrupt::TIMER1,
         peripherals.timer1.into(),
     );
 
     loop {
         defmt::info!("Hello from Embassy");
+        qemu_thumbv7em::stack::report();
         Delay.delay_ms(1000).await;
     }
 }
 
 #[interrupt]
-fn Timer0() {
+fn TIMER0() {
     // Safety: We only call this once here for timekeeping.
     unsafe { embassy_time::driver_cmsdk::on_interrupt_timekeeping() }
 }
 
 #[interrupt]
-fn Timer1() {
+fn TIMER1() {
     // Safety: We only call this once here for alarm handling.
     unsafe { embassy_time::driver_cmsdk::on_interrupt_alarm() }
 }
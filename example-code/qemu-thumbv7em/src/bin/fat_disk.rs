@@ -0,0 +1,129 @@
+//! An example program for QEMU's Armv7E-M Virtual Machine
+//!
+//! Mounts a FAT formatted disk image from the host, using semihosting, lists
+//! the root directory, prints `README.TXT` and then writes `HELLO.TXT`.
+//!
+//! Make the disk image with `./make_disk_image.sh` before running this.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use core::cell::RefCell;
+
+use defmt_semihosting as _;
+use embedded_sdmmc::{Block, BlockCount, BlockDevice, BlockIdx, Mode, TimeSource, Timestamp};
+use qemu_common::host_disk::{self, HostDisk};
+
+/// The disk image, relative to the directory QEMU was started in
+const DISK_IMAGE: &core::ffi::CStr = c"disk.img";
+
+/// Makes a [`HostDisk`] look like an SD card to `embedded-sdmmc`
+///
+/// `embedded-sdmmc` only gives us a shared reference, so we need a `RefCell`.
+struct SdmmcDisk(RefCell<HostDisk>);
+
+impl BlockDevice for SdmmcDisk {
+    type Error = host_disk::Error;
+
+    fn read(&self, blocks: &mut [Block], start_block_idx: BlockIdx) -> Result<(), Self::Error> {
+        let mut disk = self.0.borrow_mut();
+        for (block, idx) in blocks.iter_mut().zip(start_block_idx.0..) {
+            disk.read_sectors(idx, &mut block.contents)?;
+        }
+        Ok(())
+    }
+
+    fn write(&self, blocks: &[Block], start_block_idx: BlockIdx) -> Result<(), Self::Error> {
+        let mut disk = self.0.borrow_mut();
+        for (block, idx) in blocks.iter().zip(start_block_idx.0..) {
+            disk.write_sectors(idx, &block.contents)?;
+        }
+        Ok(())
+    }
+
+    fn num_blocks(&self) -> Result<BlockCount, Self::Error> {
+        Ok(BlockCount(self.0.borrow().sector_count()))
+    }
+}
+
+/// We have no real-time clock, so every file is stamped with the same time
+struct FixedTime;
+
+impl TimeSource for FixedTime {
+    fn get_timestamp(&self) -> Timestamp {
+        Timestamp {
+            year_since_1970: 56,
+            zero_indexed_month: 0,
+            zero_indexed_day: 0,
+            hours: 12,
+            minutes: 0,
+            seconds: 0,
+        }
+    }
+}
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    defmt::println!("This is the FAT disk example");
+
+    let disk = match HostDisk::open(DISK_IMAGE) {
+        Ok(disk) => disk,
+        Err(e) => {
+            defmt::error!(
+                "Failed to open disk.img: {}. Did you run ./make_disk_image.sh?",
+                e
+            );
+            qemu_thumbv7em::exit(1);
+        }
+    };
+    defmt::println!(
+        "Opened disk.img: {=u32} sectors of {=usize} bytes",
+        disk.sector_count(),
+        host_disk::SECTOR_SIZE
+    );
+
+    let volume_mgr = embedded_sdmmc::VolumeManager::new(SdmmcDisk(RefCell::new(disk)), FixedTime);
+    let volume = volume_mgr
+        .open_volume(embedded_sdmmc::VolumeIdx(0))
+        .expect("opening volume 0");
+    let root_dir = volume.open_root_dir().expect("opening root directory");
+
+    defmt::println!("Root directory:");
+    root_dir
+        .iterate_dir(|entry| {
+            let name = entry.name.base_name();
+            let ext = entry.name.extension();
+            if entry.attributes.is_directory() {
+                defmt::println!("  {=[u8]:a}.{=[u8]:a} <DIR>", name, ext);
+            } else {
+                defmt::println!("  {=[u8]:a}.{=[u8]:a} {=u32} bytes", name, ext, entry.size);
+            }
+        })
+        .expect("listing root directory");
+
+    let readme = root_dir
+        .open_file_in_dir("README.TXT", Mode::ReadOnly)
+        .expect("opening README.TXT");
+    let mut buffer = [0u8; 128];
+    defmt::println!("README.TXT says:");
+    while !readme.is_eof() {
+        let n = readme.read(&mut buffer).expect("reading README.TXT");
+        defmt::println!("{=[u8]:a}", &buffer[..n]);
+    }
+    readme.close().expect("closing README.TXT");
+
+    let hello = root_dir
+        .open_file_in_dir("HELLO.TXT", Mode::ReadWriteCreateOrTruncate)
+        .expect("creating HELLO.TXT");
+    hello
+        .write(b"Hello from QEMU's Armv7E-M machine!\n")
+        .expect("writing HELLO.TXT");
+    hello.close().expect("closing HELLO.TXT");
+    defmt::println!("Wrote HELLO.TXT - try `mtype -i disk.img@@1M ::HELLO.TXT`");
+
+    qemu_thumbv7em::exit(0);
+}
+
+// End of file
@@ -0,0 +1,92 @@
+//! An example program for QEMU's Armv7E-M Virtual Machine
+//!
+//! Deliberately causes a fault, a few function calls deep, to show off the
+//! fault decoding in the HardFault/UsageFault/BusFault handlers.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use defmt_semihosting as _;
+
+use qemu_thumbv7em::fault;
+
+/// The different faults this example can cause
+#[allow(dead_code)]
+#[derive(Debug, Copy, Clone, defmt::Format)]
+enum Fault {
+    /// An integer division by zero (UsageFault, once trapped)
+    DivideByZero,
+    /// An undefined instruction (UsageFault)
+    Undefined,
+    /// A read from an address with nothing behind it (BusFault)
+    BadRead,
+    /// Calling a function pointer with bit 0 clear (UsageFault)
+    ArmState,
+}
+
+/// Change this to try out a different fault
+const FAULT: Fault = Fault::DivideByZero;
+
+/// Set this to `false` to see everything escalate to a HardFault
+const ENABLE_FAULT_EXCEPTIONS: bool = true;
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    defmt::println!("This is the fault example. Causing {}", FAULT);
+    let mut cp = cortex_m::Peripherals::take().unwrap();
+    if ENABLE_FAULT_EXCEPTIONS {
+        fault::enable_fault_exceptions(&mut cp.SCB);
+    }
+    fault::trap_divide_by_zero(&mut cp.SCB, true);
+    let result = outer(FAULT);
+    defmt::println!("We didn't fault? Got {}", result);
+    qemu_thumbv7em::exit(0);
+}
+
+/// Some stack frames for the backtrace to find
+#[inline(never)]
+fn outer(fault: Fault) -> u32 {
+    middle(fault) + 1
+}
+
+/// Some more stack frames for the backtrace to find
+#[inline(never)]
+fn middle(fault: Fault) -> u32 {
+    inner(fault, core::hint::black_box(0)) + 1
+}
+
+/// Actually does the bad thing
+#[inline(never)]
+fn inner(fault: Fault, zero: u32) -> u32 {
+    match fault {
+        Fault::DivideByZero => {
+            let result: u32;
+            // Rust checks for division by zero and panics, so we need to use
+            // assembly to get the hardware to do it.
+            unsafe {
+                core::arch::asm!("udiv {0}, {1}, {2}", out(reg) result, in(reg) 100, in(reg) zero);
+            }
+            result
+        }
+        Fault::Undefined => {
+            unsafe {
+                core::arch::asm!("udf 0");
+            }
+            0
+        }
+        Fault::BadRead => {
+            // This is in the 'Reserved' part of the memory map on an MPS2
+            let ptr = 0xF000_0000 as *const u32;
+            unsafe { ptr.read_volatile() }
+        }
+        Fault::ArmState => {
+            let addr = (outer as usize) & !1;
+            let f: fn(Fault) -> u32 = unsafe { core::mem::transmute(addr) };
+            f(Fault::DivideByZero)
+        }
+    }
+}
+
+// End of file
@@ -0,0 +1,75 @@
+//! An example program for QEMU's Armv7E-M Virtual Machine
+//!
+//! Does some pointless work with the profiler running, then writes the
+//! profile to `profile.bin` on the host. Build it with `--features profiler`,
+//! and look at the results with the `qemu-profile` tool.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use defmt_semihosting as _;
+use qemu_thumbv7em::profiler;
+
+/// How many times a second we take a sample
+const SAMPLE_RATE_HZ: u32 = 10_000;
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    defmt::println!("This is the profile example.");
+    let cp = cortex_m::Peripherals::take().unwrap();
+    profiler::start(cp.SYST, SAMPLE_RATE_HZ).unwrap();
+
+    let mut total = 0;
+    for round in 0..20 {
+        total += count_primes(20_000);
+        let mut data = core::hint::black_box([0u32; 256]);
+        fill_pseudo_random(&mut data, round);
+        bubble_sort(&mut data);
+        total += data[0];
+    }
+    defmt::println!("Result: {=u32}", total);
+
+    match profiler::finish(c"profile.bin") {
+        Ok(summary) => defmt::println!("Wrote profile.bin: {}", summary),
+        Err(e) => defmt::error!("Failed to write profile: {}", e),
+    }
+    qemu_thumbv7em::exit(0);
+}
+
+/// Count the primes below `limit`, the slow way
+#[inline(never)]
+fn count_primes(limit: u32) -> u32 {
+    (2..limit).filter(|n| is_prime(*n)).count() as u32
+}
+
+/// Is `n` a prime number?
+#[inline(never)]
+fn is_prime(n: u32) -> bool {
+    (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+/// Fill a buffer with numbers from a simple linear congruential generator
+#[inline(never)]
+fn fill_pseudo_random(data: &mut [u32], seed: u32) {
+    let mut state = seed.wrapping_add(1);
+    for value in data.iter_mut() {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        *value = state;
+    }
+}
+
+/// Sort a slice, the slow way
+#[inline(never)]
+fn bubble_sort(data: &mut [u32]) {
+    for i in 0..data.len() {
+        for j in 0..data.len() - 1 - i {
+            if data[j] > data[j + 1] {
+                data.swap(j, j + 1);
+            }
+        }
+    }
+}
+
+// End of file
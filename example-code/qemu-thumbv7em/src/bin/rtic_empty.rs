@@ -10,7 +10,7 @@ use defmt_semihosting as _;
 use qemu_thumbv7em as _;
 use rtic_monotonics::systick_monotonic;
 
-#[rtic::app(device = qemu_thumbv7em, dispatchers = [AudioI2S])]
+#[rtic::app(device = qemu_thumbv7em, dispatchers = [AUDIO_I2S])]
 mod app {
     use qemu_thumbv7em::SYSTEM_CLOCK;
     use rtic_monotonics::fugit::ExtU32;
@@ -38,6 +38,7 @@ mod app {
     async fn hello(_cx: hello::Context) -> ! {
         loop {
             defmt::info!("Hello from RTIC");
+            qemu_thumbv7em::stack::report();
             Mono::delay(1000.millis()).await;
         }
     }
@@ -0,0 +1,52 @@
+//! An example program for QEMU's Armv7E-M Virtual Machine
+//!
+//! Recurses until the stack runs into the MPU guard region, to show off the
+//! stack overflow detection in the MemManage handler.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use defmt_semihosting as _;
+
+/// How many bytes of stack each call uses, give or take
+///
+/// This must be smaller than the guard region, or we might jump straight
+/// over it.
+const FRAME_SIZE: usize = 256;
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    #[cfg(not(feature = "an505"))]
+    {
+        let guard = qemu_thumbv7em::mpu::stack_guard();
+        defmt::println!(
+            "This is the stack overflow example. Stack guard is at 0x{=u32:08x}..0x{=u32:08x}",
+            guard.start,
+            guard.end
+        );
+    }
+    #[cfg(feature = "an505")]
+    defmt::println!("This is the stack overflow example. Stack limit is in MSPLIM");
+    let result = recurse(0);
+    defmt::println!("We didn't overflow? Got {}", result);
+    qemu_thumbv7em::exit(0);
+}
+
+/// Calls itself forever, using up [`FRAME_SIZE`] bytes of stack each time
+#[inline(never)]
+fn recurse(depth: u32) -> u32 {
+    let buffer = core::hint::black_box([depth as u8; FRAME_SIZE]);
+    if depth % 1000 == 0 {
+        defmt::println!(
+            "depth = {}, sp = 0x{=u32:08x}",
+            depth,
+            buffer.as_ptr() as u32
+        );
+    }
+    // Do some work after the call, so this can't be turned into a loop
+    recurse(depth + 1) + u32::from(buffer[0])
+}
+
+// End of file
@@ -11,7 +11,7 @@ use defmt_semihosting as _;
 use qemu_thumbv7em::{uart, SYSTEM_CLOCK};
 use rtic_monotonics::{fugit::ExtU32, systick_monotonic, Monotonic as _};
 
-#[rtic::app(device = qemu_thumbv7em, dispatchers = [AudioI2S])]
+#[rtic::app(device = qemu_thumbv7em, dispatchers = [AUDIO_I2S])]
 mod app {
     use super::*;
 
@@ -70,6 +70,7 @@ mod app {
                 cx.local.async_tx.write(s.as_bytes()).await;
                 Mono::delay(500.millis()).await;
             }
+            qemu_thumbv7em::stack::report();
         }
     }
 
@@ -83,7 +84,7 @@ mod app {
     }
 
     /// This interrupt indicates that the async UART transmission can progress.
-    #[task(binds = Uart0Tx, local = [async_tx_irq_ctx])]
+    #[task(binds = UART0_TX, local = [async_tx_irq_ctx])]
     fn tx_interrupt(cx: tx_interrupt::Context) {
         // Safety: We're in the UART TX interrupt handler
         unsafe {
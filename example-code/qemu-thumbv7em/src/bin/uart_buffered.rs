@@ -11,7 +11,7 @@ extern crate defmt_semihosting;
 
 use core::fmt::Write as _;
 
-use qemu_thumbv7em::{interrupt, interrupts::Interrupts, uart, uart::BufferedUart, SYSTEM_CLOCK};
+use qemu_thumbv7em::{interrupt, uart, uart::BufferedUart, Interrupt, SYSTEM_CLOCK};
 
 /// Our UART buffer size
 ///
@@ -36,7 +36,7 @@ fn main() -> ! {
         .unwrap();
 
     unsafe {
-        cortex_m::peripheral::NVIC::unmask(Interrupts::Uart0Tx);
+        cortex_m::peripheral::NVIC::unmask(Interrupt::UART0_TX);
         cortex_m::interrupt::enable();
     }
 
@@ -54,12 +54,12 @@ fn main() -> ! {
     // Wait for the UART bytes to be send
     UART0.flush();
 
-    semihosting::process::exit(0);
+    qemu_thumbv7em::exit(0);
 }
 
 /// Called when UART0 has a TX interrupt
 #[interrupt]
-fn Uart0Tx() {
+fn UART0_TX() {
     UART0.tx_isr();
 }
 
@@ -13,7 +13,7 @@
 use defmt_semihosting as _;
 use embedded_io::Write as _;
 
-use qemu_thumbv7em::{interrupt, interrupts::Interrupts, uart, uart::BufferedUart, SYSTEM_CLOCK};
+use qemu_thumbv7em::{interrupt, uart, uart::BufferedUart, Interrupt, SYSTEM_CLOCK};
 
 /// Our UART buffer size
 ///
@@ -43,11 +43,11 @@ fn main() -> ! {
 
     unsafe {
         // mark receive as higher prio than transmit
-        cp.NVIC.set_priority(Interrupts::Uart0Rx, 0);
-        cp.NVIC.set_priority(Interrupts::Uart0Tx, 255);
+        cp.NVIC.set_priority(Interrupt::UART0_RX, 0);
+        cp.NVIC.set_priority(Interrupt::UART0_TX, 255);
         // enable those interrupts
-        cortex_m::peripheral::NVIC::unmask(Interrupts::Uart0Tx);
-        cortex_m::peripheral::NVIC::unmask(Interrupts::Uart0Rx);
+        cortex_m::peripheral::NVIC::unmask(Interrupt::UART0_TX);
+        cortex_m::peripheral::NVIC::unmask(Interrupt::UART0_RX);
         cortex_m::interrupt::enable();
     }
 
@@ -87,13 +87,13 @@ fn main() -> ! {
 
 /// Called when UART0 has a TX interrupt
 #[interrupt]
-fn Uart0Tx() {
+fn UART0_TX() {
     UART0.tx_isr();
 }
 
 /// Called when UART0 has a RX interrupt
 #[interrupt]
-fn Uart0Rx() {
+fn UART0_RX() {
     UART0.rx_isr();
 }
 
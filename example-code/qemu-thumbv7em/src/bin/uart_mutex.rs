@@ -28,7 +28,7 @@ fn main() -> ! {
     // Some time for the telnet server to receive the data.
     cortex_m::asm::delay(500_000_000);
 
-    semihosting::process::exit(0);
+    qemu_thumbv7em::exit(0);
 }
 
 // End of file
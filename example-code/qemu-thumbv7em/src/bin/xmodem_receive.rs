@@ -0,0 +1,105 @@
+//! An example program for QEMU's Armv7E-M Virtual Machine
+//!
+//! Receives a file over UART0 using YMODEM (or XMODEM-1K, if you pass
+//! `--xmodem`), puts it in PSRAM, and prints its length and CRC-32.
+//!
+//! Run as `cargo run --bin xmodem_receive -- --uart-telnet`, then send a file
+//! with the `qemu-xmodem` tool.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use defmt_semihosting as _;
+use qemu_common::{
+    boot_image::crc32,
+    host_env::CommandLine,
+    xmodem::{MemoryStorage, Protocol, Receiver},
+};
+use qemu_thumbv7em::{
+    interrupt,
+    timer::{DelayTimer, Timer},
+    uart::{BufferedUart, CmsdkUart},
+    Interrupt, SYSTEM_CLOCK,
+};
+
+/// Our UART buffer size
+///
+/// This needs to hold at least one whole 1 KiB block, plus a few bytes of
+/// framing.
+const QLEN: usize = 2048;
+
+/// Where PSRAM starts on this machine
+#[cfg(any(feature = "an385", feature = "an386"))]
+const PSRAM_START: usize = 0x2100_0000;
+
+/// Where PSRAM starts on this machine
+#[cfg(feature = "an500")]
+const PSRAM_START: usize = 0x6000_0000;
+
+/// Where PSRAM starts on this machine
+#[cfg(feature = "an505")]
+const PSRAM_START: usize = 0x8000_0000;
+
+/// How much PSRAM there is
+const PSRAM_LEN: usize = 16 * 1024 * 1024;
+
+/// A global UART, fed by interrupts
+static UART0: BufferedUart<QLEN> = BufferedUart::empty();
+
+#[cortex_m_rt::entry]
+fn main() -> ! {
+    let protocol = match CommandLine::fetch() {
+        Ok(cmdline) if cmdline.has_flag("--xmodem") => Protocol::Xmodem,
+        _ => Protocol::Ymodem,
+    };
+    defmt::info!("Waiting for a file over {} on UART0", protocol);
+
+    let peripherals = qemu_thumbv7em::Peripherals::take().unwrap();
+    UART0
+        .init(CmsdkUart::new(peripherals.uart0), 115200, SYSTEM_CLOCK)
+        .unwrap();
+    // Safety: the handlers below only touch UART0, which is ready
+    unsafe {
+        cortex_m::peripheral::NVIC::unmask(Interrupt::UART0_TX);
+        cortex_m::peripheral::NVIC::unmask(Interrupt::UART0_RX);
+        cortex_m::interrupt::enable();
+    }
+    let timer = DelayTimer::new(Timer::new(peripherals.timer0), SYSTEM_CLOCK);
+
+    // Safety: nothing else in this program uses PSRAM
+    let psram = unsafe { core::slice::from_raw_parts_mut(PSRAM_START as *mut u8, PSRAM_LEN) };
+    let mut storage = MemoryStorage::new(psram);
+    let mut receiver = Receiver::new(&UART0, timer);
+    match receiver.receive(protocol, &mut storage) {
+        Ok(received) => {
+            let data = &storage.as_slice()[..received.len];
+            defmt::info!(
+                "Received {=str} ({=usize} bytes, CRC-32 {=u32:#010x})",
+                received.name.as_str(),
+                received.len,
+                crc32(data)
+            );
+            qemu_thumbv7em::exit(0);
+        }
+        Err(e) => {
+            defmt::error!("Transfer failed: {}", e);
+            qemu_thumbv7em::exit(1);
+        }
+    }
+}
+
+/// Called when UART0 has a TX interrupt
+#[interrupt]
+fn UART0_TX() {
+    UART0.tx_isr();
+}
+
+/// Called when UART0 has a RX interrupt
+#[interrupt]
+fn UART0_RX() {
+    UART0.rx_isr();
+}
+
+// End of file
@@ -0,0 +1,71 @@
+//! Crash records which survive a reset
+//!
+//! Call [`set_reset_on_crash(true)`](set_reset_on_crash) and the panic and
+//! fault handlers will store what went wrong in a [`CrashRecord`] and reset
+//! the chip with `SCB::sys_reset`, instead of exiting QEMU. Call
+//! [`take_previous`] when you boot to see if the last run crashed.
+//!
+//! The record lives in the top 1 KiB of RAM - see `crash_log.x`.
+
+pub use qemu_common::crash::{reset_on_crash, set_reset_on_crash, CrashKind, CrashRecord};
+
+use qemu_common::crash::CrashLog;
+
+unsafe extern "C" {
+    /// The start of the memory reserved for the crash log, from `crash_log.x`
+    static mut _crash_log: u8;
+}
+
+/// Get a handle to our crash log
+fn crash_log() -> CrashLog {
+    // Safety: `crash_log.x` reserves this memory for us, and moves the stack
+    // out of its way
+    unsafe { CrashLog::new(&raw mut _crash_log) }
+}
+
+/// Get the crash record from before the last reset, if there is one
+///
+/// This also clears it, so you only see each crash once.
+pub fn take_previous() -> Option<CrashRecord> {
+    crash_log().take()
+}
+
+/// How many times we have reset because of a crash since QEMU started
+pub fn reset_count() -> u32 {
+    crash_log().reset_count()
+}
+
+/// Store a crash record for a panic, and reset
+pub(crate) fn panic_reset(info: &core::panic::PanicInfo) -> ! {
+    crash_log().record_panic(info);
+    cortex_m::peripheral::SCB::sys_reset()
+}
+
+/// Store a crash record for a fault, and reset
+///
+/// The registers are PC, LR, xPSR, CFSR, HFSR, MMFAR and BFAR.
+pub(crate) fn fault_reset(
+    kind: crate::fault::FaultKind,
+    frame: &cortex_m_rt::ExceptionFrame,
+    status: &crate::fault::FaultStatus,
+) -> ! {
+    let name = match kind {
+        crate::fault::FaultKind::HardFault => "HardFault",
+        crate::fault::FaultKind::MemoryManagement => "MemoryManagement",
+        crate::fault::FaultKind::BusFault => "BusFault",
+        crate::fault::FaultKind::UsageFault => "UsageFault",
+    };
+    let registers = [
+        frame.pc(),
+        frame.lr(),
+        frame.xpsr(),
+        status.cfsr,
+        status.hfsr,
+        status.mmfar,
+        status.bfar,
+    ];
+    crash_log().record_fault(name, &registers.map(u64::from));
+    cortex_m::peripheral::SCB::sys_reset()
+}
+
+// End of file
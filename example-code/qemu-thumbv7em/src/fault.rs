@@ -0,0 +1,548 @@
+//! Decoding and reporting of Cortex-M fault exceptions
+//!
+//! When something goes wrong, an Armv7-M processor records why in the
+//! Configurable Fault Status Register (CFSR) and the HardFault Status Register
+//! (HFSR), and may also record the address it was trying to access in MMFAR or
+//! BFAR. This module reads those registers, turns the bits into something a
+//! human can read, and makes a best-effort attempt at showing you the call
+//! stack.
+//!
+//! By default every fault except MemManage (which the stack guard in
+//! [`crate::mpu`] needs) escalates to HardFault. Call
+//! [`enable_fault_exceptions`] to have BusFault and UsageFault reported through
+//! their own handlers instead.
+
+use cortex_m::peripheral::{scb::Exception, SCB};
+use cortex_m_rt::ExceptionFrame;
+
+/// The most return addresses we will report
+const MAX_BACKTRACE_DEPTH: usize = 16;
+
+/// The extra space taken up on the stack by the FPU registers, when the
+/// processor stacks an extended exception frame
+const FP_EXTENSION_SIZE: usize = 18 * 4;
+
+/// CCR bit which makes unaligned accesses fault
+const CCR_UNALIGN_TRP: u32 = 1 << 3;
+
+/// CCR bit which makes division by zero fault
+const CCR_DIV_0_TRP: u32 = 1 << 4;
+
+/// CFSR bit which says MMFAR holds a valid address
+const CFSR_MMARVALID: u32 = 1 << 7;
+
+/// CFSR bit which says BFAR holds a valid address
+const CFSR_BFARVALID: u32 = 1 << 15;
+
+/// Which exception handler is reporting the fault
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum FaultKind {
+    /// A HardFault, or a configurable fault that was escalated
+    HardFault,
+    /// A Memory Management fault (an MPU violation)
+    MemoryManagement,
+    /// A Bus Fault
+    BusFault,
+    /// A Usage Fault
+    UsageFault,
+}
+
+/// A single reason for a fault, decoded from CFSR or HFSR
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FaultCause {
+    /// MMFSR.IACCVIOL
+    InstructionAccessViolation,
+    /// MMFSR.DACCVIOL
+    DataAccessViolation,
+    /// MMFSR.MUNSTKERR
+    MemManageUnstackingError,
+    /// MMFSR.MSTKERR
+    MemManageStackingError,
+    /// MMFSR.MLSPERR
+    MemManageLazyFpError,
+    /// BFSR.IBUSERR
+    InstructionBusError,
+    /// BFSR.PRECISERR
+    PreciseBusError,
+    /// BFSR.IMPRECISERR
+    ImpreciseBusError,
+    /// BFSR.UNSTKERR
+    BusUnstackingError,
+    /// BFSR.STKERR
+    BusStackingError,
+    /// BFSR.LSPERR
+    BusLazyFpError,
+    /// UFSR.UNDEFINSTR
+    UndefinedInstruction,
+    /// UFSR.INVSTATE
+    InvalidState,
+    /// UFSR.INVPC
+    InvalidExceptionReturn,
+    /// UFSR.NOCP
+    NoCoprocessor,
+    /// UFSR.STKOF (Armv8-M only)
+    StackOverflow,
+    /// UFSR.UNALIGNED
+    UnalignedAccess,
+    /// UFSR.DIVBYZERO
+    DivideByZero,
+    /// HFSR.VECTTBL
+    VectorTableReadError,
+    /// HFSR.FORCED
+    Forced,
+    /// HFSR.DEBUGEVT
+    DebugEvent,
+}
+
+impl FaultCause {
+    /// Maps each CFSR bit to the cause it indicates
+    const CFSR_BITS: [(u32, FaultCause); 18] = [
+        (1 << 0, FaultCause::InstructionAccessViolation),
+        (1 << 1, FaultCause::DataAccessViolation),
+        (1 << 3, FaultCause::MemManageUnstackingError),
+        (1 << 4, FaultCause::MemManageStackingError),
+        (1 << 5, FaultCause::MemManageLazyFpError),
+        (1 << 8, FaultCause::InstructionBusError),
+        (1 << 9, FaultCause::PreciseBusError),
+        (1 << 10, FaultCause::ImpreciseBusError),
+        (1 << 11, FaultCause::BusUnstackingError),
+        (1 << 12, FaultCause::BusStackingError),
+        (1 << 13, FaultCause::BusLazyFpError),
+        (1 << 16, FaultCause::UndefinedInstruction),
+        (1 << 17, FaultCause::InvalidState),
+        (1 << 18, FaultCause::InvalidExceptionReturn),
+        (1 << 19, FaultCause::NoCoprocessor),
+        (1 << 20, FaultCause::StackOverflow),
+        (1 << 24, FaultCause::UnalignedAccess),
+        (1 << 25, FaultCause::DivideByZero),
+    ];
+
+    /// Maps each HFSR bit to the cause it indicates
+    const HFSR_BITS: [(u32, FaultCause); 3] = [
+        (1 << 1, FaultCause::VectorTableReadError),
+        (1 << 30, FaultCause::Forced),
+        (1 << 31, FaultCause::DebugEvent),
+    ];
+
+    /// A human-readable description of this cause
+    pub const fn description(self) -> &'static str {
+        match self {
+            FaultCause::InstructionAccessViolation => {
+                "instruction fetch from a region the MPU does not allow (or that is XN)"
+            }
+            FaultCause::DataAccessViolation => "data access to a region the MPU does not allow",
+            FaultCause::MemManageUnstackingError => {
+                "MPU violation while unstacking on exception return"
+            }
+            FaultCause::MemManageStackingError => {
+                "MPU violation while stacking on exception entry (stack overflow?)"
+            }
+            FaultCause::MemManageLazyFpError => "MPU violation during lazy FP state preservation",
+            FaultCause::InstructionBusError => "bus error on instruction fetch",
+            FaultCause::PreciseBusError => "precise data bus error (see BFAR)",
+            FaultCause::ImpreciseBusError => {
+                "imprecise data bus error (the PC may be past the faulting instruction)"
+            }
+            FaultCause::BusUnstackingError => "bus error while unstacking on exception return",
+            FaultCause::BusStackingError => {
+                "bus error while stacking on exception entry (stack pointer invalid?)"
+            }
+            FaultCause::BusLazyFpError => "bus error during lazy FP state preservation",
+            FaultCause::UndefinedInstruction => "undefined instruction",
+            FaultCause::InvalidState => {
+                "invalid state - tried to execute in Arm state (function pointer with bit 0 clear?)"
+            }
+            FaultCause::InvalidExceptionReturn => "invalid EXC_RETURN value on exception return",
+            FaultCause::NoCoprocessor => "coprocessor instruction with the FPU disabled",
+            FaultCause::StackOverflow => "stack pointer went below its stack limit register",
+            FaultCause::UnalignedAccess => "unaligned memory access",
+            FaultCause::DivideByZero => "integer divide by zero",
+            FaultCause::VectorTableReadError => "bus error reading the vector table",
+            FaultCause::Forced => "configurable fault escalated to HardFault",
+            FaultCause::DebugEvent => "debug event with no debugger attached",
+        }
+    }
+
+    /// Does this cause mean the exception frame itself might be garbage?
+    pub const fn is_stacking_error(self) -> bool {
+        matches!(
+            self,
+            FaultCause::MemManageStackingError
+                | FaultCause::BusStackingError
+                | FaultCause::StackOverflow
+        )
+    }
+}
+
+impl defmt::Format for FaultCause {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{=str}", self.description());
+    }
+}
+
+/// A snapshot of the fault status registers
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FaultStatus {
+    /// Configurable Fault Status Register (MMFSR, BFSR and UFSR combined)
+    pub cfsr: u32,
+    /// HardFault Status Register
+    pub hfsr: u32,
+    /// MemManage Fault Address Register
+    pub mmfar: u32,
+    /// BusFault Address Register
+    pub bfar: u32,
+}
+
+impl FaultStatus {
+    /// Read the fault status registers from the SCB
+    pub fn read() -> FaultStatus {
+        // Safety: We only read registers here, which has no side-effects
+        let scb = unsafe { &*SCB::PTR };
+        FaultStatus {
+            cfsr: scb.cfsr.read(),
+            hfsr: scb.hfsr.read(),
+            mmfar: scb.mmfar.read(),
+            bfar: scb.bfar.read(),
+        }
+    }
+
+    /// Clear all the status bits we read, so the next fault starts afresh
+    pub fn clear(&self) {
+        // Safety: These bits are all write-one-to-clear
+        unsafe {
+            let scb = &*SCB::PTR;
+            scb.cfsr.write(self.cfsr);
+            scb.hfsr.write(self.hfsr);
+        }
+    }
+
+    /// Iterate through all the causes flagged in CFSR and HFSR
+    pub fn causes(&self) -> impl Iterator<Item = FaultCause> + '_ {
+        let cfsr = FaultCause::CFSR_BITS
+            .iter()
+            .filter(|(mask, _)| self.cfsr & mask != 0);
+        let hfsr = FaultCause::HFSR_BITS
+            .iter()
+            .filter(|(mask, _)| self.hfsr & mask != 0);
+        cfsr.chain(hfsr).map(|(_, cause)| *cause)
+    }
+
+    /// The address of the faulting data access, if the MPU recorded one
+    pub fn mem_manage_address(&self) -> Option<u32> {
+        (self.cfsr & CFSR_MMARVALID != 0).then_some(self.mmfar)
+    }
+
+    /// The address of the faulting data access, if the bus recorded one
+    pub fn bus_fault_address(&self) -> Option<u32> {
+        (self.cfsr & CFSR_BFARVALID != 0).then_some(self.bfar)
+    }
+
+    /// Did the processor fail to push the exception frame?
+    pub fn stacking_error(&self) -> bool {
+        self.causes().any(FaultCause::is_stacking_error)
+    }
+}
+
+/// Route MemManage, BusFault and UsageFault to their own handlers
+///
+/// Without this, they all escalate to HardFault (and HFSR.FORCED is set).
+pub fn enable_fault_exceptions(scb: &mut SCB) {
+    scb.enable(Exception::MemoryManagement);
+    scb.enable(Exception::BusFault);
+    scb.enable(Exception::UsageFault);
+}
+
+/// Control whether integer division by zero raises a UsageFault
+///
+/// If disabled (the reset default), `udiv` and `sdiv` just return zero.
+pub fn trap_divide_by_zero(scb: &mut SCB, enabled: bool) {
+    // Safety: We have exclusive access to the SCB
+    unsafe {
+        scb.ccr.modify(|ccr| {
+            if enabled {
+                ccr | CCR_DIV_0_TRP
+            } else {
+                ccr & !CCR_DIV_0_TRP
+            }
+        });
+    }
+}
+
+/// Control whether any unaligned memory access raises a UsageFault
+///
+/// If disabled (the reset default), only unaligned `ldm`, `stm`, `ldrd` and
+/// `strd` instructions will fault.
+pub fn trap_unaligned(scb: &mut SCB, enabled: bool) {
+    // Safety: We have exclusive access to the SCB
+    unsafe {
+        scb.ccr.modify(|ccr| {
+            if enabled {
+                ccr | CCR_UNALIGN_TRP
+            } else {
+                ccr & !CCR_UNALIGN_TRP
+            }
+        });
+    }
+}
+
+/// Print everything we know about a fault, and exit QEMU.
+///
+/// If [`crate::crash::set_reset_on_crash`] has been turned on, store a crash
+/// record and reset instead.
+///
+/// If you know the `EXC_RETURN` value the processor used when taking the
+/// exception, pass it in so we can skip over any stacked FPU registers.
+pub fn report(kind: FaultKind, frame: &ExceptionFrame, exc_return: Option<u32>) -> ! {
+    let status = FaultStatus::read();
+    if crate::crash::reset_on_crash() {
+        crate::crash::fault_reset(kind, frame, &status);
+    }
+    defmt::error!("{} at pc=0x{=u32:08x}", kind, frame.pc());
+    defmt::error!(
+        "r0=0x{=u32:08x}, r1=0x{=u32:08x}, r2=0x{=u32:08x}, r3=0x{=u32:08x}, r12=0x{=u32:08x}, lr=0x{=u32:08x}, pc=0x{=u32:08x}, xpsr=0x{=u32:08x}",
+        frame.r0(),
+        frame.r1(),
+        frame.r2(),
+        frame.r3(),
+        frame.r12(),
+        frame.lr(),
+        frame.pc(),
+        frame.xpsr()
+    );
+    defmt::error!(
+        "CFSR=0x{=u32:08x}, HFSR=0x{=u32:08x}",
+        status.cfsr,
+        status.hfsr
+    );
+    for cause in status.causes() {
+        defmt::error!("- {}", cause);
+    }
+    if let Some(addr) = status.mem_manage_address() {
+        defmt::error!("- MemManage fault address (MMFAR) = 0x{=u32:08x}", addr);
+    }
+    if let Some(addr) = status.bus_fault_address() {
+        defmt::error!("- Bus fault address (BFAR) = 0x{=u32:08x}", addr);
+    }
+
+    report_stack_overflow(&status, frame);
+
+    if status.stacking_error() {
+        defmt::error!("Exception frame was not stacked correctly - not unwinding");
+    } else {
+        backtrace(frame, exc_return);
+    }
+    status.clear();
+    crate::exit(1);
+}
+
+/// Say so if this fault was caused by the stack hitting the MPU guard region
+#[cfg(not(feature = "an505"))]
+fn report_stack_overflow(status: &FaultStatus, frame: &ExceptionFrame) {
+    let guard = crate::mpu::stack_guard();
+    let frame_address = frame as *const ExceptionFrame as u32;
+    let hit_guard = status
+        .mem_manage_address()
+        .is_some_and(|addr| guard.contains(&addr));
+    if hit_guard || guard.contains(&frame_address) {
+        defmt::error!(
+            "Stack overflow! The stack grew into the guard region at 0x{=u32:08x}..0x{=u32:08x}",
+            guard.start,
+            guard.end
+        );
+    }
+}
+
+/// Say so if this fault was caused by the stack going below MSPLIM
+#[cfg(feature = "an505")]
+fn report_stack_overflow(status: &FaultStatus, _frame: &ExceptionFrame) {
+    if status
+        .causes()
+        .any(|cause| cause == FaultCause::StackOverflow)
+    {
+        defmt::error!("Stack overflow! The stack went below its limit (MSPLIM)");
+    }
+}
+
+/// Print a best-effort call stack, starting at the exception frame
+///
+/// We don't have unwind tables, so we scan the stack above the exception frame
+/// for words that look like return addresses - that is, odd (Thumb) addresses
+/// inside `.text` that come straight after a `bl` or `blx` instruction. This
+/// can report stale values left over from earlier calls, but it's usually
+/// enough to find your way back to your own code.
+///
+/// Feed the addresses to `arm-none-eabi-addr2line -e <elf>` (or look them up
+/// in `cargo objdump`) to get function names and line numbers.
+fn backtrace(frame: &ExceptionFrame, exc_return: Option<u32>) {
+    unsafe extern "C" {
+        static __stext: u32;
+        static __etext: u32;
+        static _stack_start: u32;
+        static _stack_end: u32;
+    }
+    let stack_top = (&raw const _stack_start) as usize;
+    let stack_bottom = (&raw const _stack_end) as usize;
+
+    defmt::error!("Call stack (most recent first):");
+    defmt::error!("#0  0x{=u32:08x} (pc)", frame.pc());
+    let mut depth = 1;
+    if is_return_address(frame.lr()) {
+        defmt::error!("#1  0x{=u32:08x} (lr)", frame.lr() & !1);
+        depth += 1;
+    }
+
+    // EXC_RETURN bit 4 is clear if the FPU registers were stacked too
+    let fp_extension = match exc_return {
+        Some(value) if value & (1 << 4) == 0 => FP_EXTENSION_SIZE,
+        _ => 0,
+    };
+    let frame_start = frame as *const ExceptionFrame as usize;
+    if frame_start < stack_bottom || frame_start >= stack_top {
+        defmt::error!(
+            "Exception frame @ 0x{=usize:08x} is not on the main stack - not unwinding",
+            frame_start
+        );
+        return;
+    }
+    let mut addr = frame_start + core::mem::size_of::<ExceptionFrame>() + fp_extension;
+    while addr < stack_top && depth < MAX_BACKTRACE_DEPTH {
+        // Safety: we checked this is within the stack, and it is word aligned
+        let word = unsafe { (addr as *const u32).read_volatile() };
+        if is_return_address(word) {
+            defmt::error!("#{=usize}  0x{=u32:08x}", depth, word & !1);
+            depth += 1;
+        }
+        addr += 4;
+    }
+
+    /// Does this value look like a Thumb return address?
+    fn is_return_address(value: u32) -> bool {
+        let text_start = (&raw const __stext) as u32;
+        let text_end = (&raw const __etext) as u32;
+        let addr = value & !1;
+        if value & 1 == 0 || addr < text_start + 4 || addr > text_end {
+            return false;
+        }
+        // Safety: we checked these addresses are within `.text`
+        let (first, second) = unsafe {
+            (
+                ((addr - 4) as *const u16).read_volatile(),
+                ((addr - 2) as *const u16).read_volatile(),
+            )
+        };
+        // `blx <reg>` is 0b0100_0111_1xxx_x000
+        let is_blx = second & 0xFF87 == 0x4780;
+        // `bl <label>` is 0b11110xxx_xxxxxxxx, 0b11x1xxxx_xxxxxxxx
+        let is_bl = first & 0xF800 == 0xF000 && second & 0xD000 == 0xD000;
+        is_blx || is_bl
+    }
+}
+
+// Assembly trampolines for the fault handlers.
+//
+// `cortex-m-rt` only gives the HardFault handler access to the exception
+// frame, and not to `EXC_RETURN`, which we need to find our way past any
+// stacked FPU registers. So we do it ourselves here, for HardFault as well as
+// the configurable faults: work out which stack the frame was pushed to (using
+// bit 2 of `EXC_RETURN` in `lr`), then call `fault_handler` with a pointer to
+// the frame, the fault kind and `EXC_RETURN`. Defining `HardFault` here
+// overrides the default one from `cortex-m-rt`.
+//
+// MemManage turns the MPU off first. If the stack overflowed into the guard
+// region, the stack pointer is now inside it, and the handler needs somewhere
+// to put its own stack frames. We're not coming back, so that's fine.
+core::arch::global_asm!(
+    r#"
+    .section .text.FaultTrampolines, "ax"
+    .thumb_func
+    .global HardFault
+    .type HardFault, %function
+    HardFault:
+        movs r1, #{hard}
+        b 1f
+
+    .thumb_func
+    .global MemoryManagement
+    .type MemoryManagement, %function
+    MemoryManagement:
+        ldr r0, =0xE000ED94
+        movs r1, #0
+        str r1, [r0]
+        dsb
+        isb
+        movs r1, #{mem}
+        b 1f
+
+    .thumb_func
+    .global BusFault
+    .type BusFault, %function
+    BusFault:
+        movs r1, #{bus}
+        b 1f
+
+    .thumb_func
+    .global UsageFault
+    .type UsageFault, %function
+    UsageFault:
+        movs r1, #{usage}
+    1:
+        tst lr, #4
+        ite eq
+        mrseq r0, msp
+        mrsne r0, psp
+        mov r2, lr
+        b {handler}
+    .ltorg
+    "#,
+    hard = const FaultKind::HardFault as u32,
+    mem = const FaultKind::MemoryManagement as u32,
+    bus = const FaultKind::BusFault as u32,
+    usage = const FaultKind::UsageFault as u32,
+    handler = sym fault_handler,
+);
+
+// The Cortex-M33 in the AN505 has an Armv8-M MPU, which our `mpu` module
+// doesn't drive. It does have a stack limit register though, so we use that as
+// our stack guard instead. If the main stack pointer goes below MSPLIM the CPU
+// raises a UsageFault (STKOF), which escalates to HardFault unless you called
+// `enable_fault_exceptions`. Setting CCR.STKOFHFNMIGN lets the HardFault
+// handler run below the limit.
+//
+// This must be in assembly, as `__pre_init` runs before RAM is initialised.
+#[cfg(feature = "an505")]
+core::arch::global_asm!(
+    r#"
+    .section .text.__pre_init, "ax"
+    .thumb_func
+    .global __pre_init
+    .type __pre_init, %function
+    __pre_init:
+        // Paint the stack (see `src/stack.rs`)
+        mov r3, lr
+        bl __paint_stack
+        mov lr, r3
+        // CCR.STKOFHFNMIGN = 1
+        ldr r0, =0xE000ED14
+        ldr r1, [r0]
+        orr r1, r1, #(1 << 10)
+        str r1, [r0]
+        ldr r0, =_stack_end
+        msr msplim, r0
+        isb
+        bx lr
+    .ltorg
+    "#
+);
+
+/// Called from the assembly trampolines above
+extern "C" fn fault_handler(frame: &ExceptionFrame, kind: u32, exc_return: u32) -> ! {
+    let kind = match kind {
+        x if x == FaultKind::HardFault as u32 => FaultKind::HardFault,
+        x if x == FaultKind::MemoryManagement as u32 => FaultKind::MemoryManagement,
+        x if x == FaultKind::BusFault as u32 => FaultKind::BusFault,
+        _ => FaultKind::UsageFault,
+    };
+    report(kind, frame, Some(exc_return))
+}
+
+// End of file
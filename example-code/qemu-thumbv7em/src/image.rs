@@ -0,0 +1,24 @@
+//! The image header, for when we're built to run from the bootloader
+//!
+//! With the `slot-a` or `slot-b` feature enabled, `build.rs` moves FLASH
+//! into that bootloader slot and `image.x` puts this header at the start of
+//! it. The header goes out unsigned - run the ELF file through `qemu-image
+//! sign` (which `run_with_bootloader.sh` does for you) to fill in the length
+//! and checksums, otherwise the bootloader won't run it.
+//!
+//! The version comes from `IMAGE_VERSION` at build time, or the package
+//! version if that isn't set.
+
+use qemu_common::boot_image::{ImageHeader, Version};
+
+include!(concat!(env!("OUT_DIR"), "/image_version.rs"));
+
+/// The header itself
+///
+/// `image.x` refers to this by name, so it can't be mangled.
+#[used]
+#[unsafe(no_mangle)]
+#[unsafe(link_section = ".image_header")]
+static __IMAGE_HEADER: ImageHeader = ImageHeader::unsigned(IMAGE_VERSION);
+
+// End of file
@@ -2,25 +2,36 @@
 
 use core::sync::atomic::AtomicBool;
 
-// Yes, these two must be imported with the same name. It is also required for RTIC.
-//
-// this is a macro
-pub use cortex_m_rt::interrupt;
-// this is an enum that the macro uses
-pub use interrupts::Interrupts as interrupt;
+/// Our svd2rust generated Peripheral Access Crate
+pub use mps2_pac as pac;
+
+// The PAC exports both the `interrupt` macro and an `interrupt` enum with the
+// same name. It is also required for RTIC, as is `NVIC_PRIO_BITS`.
+pub use pac::{interrupt, Interrupt, NVIC_PRIO_BITS};
 
 use defmt_semihosting as _;
 
-pub mod interrupts;
+pub mod crash;
+pub mod fault;
+#[cfg(any(feature = "slot-a", feature = "slot-b"))]
+pub mod image;
+#[cfg(not(feature = "an505"))]
+pub mod mpu;
+#[cfg(feature = "profiler")]
+pub mod profiler;
+pub mod stack;
+pub mod testing;
 pub mod timer;
 pub mod uart;
 
-/// Number available in the NVIC for configuring priority. Required for RTIC as well.
-pub const NVIC_PRIO_BITS: u8 = 3;
-
 /// Our system clock speed
+#[cfg(not(feature = "an505"))]
 pub const SYSTEM_CLOCK: u32 = 25_000_000;
 
+/// Our system clock speed
+#[cfg(feature = "an505")]
+pub const SYSTEM_CLOCK: u32 = 20_000_000;
+
 static PERIPHS_TAKEN: AtomicBool = AtomicBool::new(false);
 
 /// Singleton containing the CMSDK device peripherals.
@@ -52,23 +63,27 @@ impl Peripherals {
     /// peripherals.
     pub unsafe fn steal() -> Self {
         Self {
-            uart0: unsafe { uart::Registers::new_mmio_at(uart::UART0_ADDR) },
-            uart1: unsafe { uart::Registers::new_mmio_at(uart::UART1_ADDR) },
-            uart2: unsafe { uart::Registers::new_mmio_at(uart::UART2_ADDR) },
-            uart3: unsafe { uart::Registers::new_mmio_at(uart::UART3_ADDR) },
-            uart4: unsafe { uart::Registers::new_mmio_at(uart::UART4_ADDR) },
-            timer0: unsafe { timer::registers::Registers::new_mmio_at(timer::TIMER_0_ADDR) },
-            timer1: unsafe { timer::registers::Registers::new_mmio_at(timer::TIMER_1_ADDR) },
+            uart0: unsafe { uart::Registers::new_mmio_at(pac::Uart0::PTR as usize) },
+            uart1: unsafe { uart::Registers::new_mmio_at(pac::Uart1::PTR as usize) },
+            uart2: unsafe { uart::Registers::new_mmio_at(pac::Uart2::PTR as usize) },
+            uart3: unsafe { uart::Registers::new_mmio_at(pac::Uart3::PTR as usize) },
+            uart4: unsafe { uart::Registers::new_mmio_at(pac::Uart4::PTR as usize) },
+            timer0: unsafe { timer::registers::Registers::new_mmio_at(pac::Timer0::PTR as usize) },
+            timer1: unsafe { timer::registers::Registers::new_mmio_at(pac::Timer1::PTR as usize) },
         }
     }
 }
 
 /// Called when the application raises an unrecoverable `panic!`.
 ///
-/// Prints the panic to the console and then exits QEMU using a semihosting
-/// breakpoint.
+/// Prints the panic, and how much stack we used, to the console and then exits
+/// QEMU using a semihosting breakpoint. If [`crash::set_reset_on_crash`] has
+/// been turned on, it stores a crash record and resets instead.
 #[panic_handler]
 fn panic(info: &core::panic::PanicInfo) -> ! {
+    if crash::reset_on_crash() {
+        crash::panic_reset(info);
+    }
     match (info.message().as_str(), info.location()) {
         (Some(m), Some(l)) => {
             defmt::error!("PANIC at {=str}:{=u32}: {}", l.file(), l.line(), m);
@@ -83,24 +98,18 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
             defmt::error!("PANIC!");
         }
     }
-    semihosting::process::exit(1);
+    // If we're running tests, this might not return
+    qemu_common::testing::on_panic();
+    exit(1);
 }
 
-/// A Hard Fault handler which logs to defmt and then does a semihosting exit.
-#[cortex_m_rt::exception(trampoline = true)]
-unsafe fn HardFault(frame: &cortex_m_rt::ExceptionFrame) -> ! {
-    defmt::error!(
-        "HardFault: r0=0x{=u32:08x}, r1=0x{=u32:08x}, r2=0x{=u32:08x}, r3=0x{=u32:08x}, r12=0x{=u32:08x}, lr=0x{=u32:08x}, pc=0x{=u32:08x}, xpsr=0x{=u32:08x}",     
-        frame.r0(),
-        frame.r1(),
-        frame.r2(),
-        frame.r3(),
-        frame.r12(),
-        frame.lr(),
-        frame.pc(),
-        frame.xpsr()
-    );
-    semihosting::process::exit(1);
+/// Print how much stack we used, and then exit QEMU
+pub fn exit(code: i32) -> ! {
+    stack::report();
+    semihosting::process::exit(code);
 }
 
+// The HardFault handler, which decodes the fault, logs to defmt and then does a
+// semihosting exit, is an assembly trampoline in `fault.rs`.
+
 // End of file
@@ -0,0 +1,437 @@
+//! A driver for the Armv7-M Memory Protection Unit
+//!
+//! The MPU divides memory into up to eight (on the Cortex-M4) regions, each of
+//! which has a base address, a size, access permissions and memory attributes.
+//! Where regions overlap, the highest numbered region wins.
+//!
+//! Registers:
+//!
+//! * TYPE
+//!   * 15-8: DREGION - number of data regions
+//! * CTRL
+//!   * 0: ENABLE
+//!   * 1: HFNMIENA - keep the MPU on in HardFault and NMI handlers
+//!   * 2: PRIVDEFENA - use the default memory map as a background region
+//! * RNR
+//!   * 7-0: Region number
+//! * RBAR
+//!   * 31-5: Base address (aligned to the region size)
+//!   * 4: VALID - use the REGION field instead of RNR
+//!   * 3-0: REGION
+//! * RASR
+//!   * 28: XN - execute never
+//!   * 26-24: AP - access permissions
+//!   * 21-19, 17, 16: TEX, C, B - memory type
+//!   * 18: S - shareable
+//!   * 15-8: SRD - subregion disable bits
+//!   * 5-1: SIZE - region is 2^(SIZE+1) bytes
+//!   * 0: ENABLE
+//!
+//! This module also installs a *stack guard* - a no-access region just below
+//! the stack. If the stack overflows, the CPU takes a MemManage fault instead of
+//! quietly overwriting whatever is in `.bss`. See [`stack_guard`].
+
+/// The smallest region the MPU supports, in bytes
+pub const MIN_REGION_SIZE: u32 = 32;
+
+/// The smallest region which can be split into subregions, in bytes
+pub const MIN_SUBREGION_REGION_SIZE: u32 = 256;
+
+/// The size of the no-access region below the stack, in bytes
+///
+/// A function with a stack frame larger than this could jump over the guard
+/// entirely, so don't make it too small.
+pub const STACK_GUARD_SIZE: u32 = 1024;
+
+/// The MPU region used for the stack guard
+///
+/// We use the highest numbered region so it takes priority over everything.
+pub const STACK_GUARD_REGION: u8 = 7;
+
+/// The attributes for our stack guard region
+const STACK_GUARD: RegionBuilder = RegionBuilder::new(STACK_GUARD_SIZE)
+    .access(AccessPermissions::NoAccess)
+    .execute_never(true)
+    .memory(MemoryType::StronglyOrdered);
+
+/// The RASR value for our stack guard region, checked at compile time
+const STACK_GUARD_RASR: u32 = match STACK_GUARD.rasr() {
+    Ok(rasr) => rasr,
+    Err(_) => panic!("Invalid stack guard region"),
+};
+
+const CTRL_ENABLE: u32 = 1 << 0;
+const CTRL_PRIVDEFENA: u32 = 1 << 2;
+const RBAR_VALID: u32 = 1 << 4;
+const RASR_ENABLE: u32 = 1 << 0;
+const RASR_XN: u32 = 1 << 28;
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// Region sizes must be a power of two.
+    SizeNotPowerOfTwo,
+    /// Region is smaller than [`MIN_REGION_SIZE`].
+    SizeTooSmall,
+    /// The base address must be a multiple of the region size.
+    BaseNotAligned,
+    /// Regions smaller than [`MIN_SUBREGION_REGION_SIZE`] cannot have subregions disabled.
+    SubregionsNotSupported,
+    /// This MPU doesn't have that many regions.
+    InvalidRegion,
+}
+
+/// Access permissions for a region (the RASR.AP field)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+#[repr(u8)]
+pub enum AccessPermissions {
+    /// Any access faults
+    NoAccess = 0b000,
+    /// Privileged code can read and write, unprivileged code has no access
+    PrivilegedReadWrite = 0b001,
+    /// Privileged code can read and write, unprivileged code can read
+    PrivilegedReadWriteUnprivilegedReadOnly = 0b010,
+    /// Anyone can read and write
+    ReadWrite = 0b011,
+    /// Privileged code can read, unprivileged code has no access
+    PrivilegedReadOnly = 0b101,
+    /// Anyone can read
+    ReadOnly = 0b110,
+}
+
+/// The memory type of a region (the RASR.TEX, C, B and S fields)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum MemoryType {
+    /// Strongly ordered - every access happens, in order
+    StronglyOrdered,
+    /// Device memory (for peripherals)
+    Device,
+    /// Normal memory, not cached
+    NormalNonCacheable {
+        /// Is this memory shared with other bus masters?
+        shareable: bool,
+    },
+    /// Normal memory, write-through cached
+    NormalWriteThrough {
+        /// Is this memory shared with other bus masters?
+        shareable: bool,
+    },
+    /// Normal memory, write-back cached, with write-allocate
+    NormalWriteBack {
+        /// Is this memory shared with other bus masters?
+        shareable: bool,
+    },
+}
+
+impl MemoryType {
+    /// Get the TEX, S, C and B bits, in their RASR positions
+    const fn rasr_bits(self) -> u32 {
+        let (tex, s, c, b) = match self {
+            MemoryType::StronglyOrdered => (0b000, false, false, false),
+            MemoryType::Device => (0b000, false, false, true),
+            MemoryType::NormalNonCacheable { shareable } => (0b001, shareable, false, false),
+            MemoryType::NormalWriteThrough { shareable } => (0b000, shareable, true, false),
+            MemoryType::NormalWriteBack { shareable } => (0b001, shareable, true, true),
+        };
+        (tex << 19) | ((s as u32) << 18) | ((c as u32) << 17) | ((b as u32) << 16)
+    }
+}
+
+/// Builds the attributes for an MPU region
+///
+/// All the methods are `const fn`, so you can check your regions at compile
+/// time:
+///
+/// ```rust ignore
+/// const FLASH: Region = match RegionBuilder::new(4 * 1024 * 1024)
+///     .access(AccessPermissions::ReadOnly)
+///     .memory(MemoryType::NormalWriteThrough { shareable: false })
+///     .build(0x0000_0000)
+/// {
+///     Ok(region) => region,
+///     Err(_) => panic!("bad region"),
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RegionBuilder {
+    size: u32,
+    access: AccessPermissions,
+    execute_never: bool,
+    disabled_subregions: u8,
+    memory: MemoryType,
+}
+
+impl RegionBuilder {
+    /// Start building a region of the given size, in bytes.
+    ///
+    /// The default is read-write for everyone, executable, normal write-back
+    /// memory, with all subregions enabled.
+    pub const fn new(size: u32) -> RegionBuilder {
+        RegionBuilder {
+            size,
+            access: AccessPermissions::ReadWrite,
+            execute_never: false,
+            disabled_subregions: 0,
+            memory: MemoryType::NormalWriteBack { shareable: false },
+        }
+    }
+
+    /// Set the access permissions
+    pub const fn access(mut self, access: AccessPermissions) -> RegionBuilder {
+        self.access = access;
+        self
+    }
+
+    /// Set whether instruction fetches from this region will fault
+    pub const fn execute_never(mut self, execute_never: bool) -> RegionBuilder {
+        self.execute_never = execute_never;
+        self
+    }
+
+    /// Disable some of the eight equally sized subregions
+    ///
+    /// Bit `n` disables subregion `n`, counting up from the base address.
+    /// Accesses to a disabled subregion fall through to the next lower
+    /// numbered region that matches (or the background map).
+    pub const fn disable_subregions(mut self, mask: u8) -> RegionBuilder {
+        self.disabled_subregions = mask;
+        self
+    }
+
+    /// Set the memory type
+    pub const fn memory(mut self, memory: MemoryType) -> RegionBuilder {
+        self.memory = memory;
+        self
+    }
+
+    /// Calculate the RASR value for this region
+    pub const fn rasr(&self) -> Result<u32, Error> {
+        if !self.size.is_power_of_two() {
+            return Err(Error::SizeNotPowerOfTwo);
+        }
+        if self.size < MIN_REGION_SIZE {
+            return Err(Error::SizeTooSmall);
+        }
+        if self.disabled_subregions != 0 && self.size < MIN_SUBREGION_REGION_SIZE {
+            return Err(Error::SubregionsNotSupported);
+        }
+        // region is 2^(SIZE+1) bytes
+        let size_field = self.size.trailing_zeros() - 1;
+        let xn = if self.execute_never { RASR_XN } else { 0 };
+        Ok(xn
+            | ((self.access as u32) << 24)
+            | self.memory.rasr_bits()
+            | ((self.disabled_subregions as u32) << 8)
+            | (size_field << 1)
+            | RASR_ENABLE)
+    }
+
+    /// Check everything and produce a region starting at `base`.
+    pub const fn build(self, base: u32) -> Result<Region, Error> {
+        let rasr = match self.rasr() {
+            Ok(rasr) => rasr,
+            Err(e) => return Err(e),
+        };
+        if base % self.size != 0 {
+            return Err(Error::BaseNotAligned);
+        }
+        Ok(Region { base, rasr })
+    }
+}
+
+/// A validated MPU region, ready to be loaded into the MPU
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Region {
+    base: u32,
+    rasr: u32,
+}
+
+impl Region {
+    /// The lowest address in the region
+    pub const fn base(&self) -> u32 {
+        self.base
+    }
+
+    /// The size of the region, in bytes
+    pub const fn size(&self) -> u32 {
+        2 << ((self.rasr >> 1) & 0x1F)
+    }
+
+    /// The raw RASR value
+    pub const fn rasr(&self) -> u32 {
+        self.rasr
+    }
+}
+
+/// An Armv7-M MPU driver
+pub struct Mpu {
+    mpu: cortex_m::peripheral::MPU,
+}
+
+impl Mpu {
+    /// Create a new MPU driver
+    pub fn new(mpu: cortex_m::peripheral::MPU) -> Mpu {
+        Mpu { mpu }
+    }
+
+    /// How many regions does this MPU support?
+    pub fn num_regions(&self) -> u8 {
+        (self.mpu._type.read() >> 8) as u8
+    }
+
+    /// Load a region into the MPU
+    ///
+    /// If the MPU is enabled you probably want to disable it first, as the
+    /// region is updated with two separate register writes.
+    pub fn set_region(&mut self, number: u8, region: &Region) -> Result<(), Error> {
+        if number >= self.num_regions() {
+            return Err(Error::InvalidRegion);
+        }
+        // Safety: we have exclusive ownership of the MPU. It is up to the
+        // caller to not break their own program with the new region.
+        unsafe {
+            self.mpu.rnr.write(u32::from(number));
+            self.mpu.rbar.write(region.base);
+            self.mpu.rasr.write(region.rasr);
+        }
+        Ok(())
+    }
+
+    /// Read back a region from the MPU
+    pub fn region(&mut self, number: u8) -> Result<Option<Region>, Error> {
+        if number >= self.num_regions() {
+            return Err(Error::InvalidRegion);
+        }
+        // Safety: selecting a region doesn't change the memory map
+        unsafe {
+            self.mpu.rnr.write(u32::from(number));
+        }
+        let rasr = self.mpu.rasr.read();
+        if rasr & RASR_ENABLE == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Region {
+            base: self.mpu.rbar.read() & !0x1F,
+            rasr,
+        }))
+    }
+
+    /// Turn off a region
+    pub fn clear_region(&mut self, number: u8) -> Result<(), Error> {
+        if number >= self.num_regions() {
+            return Err(Error::InvalidRegion);
+        }
+        // Safety: see `set_region`
+        unsafe {
+            self.mpu.rnr.write(u32::from(number));
+            self.mpu.rasr.write(0);
+        }
+        Ok(())
+    }
+
+    /// Turn on the MPU
+    ///
+    /// If `background` is true, privileged code can access anything not
+    /// covered by a region, using the default memory map. The MPU is always
+    /// disabled inside the HardFault handler, so it has some stack to work
+    /// with if the stack guard is hit.
+    pub fn enable(&mut self, background: bool) {
+        let ctrl = if background {
+            CTRL_ENABLE | CTRL_PRIVDEFENA
+        } else {
+            CTRL_ENABLE
+        };
+        // Safety: It's up to the caller to have set up some sensible regions
+        unsafe {
+            self.mpu.ctrl.write(ctrl);
+        }
+        cortex_m::asm::dsb();
+        cortex_m::asm::isb();
+    }
+
+    /// Turn off the MPU
+    pub fn disable(&mut self) {
+        cortex_m::asm::dmb();
+        // Safety: Turning the MPU off only ever allows more accesses
+        unsafe {
+            self.mpu.ctrl.write(0);
+        }
+        cortex_m::asm::dsb();
+        cortex_m::asm::isb();
+    }
+
+    /// Is the MPU turned on?
+    pub fn is_enabled(&self) -> bool {
+        self.mpu.ctrl.read() & CTRL_ENABLE != 0
+    }
+}
+
+/// The address range covered by the stack guard region
+///
+/// This is the first [`STACK_GUARD_SIZE`] aligned block at or above the
+/// bottom of the stack (`_stack_end`), which is where `__pre_init` put it.
+pub fn stack_guard() -> core::ops::Range<u32> {
+    unsafe extern "C" {
+        static _stack_end: u32;
+    }
+    let stack_end = (&raw const _stack_end) as u32;
+    let base = (stack_end + STACK_GUARD_SIZE - 1) & !(STACK_GUARD_SIZE - 1);
+    base..base + STACK_GUARD_SIZE
+}
+
+// Paint the stack (see `src/stack.rs`) and install the stack guard before
+// `main` runs.
+//
+// This must be in assembly - `__pre_init` runs before `.data` and `.bss` are
+// initialised, so it isn't safe to run Rust code here. It is the equivalent
+// of:
+//
+// * painting the stack
+// * enabling the MemManage exception (otherwise it escalates to HardFault)
+// * `Mpu::set_region(STACK_GUARD_REGION, &STACK_GUARD.build(stack_guard().start))`
+// * `Mpu::enable(true)`
+core::arch::global_asm!(
+    r#"
+    .section .text.__pre_init, "ax"
+    .thumb_func
+    .global __pre_init
+    .type __pre_init, %function
+    __pre_init:
+        // Paint the stack first, as the guard will stop us writing to it
+        mov r3, lr
+        bl __paint_stack
+        mov lr, r3
+        // SHCSR.MEMFAULTENA = 1
+        ldr r0, =0xE000ED24
+        ldr r1, [r0]
+        orr r1, r1, #(1 << 16)
+        str r1, [r0]
+        // r1 = _stack_end, rounded up to the guard size
+        ldr r1, =_stack_end
+        ldr r2, ={mask}
+        add r1, r1, r2
+        bic r1, r1, r2
+        // RBAR = base | VALID | REGION
+        orr r1, r1, #{rbar_bits}
+        ldr r0, =0xE000ED9C
+        str r1, [r0]
+        // RASR
+        ldr r1, ={rasr}
+        ldr r0, =0xE000EDA0
+        str r1, [r0]
+        // CTRL = ENABLE | PRIVDEFENA
+        movs r1, #{ctrl}
+        ldr r0, =0xE000ED94
+        str r1, [r0]
+        dsb
+        isb
+        bx lr
+    .ltorg
+    "#,
+    mask = const STACK_GUARD_SIZE - 1,
+    rbar_bits = const RBAR_VALID | STACK_GUARD_REGION as u32,
+    rasr = const STACK_GUARD_RASR,
+    ctrl = const CTRL_ENABLE | CTRL_PRIVDEFENA,
+);
+
+// End of file
@@ -0,0 +1,216 @@
+//! A statistical profiler, driven by SysTick
+//!
+//! Enable the `profiler` feature, call [`start`] early in `main`, and call
+//! [`finish`] before you exit. In between, SysTick interrupts the program
+//! thousands of times a second and we note down where it was: the stacked PC,
+//! and the stacked LR (which is usually the return address of the function
+//! that was running, so it tells us who called it). Identical samples are
+//! counted together in a fixed-size table.
+//!
+//! [`finish`] writes the table to a file on the host using semihosting, and
+//! the `qemu-profile` tool in `../qemu-profile` turns the addresses back into
+//! function names.
+//!
+//! The `profiler` feature defines the `SysTick` exception handler, so you
+//! can't use SysTick for anything else (including the test harness, or the
+//! RTIC SysTick monotonic) whilst profiling.
+
+use core::{
+    ffi::CStr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use cortex_m::peripheral::{syst::SystClkSource, SYST};
+use cortex_m_rt::ExceptionFrame;
+use semihosting::{fs::File, io::Write};
+
+/// How many different (PC, LR) pairs we can count
+const TABLE_SIZE: usize = 2048;
+
+/// How many slots we try before giving up on a sample
+const MAX_PROBES: usize = 32;
+
+/// Identifies our profile files
+const FILE_MAGIC: [u8; 4] = *b"QPRF";
+
+/// The version of our file format
+const FILE_VERSION: u32 = 1;
+
+/// Error codes from this module
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub enum Error {
+    /// The host reported an error, with this `errno` value (if it gave one)
+    Host(Option<i32>),
+    /// SysTick can't interrupt that slowly (or that quickly)
+    BadRate,
+}
+
+impl From<semihosting::io::Error> for Error {
+    fn from(error: semihosting::io::Error) -> Error {
+        Error::Host(error.raw_os_error())
+    }
+}
+
+/// One slot in the sample table
+///
+/// A PC of zero means the slot is empty. The PC is written last, so a slot
+/// with a PC always has the right LR.
+struct Slot {
+    pc: AtomicU32,
+    lr: AtomicU32,
+    count: AtomicU32,
+}
+
+impl Slot {
+    const EMPTY: Slot = Slot {
+        pc: AtomicU32::new(0),
+        lr: AtomicU32::new(0),
+        count: AtomicU32::new(0),
+    };
+}
+
+/// The sample table
+static TABLE: [Slot; TABLE_SIZE] = [Slot::EMPTY; TABLE_SIZE];
+
+/// How many samples we have taken
+static SAMPLES: AtomicU32 = AtomicU32::new(0);
+
+/// How many samples didn't fit in the table
+static DROPPED: AtomicU32 = AtomicU32::new(0);
+
+/// How often we sample, in Hz
+static RATE_HZ: AtomicU32 = AtomicU32::new(0);
+
+/// What we found when we stopped profiling
+#[derive(Debug, Copy, Clone, PartialEq, Eq, defmt::Format)]
+pub struct Summary {
+    /// How many samples we took
+    pub samples: u32,
+    /// How many samples we had to throw away because the table was full
+    pub dropped: u32,
+    /// How many different (PC, LR) pairs we saw
+    pub unique: u32,
+}
+
+/// Start sampling, `rate_hz` times per second
+///
+/// We take the SysTick peripheral, and never give it back.
+pub fn start(mut syst: SYST, rate_hz: u32) -> Result<(), Error> {
+    let reload = crate::SYSTEM_CLOCK
+        .checked_div(rate_hz)
+        .and_then(|ticks| ticks.checked_sub(1))
+        .filter(|reload| (1..=0x00FF_FFFF).contains(reload))
+        .ok_or(Error::BadRate)?;
+    RATE_HZ.store(rate_hz, Ordering::Relaxed);
+    syst.set_clock_source(SystClkSource::Core);
+    syst.set_reload(reload);
+    syst.clear_current();
+    syst.enable_interrupt();
+    syst.enable_counter();
+    Ok(())
+}
+
+/// Stop sampling, and write what we found to `path` on the host
+///
+/// The file is in the format the `qemu-profile` tool expects.
+pub fn finish(path: &CStr) -> Result<Summary, Error> {
+    // Safety: we own SysTick (we were given it in `start`). Clearing the
+    // enable bits is all we do.
+    unsafe {
+        let syst = &*SYST::PTR;
+        syst.csr.modify(|csr| csr & !0b11);
+    }
+    let summary = Summary {
+        samples: SAMPLES.load(Ordering::Relaxed),
+        dropped: DROPPED.load(Ordering::Relaxed),
+        unique: TABLE
+            .iter()
+            .filter(|slot| slot.pc.load(Ordering::Relaxed) != 0)
+            .count() as u32,
+    };
+
+    let mut file = File::create(path)?;
+    let mut header = [0u8; 24];
+    header[0..4].copy_from_slice(&FILE_MAGIC);
+    header[4..8].copy_from_slice(&FILE_VERSION.to_le_bytes());
+    header[8..12].copy_from_slice(&RATE_HZ.load(Ordering::Relaxed).to_le_bytes());
+    header[12..16].copy_from_slice(&summary.samples.to_le_bytes());
+    header[16..20].copy_from_slice(&summary.dropped.to_le_bytes());
+    header[20..24].copy_from_slice(&summary.unique.to_le_bytes());
+    file.write_all(&header)?;
+
+    // Semihosting calls are slow, so send a few entries at a time
+    let mut chunk = [0u8; 12 * 32];
+    let mut used = 0;
+    for slot in TABLE.iter() {
+        let pc = slot.pc.load(Ordering::Relaxed);
+        if pc == 0 {
+            continue;
+        }
+        let entry = &mut chunk[used..used + 12];
+        entry[0..4].copy_from_slice(&pc.to_le_bytes());
+        entry[4..8].copy_from_slice(&slot.lr.load(Ordering::Relaxed).to_le_bytes());
+        entry[8..12].copy_from_slice(&slot.count.load(Ordering::Relaxed).to_le_bytes());
+        used += 12;
+        if used == chunk.len() {
+            file.write_all(&chunk)?;
+            used = 0;
+        }
+    }
+    file.write_all(&chunk[..used])?;
+    Ok(summary)
+}
+
+/// Count one sample
+///
+/// Only SysTick calls this, so there's only ever one of us running - but the
+/// atomics keep [`finish`] honest if it runs while we're in the middle.
+fn record(pc: u32, lr: u32) {
+    SAMPLES.fetch_add(1, Ordering::Relaxed);
+    // A cheap hash, good enough to spread nearby addresses around the table
+    let hash = (pc ^ lr.rotate_left(16)).wrapping_mul(0x9E37_79B1);
+    let start = (hash >> 16) as usize;
+    for probe in 0..MAX_PROBES {
+        let slot = &TABLE[(start + probe) % TABLE_SIZE];
+        match slot.pc.load(Ordering::Relaxed) {
+            0 => {
+                slot.lr.store(lr, Ordering::Relaxed);
+                slot.count.store(1, Ordering::Relaxed);
+                slot.pc.store(pc, Ordering::Release);
+                return;
+            }
+            p if p == pc && slot.lr.load(Ordering::Relaxed) == lr => {
+                slot.count.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            _ => {}
+        }
+    }
+    DROPPED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Called from our SysTick handler, with the frame the processor stacked
+extern "C" fn on_systick(frame: &ExceptionFrame) {
+    record(frame.pc(), frame.lr());
+}
+
+// The SysTick handler. We need to find the exception frame, which is on
+// whichever stack was in use when we were interrupted - bit 2 of EXC_RETURN
+// (in LR) tells us which. We then tail-call `on_systick`, which returns
+// straight from the exception.
+core::arch::global_asm!(
+    ".section .text.SysTick,\"ax\",%progbits",
+    ".global SysTick",
+    ".type SysTick,%function",
+    ".thumb_func",
+    "SysTick:",
+    "tst lr, #4",
+    "ite eq",
+    "mrseq r0, msp",
+    "mrsne r0, psp",
+    "b {on_systick}",
+    ".size SysTick, . - SysTick",
+    on_systick = sym on_systick,
+);
+
+// End of file
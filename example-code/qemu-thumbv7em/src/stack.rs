@@ -0,0 +1,69 @@
+//! Measuring how much stack we use
+//!
+//! Before anything else runs, `__pre_init` calls `__paint_stack`, which fills
+//! everything from `_stack_end` (the bottom of the stack, according to
+//! `cortex-m-rt`) up to the initial stack pointer with
+//! [`PAINT`](qemu_common::stack::PAINT). Call [`usage`] at any time to see how
+//! much of it has been used since. The panic handler, the fault handlers and
+//! [`crate::exit`] all print it for you.
+//!
+//! The MPU stack guard (see [`crate::mpu::stack_guard`]) sits at the bottom of
+//! the stack and can never be used, so we don't count it.
+
+use qemu_common::stack::{Stack, Usage};
+
+unsafe extern "C" {
+    /// The bottom of the stack, from `cortex-m-rt`
+    static _stack_end: u32;
+    /// The top of the stack, from `crash_log.x`
+    static _stack_start: u32;
+}
+
+/// Get our stack
+pub fn stack() -> Stack {
+    #[cfg(not(feature = "an505"))]
+    let bottom = crate::mpu::stack_guard().end as usize;
+    #[cfg(feature = "an505")]
+    let bottom = (&raw const _stack_end) as usize;
+    let top = (&raw const _stack_start) as usize;
+    // Safety: the linker gives this memory to the stack
+    unsafe { Stack::new(bottom, top) }
+}
+
+/// Work out how much of the stack has been used
+pub fn usage() -> Usage {
+    stack().usage()
+}
+
+/// Print the stack usage with defmt
+pub fn report() {
+    stack().report();
+}
+
+// Paint the stack. This is called from `__pre_init`, before RAM is
+// initialised and before the stack guard is turned on, so it must be in
+// assembly. It only uses r0-r2, so the caller can keep its return address in
+// r3.
+core::arch::global_asm!(
+    r#"
+    .section .text.__paint_stack, "ax"
+    .thumb_func
+    .global __paint_stack
+    .type __paint_stack, %function
+    __paint_stack:
+        ldr r0, =_stack_end
+        ldr r1, ={paint}
+        mov r2, sp
+    1:
+        cmp r0, r2
+        bhs 2f
+        str r1, [r0], #4
+        b 1b
+    2:
+        bx lr
+    .ltorg
+    "#,
+    paint = const qemu_common::stack::PAINT,
+);
+
+// End of file
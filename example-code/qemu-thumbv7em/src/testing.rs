@@ -0,0 +1,108 @@
+//! Support for running the on-target tests in `./tests`
+//!
+//! The harness itself lives in `qemu_common::testing`. This module provides
+//! the clock it needs, and a [`test_suite!`](crate::test_suite) macro which
+//! adds an entry point and a SysTick handler.
+//!
+//! We run SysTick at 1 kHz whilst the tests run, so tests cannot use SysTick
+//! themselves (and that includes the RTIC SysTick monotonic).
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+pub use qemu_common::testing::*;
+
+#[doc(hidden)]
+pub use qemu_common::test_suite as __test_suite;
+
+/// How many SysTick interrupts we have had
+static TICKS_MS: AtomicU32 = AtomicU32::new(0);
+
+/// Our implementation of the harness's [`Platform`] trait
+pub struct Qemu;
+
+impl Platform for Qemu {
+    fn init() {
+        // SysTick has a 24-bit reload value, so a 1ms tick is fine up to a
+        // system clock of about 16 GHz
+        let reload = crate::SYSTEM_CLOCK / 1000 - 1;
+        // Safety: the test harness owns SysTick. We don't take the
+        // `cortex_m::Peripherals` singleton, so the tests still can.
+        unsafe {
+            let syst = &*cortex_m::peripheral::SYST::PTR;
+            syst.rvr.write(reload);
+            syst.cvr.write(0);
+            // ENABLE | TICKINT | CLKSOURCE (processor clock)
+            syst.csr.write(0b111);
+        }
+    }
+
+    fn now_us() -> u64 {
+        u64::from(TICKS_MS.load(Ordering::Relaxed)) * 1000
+    }
+
+    fn exit(code: i32) -> ! {
+        crate::exit(code)
+    }
+
+    fn checkpoint() -> Checkpoint {
+        Checkpoint {
+            stack_pointer: cortex_m::register::msp::read() as usize,
+            interrupts_enabled: cortex_m::register::primask::read().is_active(),
+        }
+    }
+
+    /// Go back to the checkpoint
+    ///
+    /// This assumes the test panicked in Thread mode. A panic in an
+    /// exception handler carries on in Handler mode.
+    unsafe fn restart(checkpoint: Checkpoint, resume: Resume, next: usize) -> ! {
+        if checkpoint.interrupts_enabled {
+            // Safety: they were enabled when the tests started
+            unsafe { cortex_m::interrupt::enable() };
+        } else {
+            cortex_m::interrupt::disable();
+        }
+        // Safety: our caller says nothing below the checkpoint is in use
+        unsafe {
+            core::arch::asm!(
+                "mov sp, {stack_pointer}",
+                "bx {resume}",
+                stack_pointer = in(reg) checkpoint.stack_pointer,
+                resume = in(reg) resume,
+                in("r0") next,
+                options(noreturn)
+            )
+        }
+    }
+}
+
+/// Call this from the SysTick handler
+pub fn on_tick() {
+    TICKS_MS.fetch_add(1, Ordering::Relaxed);
+    check_timeout::<Qemu>();
+}
+
+/// Declare some tests, and an entry point which runs them
+///
+/// See `qemu_common::test_suite!` for the syntax.
+#[macro_export]
+macro_rules! test_suite {
+    ($($tests:tt)*) => {
+        $crate::testing::__test_suite! {
+            platform: $crate::testing::Qemu;
+            $($tests)*
+        }
+
+        #[cortex_m_rt::entry]
+        fn main() -> ! {
+            __run_tests(0)
+        }
+
+        #[cortex_m_rt::exception]
+        fn SysTick() {
+            $crate::testing::on_tick();
+        }
+    };
+}
+
+// End of file
@@ -1,6 +1,5 @@
-//! A driver for the MPS2-AN386 timers
+//! A driver for the MPS2 timers
+//!
+//! The base addresses of the timers come from the PAC (e.g. [`crate::pac::Timer0::PTR`]).
 
 pub use qemu_common::cmsdk_timer::*;
-
-pub const TIMER_0_ADDR: usize = 0x4000_0000;
-pub const TIMER_1_ADDR: usize = 0x4000_1000;
@@ -0,0 +1,69 @@
+//! On-target tests for the CMSDK Timer driver
+//!
+//! Run with `cargo test --test cmsdk_timer`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use embedded_hal::delay::DelayNs;
+use qemu_thumbv7em::{
+    testing::{Platform, Qemu},
+    timer::{DelayTimer, Timer},
+    Peripherals, SYSTEM_CLOCK,
+};
+
+qemu_thumbv7em::test_suite! {
+    setup: fn setup() -> Timer {
+        // Safety: we only take Timer0, which the harness has finished with
+        // by the time it calls `setup` again. `delay_waits_long_enough` leaves a
+        // second driver in its state, but never uses it.
+        let p = unsafe { Peripherals::steal() };
+        let mut timer = Timer::new(p.timer0);
+        timer.disable();
+        timer.enable_interrupt(false);
+        timer.clear_interrupt();
+        timer.write_reload(u32::MAX);
+        timer
+    }
+
+    fn counts_down(timer: &mut Timer) {
+        timer.write_value(u32::MAX);
+        timer.enable();
+        let first = timer.read();
+        while timer.read() == first {
+            core::hint::spin_loop();
+        }
+        assert!(timer.read() < first);
+        timer.disable();
+    }
+
+    #[timeout(100)]
+    fn interrupt_flag_sets_and_clears(timer: &mut Timer) {
+        timer.enable_interrupt(true);
+        timer.write_value(1000);
+        timer.enable();
+        while !timer.interrupt_fired() {
+            core::hint::spin_loop();
+        }
+        timer.disable();
+        timer.clear_interrupt();
+        assert!(!timer.interrupt_fired());
+        timer.enable_interrupt(false);
+    }
+
+    #[timeout(500)]
+    fn delay_waits_long_enough(timer: &mut Timer) {
+        // `DelayTimer` wants to own its timer, so swap in a spare
+        let timer = core::mem::replace(timer, setup());
+        let mut delay = DelayTimer::new(timer, SYSTEM_CLOCK);
+        let start = Qemu::now_us();
+        delay.delay_ms(20);
+        let elapsed = Qemu::now_us() - start;
+        // our clock ticks once a millisecond, so allow for that
+        assert!(elapsed >= 19_000, "only waited {} us", elapsed);
+    }
+}
+
+// End of file
@@ -0,0 +1,50 @@
+//! On-target tests for the CMSDK UART driver
+//!
+//! Run with `cargo test --test cmsdk_uart`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+#![no_std]
+#![no_main]
+
+use qemu_thumbv7em::{pac, uart, Peripherals, SYSTEM_CLOCK};
+
+qemu_thumbv7em::test_suite! {
+    setup: fn setup() -> uart::CmsdkUart {
+        // Safety: we only take UART0, which the harness has finished with by
+        // the time it calls `setup` again
+        let p = unsafe { Peripherals::steal() };
+        uart::CmsdkUart::new(p.uart0)
+    }
+
+    fn passes_id_check(uart: &mut uart::CmsdkUart) {
+        uart.check().unwrap();
+    }
+
+    fn accepts_115200_baud(uart: &mut uart::CmsdkUart) {
+        assert!(uart.init(115200, SYSTEM_CLOCK).is_ok());
+    }
+
+    fn rejects_baud_rate_above_clock_over_16(uart: &mut uart::CmsdkUart) {
+        let result = uart.init(SYSTEM_CLOCK / 8, SYSTEM_CLOCK);
+        assert!(matches!(result, Err(uart::Error::InvalidBaudRate)));
+    }
+
+    #[timeout(100)]
+    fn writes_without_blocking_forever(uart: &mut uart::CmsdkUart) {
+        uart.init(115200, SYSTEM_CLOCK).unwrap();
+        for byte in b"Hello from the UART test\n" {
+            uart.tx().write_blocking(*byte);
+        }
+    }
+
+    #[should_panic]
+    fn timer_is_not_a_uart(_: &mut uart::CmsdkUart) {
+        // Safety: we only read the ID registers
+        let mut not_a_uart =
+            unsafe { uart::CmsdkUart::new_with_raw_addr(pac::Timer0::PTR as usize) };
+        not_a_uart.check().unwrap();
+    }
+}
+
+// End of file
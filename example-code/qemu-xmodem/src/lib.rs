@@ -0,0 +1,10 @@
+//! The XMODEM-1K and YMODEM sender behind the `qemu-xmodem` tool
+//!
+//! It lives in a library so the tests in `./tests` can run it against the
+//! receiver in `qemu_common::xmodem`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+pub mod sender;
+
+// End of file
@@ -0,0 +1,128 @@
+//! Sends files to a QEMU machine's UART over XMODEM-1K or YMODEM
+//!
+//! Start the firmware with its UART on a telnet server (e.g. `cargo run --bin
+//! xmodem_receive -- --uart-telnet` in `qemu-thumbv7em`), then:
+//!
+//! ```console
+//! $ qemu-xmodem some_file.bin
+//! ```
+//!
+//! We use YMODEM unless you pass `--xmodem`, and connect to
+//! `localhost:4321` unless you pass `--address <host:port>`. Pass `--raw` if
+//! the server is a plain TCP socket rather than telnet.
+//!
+//! When we're done we print the CRC-32 of the file, which the
+//! `xmodem_receive` example also prints, so you can check they match.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+mod telnet;
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+use qemu_xmodem::sender::{self, Error, Protocol, Sender};
+
+/// Where QEMU's telnet server listens, with `--uart-telnet`
+const DEFAULT_ADDRESS: &str = "localhost:4321";
+
+/// How long we keep trying to connect, whilst QEMU starts up
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long we wait for the receiver to say something
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Our command-line options
+struct Options {
+    protocol: Protocol,
+    raw: bool,
+    address: String,
+    path: String,
+}
+
+impl Options {
+    fn parse() -> Option<Options> {
+        let mut options = Options {
+            protocol: Protocol::Ymodem,
+            raw: false,
+            address: DEFAULT_ADDRESS.to_string(),
+            path: String::new(),
+        };
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--xmodem" => options.protocol = Protocol::Xmodem,
+                "--ymodem" => options.protocol = Protocol::Ymodem,
+                "--raw" => options.raw = true,
+                "--address" => options.address = args.next()?,
+                path if options.path.is_empty() && !path.starts_with('-') => {
+                    options.path = path.to_string()
+                }
+                _ => return None,
+            }
+        }
+        (!options.path.is_empty()).then_some(options)
+    }
+}
+
+/// Connect to the server, waiting for it to come up if it isn't yet
+fn connect(address: &str) -> Result<TcpStream, Error> {
+    let started = Instant::now();
+    loop {
+        match TcpStream::connect(address) {
+            Ok(stream) => return Ok(stream),
+            Err(e) if started.elapsed() > CONNECT_TIMEOUT => return Err(e.into()),
+            Err(_) => std::thread::sleep(Duration::from_millis(200)),
+        }
+    }
+}
+
+/// Send the file, printing progress as we go
+fn send<P: Read + Write>(port: P, options: &Options, data: &[u8]) -> Result<(), Error> {
+    let name = std::path::Path::new(&options.path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("file");
+    let total = data.len();
+    let mut sender = Sender::new(port).on_progress(move |sent| {
+        eprint!("\rSent {sent} of {total} bytes");
+    });
+    let result = sender.send(options.protocol, name, data);
+    eprintln!();
+    if result.is_err() {
+        // Best effort, so the receiver isn't left waiting
+        let _ = sender.cancel();
+    }
+    result
+}
+
+fn main() -> Result<(), Error> {
+    let Some(options) = Options::parse() else {
+        eprintln!(
+            "Usage: qemu-xmodem [--xmodem | --ymodem] [--raw] [--address <host:port>] <file>"
+        );
+        std::process::exit(2);
+    };
+    let data = std::fs::read(&options.path)?;
+
+    let stream = connect(&options.address)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    eprintln!("Connected to {}", options.address);
+    if options.raw {
+        send(stream, &options, &data)?;
+    } else {
+        send(telnet::Telnet::new(stream), &options, &data)?;
+    }
+    println!(
+        "Sent {} ({} bytes, CRC-32 {:#010x})",
+        options.path,
+        data.len(),
+        sender::crc32(&data)
+    );
+    Ok(())
+}
+
+// End of file
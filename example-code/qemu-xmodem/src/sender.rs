@@ -0,0 +1,220 @@
+//! The sending side of XMODEM-1K and YMODEM
+//!
+//! This pairs with the receiver in `qemu_common::xmodem`. We always use
+//! CRC-16 rather than the old 8-bit checksum, so we wait for the receiver to
+//! send `C`.
+
+use std::io::{Read, Write};
+
+/// Start of a 128 byte block
+const SOH: u8 = 0x01;
+/// Start of a 1024 byte block
+const STX: u8 = 0x02;
+/// End of transmission
+const EOT: u8 = 0x04;
+/// Block received OK
+const ACK: u8 = 0x06;
+/// Block not received OK - please send it again
+const NAK: u8 = 0x15;
+/// Cancel the transfer
+const CAN: u8 = 0x18;
+/// The receiver wants CRC-16 mode
+const CRC_MODE: u8 = b'C';
+/// Pads out the last XMODEM block
+const PAD: u8 = 0x1A;
+
+/// How many times we try to send each block
+const MAX_RETRIES: u32 = 10;
+
+/// What to do when things go wrong
+pub type Error = Box<dyn std::error::Error>;
+
+/// Which protocol to use
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Protocol {
+    /// XMODEM-1K, with CRC-16
+    Xmodem,
+    /// YMODEM, which also sends the file name and length
+    Ymodem,
+}
+
+/// Sends files to a receiver on the other end of `port`
+///
+/// Reads from `port` should time out rather than block forever.
+pub struct Sender<P> {
+    port: P,
+    /// Called with the number of bytes sent so far
+    progress: Box<dyn FnMut(usize)>,
+}
+
+impl<P: Read + Write> Sender<P> {
+    /// Make a sender, talking over `port`
+    pub fn new(port: P) -> Sender<P> {
+        Sender {
+            port,
+            progress: Box::new(|_| {}),
+        }
+    }
+
+    /// Call `progress` with the number of bytes sent after each block
+    pub fn on_progress(mut self, progress: impl FnMut(usize) + 'static) -> Sender<P> {
+        self.progress = Box::new(progress);
+        self
+    }
+
+    /// Send one file
+    pub fn send(&mut self, protocol: Protocol, name: &str, data: &[u8]) -> Result<(), Error> {
+        self.wait_for_start()?;
+        if protocol == Protocol::Ymodem {
+            let mut header = format!("{name}\0{}", data.len()).into_bytes();
+            if header.len() > 1024 {
+                return Err("file name is too long for YMODEM".into());
+            }
+            let len = if header.len() < 128 { 128 } else { 1024 };
+            header.resize(len, 0);
+            self.send_block(0, &header)?;
+            self.wait_for_start()?;
+        }
+
+        let mut sent = 0;
+        let mut number = 1u8;
+        while sent < data.len() {
+            let remaining = data.len() - sent;
+            // Use a short block for a short tail, to save sending padding
+            let len = if remaining <= 128 { 128 } else { 1024 };
+            let mut block = data[sent..][..remaining.min(len)].to_vec();
+            block.resize(len, PAD);
+            self.send_block(number, &block)?;
+            sent += remaining.min(len);
+            number = number.wrapping_add(1);
+            (self.progress)(sent);
+        }
+        self.send_eot()?;
+
+        if protocol == Protocol::Ymodem {
+            // An empty header block ends the batch
+            self.wait_for_start()?;
+            self.send_block(0, &[0u8; 128])?;
+        }
+        Ok(())
+    }
+
+    /// Tell the receiver we're giving up
+    pub fn cancel(&mut self) -> Result<(), Error> {
+        self.port.write_all(&[CAN; 3])?;
+        self.port.flush()?;
+        Ok(())
+    }
+
+    /// Wait for the receiver to ask for CRC mode
+    fn wait_for_start(&mut self) -> Result<(), Error> {
+        for _ in 0..MAX_RETRIES * 2 {
+            match self.read_byte()? {
+                Some(CRC_MODE) => return Ok(()),
+                Some(NAK) => {
+                    return Err("the receiver wants checksum mode, which we don't support".into());
+                }
+                Some(CAN) => return Err("the receiver cancelled the transfer".into()),
+                Some(_) | None => {}
+            }
+        }
+        Err("the receiver didn't start".into())
+    }
+
+    /// Send a block, retrying until it's acknowledged
+    fn send_block(&mut self, number: u8, data: &[u8]) -> Result<(), Error> {
+        let start = if data.len() == 128 { SOH } else { STX };
+        let mut packet = Vec::with_capacity(data.len() + 5);
+        packet.extend_from_slice(&[start, number, !number]);
+        packet.extend_from_slice(data);
+        packet.extend_from_slice(&crc16(data).to_be_bytes());
+        for _ in 0..MAX_RETRIES {
+            self.port.write_all(&packet)?;
+            self.port.flush()?;
+            match self.read_reply()? {
+                Some(ACK) => return Ok(()),
+                Some(CAN) => return Err("the receiver cancelled the transfer".into()),
+                // A NAK, or no answer, means try again
+                _ => {}
+            }
+        }
+        Err(format!("block {number} was never acknowledged").into())
+    }
+
+    /// Send EOT until it's acknowledged
+    ///
+    /// YMODEM receivers NAK the first EOT, to make sure it's real.
+    fn send_eot(&mut self) -> Result<(), Error> {
+        for _ in 0..MAX_RETRIES {
+            self.port.write_all(&[EOT])?;
+            self.port.flush()?;
+            match self.read_reply()? {
+                Some(ACK) => return Ok(()),
+                Some(CAN) => return Err("the receiver cancelled the transfer".into()),
+                _ => {}
+            }
+        }
+        Err("EOT was never acknowledged".into())
+    }
+
+    /// Wait for ACK, NAK or CAN, skipping anything else (such as a `C` the
+    /// receiver sent before it saw our block)
+    fn read_reply(&mut self) -> Result<Option<u8>, Error> {
+        loop {
+            match self.read_byte()? {
+                Some(byte @ (ACK | NAK | CAN)) => return Ok(Some(byte)),
+                Some(_) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Read a byte, or `None` if the read timed out
+    fn read_byte(&mut self) -> Result<Option<u8>, Error> {
+        let mut byte = [0u8; 1];
+        match self.port.read(&mut byte) {
+            Ok(0) => Err("the connection closed".into()),
+            Ok(_) => Ok(Some(byte[0])),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// CRC-16/XMODEM (polynomial 0x1021, starting from zero)
+pub fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for byte in data {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// The standard CRC-32, which the `xmodem_receive` example prints
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+// End of file
@@ -0,0 +1,96 @@
+//! Just enough telnet to talk to QEMU's UART telnet server
+//!
+//! QEMU sends a few option negotiations when we connect, which we ignore, and
+//! treats 0xFF (IAC) as the start of a command, so we have to double up any
+//! 0xFF bytes we send. QEMU turns on binary mode, so nothing else is
+//! translated.
+
+use std::io::{Read, Write};
+
+/// Interpret As Command - starts every telnet command
+const IAC: u8 = 0xFF;
+/// Subnegotiation begin
+const SB: u8 = 0xFA;
+/// Subnegotiation end
+const SE: u8 = 0xF0;
+/// WILL, WONT, DO and DONT are all followed by an option byte
+const OPTION_COMMANDS: std::ops::RangeInclusive<u8> = 0xFB..=0xFE;
+
+/// A telnet connection, carrying binary data
+pub struct Telnet<S> {
+    stream: S,
+}
+
+impl<S: Read + Write> Telnet<S> {
+    /// Wrap a connected stream
+    pub fn new(stream: S) -> Telnet<S> {
+        Telnet { stream }
+    }
+
+    fn read_raw(&mut self) -> std::io::Result<Option<u8>> {
+        let mut byte = [0u8; 1];
+        match self.stream.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+}
+
+impl<S: Read + Write> Read for Telnet<S> {
+    /// Read one data byte, skipping any telnet commands
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let Some(byte) = self.read_raw()? else {
+                return Ok(0);
+            };
+            if byte != IAC {
+                buf[0] = byte;
+                return Ok(1);
+            }
+            match self.read_raw()? {
+                None => return Ok(0),
+                Some(IAC) => {
+                    buf[0] = IAC;
+                    return Ok(1);
+                }
+                Some(command) if OPTION_COMMANDS.contains(&command) => {
+                    self.read_raw()?;
+                }
+                Some(SB) => {
+                    // Skip to IAC SE
+                    let mut last = 0;
+                    while let Some(b) = self.read_raw()? {
+                        if last == IAC && b == SE {
+                            break;
+                        }
+                        last = b;
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+impl<S: Read + Write> Write for Telnet<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut escaped = Vec::with_capacity(buf.len());
+        for byte in buf {
+            escaped.push(*byte);
+            if *byte == IAC {
+                escaped.push(IAC);
+            }
+        }
+        self.stream.write_all(&escaped)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+// End of file
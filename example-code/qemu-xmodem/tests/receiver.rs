@@ -0,0 +1,523 @@
+//! Host tests for the receiver in `qemu_common::xmodem`
+//!
+//! Some tests feed the receiver a scripted stream of bytes and check what it
+//! sent back. The rest connect it to our [`Sender`] over an in-memory serial
+//! line, with the receiver on its own thread. Run with `cargo test`.
+//!
+//! Copyright (c) Ferrous Systems, 2026
+
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use qemu_common::xmodem::{self, Error, MemoryStorage, Received, Receiver, Timeout};
+use qemu_xmodem::sender::{self, Protocol, Sender};
+
+const SOH: u8 = 0x01;
+const STX: u8 = 0x02;
+const EOT: u8 = 0x04;
+const ACK: u8 = 0x06;
+const NAK: u8 = 0x15;
+const CAN: u8 = 0x18;
+const CRC_MODE: u8 = b'C';
+const PAD: u8 = 0x1A;
+
+/// How long the sender waits for each reply
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Some data that doesn't repeat every block
+fn file(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + i / 251) as u8).collect()
+}
+
+/// Build a block as a sender would, padding `data` with `pad`
+fn packet(number: u8, data: &[u8], pad: u8) -> Vec<u8> {
+    let (start, len) = if data.len() <= 128 {
+        (SOH, 128)
+    } else {
+        (STX, 1024)
+    };
+    let mut block = data.to_vec();
+    block.resize(len, pad);
+    let mut packet = vec![start, number, !number];
+    packet.extend_from_slice(&block);
+    packet.extend_from_slice(&xmodem::crc16(&block).to_be_bytes());
+    packet
+}
+
+/// A port that plays back `input`, and records what the receiver sends
+#[derive(Default)]
+struct Script {
+    input: VecDeque<u8>,
+    output: Vec<u8>,
+}
+
+impl embedded_io::ErrorType for Script {
+    type Error = Infallible;
+}
+
+impl embedded_io::Read for Script {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+        let mut count = 0;
+        for byte in buf.iter_mut() {
+            let Some(b) = self.input.pop_front() else {
+                break;
+            };
+            *byte = b;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl embedded_io::ReadReady for Script {
+    fn read_ready(&mut self) -> Result<bool, Infallible> {
+        Ok(!self.input.is_empty())
+    }
+}
+
+impl embedded_io::Write for Script {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Infallible> {
+        self.output.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// A timeout that has always expired - a script never has more to come
+struct NoWait;
+
+impl Timeout for NoWait {
+    fn start(&mut self, _ms: u32) {}
+
+    fn expired(&mut self) -> bool {
+        true
+    }
+}
+
+/// What happened when we ran a receiver
+struct Outcome {
+    result: Result<Received, Error>,
+    stored: Vec<u8>,
+    sent: Vec<u8>,
+}
+
+/// Run a receiver over a script, with `capacity` bytes of storage
+fn run_script(protocol: xmodem::Protocol, input: &[u8], capacity: usize) -> Outcome {
+    let script = Script {
+        input: input.iter().copied().collect(),
+        output: Vec::new(),
+    };
+    let mut memory = vec![0u8; capacity];
+    let mut storage = MemoryStorage::new(&mut memory);
+    let mut receiver = Receiver::new(script, NoWait);
+    let result = receiver.receive(protocol, &mut storage);
+    let (script, _) = receiver.free();
+    Outcome {
+        result,
+        stored: memory,
+        sent: script.output,
+    }
+}
+
+/// The YMODEM header for a file
+fn header(info: &[u8]) -> Vec<u8> {
+    packet(0, info, 0)
+}
+
+#[test]
+fn crc16_matches_known_vectors() {
+    // The CRC-16/XMODEM check value
+    assert_eq!(xmodem::crc16(b"123456789"), 0x31C3);
+    assert_eq!(xmodem::crc16(b""), 0x0000);
+    assert_eq!(xmodem::crc16(&[0x00]), 0x0000);
+    assert_eq!(xmodem::crc16(&[0xFF]), 0x1EF0);
+    assert_eq!(xmodem::crc16(b"A"), 0x58E5);
+}
+
+#[test]
+fn crc16_agrees_with_sender() {
+    for len in [1, 128, 1024, 3000] {
+        let data = file(len);
+        assert_eq!(xmodem::crc16(&data), sender::crc16(&data), "{len} bytes");
+    }
+}
+
+#[test]
+fn header_gives_name_and_length() {
+    let mut input = header(b"hello.txt\x0012 14174012345 100644");
+    input.extend(packet(1, b"Hello, world", PAD));
+    input.extend([EOT, EOT]);
+    input.extend(header(b""));
+    let outcome = run_script(xmodem::Protocol::Ymodem, &input, 256);
+    let received = outcome.result.unwrap();
+    assert_eq!(received.name.as_str(), "hello.txt");
+    assert_eq!(received.len, 12);
+    assert_eq!(&outcome.stored[..12], b"Hello, world");
+    // The padding didn't get stored
+    assert!(outcome.stored[12..].iter().all(|b| *b == 0));
+    assert_eq!(
+        outcome.sent,
+        [CRC_MODE, ACK, CRC_MODE, ACK, NAK, ACK, CRC_MODE, ACK]
+    );
+}
+
+#[test]
+fn header_without_length_keeps_padding() {
+    let mut input = header(b"data.bin\x00");
+    input.extend(packet(1, b"abc", PAD));
+    input.extend([EOT, EOT]);
+    input.extend(header(b""));
+    let outcome = run_script(xmodem::Protocol::Ymodem, &input, 256);
+    let received = outcome.result.unwrap();
+    assert_eq!(received.name.as_str(), "data.bin");
+    assert_eq!(received.len, 128);
+    assert_eq!(outcome.stored[3], PAD);
+}
+
+#[test]
+fn header_truncates_long_names() {
+    let name = "n".repeat(xmodem::MAX_NAME_LEN + 10);
+    let mut info = name.clone().into_bytes();
+    info.extend(b"\x000");
+    let mut input = header(&info);
+    input.extend([EOT, EOT]);
+    input.extend(header(b""));
+    let outcome = run_script(xmodem::Protocol::Ymodem, &input, 256);
+    let received = outcome.result.unwrap();
+    assert_eq!(received.name.as_str(), &name[..xmodem::MAX_NAME_LEN]);
+    assert_eq!(received.len, 0);
+}
+
+#[test]
+fn empty_header_is_an_empty_batch() {
+    let outcome = run_script(xmodem::Protocol::Ymodem, &header(b""), 256);
+    let received = outcome.result.unwrap();
+    assert_eq!(received.name.as_str(), "");
+    assert_eq!(received.len, 0);
+    assert_eq!(outcome.sent, [CRC_MODE, ACK]);
+}
+
+#[test]
+fn bad_header_is_cancelled() {
+    let outcome = run_script(xmodem::Protocol::Ymodem, &header(b"\xFF\xFE\x0012"), 256);
+    assert_eq!(outcome.result, Err(Error::BadHeader));
+    assert!(outcome.sent.ends_with(&[CAN; 3]));
+}
+
+#[test]
+fn header_longer_than_storage_is_too_big() {
+    let outcome = run_script(xmodem::Protocol::Ymodem, &header(b"big.bin\x00257"), 256);
+    assert_eq!(outcome.result, Err(Error::TooBig));
+    assert_eq!(outcome.sent[0], CRC_MODE);
+    assert!(outcome.sent.ends_with(&[CAN; 3]));
+}
+
+#[test]
+fn xmodem_eot_ends_the_file() {
+    let data = file(1024 + 100);
+    let mut input = packet(1, &data[..1024], PAD);
+    input.extend(packet(2, &data[1024..], PAD));
+    input.push(EOT);
+    let outcome = run_script(xmodem::Protocol::Xmodem, &input, 2048);
+    let received = outcome.result.unwrap();
+    // XMODEM doesn't know the length, so we keep the padding
+    assert_eq!(received.len, 1024 + 128);
+    assert_eq!(&outcome.stored[..data.len()], &data[..]);
+    assert!(
+        outcome.stored[data.len()..received.len]
+            .iter()
+            .all(|b| *b == PAD)
+    );
+    // Only one EOT is needed, and it is ACKed straight away
+    assert_eq!(outcome.sent, [CRC_MODE, ACK, ACK, ACK]);
+}
+
+#[test]
+fn ymodem_needs_a_second_eot() {
+    let mut input = header(b"a\x001");
+    input.extend(packet(1, b"a", PAD));
+    input.push(EOT);
+    let outcome = run_script(xmodem::Protocol::Ymodem, &input, 256);
+    assert_eq!(outcome.result, Err(Error::TooManyErrors));
+    assert_eq!(&outcome.sent[..5], [CRC_MODE, ACK, CRC_MODE, ACK, NAK]);
+}
+
+#[test]
+fn two_cans_cancel_at_the_start() {
+    let outcome = run_script(xmodem::Protocol::Xmodem, &[CAN, CAN], 256);
+    assert_eq!(outcome.result, Err(Error::Cancelled));
+    // The sender has gone, so we don't cancel back
+    assert_eq!(outcome.sent, [CRC_MODE]);
+}
+
+#[test]
+fn two_cans_cancel_between_blocks() {
+    let mut input = packet(1, &file(128), PAD);
+    input.extend([CAN, CAN, CAN]);
+    let outcome = run_script(xmodem::Protocol::Xmodem, &input, 256);
+    assert_eq!(outcome.result, Err(Error::Cancelled));
+    assert_eq!(outcome.sent, [CRC_MODE, ACK]);
+}
+
+#[test]
+fn out_of_sequence_block_is_cancelled() {
+    let mut input = packet(1, &file(128), PAD);
+    input.extend(packet(3, &file(128), PAD));
+    let outcome = run_script(xmodem::Protocol::Xmodem, &input, 256);
+    assert_eq!(outcome.result, Err(Error::OutOfSequence));
+    assert!(outcome.sent.ends_with(&[CAN; 3]));
+}
+
+#[test]
+fn nobody_sending() {
+    let outcome = run_script(xmodem::Protocol::Xmodem, &[], 256);
+    assert_eq!(outcome.result, Err(Error::NoSender));
+    assert!(outcome.sent.iter().all(|b| *b == CRC_MODE));
+}
+
+/// The receiver's end of an in-memory serial line
+struct ReceiverPort {
+    rx: mpsc::Receiver<u8>,
+    tx: mpsc::Sender<u8>,
+    /// A byte we took off the line to see if there was one
+    next: Option<u8>,
+}
+
+impl embedded_io::ErrorType for ReceiverPort {
+    type Error = embedded_io::ErrorKind;
+}
+
+impl embedded_io::Read for ReceiverPort {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let byte = match self.next.take() {
+            Some(byte) => byte,
+            None => self
+                .rx
+                .recv()
+                .map_err(|_| embedded_io::ErrorKind::BrokenPipe)?,
+        };
+        buf[0] = byte;
+        Ok(1)
+    }
+}
+
+impl embedded_io::ReadReady for ReceiverPort {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        if self.next.is_none() {
+            // If the sender has hung up, that just looks like silence
+            self.next = self.rx.try_recv().ok();
+        }
+        Ok(self.next.is_some())
+    }
+}
+
+impl embedded_io::Write for ReceiverPort {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        for byte in buf {
+            self.tx
+                .send(*byte)
+                .map_err(|_| embedded_io::ErrorKind::BrokenPipe)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// The sender's end of an in-memory serial line
+struct SenderPort {
+    rx: mpsc::Receiver<u8>,
+    tx: mpsc::Sender<u8>,
+    /// How many bytes we have sent
+    written: usize,
+    /// Flip the bits of the byte at this offset, to spoil a block
+    corrupt: Option<usize>,
+}
+
+impl std::io::Read for SenderPort {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        match self.rx.recv_timeout(READ_TIMEOUT) {
+            Ok(byte) => {
+                buf[0] = byte;
+                Ok(1)
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(std::io::ErrorKind::TimedOut.into()),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Ok(0),
+        }
+    }
+}
+
+impl std::io::Write for SenderPort {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for byte in buf {
+            let byte = if self.corrupt == Some(self.written) {
+                !byte
+            } else {
+                *byte
+            };
+            self.written += 1;
+            self.tx
+                .send(byte)
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Make a serial line, with a sender on one end and a receiver on the other
+fn line() -> (SenderPort, ReceiverPort) {
+    let (to_receiver, from_sender) = mpsc::channel();
+    let (to_sender, from_receiver) = mpsc::channel();
+    let sender = SenderPort {
+        rx: from_receiver,
+        tx: to_receiver,
+        written: 0,
+        corrupt: None,
+    };
+    let receiver = ReceiverPort {
+        rx: from_sender,
+        tx: to_sender,
+        next: None,
+    };
+    (sender, receiver)
+}
+
+/// A timeout that uses the host's clock
+struct Clock {
+    deadline: Instant,
+}
+
+impl Timeout for Clock {
+    fn start(&mut self, ms: u32) {
+        self.deadline = Instant::now() + Duration::from_millis(u64::from(ms));
+    }
+
+    fn expired(&mut self) -> bool {
+        Instant::now() >= self.deadline
+    }
+}
+
+/// Start a receiver on its own thread, with `capacity` bytes of storage
+///
+/// Join the thread to get the result, and what it stored.
+fn spawn_receiver(
+    port: ReceiverPort,
+    protocol: xmodem::Protocol,
+    capacity: usize,
+) -> thread::JoinHandle<(Result<Received, Error>, Vec<u8>)> {
+    thread::spawn(move || {
+        let clock = Clock {
+            deadline: Instant::now(),
+        };
+        let mut memory = vec![0u8; capacity];
+        let mut storage = MemoryStorage::new(&mut memory);
+        let result = Receiver::new(port, clock).receive(protocol, &mut storage);
+        (result, memory)
+    })
+}
+
+#[test]
+fn loopback_ymodem() {
+    let data = file(2100);
+    let (port, receiver_port) = line();
+    let receiver = spawn_receiver(receiver_port, xmodem::Protocol::Ymodem, 4096);
+    Sender::new(port)
+        .send(Protocol::Ymodem, "file.bin", &data)
+        .unwrap();
+    let (result, stored) = receiver.join().unwrap();
+    let received = result.unwrap();
+    assert_eq!(received.name.as_str(), "file.bin");
+    assert_eq!(received.len, data.len());
+    assert_eq!(&stored[..data.len()], &data[..]);
+}
+
+#[test]
+fn loopback_xmodem() {
+    let data = file(2100);
+    let (port, receiver_port) = line();
+    let receiver = spawn_receiver(receiver_port, xmodem::Protocol::Xmodem, 4096);
+    Sender::new(port)
+        .send(Protocol::Xmodem, "file.bin", &data)
+        .unwrap();
+    let (result, stored) = receiver.join().unwrap();
+    let received = result.unwrap();
+    assert_eq!(received.name.as_str(), "");
+    // Two big blocks, and a short one for the tail
+    assert_eq!(received.len, 1024 * 2 + 128);
+    assert_eq!(&stored[..data.len()], &data[..]);
+}
+
+#[test]
+fn loopback_retries_corrupted_block() {
+    let data = file(3000);
+    let (mut port, receiver_port) = line();
+    // The header block, then part-way into the second data block
+    port.corrupt = Some(133 + 1029 + 500);
+    let receiver = spawn_receiver(receiver_port, xmodem::Protocol::Ymodem, 4096);
+    let mut sender = Sender::new(port);
+    sender.send(Protocol::Ymodem, "file.bin", &data).unwrap();
+    let (result, stored) = receiver.join().unwrap();
+    assert_eq!(result.unwrap().len, data.len());
+    assert_eq!(&stored[..data.len()], &data[..]);
+}
+
+#[test]
+fn loopback_retries_corrupted_first_block() {
+    let data = file(300);
+    let (mut port, receiver_port) = line();
+    port.corrupt = Some(10);
+    let receiver = spawn_receiver(receiver_port, xmodem::Protocol::Xmodem, 4096);
+    Sender::new(port)
+        .send(Protocol::Xmodem, "file.bin", &data)
+        .unwrap();
+    let (result, stored) = receiver.join().unwrap();
+    assert_eq!(result.unwrap().len, 1024);
+    assert_eq!(&stored[..data.len()], &data[..]);
+}
+
+#[test]
+fn loopback_sender_cancels() {
+    let (port, receiver_port) = line();
+    let receiver = spawn_receiver(receiver_port, xmodem::Protocol::Ymodem, 4096);
+    // Give up once the receiver has asked us to start
+    assert_eq!(port.rx.recv_timeout(READ_TIMEOUT), Ok(CRC_MODE));
+    let mut sender = Sender::new(port);
+    sender.cancel().unwrap();
+    let (result, _) = receiver.join().unwrap();
+    drop(sender);
+    assert_eq!(result, Err(Error::Cancelled));
+}
+
+#[test]
+fn loopback_receiver_cancels() {
+    let (port, receiver_port) = line();
+    let receiver = spawn_receiver(receiver_port, xmodem::Protocol::Ymodem, 1024);
+    let error = Sender::new(port)
+        .send(Protocol::Ymodem, "big.bin", &file(2000))
+        .unwrap_err();
+    assert!(error.to_string().contains("cancelled"), "{error}");
+    let (result, _) = receiver.join().unwrap();
+    assert_eq!(result, Err(Error::TooBig));
+}
+
+// End of file